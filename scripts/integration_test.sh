@@ -54,7 +54,32 @@ assert_post_response "chess move" "http://localhost:7878/api/v0/chess" "rnbqkb1r
 
 assert_post_response "chess openings db" "http://localhost:7878/api/v0/chess/openings_db" "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1" "e4"
 
-assert_post_response "chess minimax" "http://localhost:7878/api/v0/chess/minimax" "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1" "e3"
+assert_post_response "chess minimax" "http://localhost:7878/api/v0/chess/minimax" "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1" "e4"
+
+# crazyhouse tests
+assert_post_response "crazyhouse move" "http://localhost:7878/api/v0/crazyhouse" "1r2k3/8/8/8/8/p7/P7/K7[N] w - - 0 1" "N@h1"
+
+assert_post_response "crazyhouse minimax" "http://localhost:7878/api/v0/crazyhouse/minimax" "1r2k3/8/8/8/8/p7/P7/K7[N] w - - 0 1" "N@h1"
+
+# king of the hill tests
+assert_post_response "king of the hill move" "http://localhost:7878/api/v0/king_of_the_hill" "4k3/8/8/8/8/4K3/8/8 w - - 0 1" "Ke4"
+
+assert_post_response "king of the hill minimax" "http://localhost:7878/api/v0/king_of_the_hill/minimax" "4k3/8/8/8/8/4K3/8/8 w - - 0 1" "Ke4"
+
+# three check tests
+assert_post_response "three check move" "http://localhost:7878/api/v0/three_check" "4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1" "Ra8+"
+
+assert_post_response "three check minimax" "http://localhost:7878/api/v0/three_check/minimax" "4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1" "Ra8+"
+
+# atomic tests
+assert_post_response "atomic move" "http://localhost:7878/api/v0/atomic" "rnbqkbnr/pppp1ppp/8/4p1N1/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 2" "Nxf7"
+
+assert_post_response "atomic minimax" "http://localhost:7878/api/v0/atomic/minimax" "rnbqkbnr/pppp1ppp/8/4p1N1/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 2" "Nxf7"
+
+# horde tests
+assert_post_response "horde move" "http://localhost:7878/api/v0/horde" "4k3/8/8/8/8/8/8/P7 w - - 0 1" "a3"
+
+assert_post_response "horde minimax" "http://localhost:7878/api/v0/horde/minimax" "4k3/8/8/8/8/8/8/P7 w - - 0 1" "a3"
 
 # go tests
 assert_post_response "go move" "http://localhost:7878/api/v0/go" "PL[B]XB[0]XW[0]" "ss"
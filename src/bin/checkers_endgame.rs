@@ -0,0 +1,149 @@
+// Builds or verifies the checkers endgame database
+//
+//   checkers_endgame [-n pieces] -o path
+//   checkers_endgame -v path
+//
+// Every position with up to the given number of pieces, 6 by default, is solved by retrograde analysis
+// and written as win, loss or draw for the player to move. Larger databases take much longer to build,
+// so the materials are reported as they are finished. The server probes the database at
+// CHECKERS_ENDGAME_DB_PATH, e.g.
+//
+//   checkers_endgame -n 4 -o endgame.db
+//   CHECKERS_ENDGAME_DB_PATH=endgame.db salieri
+use std::env;
+use std::fs;
+use std::process;
+
+use salieri::checkers::endgame::MAX_PIECES;
+use salieri::checkers::endgame::database::Database;
+use salieri::checkers::endgame::generator;
+use salieri::checkers::endgame::index::Material;
+
+#[derive(Debug, PartialEq)]
+enum Options {
+    Generate { max_pieces: u8, output_path: String },
+    Verify { path: String }
+}
+
+const USAGE: &str = "usage: checkers_endgame [-n pieces] -o path | checkers_endgame -v path";
+
+fn parse_args(args: &[String]) -> Result<Options, &'static str> {
+    let mut max_pieces = MAX_PIECES;
+    let mut output_path = None;
+    let mut verify_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" => {
+                max_pieces = match args.next().map(|a| a.parse()) {
+                    Some(Ok(n)) if n > 0 && n <= MAX_PIECES => n,
+                    _ => return Err("-n must be followed by a number of pieces from 1 to 6")
+                };
+            },
+            "-o" => {
+                output_path = match args.next() {
+                    Some(path) => Some(path.clone()),
+                    None => return Err("-o must be followed by a path")
+                };
+            },
+            "-v" => {
+                verify_path = match args.next() {
+                    Some(path) => Some(path.clone()),
+                    None => return Err("-v must be followed by a path")
+                };
+            },
+            _ => return Err(USAGE)
+        }
+    }
+
+    match (output_path, verify_path) {
+        (Some(output_path), None) => Ok(Options::Generate { max_pieces, output_path }),
+        (None, Some(path)) => Ok(Options::Verify { path }),
+        _ => Err(USAGE)
+    }
+}
+
+fn generate(max_pieces: u8, output_path: &str) -> Result<(), String> {
+    let mut database = Database::new(max_pieces);
+    for material in Material::all(max_pieces) {
+        generator::generate_material(&mut database, material)?;
+        eprintln!("{:?} - {} positions", material, material.size());
+    }
+
+    fs::write(output_path, database.format()).map_err(|e| format!("{}: {}", output_path, e))
+}
+
+fn verify(path: &str) -> Result<(), String> {
+    let encoded = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+    let database = Database::parse(&encoded)?;
+    generator::verify(&database)?;
+    eprintln!("{}: {} pieces, ok", path, database.max_pieces);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+
+    let result = match options {
+        Options::Generate { max_pieces, output_path } => generate(max_pieces, &output_path),
+        Options::Verify { path } => verify(&path)
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_generate_test() {
+        let result = parse_args(&args(&["-n", "4", "-o", "endgame.db"])).unwrap();
+        assert_eq!(result, Options::Generate { max_pieces: 4, output_path: String::from("endgame.db") });
+    }
+
+    #[test]
+    fn parse_args_defaults_test() {
+        let result = parse_args(&args(&["-o", "endgame.db"])).unwrap();
+        assert_eq!(result, Options::Generate { max_pieces: MAX_PIECES, output_path: String::from("endgame.db") });
+    }
+
+    #[test]
+    fn parse_args_verify_test() {
+        let result = parse_args(&args(&["-v", "endgame.db"])).unwrap();
+        assert_eq!(result, Options::Verify { path: String::from("endgame.db") });
+    }
+
+    #[test]
+    fn parse_args_invalid_test() {
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["-n", "7", "-o", "endgame.db"])).is_err());
+        assert!(parse_args(&args(&["-o", "a.db", "-v", "b.db"])).is_err());
+        assert!(parse_args(&args(&["endgame.db"])).is_err());
+    }
+
+    #[test]
+    fn generate_and_verify_test() {
+        let path = env::temp_dir().join(format!("checkers_endgame_{}.db", process::id()));
+        let path = path.to_str().unwrap();
+
+        generate(2, path).unwrap();
+        assert!(verify(path).is_ok());
+        fs::remove_file(path).unwrap();
+    }
+}
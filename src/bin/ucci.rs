@@ -0,0 +1,634 @@
+// UCCI (Universal Chinese Chess Protocol) engine
+//
+// Reads commands from stdin and writes responses to stdout so that the engine
+// can be used from xiangqi GUIs. The UCI dialect most xiangqi GUIs use is also
+// understood, starting with `uci` instead of `ucci`, e.g.
+//
+//   ucci
+//   isready
+//   position startpos moves h2e2 h9g7
+//   go time 60000 increment 1000
+use std::env;
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use salieri::xiangqi;
+use salieri::xiangqi::state::game_state::GameState;
+use salieri::xiangqi::state::mov::Move;
+use salieri::xiangqi::state::iccs_mov;
+
+const STARTING_POSITION: &str = "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1";
+
+// time kept back for talking to the GUI
+const TIME_MARGIN: u64 = 100;
+
+// number of moves the remaining time is expected to last
+const MOVES_TO_GO: u64 = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Protocol {
+    Ucci,
+    Uci
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Algorithm {
+    Minimax,
+    Mcts
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Options {
+    algorithm: Algorithm,
+    minimax_depth: i8,
+    mcts_simulation_count: i16,
+    mcts_simulation_depth: i16,
+    use_millisec: bool
+}
+
+impl Options {
+    fn from_env() -> Options {
+        Options {
+            algorithm: Algorithm::Minimax,
+            minimax_depth: env_number("XIANGQI_MINIMAX_DEPTH", 2),
+            mcts_simulation_count: env_number("XIANGQI_MCTS_SIMULATION_COUNT", 100),
+            mcts_simulation_depth: env_number("XIANGQI_MCTS_SIMULATION_DEPTH", 50),
+            use_millisec: false
+        }
+    }
+
+    // UCCI leaves out `name` before the option name
+    fn format(&self, protocol: Protocol) -> Vec<String> {
+        let algorithm = match self.algorithm {
+            Algorithm::Minimax => "minimax",
+            Algorithm::Mcts => "mcts"
+        };
+
+        let prefix = match protocol {
+            Protocol::Ucci => "option",
+            Protocol::Uci => "option name"
+        };
+
+        let mut acc = vec![];
+        if protocol == Protocol::Ucci {
+            acc.push(format!("{} usemillisec type check default {}", prefix, self.use_millisec));
+        }
+        acc.push(format!("{} Algorithm type combo default {} var minimax var mcts", prefix, algorithm));
+        acc.push(format!("{} MinimaxDepth type spin default {} min 0 max 8", prefix, self.minimax_depth));
+        acc.push(format!("{} MCTSSimulationCount type spin default {} min 1 max 10000", prefix, self.mcts_simulation_count));
+        acc.push(format!("{} MCTSSimulationDepth type spin default {} min 1 max 1000", prefix, self.mcts_simulation_depth));
+        acc
+    }
+
+    // setoption MinimaxDepth 2
+    // setoption name MinimaxDepth value 2
+    fn set(&mut self, tokens: &[&str]) -> Result<(), &'static str> {
+        let (name, value) = match tokens.get(1) {
+            Some(&"name") => {
+                let name = tokens.get(2).ok_or("Invalid Option")?;
+                let value = match tokens.iter().position(|t| *t == "value") {
+                    Some(index) => tokens.get(index + 1).ok_or("Invalid Option Value")?,
+                    None => return Err("Invalid Option Value")
+                };
+                (name, value)
+            },
+            Some(name) => (name, tokens.get(2).ok_or("Invalid Option Value")?),
+            None => return Err("Invalid Option")
+        };
+
+        match *name {
+            "usemillisec" => {
+                self.use_millisec = value.parse().map_err(|_| "Invalid Option Value")?;
+            },
+            "Algorithm" => {
+                self.algorithm = match *value {
+                    "minimax" => Algorithm::Minimax,
+                    "mcts" => Algorithm::Mcts,
+                    _ => return Err("Invalid Option Value")
+                };
+            },
+            "MinimaxDepth" => {
+                self.minimax_depth = value.parse().map_err(|_| "Invalid Option Value")?;
+            },
+            "MCTSSimulationCount" => {
+                self.mcts_simulation_count = value.parse().map_err(|_| "Invalid Option Value")?;
+            },
+            "MCTSSimulationDepth" => {
+                self.mcts_simulation_depth = value.parse().map_err(|_| "Invalid Option Value")?;
+            },
+            // options every GUI sends, e.g. batch, hashsize and Hash
+            _ => ()
+        }
+
+        Ok(())
+    }
+}
+
+fn env_number<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) => value.parse().unwrap_or(default),
+        Err(_) => default
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct GoParams {
+    depth: Option<i8>,
+    nodes: Option<i16>,
+    time: Option<u64>,
+    increment: Option<u64>,
+    movestogo: Option<u64>,
+    wtime: Option<u64>,
+    btime: Option<u64>,
+    winc: Option<u64>,
+    binc: Option<u64>,
+    movetime: Option<u64>,
+    infinite: bool
+}
+
+// go depth 4
+// go time 60000 increment 1000
+// go wtime 60000 btime 60000 winc 1000 binc 1000
+fn parse_go(tokens: &[&str]) -> GoParams {
+    let mut params = GoParams::default();
+    let mut iter = tokens.iter().skip(1);
+
+    while let Some(token) = iter.next() {
+        match *token {
+            "infinite" => params.infinite = true,
+            "depth" => {
+                match iter.next() {
+                    Some(&"infinite") => params.infinite = true,
+                    Some(value) => params.depth = value.parse().ok(),
+                    None => ()
+                }
+            },
+            "nodes" => params.nodes = iter.next().and_then(|v| v.parse().ok()),
+            "time" | "increment" | "movestogo" | "wtime" | "btime" | "winc" | "binc" | "movetime" => {
+                let value = iter.next().and_then(|v| v.parse().ok());
+                match *token {
+                    "time" => params.time = value,
+                    "increment" => params.increment = value,
+                    "movestogo" => params.movestogo = value,
+                    "wtime" => params.wtime = value,
+                    "btime" => params.btime = value,
+                    "winc" => params.winc = value,
+                    "binc" => params.binc = value,
+                    _ => params.movetime = value
+                }
+            },
+            _ => ()
+        }
+    }
+
+    params
+}
+
+// milliseconds to search for, None when the search runs until it finishes or is stopped
+// UCCI times are in seconds unless usemillisec is set
+fn time_budget(params: &GoParams, player_number: i8, protocol: Protocol, use_millisec: bool) -> Option<u64> {
+    if params.infinite {
+        return None;
+    }
+
+    let unit = if protocol == Protocol::Ucci && !use_millisec { 1000 } else { 1 };
+
+    if let Some(movetime) = params.movetime {
+        return Some((movetime * unit).saturating_sub(TIME_MARGIN).max(1));
+    }
+
+    let (remaining, increment) = match (params.time, params.increment) {
+        (None, None) => if player_number == 1 {
+            (params.wtime, params.winc)
+        } else {
+            (params.btime, params.binc)
+        },
+        own => own
+    };
+
+    if remaining.is_none() && increment.is_none() {
+        return None;
+    }
+
+    let moves_to_go = params.movestogo.filter(|m| *m > 0).unwrap_or(MOVES_TO_GO);
+    let budget = (remaining.unwrap_or(0) / moves_to_go + increment.unwrap_or(0)) * unit;
+
+    Some(budget.saturating_sub(TIME_MARGIN).max(1))
+}
+
+// position startpos moves h2e2 h9g7
+// position fen rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2e2
+fn parse_position(tokens: &[&str]) -> Result<GameState, &'static str> {
+    let encoded = match tokens.get(1) {
+        Some(&"startpos") => {
+            let mut encoded = STARTING_POSITION.to_string();
+            for token in tokens.iter().skip(2) {
+                encoded.push(' ');
+                encoded.push_str(token);
+            }
+            encoded
+        },
+        Some(&"fen") => tokens[2..].join(" "),
+        _ => return Err("Invalid Position")
+    };
+
+    xiangqi::state::game_state::parse(&encoded)
+}
+
+// state shared between the command loop, the search thread and the timer thread
+struct Search {
+    protocol: Protocol,
+    best_move: Mutex<Option<Move>>,
+    stopped: AtomicBool,
+    reported: AtomicBool
+}
+
+impl Search {
+    fn report(&self) {
+        if self.reported.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        match (self.best_move.lock().unwrap().as_ref(), self.protocol) {
+            (Some(mov), _) => println!("bestmove {}", iccs_mov::format(mov)),
+            (None, Protocol::Ucci) => println!("nobestmove"),
+            (None, Protocol::Uci) => println!("bestmove (none)")
+        }
+    }
+
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.report();
+    }
+}
+
+fn start_search(game_state: &GameState, protocol: Protocol, options: Options, params: GoParams) -> Arc<Search> {
+    let mut game_state = game_state.clone();
+
+    // fall back to any legal move in case time runs out before the first iteration
+    let first_move = if game_state.outcome().is_some() {
+        None
+    } else {
+        game_state.possible_moves().into_iter().next()
+    };
+    let has_moves = first_move.is_some();
+
+    let search = Arc::new(Search {
+        protocol,
+        best_move: Mutex::new(first_move),
+        stopped: AtomicBool::new(false),
+        reported: AtomicBool::new(false)
+    });
+
+    if !has_moves {
+        search.report();
+        return search;
+    }
+
+    if let Some(budget) = time_budget(&params, game_state.current_player_number, protocol, options.use_millisec) {
+        let timer_search = Arc::clone(&search);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(budget));
+            timer_search.stop();
+        });
+    }
+
+    let search_thread_search = Arc::clone(&search);
+    thread::spawn(move || {
+        run_search(game_state, options, params, &search_thread_search);
+        if !params.infinite {
+            search_thread_search.report();
+        }
+    });
+
+    search
+}
+
+fn run_search(game_state: GameState, options: Options, params: GoParams, search: &Search) {
+    let start = Instant::now();
+
+    match options.algorithm {
+        Algorithm::Minimax => {
+            // go depth counts plies while the minimax depth counts the layers after the first move
+            let max_depth = match params.depth {
+                Some(depth) => (depth - 1).max(0),
+                None => options.minimax_depth
+            };
+
+            // iterative deepening so that a result is available when time runs out
+            for depth in 0..=max_depth {
+                if search.stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let mut depth_game_state = game_state.clone();
+                match xiangqi::minimax::recommended_move(&mut depth_game_state, depth) {
+                    Some(mov) => {
+                        if search.stopped.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        println!("info depth {} time {} pv {}", depth + 1, start.elapsed().as_millis(), iccs_mov::format(&mov));
+                        *search.best_move.lock().unwrap() = Some(mov);
+                    },
+                    None => break
+                }
+            }
+        },
+        Algorithm::Mcts => {
+            let simulation_count = params.nodes.unwrap_or(options.mcts_simulation_count);
+            let mut simulation_game_state = game_state;
+            if let Ok(mov) = xiangqi::mcts::recommended_move(&mut simulation_game_state, simulation_count, options.mcts_simulation_depth) {
+                if !search.stopped.load(Ordering::SeqCst) {
+                    println!("info time {} nodes {} pv {}", start.elapsed().as_millis(), simulation_count, iccs_mov::format(&mov));
+                    *search.best_move.lock().unwrap() = Some(mov);
+                }
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut protocol = Protocol::Ucci;
+    let mut options = Options::from_env();
+    let mut game_state = xiangqi::state::game_state::parse(&STARTING_POSITION.to_string()).ok();
+    let mut current_search: Option<Arc<Search>> = None;
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => break
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.first() {
+            Some(&"ucci") | Some(&"uci") => {
+                protocol = if tokens.first() == Some(&"uci") { Protocol::Uci } else { Protocol::Ucci };
+                println!("id name salieri {}", env!("CARGO_PKG_VERSION"));
+                println!("id author {}", env!("CARGO_PKG_AUTHORS"));
+                for option in options.format(protocol) {
+                    println!("{}", option);
+                }
+                match protocol {
+                    Protocol::Ucci => println!("ucciok"),
+                    Protocol::Uci => println!("uciok")
+                }
+            },
+            Some(&"isready") => println!("readyok"),
+            Some(&"setoption") => {
+                if let Err(e) = options.set(&tokens) {
+                    println!("info string {}", e);
+                }
+            },
+            Some(&"ucinewgame") => (),
+            Some(&"position") => {
+                match parse_position(&tokens) {
+                    Ok(gs) => game_state = Some(gs),
+                    Err(e) => {
+                        game_state = None;
+                        println!("info string {}", e);
+                    }
+                }
+            },
+            Some(&"go") => {
+                if let Some(ref search) = current_search {
+                    search.stop();
+                }
+                current_search = match game_state {
+                    Some(ref gs) => Some(start_search(gs, protocol, options, parse_go(&tokens))),
+                    None => {
+                        let search = Search {
+                            protocol,
+                            best_move: Mutex::new(None),
+                            stopped: AtomicBool::new(true),
+                            reported: AtomicBool::new(false)
+                        };
+                        search.report();
+                        None
+                    }
+                };
+            },
+            Some(&"stop") => {
+                if let Some(ref search) = current_search {
+                    search.stop();
+                }
+            },
+            Some(&"quit") => {
+                if protocol == Protocol::Ucci {
+                    println!("bye");
+                }
+                break;
+            },
+            _ => ()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_options() -> Options {
+        Options {
+            algorithm: Algorithm::Minimax,
+            minimax_depth: 1,
+            mcts_simulation_count: 100,
+            mcts_simulation_depth: 50,
+            use_millisec: false
+        }
+    }
+
+    #[test]
+    fn parse_position_startpos_test() {
+        let tokens = vec!["position", "startpos"];
+        let result = parse_position(&tokens).unwrap();
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.position_hashes.len(), 1);
+    }
+
+    #[test]
+    fn parse_position_startpos_with_moves_test() {
+        let tokens = vec!["position", "startpos", "moves", "h2e2", "h9g7"];
+        let result = parse_position(&tokens).unwrap();
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.position_hashes.len(), 3);
+    }
+
+    #[test]
+    fn parse_position_fen_with_moves_test() {
+        let tokens = vec!["position", "fen", "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR", "w", "-", "-", "0", "1", "moves", "h2e2"];
+        let result = parse_position(&tokens).unwrap();
+        assert_eq!(result.current_player_number, 2);
+        assert_eq!(result.position_hashes.len(), 2);
+    }
+
+    #[test]
+    fn parse_position_illegal_move_test() {
+        let tokens = vec!["position", "startpos", "moves", "a0a5"];
+        let result = parse_position(&tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_position_invalid_test() {
+        let tokens = vec!["position", "sfen"];
+        let result = parse_position(&tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_go_ucci_test() {
+        let tokens = vec!["go", "time", "300", "increment", "5", "opptime", "200"];
+        let result = parse_go(&tokens);
+        let expected = GoParams {
+            time: Some(300),
+            increment: Some(5),
+            ..GoParams::default()
+        };
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_go_uci_test() {
+        let tokens = vec!["go", "wtime", "60000", "btime", "50000", "winc", "1000"];
+        let result = parse_go(&tokens);
+        let expected = GoParams {
+            wtime: Some(60000),
+            btime: Some(50000),
+            winc: Some(1000),
+            ..GoParams::default()
+        };
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_go_depth_test() {
+        let tokens = vec!["go", "depth", "3"];
+        let result = parse_go(&tokens);
+        assert_eq!(result.depth, Some(3));
+    }
+
+    #[test]
+    fn parse_go_depth_infinite_test() {
+        let tokens = vec!["go", "depth", "infinite"];
+        let result = parse_go(&tokens);
+        assert!(result.infinite);
+    }
+
+    #[test]
+    fn time_budget_ucci_seconds_test() {
+        let params = GoParams { time: Some(400), increment: Some(5), ..GoParams::default() };
+        let result = time_budget(&params, 1, Protocol::Ucci, false);
+        assert_eq!(result, Some((400 / 40 + 5) * 1000 - 100));
+    }
+
+    #[test]
+    fn time_budget_ucci_millisec_test() {
+        let params = GoParams { time: Some(60000), movestogo: Some(20), ..GoParams::default() };
+        let result = time_budget(&params, 2, Protocol::Ucci, true);
+        assert_eq!(result, Some(60000 / 20 - 100));
+    }
+
+    #[test]
+    fn time_budget_uci_red_test() {
+        let params = GoParams { wtime: Some(60000), btime: Some(20000), winc: Some(1000), ..GoParams::default() };
+        let result = time_budget(&params, 1, Protocol::Uci, false);
+        assert_eq!(result, Some(60000 / 40 + 1000 - 100));
+    }
+
+    #[test]
+    fn time_budget_uci_black_test() {
+        let params = GoParams { wtime: Some(60000), btime: Some(20000), ..GoParams::default() };
+        let result = time_budget(&params, 2, Protocol::Uci, false);
+        assert_eq!(result, Some(20000 / 40 - 100));
+    }
+
+    #[test]
+    fn time_budget_movetime_test() {
+        let params = GoParams { movetime: Some(2000), ..GoParams::default() };
+        let result = time_budget(&params, 1, Protocol::Uci, false);
+        assert_eq!(result, Some(1900));
+    }
+
+    #[test]
+    fn time_budget_infinite_test() {
+        let params = GoParams { time: Some(60000), infinite: true, ..GoParams::default() };
+        let result = time_budget(&params, 1, Protocol::Ucci, true);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn time_budget_no_clock_test() {
+        let params = GoParams { depth: Some(3), ..GoParams::default() };
+        let result = time_budget(&params, 1, Protocol::Ucci, false);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn set_option_ucci_test() {
+        let mut options = default_options();
+        options.set(&["setoption", "usemillisec", "true"]).unwrap();
+        options.set(&["setoption", "MinimaxDepth", "3"]).unwrap();
+        options.set(&["setoption", "hashsize", "64"]).unwrap();
+        assert!(options.use_millisec);
+        assert_eq!(options.minimax_depth, 3);
+    }
+
+    #[test]
+    fn set_option_uci_test() {
+        let mut options = default_options();
+        options.set(&["setoption", "name", "Algorithm", "value", "mcts"]).unwrap();
+        options.set(&["setoption", "name", "Hash", "value", "256"]).unwrap();
+        assert_eq!(options.algorithm, Algorithm::Mcts);
+    }
+
+    #[test]
+    fn set_option_invalid_value_test() {
+        let mut options = default_options();
+        let result = options.set(&["setoption", "MinimaxDepth", "deep"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn format_options_test() {
+        let options = default_options();
+        let ucci = options.format(Protocol::Ucci);
+        let uci = options.format(Protocol::Uci);
+        assert_eq!(ucci[0], "option usemillisec type check default false");
+        assert_eq!(uci[0], "option name Algorithm type combo default minimax var minimax var mcts");
+    }
+
+    #[test]
+    fn search_reports_best_move_test() {
+        let game_state = parse_position(&["position", "startpos"]).unwrap();
+        let options = Options {
+            minimax_depth: 0,
+            ..default_options()
+        };
+        let search = Search {
+            protocol: Protocol::Ucci,
+            best_move: Mutex::new(None),
+            stopped: AtomicBool::new(false),
+            reported: AtomicBool::new(false)
+        };
+        run_search(game_state, options, GoParams::default(), &search);
+        assert!(search.best_move.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn search_go_depth_test() {
+        let game_state = parse_position(&["position", "fen", "4k4/9/9/9/9/9/9/9/9/R2K5", "w", "-", "-", "0", "1"]).unwrap();
+        let params = GoParams { depth: Some(1), ..GoParams::default() };
+        let search = Search {
+            protocol: Protocol::Ucci,
+            best_move: Mutex::new(None),
+            stopped: AtomicBool::new(false),
+            reported: AtomicBool::new(false)
+        };
+        run_search(game_state, default_options(), params, &search);
+        assert!(search.best_move.lock().unwrap().is_some());
+    }
+}
@@ -0,0 +1,504 @@
+// USI (Universal Shogi Interface) engine
+//
+// Reads commands from stdin and writes responses to stdout so that the engine
+// can be used from shogi GUIs, e.g.
+//
+//   usi
+//   isready
+//   usinewgame
+//   position startpos moves 7g7f 3c3d
+//   go btime 60000 wtime 60000 byoyomi 10000
+use std::env;
+use std::io::{self, BufRead};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use salieri::shogi;
+use salieri::shogi::record::STARTING_POSITION;
+use salieri::shogi::state::game_state::GameState;
+use salieri::shogi::state::mov::Move;
+use salieri::shogi::state::usi_mov;
+
+// time kept back for talking to the GUI
+const TIME_MARGIN: u64 = 100;
+
+// number of moves the remaining time is expected to last
+const MOVES_TO_GO: u64 = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Algorithm {
+    Minimax,
+    Mcts
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Options {
+    algorithm: Algorithm,
+    minimax_depth: i8,
+    mcts_simulation_count: i16,
+    mcts_simulation_depth: i16
+}
+
+impl Options {
+    fn from_env() -> Options {
+        Options {
+            algorithm: Algorithm::Minimax,
+            minimax_depth: env_number("SHOGI_MINIMAX_DEPTH", 1),
+            mcts_simulation_count: env_number("SHOGI_MCTS_SIMULATION_COUNT", 100),
+            mcts_simulation_depth: env_number("SHOGI_MCTS_SIMULATION_DEPTH", 50)
+        }
+    }
+
+    fn format(&self) -> Vec<String> {
+        let algorithm = match self.algorithm {
+            Algorithm::Minimax => "minimax",
+            Algorithm::Mcts => "mcts"
+        };
+
+        vec![
+            format!("option name Algorithm type combo default {} var minimax var mcts", algorithm),
+            format!("option name MinimaxDepth type spin default {} min 0 max 8", self.minimax_depth),
+            format!("option name MCTSSimulationCount type spin default {} min 1 max 10000", self.mcts_simulation_count),
+            format!("option name MCTSSimulationDepth type spin default {} min 1 max 1000", self.mcts_simulation_depth)
+        ]
+    }
+
+    // setoption name MinimaxDepth value 2
+    fn set(&mut self, tokens: &[&str]) -> Result<(), &'static str> {
+        let name = match tokens.iter().position(|t| *t == "name") {
+            Some(index) => tokens.get(index + 1).ok_or("Invalid Option")?,
+            None => return Err("Invalid Option")
+        };
+
+        let value = match tokens.iter().position(|t| *t == "value") {
+            Some(index) => tokens.get(index + 1).ok_or("Invalid Option Value")?,
+            None => return Err("Invalid Option Value")
+        };
+
+        match *name {
+            "Algorithm" => {
+                self.algorithm = match *value {
+                    "minimax" => Algorithm::Minimax,
+                    "mcts" => Algorithm::Mcts,
+                    _ => return Err("Invalid Option Value")
+                };
+            },
+            "MinimaxDepth" => {
+                self.minimax_depth = value.parse().map_err(|_| "Invalid Option Value")?;
+            },
+            "MCTSSimulationCount" => {
+                self.mcts_simulation_count = value.parse().map_err(|_| "Invalid Option Value")?;
+            },
+            "MCTSSimulationDepth" => {
+                self.mcts_simulation_depth = value.parse().map_err(|_| "Invalid Option Value")?;
+            },
+            // options every GUI sends, e.g. USI_Hash and USI_Ponder
+            _ => ()
+        }
+
+        Ok(())
+    }
+}
+
+fn env_number<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) => value.parse().unwrap_or(default),
+        Err(_) => default
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct GoParams {
+    btime: Option<u64>,
+    wtime: Option<u64>,
+    binc: Option<u64>,
+    winc: Option<u64>,
+    byoyomi: Option<u64>,
+    movetime: Option<u64>,
+    infinite: bool
+}
+
+// go btime 60000 wtime 60000 byoyomi 10000
+fn parse_go(tokens: &[&str]) -> GoParams {
+    let mut params = GoParams::default();
+    let mut iter = tokens.iter().skip(1);
+
+    while let Some(token) = iter.next() {
+        match *token {
+            "infinite" => params.infinite = true,
+            "btime" | "wtime" | "binc" | "winc" | "byoyomi" | "movetime" => {
+                let value = iter.next().and_then(|v| v.parse().ok());
+                match *token {
+                    "btime" => params.btime = value,
+                    "wtime" => params.wtime = value,
+                    "binc" => params.binc = value,
+                    "winc" => params.winc = value,
+                    "byoyomi" => params.byoyomi = value,
+                    _ => params.movetime = value
+                }
+            },
+            _ => ()
+        }
+    }
+
+    params
+}
+
+// milliseconds to search for, None when the search runs until it finishes or is stopped
+fn time_budget(params: &GoParams, player_number: i8) -> Option<u64> {
+    if params.infinite {
+        return None;
+    }
+
+    if let Some(movetime) = params.movetime {
+        return Some(movetime.saturating_sub(TIME_MARGIN).max(1));
+    }
+
+    let (remaining, increment) = if player_number == 1 {
+        (params.btime, params.binc)
+    } else {
+        (params.wtime, params.winc)
+    };
+
+    if remaining.is_none() && increment.is_none() && params.byoyomi.is_none() {
+        return None;
+    }
+
+    let budget = remaining.unwrap_or(0) / MOVES_TO_GO + increment.unwrap_or(0) + params.byoyomi.unwrap_or(0);
+
+    Some(budget.saturating_sub(TIME_MARGIN).max(1))
+}
+
+// position startpos moves 7g7f 3c3d
+// position sfen lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f
+fn parse_position(tokens: &[&str]) -> Result<GameState, &'static str> {
+    let encoded = match tokens.get(1) {
+        Some(&"startpos") => {
+            let mut encoded = STARTING_POSITION.to_string();
+            for token in tokens.iter().skip(2) {
+                encoded.push(' ');
+                encoded.push_str(token);
+            }
+            encoded
+        },
+        Some(&"sfen") => tokens[2..].join(" "),
+        _ => return Err("Invalid Position")
+    };
+
+    shogi::state::game_state::parse(&encoded)
+}
+
+// state shared between the command loop, the search thread and the timer thread
+struct Search {
+    best_move: Mutex<Option<Move>>,
+    stopped: AtomicBool,
+    reported: AtomicBool
+}
+
+impl Search {
+    fn report(&self) {
+        if self.reported.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        match *self.best_move.lock().unwrap() {
+            Some(ref mov) => println!("bestmove {}", usi_mov::format(mov, 9)),
+            None => println!("bestmove resign")
+        }
+    }
+
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.report();
+    }
+}
+
+fn start_search(game_state: &GameState, options: Options, params: GoParams) -> Arc<Search> {
+    let mut game_state = game_state.clone();
+
+    // fall back to any legal move in case time runs out before the first iteration
+    let first_move = if game_state.game_over() {
+        None
+    } else {
+        game_state.possible_moves().into_iter().next()
+    };
+    let has_moves = first_move.is_some();
+
+    let search = Arc::new(Search {
+        best_move: Mutex::new(first_move),
+        stopped: AtomicBool::new(false),
+        reported: AtomicBool::new(false)
+    });
+
+    if !has_moves {
+        search.report();
+        return search;
+    }
+
+    if let Some(budget) = time_budget(&params, game_state.current_player_number) {
+        let timer_search = Arc::clone(&search);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(budget));
+            timer_search.stop();
+        });
+    }
+
+    let search_thread_search = Arc::clone(&search);
+    thread::spawn(move || {
+        run_search(game_state, options, &search_thread_search);
+        if !params.infinite {
+            search_thread_search.report();
+        }
+    });
+
+    search
+}
+
+fn run_search(game_state: GameState, options: Options, search: &Search) {
+    let start = Instant::now();
+
+    match options.algorithm {
+        Algorithm::Minimax => {
+            // iterative deepening so that a result is available when time runs out
+            for depth in 0..=options.minimax_depth {
+                if search.stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let mut depth_game_state = game_state.clone();
+                match shogi::minimax::recommended_move(&mut depth_game_state, depth) {
+                    Some(mov) => {
+                        if search.stopped.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        println!("info depth {} time {} pv {}", depth + 1, start.elapsed().as_millis(), usi_mov::format(&mov, 9));
+                        *search.best_move.lock().unwrap() = Some(mov);
+                    },
+                    None => break
+                }
+            }
+        },
+        Algorithm::Mcts => {
+            let mut simulation_game_state = game_state;
+            if let Ok(mov) = shogi::mcts::recommended_move(&mut simulation_game_state, options.mcts_simulation_count, options.mcts_simulation_depth) {
+                if !search.stopped.load(Ordering::SeqCst) {
+                    println!("info time {} nodes {} pv {}", start.elapsed().as_millis(), options.mcts_simulation_count, usi_mov::format(&mov, 9));
+                    *search.best_move.lock().unwrap() = Some(mov);
+                }
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut options = Options::from_env();
+    let mut game_state = shogi::state::game_state::parse(&STARTING_POSITION.to_string()).ok();
+    let mut current_search: Option<Arc<Search>> = None;
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => break
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.first() {
+            Some(&"usi") => {
+                println!("id name salieri {}", env!("CARGO_PKG_VERSION"));
+                println!("id author {}", env!("CARGO_PKG_AUTHORS"));
+                for option in options.format() {
+                    println!("{}", option);
+                }
+                println!("usiok");
+            },
+            Some(&"isready") => println!("readyok"),
+            Some(&"setoption") => {
+                if let Err(e) = options.set(&tokens) {
+                    println!("info string {}", e);
+                }
+            },
+            Some(&"usinewgame") => (),
+            Some(&"position") => {
+                match parse_position(&tokens) {
+                    Ok(gs) => game_state = Some(gs),
+                    Err(e) => {
+                        game_state = None;
+                        println!("info string {}", e);
+                    }
+                }
+            },
+            Some(&"go") => {
+                if let Some(ref search) = current_search {
+                    search.stop();
+                }
+                current_search = match game_state {
+                    Some(ref gs) => Some(start_search(gs, options, parse_go(&tokens))),
+                    None => {
+                        println!("bestmove resign");
+                        None
+                    }
+                };
+            },
+            Some(&"stop") | Some(&"gameover") => {
+                if let Some(ref search) = current_search {
+                    search.stop();
+                }
+            },
+            Some(&"quit") => break,
+            _ => ()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_position_startpos_test() {
+        let tokens = vec!["position", "startpos"];
+        let result = parse_position(&tokens).unwrap();
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.position_hashes.len(), 1);
+    }
+
+    #[test]
+    fn parse_position_startpos_with_moves_test() {
+        let tokens = vec!["position", "startpos", "moves", "7g7f", "3c3d"];
+        let result = parse_position(&tokens).unwrap();
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.position_hashes.len(), 3);
+    }
+
+    #[test]
+    fn parse_position_sfen_with_moves_test() {
+        let tokens = vec!["position", "sfen", "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL", "b", "-", "1", "moves", "7g7f"];
+        let result = parse_position(&tokens).unwrap();
+        assert_eq!(result.current_player_number, 2);
+        assert_eq!(result.position_hashes.len(), 2);
+    }
+
+    #[test]
+    fn parse_position_illegal_move_test() {
+        let tokens = vec!["position", "startpos", "moves", "7g7c"];
+        let result = parse_position(&tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_position_invalid_test() {
+        let tokens = vec!["position", "fen"];
+        let result = parse_position(&tokens);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_go_test() {
+        let tokens = vec!["go", "btime", "60000", "wtime", "50000", "byoyomi", "10000"];
+        let result = parse_go(&tokens);
+        let expected = GoParams {
+            btime: Some(60000),
+            wtime: Some(50000),
+            byoyomi: Some(10000),
+            ..GoParams::default()
+        };
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_go_infinite_test() {
+        let tokens = vec!["go", "infinite"];
+        let result = parse_go(&tokens);
+        assert!(result.infinite);
+    }
+
+    #[test]
+    fn time_budget_sente_test() {
+        let params = GoParams { btime: Some(60000), wtime: Some(20000), byoyomi: Some(10000), ..GoParams::default() };
+        let result = time_budget(&params, 1);
+        assert_eq!(result, Some(60000 / 40 + 10000 - 100));
+    }
+
+    #[test]
+    fn time_budget_gote_test() {
+        let params = GoParams { btime: Some(60000), wtime: Some(20000), winc: Some(1000), ..GoParams::default() };
+        let result = time_budget(&params, 2);
+        assert_eq!(result, Some(20000 / 40 + 1000 - 100));
+    }
+
+    #[test]
+    fn time_budget_movetime_test() {
+        let params = GoParams { movetime: Some(2000), ..GoParams::default() };
+        let result = time_budget(&params, 1);
+        assert_eq!(result, Some(1900));
+    }
+
+    #[test]
+    fn time_budget_out_of_time_test() {
+        let params = GoParams { btime: Some(0), byoyomi: Some(0), ..GoParams::default() };
+        let result = time_budget(&params, 1);
+        assert_eq!(result, Some(1));
+    }
+
+    #[test]
+    fn time_budget_infinite_test() {
+        let params = GoParams { btime: Some(60000), infinite: true, ..GoParams::default() };
+        let result = time_budget(&params, 1);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn time_budget_no_clock_test() {
+        let params = GoParams::default();
+        let result = time_budget(&params, 1);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn set_option_test() {
+        let mut options = Options {
+            algorithm: Algorithm::Minimax,
+            minimax_depth: 1,
+            mcts_simulation_count: 100,
+            mcts_simulation_depth: 50
+        };
+        options.set(&["setoption", "name", "Algorithm", "value", "mcts"]).unwrap();
+        options.set(&["setoption", "name", "MinimaxDepth", "value", "2"]).unwrap();
+        options.set(&["setoption", "name", "USI_Hash", "value", "256"]).unwrap();
+        assert_eq!(options.algorithm, Algorithm::Mcts);
+        assert_eq!(options.minimax_depth, 2);
+    }
+
+    #[test]
+    fn set_option_invalid_value_test() {
+        let mut options = Options {
+            algorithm: Algorithm::Minimax,
+            minimax_depth: 1,
+            mcts_simulation_count: 100,
+            mcts_simulation_depth: 50
+        };
+        let result = options.set(&["setoption", "name", "MinimaxDepth", "value", "deep"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn search_reports_best_move_test() {
+        let game_state = parse_position(&["position", "startpos"]).unwrap();
+        let options = Options {
+            algorithm: Algorithm::Minimax,
+            minimax_depth: 0,
+            mcts_simulation_count: 1,
+            mcts_simulation_depth: 1
+        };
+        let search = Search {
+            best_move: Mutex::new(None),
+            stopped: AtomicBool::new(false),
+            reported: AtomicBool::new(false)
+        };
+        run_search(game_state, options, &search);
+        assert!(search.best_move.lock().unwrap().is_some());
+    }
+}
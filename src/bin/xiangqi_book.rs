@@ -0,0 +1,185 @@
+// Builds a xiangqi opening book from game records
+//
+//   xiangqi_book [-m moves] [-o path] records...
+//
+// Records can be Xiangqi PGN (.pgn), which may hold several games, DhtmlXQ
+// (any text file with a [DhtmlXQ] block) or XQF (.xqf). The moves played from
+// each position and the results of their games are written to stdout unless a
+// path is given. The server loads the book from XIANGQI_BOOK_PATH, e.g.
+//
+//   xiangqi_book -m 16 -o book.txt games/*.pgn games/*.xqf
+//   XIANGQI_BOOK_PATH=book.txt salieri
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use salieri::xiangqi::openings::book;
+use salieri::xiangqi::record::GameRecord;
+use salieri::xiangqi::record::dhtmlxq;
+use salieri::xiangqi::record::pgn;
+use salieri::xiangqi::record::xqf;
+
+// number of moves from the start of each record added to the book
+const DEFAULT_MOVE_COUNT: usize = 20;
+
+const HEADER: &str = "# board, player to move, move, games, red wins, black wins, draws\n";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    move_count: usize,
+    output_path: Option<String>,
+    record_paths: Vec<String>
+}
+
+fn parse_args(args: &[String]) -> Result<Options, &'static str> {
+    let mut options = Options {
+        move_count: DEFAULT_MOVE_COUNT,
+        output_path: None,
+        record_paths: vec![]
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-m" => {
+                options.move_count = match args.next().map(|a| a.parse()) {
+                    Some(Ok(n)) => n,
+                    _ => return Err("-m must be followed by a number of moves")
+                };
+            },
+            "-o" => {
+                options.output_path = match args.next() {
+                    Some(path) => Some(path.clone()),
+                    None => return Err("-o must be followed by a path")
+                };
+            },
+            _ => options.record_paths.push(arg.clone())
+        }
+    }
+
+    if options.record_paths.is_empty() {
+        return Err("usage: xiangqi_book [-m moves] [-o path] records...");
+    }
+
+    Ok(options)
+}
+
+// the games of a record file, by extension for XQF and by content for DhtmlXQ and PGN
+fn parse_records(path: &str, encoded: &[u8]) -> Result<Vec<GameRecord>, &'static str> {
+    let extension = Path::new(path).extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase());
+
+    if extension.as_deref() == Some("xqf") {
+        return Ok(vec![xqf::parse(encoded)?]);
+    }
+
+    let text = match std::str::from_utf8(encoded) {
+        Ok(t) => t,
+        Err(_) => return Err("records must be UTF-8")
+    };
+
+    if text.contains("[DhtmlXQ") {
+        Ok(vec![dhtmlxq::parse(text)?])
+    } else {
+        pgn::parse_all(text)
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+
+    let mut records: Vec<GameRecord> = vec![];
+    for path in options.record_paths.iter() {
+        let encoded = match fs::read(path) {
+            Ok(e) => e,
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                process::exit(1);
+            }
+        };
+
+        match parse_records(path, &encoded) {
+            Ok(r) => records.extend(r),
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    let encoded = match book::build(&records, options.move_count) {
+        Ok(b) => format!("{}{}", HEADER, book::format(&b)),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    match options.output_path {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, encoded) {
+                eprintln!("{}: {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => print!("{}", encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_test() {
+        let result = parse_args(&args(&["-m", "12", "a.pgn", "-o", "book.txt", "b.xqf"])).unwrap();
+        assert_eq!(result, Options {
+            move_count: 12,
+            output_path: Some(String::from("book.txt")),
+            record_paths: args(&["a.pgn", "b.xqf"])
+        });
+    }
+
+    #[test]
+    fn parse_args_defaults_test() {
+        let result = parse_args(&args(&["a.pgn"])).unwrap();
+        assert_eq!(result.move_count, DEFAULT_MOVE_COUNT);
+        assert_eq!(result.output_path, None);
+    }
+
+    #[test]
+    fn parse_args_invalid_test() {
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["-m", "many", "a.pgn"])).is_err());
+        assert!(parse_args(&args(&["a.pgn", "-o"])).is_err());
+    }
+
+    #[test]
+    fn parse_records_pgn_test() {
+        let result = parse_records("games.pgn", b"[Result \"*\"]\n1. C2=5 H8+7 *\n\n[Result \"*\"]\n1. P7+1 *\n").unwrap();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn parse_records_dhtmlxq_test() {
+        let result = parse_records("game.htm", "<p>[DhtmlXQ][DhtmlXQ_movelist]7747[/DhtmlXQ_movelist][/DhtmlXQ]</p>".as_bytes()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].moves.len(), 1);
+    }
+
+    #[test]
+    fn parse_records_xqf_test() {
+        assert_eq!(parse_records("game.XQF", b"XQ").err(), Some("xqf::parse - Invalid header"));
+    }
+}
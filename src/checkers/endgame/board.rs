@@ -0,0 +1,388 @@
+use crate::checkers::endgame::index::Material;
+use crate::checkers::state::game_state::GameState;
+use crate::checkers::state::point::DIAGONAL_DIRECTIONS;
+use crate::checkers::state::point::ID_COORDINATE_MAP;
+use crate::checkers::state::point::point_to_id;
+use crate::checkers::state::square::Square;
+use crate::checkers::state::square_set::find_by_x_and_y;
+
+const SQUARE_COUNT: usize = 32;
+
+// past the edge of the board
+const NONE: u8 = 32;
+
+// the sets of a board, in the order of Material
+const BLACK_MEN: usize = 0;
+const BLACK_KINGS: usize = 1;
+const WHITE_MEN: usize = 2;
+const WHITE_KINGS: usize = 3;
+
+// the squares men promote on, 29 to 32 for black and 1 to 4 for white, a man can't stand on its own
+const BLACK_PROMOTION_SQUARES: u32 = 0xf000_0000;
+const WHITE_PROMOTION_SQUARES: u32 = 0x0000_000f;
+
+// indexes of DIAGONAL_DIRECTIONS, black men move down the board and white men up
+const BLACK_DIRECTIONS: [usize; 2] = [0, 2];
+const WHITE_DIRECTIONS: [usize; 2] = [1, 3];
+const KING_DIRECTIONS: [usize; 4] = [0, 1, 2, 3];
+
+// the square one and two steps along each diagonal from each square
+const STEPS: [[u8; 4]; SQUARE_COUNT] = steps(1);
+const JUMPS: [[u8; 4]; SQUARE_COUNT] = steps(2);
+
+// a standard checkers position as the squares of each kind of piece, bit n for square n + 1, which the
+// generator moves on without building a GameState for every position
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Board {
+    pub sets: [u32; 4],
+    pub current_player_number: i8
+}
+
+impl Board {
+    pub fn of(game_state: &GameState) -> Board {
+        let mut sets = [0u32; 4];
+        for point in ID_COORDINATE_MAP.iter().skip(1) {
+            if let Some(square) = find_by_x_and_y(&game_state.squares, point.0, point.1) {
+                let kind = match (square.player_number, square.king) {
+                    (1, false) => BLACK_MEN,
+                    (1, true) => BLACK_KINGS,
+                    (2, false) => WHITE_MEN,
+                    (2, true) => WHITE_KINGS,
+                    _ => continue
+                };
+                sets[kind] |= 1 << (point_to_id(*point) - 1);
+            }
+        }
+        Board { sets, current_player_number: game_state.current_player_number }
+    }
+
+    pub fn game_state(&self) -> GameState {
+        let mut squares = vec![vec![Square { player_number: 0, king: false }; 8]; 8];
+        for (kind, set) in self.sets.iter().enumerate() {
+            for square_number in squares_of(*set) {
+                let point = ID_COORDINATE_MAP[square_number + 1];
+                let player_number = if kind == BLACK_MEN || kind == BLACK_KINGS { 1 } else { 2 };
+                let king = kind == BLACK_KINGS || kind == WHITE_KINGS;
+                squares[point.1 as usize][point.0 as usize] = Square { player_number, king };
+            }
+        }
+        GameState::new(self.current_player_number, squares)
+    }
+
+    pub fn material(&self) -> Material {
+        Material {
+            black_men: self.sets[BLACK_MEN].count_ones() as u8,
+            black_kings: self.sets[BLACK_KINGS].count_ones() as u8,
+            white_men: self.sets[WHITE_MEN].count_ones() as u8,
+            white_kings: self.sets[WHITE_KINGS].count_ones() as u8
+        }
+    }
+
+    // no man stands on the row it promotes on
+    pub fn valid(&self) -> bool {
+        self.sets[BLACK_MEN] & BLACK_PROMOTION_SQUARES == 0 && self.sets[WHITE_MEN] & WHITE_PROMOTION_SQUARES == 0
+    }
+
+    // the positions the moves of the player to move lead to, as GameState::possible_moves plays them:
+    // captures are forced and continue while the piece can jump, and men promote where the move ends
+    pub fn successors(&self, acc: &mut Vec<Board>) {
+        acc.clear();
+        let (men, kings) = kinds(self.current_player_number);
+
+        for kind in [men, kings] {
+            for square_number in squares_of(self.sets[kind]) {
+                let mut lifted = *self;
+                lifted.sets[kind] &= !(1 << square_number);
+                lifted.jump_sequences(square_number, kind, false, acc);
+            }
+        }
+
+        if !acc.is_empty() {
+            return;
+        }
+
+        let occupied = self.occupied();
+        for kind in [men, kings] {
+            for square_number in squares_of(self.sets[kind]) {
+                for direction in directions(kind) {
+                    let to = STEPS[square_number][*direction];
+                    if to != NONE && occupied & (1 << to) == 0 {
+                        let mut lifted = *self;
+                        lifted.sets[kind] &= !(1 << square_number);
+                        acc.push(lifted.land(to as usize, kind));
+                    }
+                }
+            }
+        }
+    }
+
+    // the positions that lead here by a move that neither captures nor promotes, the moves the generator
+    // follows within a material. A piece of the player who just moved steps back, men the way the
+    // opponent's men move, to where that player had no capture
+    pub fn previous(&self, acc: &mut Vec<Board>) {
+        acc.clear();
+        let player_number = opposing_player(self.current_player_number);
+        let (men, kings) = kinds(player_number);
+        let (opposing_men, _) = kinds(self.current_player_number);
+        let occupied = self.occupied();
+
+        for kind in [men, kings] {
+            let back_directions = if kind == men { directions(opposing_men) } else { &KING_DIRECTIONS[..] };
+            for square_number in squares_of(self.sets[kind]) {
+                for direction in back_directions {
+                    let from = STEPS[square_number][*direction];
+                    if from == NONE || occupied & (1 << from) != 0 {
+                        continue;
+                    }
+
+                    let mut board = *self;
+                    board.sets[kind] ^= (1 << square_number) | (1 << from);
+                    board.current_player_number = player_number;
+                    if !board.has_jump() {
+                        acc.push(board);
+                    }
+                }
+            }
+        }
+    }
+
+    // the player to move can capture
+    pub fn has_jump(&self) -> bool {
+        let (men, kings) = kinds(self.current_player_number);
+        let opponent = self.opponent();
+        let occupied = self.occupied();
+
+        [men, kings].iter().any(|kind| {
+            squares_of(self.sets[*kind]).any(|square_number| {
+                directions(*kind).iter().any(|direction| {
+                    let over = STEPS[square_number][*direction];
+                    let to = JUMPS[square_number][*direction];
+                    to != NONE && opponent & (1 << over) != 0 && occupied & (1 << to) == 0
+                })
+            })
+        })
+    }
+
+    // the lifted piece jumps on from the square, where it lands when it can't
+    fn jump_sequences(&self, square_number: usize, kind: usize, jumped: bool, acc: &mut Vec<Board>) {
+        let opponent = self.opponent();
+        let occupied = self.occupied();
+        let mut landed = true;
+
+        for direction in directions(kind) {
+            let over = STEPS[square_number][*direction];
+            let to = JUMPS[square_number][*direction];
+            if to != NONE && opponent & (1 << over) != 0 && occupied & (1 << to) == 0 {
+                let mut board = *self;
+                for set in board.sets.iter_mut() {
+                    *set &= !(1 << over);
+                }
+                board.jump_sequences(to as usize, kind, true, acc);
+                landed = false;
+            }
+        }
+
+        if landed && jumped {
+            acc.push(self.land(square_number, kind));
+        }
+    }
+
+    // puts the lifted piece down, promoting a man on the far row, and passes the move
+    fn land(&self, square_number: usize, kind: usize) -> Board {
+        let kind = match kind {
+            BLACK_MEN if BLACK_PROMOTION_SQUARES & (1 << square_number) != 0 => BLACK_KINGS,
+            WHITE_MEN if WHITE_PROMOTION_SQUARES & (1 << square_number) != 0 => WHITE_KINGS,
+            _ => kind
+        };
+
+        let mut board = *self;
+        board.sets[kind] |= 1 << square_number;
+        board.current_player_number = opposing_player(self.current_player_number);
+        board
+    }
+
+    fn occupied(&self) -> u32 {
+        self.sets.iter().fold(0, |acc, set| acc | set)
+    }
+
+    fn opponent(&self) -> u32 {
+        match self.current_player_number {
+            1 => self.sets[WHITE_MEN] | self.sets[WHITE_KINGS],
+            _ => self.sets[BLACK_MEN] | self.sets[BLACK_KINGS]
+        }
+    }
+}
+
+fn kinds(player_number: i8) -> (usize, usize) {
+    match player_number {
+        1 => (BLACK_MEN, BLACK_KINGS),
+        _ => (WHITE_MEN, WHITE_KINGS)
+    }
+}
+
+fn directions(kind: usize) -> &'static [usize] {
+    match kind {
+        BLACK_MEN => &BLACK_DIRECTIONS,
+        WHITE_MEN => &WHITE_DIRECTIONS,
+        _ => &KING_DIRECTIONS
+    }
+}
+
+fn opposing_player(player_number: i8) -> i8 {
+    if player_number == 1 { 2 } else { 1 }
+}
+
+// the square numbers of a set, lowest first
+fn squares_of(set: u32) -> impl Iterator<Item = usize> {
+    let mut remaining = set;
+    std::iter::from_fn(move || {
+        if remaining == 0 {
+            return None;
+        }
+        let square_number = remaining.trailing_zeros() as usize;
+        remaining &= remaining - 1;
+        Some(square_number)
+    })
+}
+
+const fn steps(distance: i8) -> [[u8; 4]; SQUARE_COUNT] {
+    let mut table = [[NONE; 4]; SQUARE_COUNT];
+    let mut square_number = 0;
+    while square_number < SQUARE_COUNT {
+        let point = ID_COORDINATE_MAP[square_number + 1];
+        let mut direction = 0;
+        while direction < 4 {
+            let x = point.0 + distance * DIAGONAL_DIRECTIONS[direction].0;
+            let y = point.1 + distance * DIAGONAL_DIRECTIONS[direction].1;
+            if x >= 0 && x < 8 && y >= 0 && y < 8 {
+                // point_to_id as arithmetic, four dark squares to a row from the top right
+                table[square_number][direction] = ((7 - y) * 4 + (7 - x - y % 2) / 2) as u8;
+            }
+            direction += 1;
+        }
+        square_number += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::endgame::index::board_at;
+    use crate::checkers::state::game_state::parse;
+
+    fn board(encoded: &str) -> Board {
+        Board::of(&parse(&String::from(encoded)).unwrap())
+    }
+
+    fn sorted_sets(boards: &[Board]) -> Vec<([u32; 4], i8)> {
+        let mut result: Vec<([u32; 4], i8)> = boards.iter().map(|b| (b.sets, b.current_player_number)).collect();
+        result.sort();
+        result
+    }
+
+    fn game_state_successors(board: &Board) -> Vec<Board> {
+        let game_state = board.game_state();
+        game_state.possible_moves().iter().map(|mov| {
+            let mut next = game_state.clone();
+            next.perform_move(mov).unwrap();
+            Board::of(&next)
+        }).collect()
+    }
+
+    #[test]
+    fn steps_test() {
+        // 14 is at (5, 4), 9 and 10 are above it and 17 and 18 below
+        assert_eq!(STEPS[13], [17, 9, 16, 8]);
+        assert_eq!(JUMPS[13], [22, 6, 20, 4]);
+        // 4 is in the corner
+        assert_eq!(STEPS[3], [NONE, NONE, 7, NONE]);
+    }
+
+    #[test]
+    fn of_and_game_state_test() {
+        let game_state = parse(&String::from("W:W21,K30:B5,K12")).unwrap();
+        let result = Board::of(&game_state);
+        assert_eq!(result.material(), Material { black_men: 1, black_kings: 1, white_men: 1, white_kings: 1 });
+        assert!(result.game_state().squares == game_state.squares);
+        assert_eq!(result.game_state().current_player_number, 2);
+    }
+
+    #[test]
+    fn valid_test() {
+        assert!(board("B:W29:B1").valid());
+        assert!(!board("B:W1:B5").valid());
+        assert!(!board("B:W5:B29").valid());
+    }
+
+    #[test]
+    fn successors_jump_test() {
+        // the king takes both men, the capture is forced
+        let mut result = vec![];
+        board("B:W6,14:BK1").successors(&mut result);
+        assert_eq!(sorted_sets(&result), sorted_sets(&[board("W:W:BK17")]));
+    }
+
+    #[test]
+    fn successors_promote_test() {
+        let mut result = vec![];
+        board("B:WK1:B25").successors(&mut result);
+        assert_eq!(sorted_sets(&result), sorted_sets(&[board("W:WK1:BK29"), board("W:WK1:BK30")]));
+    }
+
+    #[test]
+    fn successors_match_game_state_test() {
+        let mut result = vec![];
+        for material in Material::all(3) {
+            for n in (0..material.size()).step_by(7) {
+                if let Some(board) = board_at(&material, n) {
+                    board.successors(&mut result);
+                    assert_eq!(sorted_sets(&result), sorted_sets(&game_state_successors(&board)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn previous_test() {
+        // stepping back from 14 or 15 would leave a capture of the man on 18
+        let mut result = vec![];
+        board("W:W18:BK10").previous(&mut result);
+        assert_eq!(sorted_sets(&result), sorted_sets(&[board("B:W18:BK6"), board("B:W18:BK7")]));
+    }
+
+    #[test]
+    fn previous_man_test() {
+        // the man hasn't left its first row
+        let mut result = vec![];
+        board("W:WK32:B1").previous(&mut result);
+        assert!(result.is_empty());
+
+        board("W:WK32:B5").previous(&mut result);
+        assert_eq!(sorted_sets(&result), sorted_sets(&[board("B:WK32:B1")]));
+    }
+
+    #[test]
+    fn previous_match_successors_test() {
+        let material = Material { black_men: 1, black_kings: 1, white_men: 1, white_kings: 0 };
+        let mut previous = vec![];
+        let mut successors = vec![];
+        for n in (0..material.size()).step_by(5) {
+            if let Some(board) = board_at(&material, n) {
+                // every position a move leads to within the material takes the move back
+                board.successors(&mut successors);
+                for next in successors.iter().filter(|b| b.material() == material) {
+                    next.previous(&mut previous);
+                    assert!(previous.contains(&board));
+                }
+
+                // and every position taken back plays it
+                board.previous(&mut previous);
+                for before in previous.iter() {
+                    before.successors(&mut successors);
+                    assert!(successors.contains(&board));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use crate::checkers::endgame::Wld;
+use crate::checkers::endgame::index::Material;
+use crate::checkers::endgame::index::index;
+use crate::checkers::state::game_state::GameState;
+use crate::checkers::state::variant::Variant;
+
+const SIGNATURE: &[u8] = b"CKDB";
+const VERSION: u8 = 1;
+
+// four 2 bit values to a byte
+const VALUES_PER_BYTE: usize = 4;
+
+// win, loss or draw for the player to move in every position of each material
+#[derive(Debug, PartialEq)]
+pub struct Database {
+    pub max_pieces: u8,
+    slices: HashMap<Material, Vec<u8>>
+}
+
+impl Database {
+    pub fn new(max_pieces: u8) -> Database {
+        Database { max_pieces, slices: HashMap::new() }
+    }
+
+    pub fn contains(&self, material: &Material) -> bool {
+        self.slices.contains_key(material)
+    }
+
+    pub fn get(&self, material: &Material, index: usize) -> Option<Wld> {
+        let values = self.slices.get(material)?;
+        let byte = values.get(index / VALUES_PER_BYTE)?;
+        decode((byte >> (2 * (index % VALUES_PER_BYTE))) & 0b11)
+    }
+
+    // checkers positions with up to max_pieces pieces
+    pub fn probe(&self, game_state: &GameState) -> Option<Wld> {
+        let material = Material::of(game_state);
+        if game_state.variant != Variant::Standard || material.pieces() > self.max_pieces {
+            return None;
+        }
+        let (material, n) = index(game_state);
+        self.get(&material, n)
+    }
+
+    pub fn insert(&mut self, material: Material, values: &[Wld]) {
+        let mut packed = vec![0u8; values.len().div_ceil(VALUES_PER_BYTE)];
+        for (n, value) in values.iter().enumerate() {
+            packed[n / VALUES_PER_BYTE] |= encode(*value) << (2 * (n % VALUES_PER_BYTE));
+        }
+        self.slices.insert(material, packed);
+    }
+
+    // "CKDB", the version and the maximum number of pieces, then for each material in the order of Material::all
+    // the black men, black kings, white men and white kings, the number of positions as a little endian u32
+    // and the values of the positions, four to a byte from the low bits
+    pub fn format(&self) -> Vec<u8> {
+        let mut encoded = vec![];
+        encoded.extend_from_slice(SIGNATURE);
+        encoded.push(VERSION);
+        encoded.push(self.max_pieces);
+
+        for material in Material::all(self.max_pieces).iter() {
+            if let Some(values) = self.slices.get(material) {
+                encoded.extend_from_slice(&[material.black_men, material.black_kings, material.white_men, material.white_kings]);
+                encoded.extend_from_slice(&(material.size() as u32).to_le_bytes());
+                encoded.extend_from_slice(values);
+            }
+        }
+
+        encoded
+    }
+
+    pub fn parse(encoded: &[u8]) -> Result<Database, &'static str> {
+        if encoded.len() < SIGNATURE.len() + 2 || !encoded.starts_with(SIGNATURE) {
+            return Err("database::parse - Invalid header");
+        }
+
+        if encoded[SIGNATURE.len()] != VERSION {
+            return Err("database::parse - Unsupported version");
+        }
+
+        let mut database = Database::new(encoded[SIGNATURE.len() + 1]);
+        let mut offset = SIGNATURE.len() + 2;
+
+        while offset < encoded.len() {
+            let header = match encoded.get(offset..offset + 8) {
+                Some(h) => h,
+                None => return Err("database::parse - Truncated material")
+            };
+            let material = Material { black_men: header[0], black_kings: header[1], white_men: header[2], white_kings: header[3] };
+            let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+            if material.pieces() == 0 || material.pieces() > database.max_pieces || size != material.size() {
+                return Err("database::parse - Invalid material");
+            }
+
+            let length = size.div_ceil(VALUES_PER_BYTE);
+            let values = match encoded.get(offset + 8..offset + 8 + length) {
+                Some(v) => v,
+                None => return Err("database::parse - Truncated material")
+            };
+
+            database.slices.insert(material, values.to_vec());
+            offset += 8 + length;
+        }
+
+        Ok(database)
+    }
+}
+
+fn encode(value: Wld) -> u8 {
+    match value {
+        Wld::Draw => 0,
+        Wld::Win => 1,
+        Wld::Loss => 2
+    }
+}
+
+fn decode(value: u8) -> Option<Wld> {
+    match value {
+        0 => Some(Wld::Draw),
+        1 => Some(Wld::Win),
+        2 => Some(Wld::Loss),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::state::game_state::parse;
+
+    #[test]
+    fn insert_and_get_test() {
+        let material = Material { black_men: 0, black_kings: 1, white_men: 0, white_kings: 0 };
+        let mut database = Database::new(1);
+        let mut values = vec![Wld::Draw; material.size()];
+        values[5] = Wld::Win;
+        values[6] = Wld::Loss;
+        database.insert(material, &values);
+
+        assert_eq!(database.get(&material, 4), Some(Wld::Draw));
+        assert_eq!(database.get(&material, 5), Some(Wld::Win));
+        assert_eq!(database.get(&material, 6), Some(Wld::Loss));
+        assert_eq!(database.get(&material, material.size() + 4), None);
+    }
+
+    #[test]
+    fn probe_too_many_pieces_test() {
+        let database = Database::new(1);
+        let game_state = parse(&String::from("B:WK30:BK5")).unwrap();
+        assert_eq!(database.probe(&game_state), None);
+    }
+
+    #[test]
+    fn probe_international_test() {
+        let material = Material { black_men: 0, black_kings: 1, white_men: 0, white_kings: 1 };
+        let mut database = Database::new(2);
+        database.insert(material, &vec![Wld::Draw; material.size()]);
+        let game_state = crate::checkers::state::game_state::parse_with_variant(&String::from("B:WK30:BK5"), Variant::International).unwrap();
+        assert_eq!(database.probe(&game_state), None);
+    }
+
+    #[test]
+    fn format_and_parse_test() {
+        let material = Material { black_men: 0, black_kings: 0, white_men: 1, white_kings: 0 };
+        let mut database = Database::new(1);
+        let mut values = vec![Wld::Loss; material.size()];
+        values[0] = Wld::Win;
+        database.insert(material, &values);
+
+        let encoded = database.format();
+        assert!(encoded.starts_with(b"CKDB"));
+        assert_eq!(encoded.len(), 6 + 8 + 16);
+        assert_eq!(Database::parse(&encoded).unwrap(), database);
+    }
+
+    #[test]
+    fn parse_invalid_test() {
+        assert_eq!(Database::parse(b"CKDA\x01\x01").err(), Some("database::parse - Invalid header"));
+        assert_eq!(Database::parse(b"CKDB\x02\x01").err(), Some("database::parse - Unsupported version"));
+        assert_eq!(Database::parse(b"CKDB\x01\x01\x00\x01\x00\x00\x40\x00\x00\x00").err(), Some("database::parse - Truncated material"));
+        assert_eq!(Database::parse(b"CKDB\x01\x01\x00\x01\x00\x00\x41\x00\x00\x00").err(), Some("database::parse - Invalid material"));
+    }
+}
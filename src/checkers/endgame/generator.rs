@@ -0,0 +1,207 @@
+use std::collections::VecDeque;
+use crate::checkers::endgame::Wld;
+use crate::checkers::endgame::database::Database;
+use crate::checkers::endgame::index::Material;
+use crate::checkers::endgame::index::board_at;
+use crate::checkers::endgame::index::board_index;
+
+// every material with up to max_pieces pieces
+pub fn generate(max_pieces: u8) -> Result<Database, &'static str> {
+    let mut database = Database::new(max_pieces);
+    for material in Material::all(max_pieces) {
+        generate_material(&mut database, material)?;
+    }
+    Ok(database)
+}
+
+// retrograde analysis of one material, the materials its captures and promotions lead to must already be in the database
+//
+// positions without moves are lost, a position is won when a move leads to a lost position and lost when every move
+// leads to a won one. Moves into other materials are looked up first and the moves within the material are only
+// counted. Each position resolved is queued, and its previous positions are won when it is lost, or lost once
+// it is won and none of their moves are left unresolved. What is left is drawn
+pub fn generate_material(database: &mut Database, material: Material) -> Result<(), &'static str> {
+    let size = material.size();
+    if size > u32::MAX as usize {
+        return Err("generator::generate_material - Too many positions");
+    }
+
+    let mut values: Vec<Option<Wld>> = vec![None; size];
+
+    // moves within the material of each unresolved position not yet known to win, and whether every other move leads to a win
+    let mut unresolved_counts = vec![0u8; size];
+    let mut can_lose = vec![false; size];
+    let mut queue: VecDeque<u32> = VecDeque::new();
+    let mut boards = vec![];
+
+    for n in 0..size {
+        let board = match board_at(&material, n) {
+            Some(b) => b,
+            None => {
+                values[n] = Some(Wld::Draw);
+                continue;
+            }
+        };
+
+        let mut count = 0u8;
+        let mut any_loss = false;
+        let mut all_win = true;
+
+        board.successors(&mut boards);
+        for next in boards.iter() {
+            let (next_material, next_index) = board_index(next);
+            if next_material == material {
+                count += 1;
+            } else {
+                match database.get(&next_material, next_index) {
+                    Some(Wld::Loss) => any_loss = true,
+                    Some(Wld::Win) => (),
+                    Some(Wld::Draw) => all_win = false,
+                    None => return Err("generator::generate_material - Missing material")
+                }
+            }
+        }
+
+        if any_loss {
+            values[n] = Some(Wld::Win);
+            queue.push_back(n as u32);
+        } else if count == 0 {
+            if all_win {
+                values[n] = Some(Wld::Loss);
+                queue.push_back(n as u32);
+            } else {
+                values[n] = Some(Wld::Draw);
+            }
+        } else {
+            unresolved_counts[n] = count;
+            can_lose[n] = all_win;
+        }
+    }
+
+    while let Some(n) = queue.pop_front() {
+        let value = values[n as usize];
+        let board = match board_at(&material, n as usize) {
+            Some(b) => b,
+            None => continue
+        };
+
+        board.previous(&mut boards);
+        for previous in boards.iter() {
+            let (_, previous) = board_index(previous);
+            if values[previous].is_some() {
+                continue;
+            }
+
+            if value == Some(Wld::Loss) {
+                values[previous] = Some(Wld::Win);
+                queue.push_back(previous as u32);
+            } else {
+                unresolved_counts[previous] -= 1;
+                if unresolved_counts[previous] == 0 && can_lose[previous] {
+                    values[previous] = Some(Wld::Loss);
+                    queue.push_back(previous as u32);
+                }
+            }
+        }
+    }
+
+    let values: Vec<Wld> = values.into_iter().map(|v| v.unwrap_or(Wld::Draw)).collect();
+    database.insert(material, &values);
+
+    Ok(())
+}
+
+// every position agrees with the positions its moves lead to
+pub fn verify(database: &Database) -> Result<(), &'static str> {
+    let mut boards = vec![];
+
+    for material in Material::all(database.max_pieces) {
+        if !database.contains(&material) {
+            return Err("generator::verify - Missing material");
+        }
+
+        for n in 0..material.size() {
+            let board = match board_at(&material, n) {
+                Some(b) => b,
+                None => continue
+            };
+
+            let mut next_values = vec![];
+            board.successors(&mut boards);
+            for next in boards.iter() {
+                let (next_material, next_index) = board_index(next);
+                match database.get(&next_material, next_index) {
+                    Some(v) => next_values.push(v),
+                    None => return Err("generator::verify - Missing material")
+                }
+            }
+
+            let expected = if next_values.contains(&Wld::Loss) {
+                Wld::Win
+            } else if next_values.iter().all(|v| *v == Wld::Win) {
+                Wld::Loss
+            } else {
+                Wld::Draw
+            };
+
+            if database.get(&material, n) != Some(expected) {
+                return Err("generator::verify - Inconsistent position");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::state::game_state::parse;
+
+    fn probe(database: &Database, encoded: &str) -> Option<Wld> {
+        database.probe(&parse(&String::from(encoded)).unwrap())
+    }
+
+    #[test]
+    fn generate_two_pieces_test() {
+        let database = generate(2).unwrap();
+
+        // the opponent has nothing left to move
+        assert_eq!(probe(&database, "B:W:BK5"), Some(Wld::Win));
+        // the king captures
+        assert_eq!(probe(&database, "B:WK6:BK1"), Some(Wld::Win));
+        assert_eq!(probe(&database, "W:WK10:BK6"), Some(Wld::Win));
+        // kings can't force anything
+        assert_eq!(probe(&database, "B:WK32:BK1"), Some(Wld::Draw));
+        // the king catches the man wherever it goes
+        assert_eq!(probe(&database, "B:WK9:B3"), Some(Wld::Loss));
+
+        assert!(verify(&database).is_ok());
+    }
+
+    #[test]
+    fn generate_three_pieces_test() {
+        let mut database = generate(2).unwrap();
+        database.max_pieces = 3;
+        generate_material(&mut database, Material { black_men: 0, black_kings: 2, white_men: 0, white_kings: 1 }).unwrap();
+
+        // two kings beat one
+        assert_eq!(probe(&database, "W:WK14:BK1,K3"), Some(Wld::Loss));
+        assert_eq!(probe(&database, "B:WK14:BK1,K3"), Some(Wld::Win));
+    }
+
+    #[test]
+    fn generate_missing_material_test() {
+        let mut database = Database::new(2);
+        let result = generate_material(&mut database, Material { black_men: 0, black_kings: 1, white_men: 0, white_kings: 1 });
+        assert_eq!(result, Err("generator::generate_material - Missing material"));
+    }
+
+    #[test]
+    fn verify_inconsistent_test() {
+        let mut database = generate(1).unwrap();
+        let material = Material { black_men: 0, black_kings: 1, white_men: 0, white_kings: 0 };
+        database.insert(material, &vec![Wld::Win; material.size()]);
+        assert_eq!(verify(&database), Err("generator::verify - Inconsistent position"));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::checkers::endgame::board::Board;
+use crate::checkers::state::game_state::GameState;
+
+const SQUARE_COUNT: usize = 32;
+
+// binomial coefficients up to 32 choose 32
+const BINOMIALS: [[usize; SQUARE_COUNT + 1]; SQUARE_COUNT + 1] = binomials();
+
+// the pieces on the board, player one is black and player two white
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Material {
+    pub black_men: u8,
+    pub black_kings: u8,
+    pub white_men: u8,
+    pub white_kings: u8
+}
+
+impl Material {
+    pub fn of(game_state: &GameState) -> Material {
+        let mut material = Material { black_men: 0, black_kings: 0, white_men: 0, white_kings: 0 };
+        for row in game_state.squares.iter() {
+            for square in row {
+                match (square.player_number, square.king) {
+                    (1, false) => material.black_men += 1,
+                    (1, true) => material.black_kings += 1,
+                    (2, false) => material.white_men += 1,
+                    (2, true) => material.white_kings += 1,
+                    _ => ()
+                }
+            }
+        }
+        material
+    }
+
+    pub fn pieces(&self) -> u8 {
+        self.black_men + self.black_kings + self.white_men + self.white_kings
+    }
+
+    pub fn men(&self) -> u8 {
+        self.black_men + self.white_men
+    }
+
+    // the number of indexes, every placement of the pieces with either player to move
+    pub fn size(&self) -> usize {
+        let mut free = SQUARE_COUNT;
+        let mut size = 2;
+        for count in self.counts().iter() {
+            size *= binomial(free, *count as usize);
+            free -= *count as usize;
+        }
+        size
+    }
+
+    // every material with at least one and at most max_pieces pieces, ordered so that
+    // captures (fewer pieces) and promotions (fewer men) lead to earlier materials
+    pub fn all(max_pieces: u8) -> Vec<Material> {
+        let mut materials = vec![];
+        for black_men in 0..=max_pieces {
+            for black_kings in 0..=(max_pieces - black_men) {
+                for white_men in 0..=(max_pieces - black_men - black_kings) {
+                    for white_kings in 0..=(max_pieces - black_men - black_kings - white_men) {
+                        let material = Material { black_men, black_kings, white_men, white_kings };
+                        if material.pieces() > 0 {
+                            materials.push(material);
+                        }
+                    }
+                }
+            }
+        }
+        materials.sort_by_key(|m| (m.pieces(), m.men(), *m));
+        materials
+    }
+
+    // black men, black kings, white men, white kings
+    fn counts(&self) -> [u8; 4] {
+        [self.black_men, self.black_kings, self.white_men, self.white_kings]
+    }
+}
+
+// the material and index of a position, see position
+pub fn index(game_state: &GameState) -> (Material, usize) {
+    board_index(&Board::of(game_state))
+}
+
+pub fn board_index(board: &Board) -> (Material, usize) {
+    let mut occupied = 0u32;
+    let mut free = SQUARE_COUNT;
+    let mut index = 0;
+    for set in board.sets.iter() {
+        let count = set.count_ones() as usize;
+        index = index * binomial(free, count) + rank(*set, occupied);
+        occupied |= set;
+        free -= count;
+    }
+
+    (board.material(), index * 2 + (board.current_player_number as usize - 1))
+}
+
+// the position at an index, none when a man stands on its promotion row
+//   index = (((black men * black kings) * white men) * white kings) * 2 + player to move - 1
+// where each set of pieces is ranked among the squares left free by the sets before it
+pub fn position(material: &Material, index: usize) -> Option<GameState> {
+    board_at(material, index).map(|b| b.game_state())
+}
+
+pub fn board_at(material: &Material, index: usize) -> Option<Board> {
+    let current_player_number = (index % 2) as i8 + 1;
+    let mut remainder = index / 2;
+
+    let counts = material.counts();
+    let mut sizes = [0; 4];
+    let mut free = SQUARE_COUNT;
+    for (n, count) in counts.iter().enumerate() {
+        sizes[n] = binomial(free, *count as usize);
+        free -= *count as usize;
+    }
+
+    let mut ranks = [0; 4];
+    for n in (0..4).rev() {
+        ranks[n] = remainder % sizes[n];
+        remainder /= sizes[n];
+    }
+
+    let mut sets = [0u32; 4];
+    let mut occupied = 0u32;
+    for (n, count) in counts.iter().enumerate() {
+        sets[n] = unrank(ranks[n], *count as usize, occupied);
+        occupied |= sets[n];
+    }
+
+    let board = Board { sets, current_player_number };
+    if board.valid() {
+        Some(board)
+    } else {
+        None
+    }
+}
+
+// the combinatorial rank of a set among the squares not occupied
+fn rank(set: u32, occupied: u32) -> usize {
+    let mut result = 0;
+    let mut ordinal = 0;
+    let mut remaining = set;
+    while remaining != 0 {
+        let square_number = remaining.trailing_zeros() as usize;
+        remaining &= remaining - 1;
+        ordinal += 1;
+        result += binomial(free_position(square_number, occupied), ordinal);
+    }
+    result
+}
+
+// the set of count squares with the rank among the squares not occupied
+fn unrank(mut rank: usize, count: usize, occupied: u32) -> u32 {
+    let mut set = 0;
+    let mut position = SQUARE_COUNT;
+    for ordinal in (1..=count).rev() {
+        position -= 1;
+        while binomial(position, ordinal) > rank {
+            position -= 1;
+        }
+        rank -= binomial(position, ordinal);
+        set |= 1 << nth_free_square(position, occupied);
+    }
+    set
+}
+
+// the number of free squares before a square
+fn free_position(square_number: usize, occupied: u32) -> usize {
+    square_number - (occupied & ((1u32 << square_number) - 1)).count_ones() as usize
+}
+
+fn nth_free_square(n: usize, occupied: u32) -> usize {
+    let mut remaining = n;
+    for square_number in 0..SQUARE_COUNT {
+        if occupied & (1 << square_number) == 0 {
+            if remaining == 0 {
+                return square_number;
+            }
+            remaining -= 1;
+        }
+    }
+    SQUARE_COUNT
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    BINOMIALS[n][k]
+}
+
+const fn binomials() -> [[usize; SQUARE_COUNT + 1]; SQUARE_COUNT + 1] {
+    let mut table = [[0; SQUARE_COUNT + 1]; SQUARE_COUNT + 1];
+    let mut n = 0;
+    while n <= SQUARE_COUNT {
+        table[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::state::game_state::parse;
+    use crate::checkers::state::square::Square;
+
+    #[test]
+    fn size_test() {
+        let material = Material { black_men: 1, black_kings: 1, white_men: 0, white_kings: 1 };
+        assert_eq!(material.size(), 32 * 31 * 30 * 2);
+    }
+
+    #[test]
+    fn all_test() {
+        let result = Material::all(2);
+        assert_eq!(result.len(), 14);
+        assert_eq!(result[0], Material { black_men: 0, black_kings: 0, white_men: 0, white_kings: 1 });
+        assert!(result.iter().all(|m| m.pieces() <= 2));
+    }
+
+    #[test]
+    fn index_round_trip_test() {
+        let game_state = parse(&String::from("W:W21,K30:B5,K12")).unwrap();
+        let (material, index) = index(&game_state);
+        assert_eq!(material, Material { black_men: 1, black_kings: 1, white_men: 1, white_kings: 1 });
+        assert!(index < material.size());
+
+        let result = position(&material, index).unwrap();
+        assert!(result.squares == game_state.squares);
+        assert_eq!(result.current_player_number, 2);
+    }
+
+    #[test]
+    fn position_round_trip_test() {
+        let material = Material { black_men: 1, black_kings: 0, white_men: 0, white_kings: 2 };
+        for n in (0..material.size()).step_by(97) {
+            if let Some(game_state) = position(&material, n) {
+                assert_eq!(index(&game_state), (material, n));
+            }
+        }
+    }
+
+    #[test]
+    fn position_man_on_promotion_row_test() {
+        let game_state = parse(&String::from("B:WK30:B5")).unwrap();
+        let (material, n) = index(&game_state);
+        assert!(position(&material, n).is_some());
+
+        // a white man on 1 would have been crowned
+        let mut crowned = game_state.clone();
+        crowned.squares[7][6] = Square { player_number: 2, king: false };
+        crowned.squares[0][5] = Square { player_number: 0, king: false };
+        let (material, n) = index(&crowned);
+        assert!(position(&material, n).is_none());
+    }
+}
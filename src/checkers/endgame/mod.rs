@@ -0,0 +1,103 @@
+pub mod index;
+pub mod board;
+pub mod database;
+pub mod generator;
+
+use std::env;
+use std::fs;
+use std::sync::OnceLock;
+use crate::checkers::endgame::database::Database;
+use crate::checkers::minimax::static_evaluation;
+use crate::checkers::state::game_state::GameState;
+use crate::checkers::state::mov::Move;
+
+// the largest endgames the generator is meant for
+pub const MAX_PIECES: u8 = 6;
+
+// the result for the player to move with perfect play, ignoring the draw rules
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wld {
+    Win,
+    Loss,
+    Draw
+}
+
+static DATABASE: OnceLock<Option<Database>> = OnceLock::new();
+
+// built with the checkers_endgame tool, there is no database without CHECKERS_ENDGAME_DB_PATH
+pub fn load_database() -> Option<&'static Database> {
+    DATABASE.get_or_init(|| {
+        match env::var("CHECKERS_ENDGAME_DB_PATH") {
+            Ok(path) => {
+                let encoded = fs::read(path).expect("CHECKERS_ENDGAME_DB_PATH must be a readable file");
+                Some(Database::parse(&encoded).expect("CHECKERS_ENDGAME_DB_PATH must be a valid database"))
+            },
+            Err(_) => None
+        }
+    }).as_ref()
+}
+
+pub fn probe(game_state: &GameState) -> Option<Wld> {
+    load_database()?.probe(game_state)
+}
+
+pub fn recommended_move(game_state: &GameState) -> Option<Move> {
+    database_move(load_database()?, game_state)
+}
+
+// a move keeping the best result, preferring moves that don't repeat a position and then the better evaluation
+pub fn database_move(database: &Database, game_state: &GameState) -> Option<Move> {
+    database.probe(game_state)?;
+
+    let sign = if game_state.current_player_number == 1 { 1 } else { -1 };
+    let mut best: Option<((u8, bool, i32), Move)> = None;
+
+    for mov in game_state.possible_moves() {
+        let mut next = game_state.clone();
+        if next.perform_move(&mov).is_err() {
+            continue;
+        }
+
+        let result = match database.probe(&next)? {
+            Wld::Loss => 2,
+            Wld::Draw => 1,
+            Wld::Win => 0
+        };
+        let key = (result, next.position_count() == 1, sign * static_evaluation(&next));
+
+        if best.as_ref().is_none_or(|(best_key, _)| key > *best_key) {
+            best = Some((key, mov));
+        }
+    }
+
+    best.map(|(_, mov)| mov)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::endgame::generator::generate;
+    use crate::checkers::state::game_state::parse;
+
+    #[test]
+    fn database_move_capture_test() {
+        let database = generate(2).unwrap();
+        let game_state = parse(&String::from("B:WK6:BK1")).unwrap();
+        assert_eq!(database_move(&database, &game_state).unwrap().format(), "1x10");
+    }
+
+    #[test]
+    fn database_move_avoids_loss_test() {
+        // 6-9 lets the king catch the man, 6-10 draws
+        let database = generate(2).unwrap();
+        let game_state = parse(&String::from("B:WK5:B6")).unwrap();
+        assert_eq!(database_move(&database, &game_state).unwrap().format(), "6-10");
+    }
+
+    #[test]
+    fn database_move_too_many_pieces_test() {
+        let database = generate(1).unwrap();
+        let game_state = parse(&String::from("B:WK6:BK1")).unwrap();
+        assert!(database_move(&database, &game_state).is_none());
+    }
+}
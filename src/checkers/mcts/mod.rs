@@ -4,6 +4,7 @@ use std::cmp::Ordering;
 
 use rand::prelude::*;
 use crate::checkers::state::game_state::GameState;
+use crate::checkers::state::game_state::Outcome;
 use crate::checkers::state::mov::Move;
 use crate::checkers::mcts::node::Node;
 
@@ -25,6 +26,7 @@ pub fn recommended_move(game_state: GameState, simulation_count: i16, max_simula
                 mov: None,
                 state: game_state,
                 wins: 0,
+                draws: 0,
                 simulations: 0
             };
 
@@ -48,7 +50,12 @@ pub fn recommended_move(game_state: GameState, simulation_count: i16, max_simula
                 }
             };
 
-            let best_node = nodes.iter().filter(|n| n.parent_id == Some(1)).max_by(|a,b| a.wins.cmp(&b.wins));
+            let best_node = nodes.iter().filter(|n| n.parent_id == Some(1)).max_by(|a,b| {
+                match a.score().partial_cmp(&b.score()) {
+                    Some(c) => c,
+                    None => Ordering::Equal
+                }
+            });
             if let Some(n) = best_node {
                 match n.mov.clone() {
                     Some(m) => Ok(m),
@@ -103,6 +110,7 @@ fn expansion(nodes: &mut Vec<Node>, id: i32) -> Result<(), &'static str> {
                     mov: Some(mov),
                     state: new_game_state,
                     wins: 0,
+                    draws: 0,
                     simulations: 0
                 };
                 child_nodes.push(child_node);
@@ -118,10 +126,11 @@ fn expansion(nodes: &mut Vec<Node>, id: i32) -> Result<(), &'static str> {
     }
 }
 
-fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<bool, &'static str> {
+// whether the player to move at the node wins, None for a draw or a simulation cut off before the end
+fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<Option<bool>, &'static str> {
     if let Some(node) = nodes.iter().find(|n| n.id == id) {
         let mut end_game = false;
-        let mut winner: Option<i8> = None;
+        let mut outcome: Option<Outcome> = None;
         let mut simulation_depth: i16 = 0;
         let mut current_game_state = node.state.clone();
 
@@ -131,7 +140,7 @@ fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<boo
             match moves.len() {
                 0 => {
                     end_game = true;
-                    winner = current_game_state.winner();
+                    outcome = current_game_state.outcome();
                 },
                 1 => {
                     let selected_move = &moves[0];
@@ -151,24 +160,24 @@ fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<boo
                 }
             }
 
-            if let Some(w) = current_game_state.winner() {
+            if let Some(o) = current_game_state.outcome() {
                 end_game = true;
-                winner = Some(w);
+                outcome = Some(o);
             } else {
                 simulation_depth = simulation_depth + 1;
             }
         }
 
-        match winner {
-            Some(w) => Ok(w == node.state.current_player_number),
-            None => Ok(false)
+        match outcome {
+            Some(Outcome::Win(w)) => Ok(Some(w == node.state.current_player_number)),
+            _ => Ok(None)
         }
     } else {
         Err("Node not found")
     }
 }
 
-fn backpropagation(nodes: &mut Vec<Node>, selected_node_id: i32, result: bool) -> Result<(), &'static str> {
+fn backpropagation(nodes: &mut Vec<Node>, selected_node_id: i32, result: Option<bool>) -> Result<(), &'static str> {
     if let Some(node) = nodes.iter_mut().find(|n| n.id == selected_node_id) {
         node.add_result(result);
         match node.parent_id {
@@ -184,7 +193,7 @@ fn upper_confidence_bound(parent_node: &Node, node: &Node) -> f32 {
     if node.simulations == 0 {
         f32::INFINITY
     } else {
-        ( node.wins as f32 / node.simulations as f32 ) + EXPLORATION * ((parent_node.simulations as f32).ln() / node.simulations as f32).sqrt()
+        ( node.score() / node.simulations as f32 ) + EXPLORATION * ((parent_node.simulations as f32).ln() / node.simulations as f32).sqrt()
     }
 }
 
@@ -224,6 +233,7 @@ mod tests {
             mov: Some(mov_a),
             state: child_node_a_state,
             wins: 1,
+            draws: 0,
             simulations: 10
         };
 
@@ -240,6 +250,7 @@ mod tests {
             mov: Some(mov_b),
             state: child_node_b_state,
             wins: 15,
+            draws: 0,
             simulations: 30
         };
 
@@ -252,6 +263,7 @@ mod tests {
             mov: None,
             state: parent_node_state,
             wins: 16,
+            draws: 0,
             simulations: 40
         };
 
@@ -279,6 +291,7 @@ mod tests {
             mov: Some(mov),
             state: node_state,
             wins: 11,
+            draws: 0,
             simulations: 21
         };
 
@@ -291,6 +304,7 @@ mod tests {
             mov: None,
             state: parent_node_state,
             wins: 7,
+            draws: 0,
             simulations: 10
         };
 
@@ -308,6 +322,7 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0,
         };
 
@@ -330,6 +345,7 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0
         };
         let nodes = vec![node];
@@ -340,6 +356,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn simulate_repetition_test() {
+        let node_state = game_state::parse(&String::from("B:WK26,K32:BK1 moves 1-6 26-22 6-1 22-26 1-6 26-22 6-1 22-26")).unwrap();
+        let node = Node {
+            id: 1,
+            parent_id: None,
+            child_ids: Vec::new(),
+            mov: None,
+            state: node_state,
+            wins: 0,
+            draws: 0,
+            simulations: 0
+        };
+        let nodes = vec![node];
+        match simulate(&nodes, 1, 5) {
+            Ok(result) => assert_eq!(result, None),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn simulate_win_test() {
+        // white's forced jump takes black's last piece
+        let node_state = game_state::parse(&String::from("W:WK18:B15")).unwrap();
+        let node = Node {
+            id: 1,
+            parent_id: None,
+            child_ids: Vec::new(),
+            mov: None,
+            state: node_state,
+            wins: 0,
+            draws: 0,
+            simulations: 0
+        };
+        let nodes = vec![node];
+        match simulate(&nodes, 1, 5) {
+            Ok(result) => assert_eq!(result, Some(true)),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
     #[test]
     fn backpropagation_test() {
         let node_state = game_state::parse(&String::from("W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,12,15")).unwrap();
@@ -355,6 +412,7 @@ mod tests {
             mov: Some(mov),
             state: node_state,
             wins: 11,
+            draws: 0,
             simulations: 21
         };
 
@@ -367,12 +425,13 @@ mod tests {
             mov: None,
             state: parent_node_state,
             wins: 7,
+            draws: 0,
             simulations: 10
         };
 
         let mut nodes = vec![parent_node, node];
 
-        match backpropagation(&mut nodes, 2, true) {
+        match backpropagation(&mut nodes, 2, Some(true)) {
             Ok(_) => {
                match nodes.iter().find(|n| n.id == 1) {
                     Some(n) => {
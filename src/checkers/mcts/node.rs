@@ -11,6 +11,7 @@ pub struct Node {
     pub mov: Option<Move>,
     pub state: GameState, 
     pub wins: i32,
+    pub draws: i32,
     pub simulations: i32,
 }
 
@@ -23,13 +24,20 @@ impl Node {
         self.child_ids = child_ids;
     }
 
-    pub fn add_result(&mut self, result: bool) -> () {
+    // None for a draw
+    pub fn add_result(&mut self, result: Option<bool>) -> () {
         match result {
-            true => self.wins = self.wins + 1,
-            false => (),
+            Some(true) => self.wins = self.wins + 1,
+            Some(false) => (),
+            None => self.draws += 1,
         }
         self.simulations = self.simulations + 1;
     }
+
+    // wins count one and draws a half
+    pub fn score(&self) -> f32 {
+        self.wins as f32 + self.draws as f32 / 2.0
+    }
 }
 
 #[cfg(test)]
@@ -47,14 +55,36 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0,
         };
-        node.add_result(true);
+        node.add_result(Some(true));
 
         assert_eq!(1, node.wins);
         assert_eq!(1, node.simulations);
     }
 
+    #[test]
+    fn add_result_draw_test() {
+        let node_state = game_state::parse(&String::from("B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12")).unwrap();
+        let mut node = Node {
+            id: 1,
+            parent_id: None,
+            child_ids: Vec::new(),
+            mov: None,
+            state: node_state,
+            wins: 1,
+            draws: 0,
+            simulations: 1
+        };
+        node.add_result(None);
+
+        assert_eq!(1, node.wins);
+        assert_eq!(1, node.draws);
+        assert_eq!(2, node.simulations);
+        assert_eq!(1.5, node.score());
+    }
+
     #[test]
     fn add_result_not_win_test() {
         let node_state = game_state::parse(&String::from("B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12")).unwrap(); 
@@ -65,9 +95,10 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0
         };
-        node.add_result(false);
+        node.add_result(Some(false));
 
         assert_eq!(0, node.wins);
         assert_eq!(1, node.simulations);
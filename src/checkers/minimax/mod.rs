@@ -1,8 +1,18 @@
 use std::cmp;
 use std::convert::TryFrom;
 use crate::checkers;
+use crate::checkers::endgame;
+use crate::checkers::endgame::Wld;
+use crate::checkers::endgame::database::Database;
+use crate::checkers::state::variant::Variant;
 
 const CENTER_SQUARE_POINTS: [(i8, i8); 4] = [(5, 4), (3, 4), (4, 3), (2, 3)];
+const INTERNATIONAL_CENTER_SQUARE_POINTS: [(i8, i8); 4] = [(6, 5), (4, 5), (5, 4), (3, 4)];
+
+const DRAW_VALUE: i32 = 0;
+
+// below a position without moves, so that won endgames still play for the material and the win itself
+const ENDGAME_WIN_VALUE: i32 = 128;
 
 pub fn recommended_move(game_state: checkers::state::game_state::GameState, depth: i8) -> Option<checkers::state::mov::Move> {
     let mut new_game_state = game_state.clone();
@@ -53,6 +63,18 @@ pub fn recommended_move(game_state: checkers::state::game_state::GameState, dept
 
 pub fn evaluate(game_state: &mut checkers::state::game_state::GameState, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
     let moves = game_state.possible_moves();
+
+    // a player without moves loses even when a draw rule is reached
+    if !moves.is_empty() && game_state.draw_rule_reached() {
+        return Ok(DRAW_VALUE);
+    }
+
+    if !moves.is_empty() {
+        if let Some(value) = endgame_evaluation(game_state, endgame::load_database()) {
+            return Ok(value);
+        }
+    }
+
     if depth == 0 || moves.len() == 0 {
         return Ok(static_evaluation(&game_state));
     }
@@ -117,6 +139,20 @@ pub fn static_evaluation(game_state: &checkers::state::game_state::GameState) ->
     2*pieces_count_value + 4*kings_count_value + 1*center_squares_count_value + 256*lose_value(game_state)
 }
 
+// the endgame database result once few enough pieces are left
+fn endgame_evaluation(game_state: &checkers::state::game_state::GameState, database: Option<&Database>) -> Option<i32> {
+    let value = match database?.probe(game_state)? {
+        Wld::Win => ENDGAME_WIN_VALUE,
+        Wld::Loss => -ENDGAME_WIN_VALUE,
+        Wld::Draw => return Some(DRAW_VALUE)
+    };
+
+    match game_state.current_player_number {
+        1 => Some(value + static_evaluation(game_state)),
+        _ => Some(-value + static_evaluation(game_state))
+    }
+}
+
 fn lose_value(game_state: &checkers::state::game_state::GameState) -> i32 {
     if game_state.possible_moves().len() == 0 {
         match game_state.current_player_number {
@@ -129,11 +165,19 @@ fn lose_value(game_state: &checkers::state::game_state::GameState) -> i32 {
     }
 }
 
+fn center_square_points(variant: Variant) -> &'static [(i8, i8)] {
+    match variant {
+        Variant::Standard => &CENTER_SQUARE_POINTS,
+        Variant::International => &INTERNATIONAL_CENTER_SQUARE_POINTS
+    }
+}
+
 fn center_squares_count(game_state: &checkers::state::game_state::GameState, player_number: i8) -> usize {
+    let center_square_points = center_square_points(game_state.variant);
     let mut counter = 0;
     for (y, row) in game_state.squares.iter().enumerate() {
         for (x, square) in row.iter().enumerate() {
-            if square.player_number == player_number && CENTER_SQUARE_POINTS.iter().any(|p| p.0 == x as i8 && p.1 == y as i8) {
+            if square.player_number == player_number && center_square_points.iter().any(|p| p.0 == x as i8 && p.1 == y as i8) {
                 counter += 1;
             }
         }
@@ -201,4 +245,70 @@ mod tests {
            None => assert!(false, "expected move"),
        }
     }
+
+    #[test]
+    fn evaluate_repetition_test() {
+        // white is a king up, but the position has occurred three times
+        let encoded = String::from("B:WK26,K32:BK1 moves 1-6 26-22 6-1 22-26 1-6 26-22 6-1 22-26");
+        let mut game_state = checkers::state::game_state::parse(&encoded).unwrap();
+
+        match evaluate(&mut game_state, 4, i32::MIN, i32::MAX, true) {
+            Ok(result) => assert_eq!(result, DRAW_VALUE),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn evaluate_no_progress_test() {
+        let encoded = String::from("B:WK26,K32:BK1");
+        let mut game_state = checkers::state::game_state::parse(&encoded).unwrap();
+        game_state.no_progress_count = 80;
+
+        match evaluate(&mut game_state, 4, i32::MIN, i32::MAX, true) {
+            Ok(result) => assert_eq!(result, DRAW_VALUE),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn evaluate_before_no_progress_test() {
+        let encoded = String::from("B:WK26,K32:BK1");
+        let mut game_state = checkers::state::game_state::parse(&encoded).unwrap();
+        game_state.no_progress_count = 78;
+
+        match evaluate(&mut game_state, 0, i32::MIN, i32::MAX, true) {
+            Ok(result) => assert_ne!(result, DRAW_VALUE),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn center_squares_count_test() {
+        let encoded = String::from("B:B14,15,18,19,22:W1");
+        let game_state = checkers::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(center_squares_count(&game_state, 1), 4);
+    }
+
+    #[test]
+    fn center_squares_count_international_test() {
+        let encoded = String::from("B:B22,23,28,29,33:W1");
+        let game_state = checkers::state::game_state::parse_with_variant(&encoded, Variant::International).unwrap();
+        assert_eq!(center_squares_count(&game_state, 1), 4);
+    }
+
+    #[test]
+    fn endgame_evaluation_test() {
+        let database = checkers::endgame::generator::generate(2).unwrap();
+
+        let lost = checkers::state::game_state::parse(&String::from("B:WK9:B3")).unwrap();
+        assert_eq!(endgame_evaluation(&lost, Some(&database)), Some(-ENDGAME_WIN_VALUE + static_evaluation(&lost)));
+
+        let drawn = checkers::state::game_state::parse(&String::from("B:WK32:BK1")).unwrap();
+        assert_eq!(endgame_evaluation(&drawn, Some(&database)), Some(DRAW_VALUE));
+
+        let too_many = checkers::state::game_state::parse(&String::from("B:WK32:BK1,K2")).unwrap();
+        assert_eq!(endgame_evaluation(&too_many, Some(&database)), None);
+        assert_eq!(endgame_evaluation(&drawn, None), None);
+    }
+
 }
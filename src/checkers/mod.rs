@@ -2,3 +2,4 @@ pub mod state;
 pub mod minimax;
 pub mod openings;
 pub mod mcts;
+pub mod endgame;
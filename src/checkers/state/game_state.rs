@@ -1,14 +1,34 @@
-use crate::checkers::state::point::ID_COORDINATE_MAP;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use crate::checkers::state::point::id_to_point_with_variant;
 use crate::checkers::state::square::Square;
 use crate::checkers::state::square_set::find_by_x_and_y;
 use crate::checkers::state::square_set::find_by_x_and_y_mut;
 use crate::checkers::state::square_set::between_point;
+use crate::checkers::state::square_set::captured_point;
 use crate::checkers::state::mov::Move;
+use crate::checkers::state::mov::MoveKind;
+use crate::checkers::state::variant::Variant;
+
+// the third occurrence of a position is a draw
+const REPETITION_COUNT: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Win(i8),
+    Draw
+}
 
 #[derive(PartialEq, Debug)]
 pub struct GameState {
     pub current_player_number: i8,
     pub squares: Vec<Vec<Square>>,
+    pub variant: Variant,
+    pub position_hashes: Vec<u64>, // every position of the game so far, including the current one
+    pub no_progress_count: u16, // moves since the last capture or man move
+    pub no_progress_count_history: Vec<u16>, // the count before each move, restored by undo
+    pub square_history: Vec<Vec<((i8, i8), Square)>> // the squares each move changed as they were before it
 }
 
 impl Clone for GameState {
@@ -16,21 +36,84 @@ impl Clone for GameState {
         GameState {
             current_player_number: self.current_player_number,
             squares: self.squares.clone(),
+            variant: self.variant,
+            position_hashes: self.position_hashes.clone(),
+            no_progress_count: self.no_progress_count,
+            no_progress_count_history: self.no_progress_count_history.clone(),
+            square_history: self.square_history.clone()
         }
     }
 }
 
 impl GameState {
+    pub fn new(current_player_number: i8, squares: Vec<Vec<Square>>) -> GameState {
+        GameState::new_with_variant(current_player_number, squares, Variant::Standard)
+    }
+
+    pub fn new_with_variant(current_player_number: i8, squares: Vec<Vec<Square>>, variant: Variant) -> GameState {
+        let mut game_state = GameState {
+            current_player_number,
+            squares,
+            variant,
+            position_hashes: vec![],
+            no_progress_count: 0,
+            no_progress_count_history: vec![],
+            square_history: vec![]
+        };
+        game_state.position_hashes.push(game_state.position_hash());
+        game_state
+    }
+
     pub fn winner(&self) -> Option<i8> {
+        match self.outcome() {
+            Some(Outcome::Win(player_number)) => Some(player_number),
+            _ => None
+        }
+    }
+
+    pub fn draw(&self) -> bool {
+        self.outcome() == Some(Outcome::Draw)
+    }
+
+    // a player without moves loses, otherwise repetition and the 40 move rule draw
+    pub fn outcome(&self) -> Option<Outcome> {
         if self.possible_moves_for_player(1).is_empty() {
-            Some(2)
+            Some(Outcome::Win(2))
         } else if self.possible_moves_for_player(2).is_empty() {
-            Some(1)
+            Some(Outcome::Win(1))
+        } else if self.draw_rule_reached() {
+            Some(Outcome::Draw)
         } else {
             None
         }
     }
 
+    // threefold repetition or the 40 move rule (25 in draughts), whether or not the players can move
+    pub fn draw_rule_reached(&self) -> bool {
+        self.repeated() || self.no_progress_count >= self.variant.no_progress_limit()
+    }
+
+    // the current position has occurred three times
+    pub fn repeated(&self) -> bool {
+        self.position_count() >= REPETITION_COUNT
+    }
+
+    // how many times the current position has occurred
+    pub fn position_count(&self) -> usize {
+        match self.position_hashes.last() {
+            Some(current_hash) => self.position_hashes.iter().filter(|h| *h == current_hash).count(),
+            None => 0
+        }
+    }
+
+    // hash of the board and player to move
+    pub fn position_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.squares.hash(&mut hasher);
+        self.current_player_number.hash(&mut hasher);
+        hasher.finish()
+    }
+
     pub fn possible_moves(&self) -> Vec<Move> {
         self.possible_moves_for_player(self.current_player_number)
     }
@@ -56,6 +139,12 @@ impl GameState {
             }
         }
 
+        // draughts captures must take the most pieces
+        if self.variant == Variant::International {
+            let most_captures = list.iter().map(|m| m.to.len()).max().unwrap_or(0);
+            list.retain(|m| m.to.len() == most_captures);
+        }
+
         list
     }
 
@@ -75,6 +164,75 @@ impl GameState {
     }
 
     pub fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        let progress = match mov.kind {
+            MoveKind::Jump => true,
+            MoveKind::Mov => match find_by_x_and_y(&self.squares, mov.from.0, mov.from.1) {
+                Some(s) => !s.king,
+                None => return Err("invalid square id")
+            }
+        };
+
+        let changed_squares = self.changed_squares(mov);
+        self.perform_board_move(mov)?;
+
+        self.square_history.push(changed_squares);
+        self.no_progress_count_history.push(self.no_progress_count);
+        self.no_progress_count = if progress { 0 } else { self.no_progress_count + 1 };
+        self.position_hashes.push(self.position_hash());
+
+        Ok(())
+    }
+
+    // moves that weren't performed on this state are undone by moving the piece back
+    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        match self.square_history.pop() {
+            Some(changed_squares) => {
+                for (point, square) in changed_squares {
+                    if let Some(s) = find_by_x_and_y_mut(&mut self.squares, point.0, point.1) {
+                        *s = square;
+                    }
+                }
+                self.current_player_number = match self.current_player_number {
+                    1 => 2,
+                    2 => 1,
+                    _ => return Err("invalid player number")
+                };
+            },
+            None => self.undo_board_move(mov)?
+        }
+
+        self.no_progress_count = self.no_progress_count_history.pop().unwrap_or(0);
+        self.position_hashes.pop();
+
+        Ok(())
+    }
+
+    // the moving piece, the squares it lands on and the pieces it captures
+    fn changed_squares(&self, mov: &Move) -> Vec<((i8, i8), Square)> {
+        let mut board = self.squares.clone();
+        let mut points = vec![mov.from];
+        for (origin, destination) in mov.legs() {
+            points.push(destination);
+            if let Some(point) = captured_point(&board, origin, destination) {
+                points.push(point);
+                if let Some(s) = find_by_x_and_y_mut(&mut board, point.0, point.1) {
+                    *s = Square { player_number: 0, king: false };
+                }
+            }
+        }
+
+        let mut changed_squares: Vec<((i8, i8), Square)> = vec![];
+        for point in points {
+            if !changed_squares.iter().any(|(p, _)| *p == point) {
+                if let Some(square) = find_by_x_and_y(&self.squares, point.0, point.1) {
+                    changed_squares.push((point, *square));
+                }
+            }
+        }
+        changed_squares
+    }
+
+    fn perform_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
         let legs = mov.legs();
 
         for (origin, destination) in legs {
@@ -83,7 +241,7 @@ impl GameState {
 
         let (next_player_number, promotion_row) = match self.current_player_number {
             1 => (2, 0),
-            2 => (1, 7),
+            2 => (1, self.variant.board_size() - 1),
             _ => return Err("invalid player number"),
         };
 
@@ -106,10 +264,10 @@ impl GameState {
         Ok(())
     }
 
-    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+    fn undo_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
         let (previous_player_number, promotion_row) = match self.current_player_number {
             1 => (2, 0),
-            2 => (1, 7),
+            2 => (1, self.variant.board_size() - 1),
             _ => return Err("invalid player number"),
         };
 
@@ -154,7 +312,7 @@ impl GameState {
             square.king = king;
         }
 
-        if let Some(point) = between_point(from, to) {
+        if let Some(point) = captured_point(&self.squares, from, to) {
             if let Some(square) = find_by_x_and_y_mut(&mut self.squares, point.0, point.1) {
                 square.player_number = 0;
                 square.king = false;
@@ -212,7 +370,62 @@ impl GameState {
 }
 
 // B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12
+//
+// optionally followed by the moves played since, used to detect repetition and the 40 move rule
+// B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12 moves 11-15 23-19
 pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
+    parse_with_variant(encoded, Variant::Standard)
+}
+
+// draughts numbers the 50 squares of the 10x10 board the same way
+// W:W31,32,33,34,35,36,37,38,39,40,41,42,43,44,45,46,47,48,49,50:B1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20
+pub fn parse_with_variant(encoded: &String, variant: Variant) -> Result<GameState, &'static str> {
+    let mut parts = encoded.splitn(2, " moves ");
+
+    let mut game_state = match parts.next() {
+        Some(position) => parse_position(position, variant)?,
+        None => return Err("Error parsing state")
+    };
+
+    if let Some(moves) = parts.next() {
+        for encoded_move in moves.split_whitespace() {
+            let mov = parse_move(encoded_move, &game_state)?;
+            game_state.perform_move(&mov)?;
+        }
+    }
+
+    Ok(game_state)
+}
+
+// a legal move by the current player, jumps may leave out the squares between the first and last
+fn parse_move(encoded: &str, game_state: &GameState) -> Result<Move, &'static str> {
+    let moves = game_state.possible_moves();
+
+    let ids = encoded.split(['-', 'x']).map(|id| id.parse::<i8>()).collect::<Result<Vec<i8>, _>>().map_err(|_| "Error parsing move")?;
+
+    if let Some(mov) = moves.iter().find(|m| m.path_ids(game_state.variant) == ids) {
+        return Ok(mov.clone());
+    }
+
+    let candidates: Vec<&Move> = moves.iter().filter(|m| {
+        let path_ids = m.path_ids(game_state.variant);
+        ids.len() == 2 && path_ids.first() == ids.first() && path_ids.last() == ids.last()
+    }).collect();
+
+    // draughts captures of the same pieces by different routes are the same move
+    let same_result = candidates.windows(2).all(|pair| {
+        let mut first = game_state.clone();
+        let mut second = game_state.clone();
+        first.perform_move(pair[0]).is_ok() && second.perform_move(pair[1]).is_ok() && first.squares == second.squares
+    });
+
+    match candidates.first() {
+        Some(mov) if candidates.len() == 1 || (game_state.variant == Variant::International && same_result) => Ok((*mov).clone()),
+        _ => Err("Error parsing move")
+    }
+}
+
+fn parse_position(encoded: &str, variant: Variant) -> Result<GameState, &'static str> {
     let mut read_player = true;
     let mut read_white_pieces = false;
     let mut read_black_pieces = false;
@@ -220,88 +433,8 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
     let mut current_piece_king = false;
     let mut current_square_id = String::from("");
     let mut current_player_number = 1;
-    let mut squares: Vec<Vec<Square>> = vec![
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ],
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ],
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ],
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ],
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ],
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ],
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ],
-        vec![
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false },
-            Square { player_number: 0, king: false }
-        ]
-    ];
+    let board_size = variant.board_size() as usize;
+    let mut squares: Vec<Vec<Square>> = vec![vec![Square { player_number: 0, king: false }; board_size]; board_size];
 
     for c in encoded.chars() {
         match c {
@@ -332,11 +465,12 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                         };
                         match parse_square(&current_square_id, current_piece_king, player_number) {
                             Ok(square) => {
-                                match current_square_id.parse::<usize>() {
+                                match current_square_id.parse::<i8>() {
                                     Ok(parsed_id) => {
-                                        let x = ID_COORDINATE_MAP[parsed_id].0;
-                                        let y = ID_COORDINATE_MAP[parsed_id].1;
-                                        squares[y as usize][x as usize] = square;
+                                        match id_to_point_with_variant(parsed_id, variant) {
+                                            Some((x, y)) => squares[y as usize][x as usize] = square,
+                                            None => parse_error = true
+                                        }
                                     },
                                     Err(_) => parse_error = true
                                 }
@@ -364,11 +498,12 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                 if read_white_pieces {
                     match parse_square(&current_square_id, current_piece_king, 2) {
                         Ok(square) => {
-                            match current_square_id.parse::<usize>() {
+                            match current_square_id.parse::<i8>() {
                                 Ok(parsed_id) => {
-                                    let x = ID_COORDINATE_MAP[parsed_id].0;
-                                    let y = ID_COORDINATE_MAP[parsed_id].1;
-                                    squares[y as usize][x as usize] = square;
+                                    match id_to_point_with_variant(parsed_id, variant) {
+                                        Some((x, y)) => squares[y as usize][x as usize] = square,
+                                        None => parse_error = true
+                                    }
                                 },
                                 Err(_) => parse_error = true
                             }
@@ -381,11 +516,12 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                 } else if read_black_pieces {
                     match parse_square(&current_square_id, current_piece_king, 1) {
                         Ok(square) => {
-                            match current_square_id.parse::<usize>() {
+                            match current_square_id.parse::<i8>() {
                                 Ok(parsed_id) => {
-                                    let x = ID_COORDINATE_MAP[parsed_id].0;
-                                    let y = ID_COORDINATE_MAP[parsed_id].1;
-                                    squares[y as usize][x as usize] = square;
+                                    match id_to_point_with_variant(parsed_id, variant) {
+                                        Some((x, y)) => squares[y as usize][x as usize] = square,
+                                        None => parse_error = true
+                                    }
                                 },
                                 Err(_) => parse_error = true
                             }
@@ -408,11 +544,12 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
         if read_white_pieces {
             match parse_square(&current_square_id, current_piece_king, 2) {
                 Ok(square) => {
-                    match current_square_id.parse::<usize>() {
+                    match current_square_id.parse::<i8>() {
                         Ok(parsed_id) => {
-                            let x = ID_COORDINATE_MAP[parsed_id].0;
-                            let y = ID_COORDINATE_MAP[parsed_id].1;
-                            squares[y as usize][x as usize] = square;
+                            match id_to_point_with_variant(parsed_id, variant) {
+                                Some((x, y)) => squares[y as usize][x as usize] = square,
+                                None => parse_error = true
+                            }
                         },
                         Err(_) => parse_error = true
                     }
@@ -422,11 +559,12 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
         } else if read_black_pieces {
             match parse_square(&current_square_id, current_piece_king, 1) {
                 Ok(square) => {
-                    match current_square_id.parse::<usize>() {
+                    match current_square_id.parse::<i8>() {
                         Ok(parsed_id) => {
-                            let x = ID_COORDINATE_MAP[parsed_id].0;
-                            let y = ID_COORDINATE_MAP[parsed_id].1;
-                            squares[y as usize][x as usize] = square;
+                            match id_to_point_with_variant(parsed_id, variant) {
+                                Some((x, y)) => squares[y as usize][x as usize] = square,
+                                None => parse_error = true
+                            }
                         },
                         Err(_) => parse_error = true
                     }
@@ -439,12 +577,12 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
     if parse_error {
         Err("Error parsing state")
     } else {
-        Ok(GameState { current_player_number, squares })
+        Ok(GameState::new_with_variant(current_player_number, squares, variant))
     }
 }
 
 fn parse_square(current_square_id: &String, current_piece_king: bool, player_number: i8) -> Result<Square, &'static str> {
-    match current_square_id.parse::<usize>() {
+    match current_square_id.parse::<i8>() {
         Ok(_) => {
             let player_number = player_number;
             let king = current_piece_king;
@@ -460,7 +598,204 @@ fn parse_square(current_square_id: &String, current_piece_king: bool, player_num
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::checkers::state::mov::MoveKind;
+
+    #[test]
+    fn parse_moves_test() {
+        let encoded = String::from("B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12 moves 11-15 23-19");
+        let result = parse(&encoded).unwrap();
+        let expected = parse(&String::from("B:W19,21,22,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,12,15")).unwrap();
+
+        assert!(result.squares == expected.squares);
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.position_hashes.len(), 3);
+    }
+
+    #[test]
+    fn parse_illegal_moves_test() {
+        let encoded = String::from("B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12 moves 11-20");
+        assert!(parse(&encoded).is_err());
+    }
+
+    #[test]
+    fn outcome_repetition_test() {
+        let encoded = String::from("B:WK26:BK1 moves 1-6 26-22 6-1 22-26 1-6 26-22 6-1 22-26");
+        let result = parse(&encoded).unwrap();
+        assert!(result.repeated());
+        assert_eq!(result.outcome(), Some(Outcome::Draw));
+        assert_eq!(result.winner(), None);
+    }
+
+    #[test]
+    fn outcome_twofold_repetition_test() {
+        let encoded = String::from("B:WK26:BK1 moves 1-6 26-22 6-1 22-26");
+        let result = parse(&encoded).unwrap();
+        assert!(!result.repeated());
+        assert_eq!(result.outcome(), None);
+    }
+
+    #[test]
+    fn outcome_no_progress_test() {
+        let encoded = String::from("B:WK26:BK1");
+        let mut result = parse(&encoded).unwrap();
+        result.no_progress_count = 79;
+        assert_eq!(result.outcome(), None);
+
+        result.no_progress_count = 80;
+        assert_eq!(result.outcome(), Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn outcome_win_test() {
+        let encoded = String::from("W:W:B1,2");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.outcome(), Some(Outcome::Win(1)));
+    }
+
+    #[test]
+    fn no_progress_count_test() {
+        // king moves count, a man moving resets
+        let encoded = String::from("B:WK26,31:BK1,12 moves 1-6 26-22 6-1 22-26");
+        let mut game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.no_progress_count, 4);
+
+        let man_move = parse_move("12-16", &game_state).unwrap();
+        game_state.perform_move(&man_move).unwrap();
+        assert_eq!(game_state.no_progress_count, 0);
+
+        game_state.undo_move(&man_move).unwrap();
+        assert_eq!(game_state.no_progress_count, 4);
+        assert_eq!(game_state.position_hashes.len(), 5);
+    }
+
+    #[test]
+    fn no_progress_count_jump_test() {
+        let mut game_state = parse(&String::from("W:WK18:BK1,15")).unwrap();
+        game_state.no_progress_count = 7;
+
+        let jump = parse_move("18x11", &game_state).unwrap();
+        game_state.perform_move(&jump).unwrap();
+        assert_eq!(game_state.no_progress_count, 0);
+
+        game_state.undo_move(&jump).unwrap();
+        assert_eq!(game_state.no_progress_count, 7);
+    }
+
+    #[test]
+    fn parse_move_shortened_jump_test() {
+        let game_state = parse(&String::from("B:WK26,18:BK1,15")).unwrap();
+        let result = parse_move("15x31", &game_state).unwrap();
+        assert_eq!(result.format(), "15x22x31");
+    }
+
+    fn international(encoded: &str) -> GameState {
+        parse_with_variant(&String::from(encoded), Variant::International).unwrap()
+    }
+
+    fn formatted_moves(game_state: &GameState) -> Vec<String> {
+        game_state.possible_moves().iter().map(|m| m.format_with_variant(game_state.variant)).collect()
+    }
+
+    #[test]
+    fn parse_international_test() {
+        let game_state = international("W:W31,32,33,34,35,36,37,38,39,40,41,42,43,44,45,46,47,48,49,50:B1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20");
+        assert_eq!(game_state.squares.len(), 10);
+        assert_eq!(game_state.current_player_number, 2);
+        assert_eq!(game_state.squares[3][8], Square { player_number: 2, king: false });
+        assert_eq!(game_state.possible_moves().len(), 9);
+    }
+
+    #[test]
+    fn parse_international_invalid_test() {
+        assert!(parse_with_variant(&String::from("W:W51:B1"), Variant::International).is_err());
+        assert!(parse(&String::from("W:W33:B1")).is_err());
+    }
+
+    #[test]
+    fn international_man_captures_backwards_test() {
+        let game_state = international("B:W18:B23");
+        assert_eq!(formatted_moves(&game_state), vec!["23x12"]);
+    }
+
+    #[test]
+    fn international_flying_king_moves_test() {
+        let game_state = international("W:WK46:B5");
+        assert_eq!(game_state.possible_moves().len(), 8);
+    }
+
+    #[test]
+    fn international_flying_king_captures_test() {
+        let game_state = international("W:WK46:B28");
+        let mut result = formatted_moves(&game_state);
+        result.sort();
+        assert_eq!(result, vec!["46x10", "46x14", "46x19", "46x23", "46x5"]);
+    }
+
+    #[test]
+    fn international_majority_capture_test() {
+        // 32x21 takes one piece, 32x23x14 takes two
+        let game_state = international("W:W32:B19,27,28");
+        let moves = game_state.possible_moves();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].format_with_variant(Variant::International), "32x14");
+        assert_eq!(moves[0].path_ids(Variant::International), vec![32, 23, 14]);
+    }
+
+    #[test]
+    fn international_captured_pieces_stay_test() {
+        // after taking 28 the king can't jump it again to reach 19
+        let game_state = international("W:WK46:B28,33");
+        let moves = game_state.possible_moves();
+        assert!(moves.iter().all(|m| m.to.len() == 1));
+    }
+
+    #[test]
+    fn international_perform_and_undo_test() {
+        let mut game_state = international("W:W32:B19,27,28 moves 32x14");
+        assert_eq!(game_state.squares[7][2], Square { player_number: 2, king: false });
+        assert!(game_state.squares[4][5].unoccupied());
+        assert!(game_state.squares[6][3].unoccupied());
+        assert_eq!(game_state.squares[4][7], Square { player_number: 1, king: false });
+        assert_eq!(game_state.current_player_number, 1);
+
+        let original = international("W:W32:B19,27,28");
+        let jump = original.possible_moves().remove(0);
+        game_state.undo_move(&jump).unwrap();
+        assert!(game_state.squares == original.squares);
+        assert_eq!(game_state.current_player_number, 2);
+    }
+
+    #[test]
+    fn international_parse_full_path_test() {
+        let game_state = international("W:W32:B19,27,28 moves 32x23x14");
+        let expected = international("B:W14:B27");
+        assert!(game_state.squares == expected.squares);
+    }
+
+    #[test]
+    fn international_promotion_test() {
+        let game_state = international("W:W6:B20 moves 6-1");
+        assert_eq!(game_state.squares[9][8], Square { player_number: 2, king: true });
+    }
+
+    #[test]
+    fn international_no_progress_test() {
+        let mut game_state = international("W:WK46:BK5");
+        game_state.no_progress_count = 49;
+        assert_eq!(game_state.outcome(), None);
+
+        game_state.no_progress_count = 50;
+        assert_eq!(game_state.outcome(), Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn undo_move_captured_king_test() {
+        let mut game_state = parse(&String::from("W:W18:BK1,K15")).unwrap();
+        let jump = parse_move("18x11", &game_state).unwrap();
+        game_state.perform_move(&jump).unwrap();
+        game_state.undo_move(&jump).unwrap();
+        assert_eq!(game_state.squares[4][3], Square { player_number: 1, king: true });
+        assert_eq!(game_state.current_player_number, 2);
+    }
 
     #[test]
     fn parse_test() {
@@ -744,7 +1079,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let result = game_state.jumps_for_player(2);
 
@@ -844,7 +1179,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let result = game_state.moves_for_player(2);
 
@@ -1031,7 +1366,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let mut game_state = GameState { current_player_number: 1, squares };
+        let mut game_state = GameState::new(1, squares);
 
         match game_state.perform_move_leg((5, 4), (7, 6)) {
             Ok(_) => (),
@@ -1133,7 +1468,7 @@ mod tests {
             ]
         ];
 
-        let mut game_state = GameState { current_player_number: 1, squares };
+        let mut game_state = GameState::new(1, squares);
 
 
         match game_state.perform_move_leg((5, 4), (6, 5)) {
@@ -1232,7 +1567,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let mut game_state = GameState { current_player_number: 1, squares };
+        let mut game_state = GameState::new(1, squares);
 
         match game_state.undo_move_leg((5, 4), (7, 6)) {
             Ok(_) => (),
@@ -1333,7 +1668,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let mut game_state = GameState { current_player_number: 1, squares };
+        let mut game_state = GameState::new(1, squares);
 
         match game_state.undo_move_leg((5, 4), (6, 5)) {
             Ok(_) => (),
@@ -1431,7 +1766,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let mut game_state = GameState { current_player_number: 1, squares };
+        let mut game_state = GameState::new(1, squares);
 
         match game_state.promote((5, 4)) {
             Ok(_) => (),
@@ -1526,7 +1861,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let mut game_state = GameState { current_player_number: 1, squares };
+        let mut game_state = GameState::new(1, squares);
 
         match game_state.demote((5, 4)) {
             Ok(_) => (),
@@ -1,6 +1,7 @@
-mod point;
+pub mod point;
 mod vector;
-mod square;
-mod square_set;
+pub mod square;
+pub mod square_set;
 pub mod mov;
+pub mod variant;
 pub mod game_state;
@@ -1,4 +1,5 @@
-use crate::checkers::state::point::point_to_id;
+use crate::checkers::state::point::point_to_id_with_variant;
+use crate::checkers::state::variant::Variant;
 
 #[derive(Clone, Copy)]
 pub enum MoveKind {
@@ -14,17 +15,33 @@ pub struct Move {
 
 impl Move {
     pub fn format(&self) -> String {
+        self.format_with_variant(Variant::Standard)
+    }
+
+    // draughts jumps only give the first and last squares, 32x23
+    pub fn format_with_variant(&self, variant: Variant) -> String {
         let separator = match self.kind {
             MoveKind::Mov => "-",
             MoveKind::Jump => "x",
         };
 
-        let from_id = point_to_id(self.from);
-        let to_ids = self.to.iter().map(|p| point_to_id(*p).to_string()).collect::<Vec<String>>().join(separator);
+        let from_id = point_to_id_with_variant(self.from, variant);
+        let to_points = match variant {
+            Variant::International => self.to.last().into_iter().collect::<Vec<&(i8, i8)>>(),
+            Variant::Standard => self.to.iter().collect::<Vec<&(i8, i8)>>()
+        };
+        let to_ids = to_points.iter().map(|p| point_to_id_with_variant(**p, variant).to_string()).collect::<Vec<String>>().join(separator);
 
         String::from(format!("{}{}{}", from_id, separator, to_ids))
     }
 
+    // the squares of every leg, 32x23x14
+    pub fn path_ids(&self, variant: Variant) -> Vec<i8> {
+        let mut ids = vec![point_to_id_with_variant(self.from, variant)];
+        ids.extend(self.to.iter().map(|p| point_to_id_with_variant(*p, variant)));
+        ids
+    }
+
     pub fn legs(&self) -> Vec<((i8, i8), (i8, i8))> {
         let mut points = vec![self.from];
         let mut tos = self.to.clone();
@@ -65,6 +82,13 @@ mod tests {
         assert_eq!(result, "1-3");
     }
 
+    #[test]
+    fn format_international_jump() {
+        let mov = Move { kind: MoveKind::Jump, from: (3, 2), to: vec![(5, 4), (3, 6)] };
+        assert_eq!(mov.format_with_variant(Variant::International), "39x19");
+        assert_eq!(mov.path_ids(Variant::International), vec![39, 28, 19]);
+    }
+
     #[test]
     fn format_jump() {
         let mov = Move { kind: MoveKind::Jump, from: (6, 7), to: vec![(2, 7), (7, 6), (3, 6)] };
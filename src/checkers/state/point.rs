@@ -1,3 +1,5 @@
+use crate::checkers::state::variant::Variant;
+
 pub const ID_COORDINATE_MAP: [(i8, i8); 33] = [
     (8, 8),
 
@@ -88,6 +90,35 @@ pub fn point_to_id(point: (i8, i8)) -> i8 {
     }
 }
 
+// the diagonals a king moves along
+pub const DIAGONAL_DIRECTIONS: [(i8, i8); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+// square numbers on either board, counted as on the 8x8 board from player one's side,
+// 0 when the point isn't a dark square
+pub fn point_to_id_with_variant(point: (i8, i8), variant: Variant) -> i8 {
+    let size = variant.board_size();
+    if point.0 < 0 || point.0 >= size || point.1 < 0 || point.1 >= size || (point.0 + point.1) % 2 == 0 {
+        return 0;
+    }
+
+    let row = size - 1 - point.1;
+    let column = (size - 1 - point.0 - point.1 % 2) / 2;
+    row * size / 2 + column + 1
+}
+
+pub fn id_to_point_with_variant(id: i8, variant: Variant) -> Option<(i8, i8)> {
+    if id < 1 || id > variant.square_count() {
+        return None;
+    }
+
+    let size = variant.board_size();
+    let row = (id - 1) / (size / 2);
+    let column = (id - 1) % (size / 2);
+    let y = size - 1 - row;
+    let x = size - 1 - 2 * column - y % 2;
+    Some((x, y))
+}
+
 pub fn potential_jump_points(point: (i8, i8), player_number: i8, king: bool) -> Vec<(i8, i8)> {
     if king {
         vec![
@@ -138,6 +169,29 @@ pub fn potential_move_points(point: (i8, i8), player_number: i8, king: bool) ->
 mod tests {
     use super::*;
 
+    #[test]
+    fn point_to_id_with_variant_standard_test() {
+        for (id, point) in ID_COORDINATE_MAP.iter().enumerate().skip(1) {
+            assert_eq!(point_to_id_with_variant(*point, Variant::Standard), point_to_id(*point));
+            assert_eq!(id_to_point_with_variant(id as i8, Variant::Standard), Some(*point));
+        }
+    }
+
+    #[test]
+    fn point_to_id_with_variant_international_test() {
+        assert_eq!(id_to_point_with_variant(1, Variant::International), Some((8, 9)));
+        assert_eq!(id_to_point_with_variant(6, Variant::International), Some((9, 8)));
+        assert_eq!(id_to_point_with_variant(50, Variant::International), Some((1, 0)));
+        assert_eq!(id_to_point_with_variant(51, Variant::International), None);
+
+        for id in 1..=50 {
+            let point = id_to_point_with_variant(id, Variant::International).unwrap();
+            assert_eq!(point_to_id_with_variant(point, Variant::International), id);
+        }
+        assert_eq!(point_to_id_with_variant((0, 9), Variant::International), 5);
+        assert_eq!(point_to_id_with_variant((9, 9), Variant::International), 0);
+    }
+
     #[test]
     fn potential_move_points_player_one_test() {
         let player_number = 1;
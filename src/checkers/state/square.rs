@@ -1,12 +1,15 @@
+use crate::checkers::state::point::DIAGONAL_DIRECTIONS;
 use crate::checkers::state::point::potential_jump_points;
 use crate::checkers::state::point::potential_move_points;
 use crate::checkers::state::square_set::find_by_x_and_y;
+use crate::checkers::state::square_set::find_by_x_and_y_mut;
 use crate::checkers::state::square_set::between_point;
 use crate::checkers::state::game_state::GameState;
 use crate::checkers::state::mov::Move;
 use crate::checkers::state::mov::MoveKind;
+use crate::checkers::state::variant::Variant;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Square {
     pub player_number: i8,
     pub king: bool
@@ -48,6 +51,10 @@ impl Square {
     }
 
     pub fn can_jump(&self, point: (i8, i8), player_number: i8, king: bool, game_state: &GameState) -> bool {
+        if game_state.variant == Variant::International {
+            return !self.capture_destinations(point, player_number, king, &game_state.squares, &[]).is_empty();
+        }
+
         let potential_destinations = potential_jump_points(point, player_number, king);
 
         potential_destinations.iter().any(|p| {
@@ -100,6 +107,21 @@ impl Square {
 
     pub fn move_destinations<'a>(&self, point: (i8, i8), player_number: i8, king: bool, game_state: &'a GameState) -> Vec<(i8, i8)> {
         let mut destinations = vec![];
+
+        // draughts kings fly along the diagonals
+        if king && game_state.variant == Variant::International {
+            for direction in DIAGONAL_DIRECTIONS.iter() {
+                let mut to = (point.0 + direction.0, point.1 + direction.1);
+                while let Some(square) = find_by_x_and_y(&game_state.squares, to.0, to.1) {
+                    if square.occupied() {
+                        break;
+                    }
+                    destinations.push(to);
+                    to = (to.0 + direction.0, to.1 + direction.1);
+                }
+            }
+            return destinations;
+        }
         let potential_destinations = potential_move_points(point, player_number, king);
 
         potential_destinations.iter().for_each(|p| {
@@ -114,6 +136,12 @@ impl Square {
     }
 
     pub fn jump_legs<'a>(&self, point: (i8, i8), player_number: i8, king: bool, game_state: &GameState, mut accumulator: &'a mut Vec<Vec<(i8, i8)>>, mut current_leg: &mut Vec<(i8, i8)>) -> &'a mut Vec<Vec<(i8, i8)>> {
+        if game_state.variant == Variant::International {
+            let mut squares = game_state.squares.clone();
+            Square { player_number, king }.capture_legs(point, &mut squares, &mut vec![], accumulator, current_leg);
+            return accumulator;
+        }
+
         let destinations = self.jump_destinations(point, player_number, king, game_state);
 
         if !destinations.is_empty() {
@@ -147,6 +175,81 @@ impl Square {
         accumulator
     }
 
+    // draughts captures in every direction, kings from any distance and landing on any square beyond,
+    // pieces already captured in the sequence stay on the board until it ends and can't be jumped again
+    //   (landing point, captured point)
+    pub fn capture_destinations(&self, point: (i8, i8), player_number: i8, king: bool, squares: &Vec<Vec<Square>>, captured: &[(i8, i8)]) -> Vec<((i8, i8), (i8, i8))> {
+        let mut destinations = vec![];
+
+        for direction in DIAGONAL_DIRECTIONS.iter() {
+            let mut jumped = (point.0 + direction.0, point.1 + direction.1);
+            if king {
+                while find_by_x_and_y(squares, jumped.0, jumped.1).is_some_and(|s| s.unoccupied()) {
+                    jumped = (jumped.0 + direction.0, jumped.1 + direction.1);
+                }
+            }
+
+            match find_by_x_and_y(squares, jumped.0, jumped.1) {
+                Some(s) if s.occupied_by_opponent(player_number) && !captured.contains(&jumped) => (),
+                _ => continue
+            }
+
+            let mut to = (jumped.0 + direction.0, jumped.1 + direction.1);
+            while find_by_x_and_y(squares, to.0, to.1).is_some_and(|s| s.unoccupied()) {
+                destinations.push((to, jumped));
+                if !king {
+                    break;
+                }
+                to = (to.0 + direction.0, to.1 + direction.1);
+            }
+        }
+
+        destinations
+    }
+
+    // every complete draughts capture sequence of this piece from the point, the piece moves but the captured pieces stay
+    pub fn capture_legs(&self, point: (i8, i8), squares: &mut Vec<Vec<Square>>, captured: &mut Vec<(i8, i8)>, accumulator: &mut Vec<Vec<(i8, i8)>>, current_leg: &mut Vec<(i8, i8)>) {
+        let destinations = self.capture_destinations(point, self.player_number, self.king, squares, captured);
+
+        if destinations.is_empty() {
+            if current_leg.len() > 1 {
+                accumulator.push(current_leg.clone());
+            }
+            return;
+        }
+
+        if current_leg.is_empty() {
+            current_leg.push(point);
+        }
+
+        for (destination, jumped) in destinations {
+            let piece = *self;
+            if let Some(square) = find_by_x_and_y_mut(squares, point.0, point.1) {
+                *square = Square { player_number: 0, king: false };
+            }
+            if let Some(square) = find_by_x_and_y_mut(squares, destination.0, destination.1) {
+                *square = piece;
+            }
+            current_leg.push(destination);
+            captured.push(jumped);
+
+            self.capture_legs(destination, squares, captured, accumulator, current_leg);
+
+            captured.pop();
+            current_leg.pop();
+            if let Some(square) = find_by_x_and_y_mut(squares, destination.0, destination.1) {
+                *square = Square { player_number: 0, king: false };
+            }
+            if let Some(square) = find_by_x_and_y_mut(squares, point.0, point.1) {
+                *square = piece;
+            }
+        }
+
+        if current_leg.len() == 1 {
+            current_leg.pop();
+        }
+    }
+
     pub fn jumps(&self, point: (i8, i8), player_number: i8, king: bool, game_state: &GameState) -> Vec<Move> {
         let mut accumulator = vec![];
         let mut current_leg = vec![];
@@ -279,7 +382,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let point = (5, 4);
         let from_square = game_state.squares[4][5];
@@ -376,7 +479,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let point = (5, 4);
         let from_square = game_state.squares[4][5];
@@ -473,7 +576,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let point = (5, 4);
         let from_square = game_state.squares[4][5];
@@ -568,7 +671,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let point = (5, 4);
         let from_square = game_state.squares[4][5];
@@ -663,7 +766,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let point = (5, 4);
         let from_square = game_state.squares[4][5];
@@ -758,7 +861,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let mut accumulator = vec![];
         let mut current_leg = vec![];
@@ -857,7 +960,7 @@ mod tests {
             ]
         ];
 
-        let game_state = GameState { current_player_number: 2, squares };
+        let game_state = GameState::new(2, squares);
 
 
         let mut accumulator = vec![];
@@ -955,7 +1058,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let point = (2, 3);
         let from_square = game_state.squares[3][2];
@@ -1052,7 +1155,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 2, squares };
+        let game_state = GameState::new(2, squares);
 
         let point = (2, 3);
         let from_square = game_state.squares[3][2];
@@ -1149,7 +1252,7 @@ mod tests {
                 Square { player_number: 0, king: false }
             ]
         ];
-        let game_state = GameState { current_player_number: 1, squares };
+        let game_state = GameState::new(1, squares);
 
         let point = (5, 4);
         let from_square = game_state.squares[4][5];
@@ -1176,4 +1279,17 @@ mod tests {
             Err(e) => assert!(false, "{}", e),
         }
     }
+
+    #[test]
+    fn capture_legs_test() {
+        let game_state = crate::checkers::state::game_state::parse_with_variant(&String::from("W:W32:B19,27,28"), Variant::International).unwrap();
+        let square = Square { player_number: 2, king: false };
+        let mut squares = game_state.squares.clone();
+        let mut accumulator = vec![];
+        square.capture_legs((6, 3), &mut squares, &mut vec![], &mut accumulator, &mut vec![]);
+        accumulator.sort();
+
+        assert_eq!(accumulator, vec![vec![(6, 3), (4, 5), (2, 7)], vec![(6, 3), (8, 5)]]);
+        assert!(squares == game_state.squares);
+    }
 }
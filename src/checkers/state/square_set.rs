@@ -2,24 +2,41 @@ use crate::checkers::state::vector::Vector;
 use crate::checkers::state::square::Square;
 
 const MIN_N: i8 = 0;
-const MAX_N: i8 = 7;
 
 pub fn find_by_x_and_y(squares: &Vec<Vec<Square>>, x: i8, y: i8) -> Option<&Square> {
-    if x < MIN_N || x > MAX_N || y < MIN_N || y > MAX_N {
+    if x < MIN_N || y < MIN_N {
         None
     } else {
-        Some(&squares[y as usize][x as usize])
+        squares.get(y as usize)?.get(x as usize)
     }
 }
 
 pub fn find_by_x_and_y_mut(squares: &mut Vec<Vec<Square>>, x: i8, y: i8) -> Option<&mut Square> {
-    if x < MIN_N || x > MAX_N || y < MIN_N || y > MAX_N {
+    if x < MIN_N || y < MIN_N {
         None
     } else {
-        Some(&mut squares[y as usize][x as usize])
+        squares.get_mut(y as usize)?.get_mut(x as usize)
     }
 }
 
+// the first occupied square strictly between two points on a diagonal, the piece a jump captures
+pub fn captured_point(squares: &Vec<Vec<Square>>, from: (i8, i8), to: (i8, i8)) -> Option<(i8, i8)> {
+    let vector = Vector { from, to };
+    if !vector.diagonal() {
+        return None;
+    }
+
+    let direction_unit = vector.direction_unit();
+    let mut point = (from.0 + direction_unit.0, from.1 + direction_unit.1);
+    while point != to {
+        if find_by_x_and_y(squares, point.0, point.1)?.occupied() {
+            return Some(point);
+        }
+        point = (point.0 + direction_unit.0, point.1 + direction_unit.1);
+    }
+    None
+}
+
 pub fn between_point(from: (i8, i8), to: (i8, i8)) -> Option<(i8, i8)> {
     let vector = Vector { from, to };
 
@@ -81,6 +98,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn find_by_x_and_y_outside_test() {
+        let squares = vec![vec![Square { player_number: 0, king: false }; 10]; 10];
+        assert!(find_by_x_and_y(&squares, 9, 9).is_some());
+        assert!(find_by_x_and_y(&squares, 10, 9).is_none());
+        assert!(find_by_x_and_y(&squares, -1, 0).is_none());
+    }
+
+    #[test]
+    fn captured_point_test() {
+        let mut squares = vec![vec![Square { player_number: 0, king: false }; 10]; 10];
+        squares[3][3] = Square { player_number: 2, king: false };
+        assert_eq!(captured_point(&squares, (0, 0), (5, 5)), Some((3, 3)));
+        assert_eq!(captured_point(&squares, (0, 0), (3, 3)), None);
+        assert_eq!(captured_point(&squares, (0, 0), (5, 4)), None);
+    }
+
     #[test]
     fn fetching_between_diagonal() {
         let from_point = (0, 0);
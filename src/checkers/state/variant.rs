@@ -0,0 +1,41 @@
+// checkers on 8x8 and international draughts on 10x10, where men capture backwards,
+// kings fly and the capture taking the most pieces must be played
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    Standard,
+    International
+}
+
+impl Variant {
+    pub fn board_size(&self) -> i8 {
+        match self {
+            Variant::Standard => 8,
+            Variant::International => 10
+        }
+    }
+
+    // the number of dark squares
+    pub fn square_count(&self) -> i8 {
+        self.board_size() * self.board_size() / 2
+    }
+
+    // moves without a capture or a man moving before the game is drawn,
+    // 40 by each player in checkers and 25 in draughts
+    pub fn no_progress_limit(&self) -> u16 {
+        match self {
+            Variant::Standard => 80,
+            Variant::International => 50
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_count_test() {
+        assert_eq!(Variant::Standard.square_count(), 32);
+        assert_eq!(Variant::International.square_count(), 50);
+    }
+}
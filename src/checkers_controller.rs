@@ -17,6 +17,10 @@ pub fn minimax(game_data: &String) -> HttpResponse {
         Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     };
 
+    if let Some(m) = checkers::endgame::recommended_move(&game_state) {
+        return HttpResponse::Ok().body(format!("{}\n", m.format()));
+    }
+
     let minimax_depth: i8 = env::var("CHECKERS_MINIMAX_DEPTH")
         .unwrap_or_else(|_| "10".to_string())
         .parse()
@@ -36,6 +40,10 @@ pub fn mcts(game_data: &String) -> HttpResponse {
         Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     };
 
+    if let Some(m) = checkers::endgame::recommended_move(&game_state) {
+        return HttpResponse::Ok().body(format!("{}\n", m.format()));
+    }
+
     let mcts_simulation_count: i16 = env::var("CHECKERS_MCTS_SIMULATION_COUNT")
         .unwrap_or_else(|_| "1000".to_string())
         .parse()
@@ -110,6 +118,18 @@ mod tests {
         };
     }
 
+    #[test]
+    fn minimax_with_illegal_moves_test() {
+        let game_state = String::from("B:W19,21,22,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,12,15 moves 15-24");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
     #[test]
     fn minimax_no_moves_test() {
         let game_state = String::from("W:W:B1,2,3,4,5,6,7,8,9,10,12,15");
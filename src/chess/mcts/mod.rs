@@ -230,7 +230,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
         let child_node_a = Node {
             id: 2,
@@ -251,7 +254,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
         let child_node_b = Node {
             id: 3,
@@ -295,7 +301,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
         let node = Node {
             id: 2,
@@ -376,7 +385,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
         let node = Node {
             id: 2,
@@ -2,6 +2,13 @@ use std::cmp;
 use std::convert::TryFrom;
 use crate::chess::state::square::PieceKind;
 use crate::chess;
+use see::static_exchange_evaluation;
+use see::hanging_value;
+
+pub mod see;
+
+// how many captures deep the quiescence search goes past the depth
+const QUIESCENCE_DEPTH: i8 = 2;
 
 pub fn recommended_move(game_state: &mut chess::state::game_state::GameState, depth: i8) -> Option<chess::state::mov::Move> {
     let mut new_game_state = game_state.clone();
@@ -54,12 +61,18 @@ pub fn recommended_move(game_state: &mut chess::state::game_state::GameState, de
 }
 
 pub fn evaluate(game_state: &mut chess::state::game_state::GameState, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
-    let moves = game_state.possible_moves();
+    let mut moves = game_state.possible_moves();
+
+    if moves.is_empty() {
+        return Ok(terminal_evaluation(game_state, depth));
+    }
 
-    if depth == 0 || moves.len() == 0 {
-        return Ok(static_evaluation(game_state));
+    if depth == 0 {
+        return quiescence(game_state, moves, QUIESCENCE_DEPTH, alpha, beta, maximizing_player);
     }
 
+    order_moves(game_state, &mut moves);
+
     if maximizing_player {
         let mut max_eval = std::i32::MIN;
         for mov in moves {
@@ -103,6 +116,81 @@ pub fn evaluate(game_state: &mut chess::state::game_state::GameState, depth: i8,
     }
 }
 
+// Searches captures past the depth so positions aren't evaluated in the middle of an exchange.
+// Captures that lose material are pruned.
+fn quiescence(game_state: &mut chess::state::game_state::GameState, moves: Vec<chess::state::mov::Move>, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
+    let stand_pat = static_evaluation(game_state);
+
+    if depth == 0 {
+        return Ok(stand_pat);
+    }
+
+    let mut captures_with_value: Vec<(chess::state::mov::Move, i32)> = moves.into_iter().filter_map(|mov| {
+        if mov.capture_piece_kind.is_some() {
+            let value = static_exchange_evaluation(game_state, &mov);
+            if value >= 0 {
+                Some((mov, value))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }).collect();
+    captures_with_value.sort_by(|a, b| (b.1).cmp(&a.1));
+
+    if maximizing_player {
+        let mut max_eval = stand_pat;
+        alpha = cmp::max(alpha, stand_pat);
+        for (mov, _) in captures_with_value {
+            if beta <= alpha {
+                break;
+            }
+
+            game_state.perform_move(&mov)?;
+            let next_moves = game_state.possible_moves();
+            let eval = quiescence(game_state, next_moves, depth - 1, alpha, beta, false)?;
+            game_state.undo_move(&mov)?;
+
+            max_eval = cmp::max(max_eval, eval);
+            alpha = cmp::max(alpha, eval);
+        }
+        Ok(max_eval)
+    } else {
+        let mut min_eval = stand_pat;
+        beta = cmp::min(beta, stand_pat);
+        for (mov, _) in captures_with_value {
+            if beta <= alpha {
+                break;
+            }
+
+            game_state.perform_move(&mov)?;
+            let next_moves = game_state.possible_moves();
+            let eval = quiescence(game_state, next_moves, depth - 1, alpha, beta, true)?;
+            game_state.undo_move(&mov)?;
+
+            min_eval = cmp::min(min_eval, eval);
+            beta = cmp::min(beta, eval);
+        }
+        Ok(min_eval)
+    }
+}
+
+// Wins found with more depth remaining are sooner and score higher.
+fn terminal_evaluation(game_state: &mut chess::state::game_state::GameState, depth: i8) -> i32 {
+    let value = static_evaluation(game_state);
+    match game_state.winner() {
+        Some(1) => value + i32::from(depth),
+        Some(2) => value - i32::from(depth),
+        _ => value
+    }
+}
+
+// Winning captures first, losing captures last.
+fn order_moves(game_state: &chess::state::game_state::GameState, moves: &mut [chess::state::mov::Move]) {
+    moves.sort_by_cached_key(|mov| -static_exchange_evaluation(game_state, mov));
+}
+
 // positive -> w
 // negative -> b
 // Piece Scores
@@ -128,31 +216,66 @@ pub fn static_evaluation(game_state: &mut chess::state::game_state::GameState) -
     let player_two_possible_moves_count = game_state.possible_moves_for_player(2).len();
     let possible_moves_value = u_to_i32(player_one_possible_moves_count) - u_to_i32(player_two_possible_moves_count);
 
+    let player_one_hanging_value = player_hanging_value(game_state, 1);
+    let player_two_hanging_value = player_hanging_value(game_state, 2);
+    let hanging_value = player_one_hanging_value - player_two_hanging_value;
+
+    // variants: king of the hill, three check, atomic, horde
+    let variant_winner_value = match game_state.variant_winner() {
+        Some(1) => 1,
+        Some(2) => -1,
+        _ => 0
+    };
+
+    // three check: fewer remaining checks is better
+    let remaining_checks_value = i32::from(game_state.remaining_checks[2]) - i32::from(game_state.remaining_checks[1]);
+
     // double, blocked, isolated counts are reverse and must be subtracted
-    10*pieces_count_value - 5*double_pawn_count_value - 5*blocked_pawn_count_value -5*isolated_pawn_count_value + 1*possible_moves_value
+    10*pieces_count_value - 5*double_pawn_count_value - 5*blocked_pawn_count_value -5*isolated_pawn_count_value + 1*possible_moves_value - 5*hanging_value + 10000*variant_winner_value + 50*remaining_checks_value
 }
 
 // p: 1, n: 3, b: 3, r: 5, q: 9, k: 200
+// includes pieces in hand (crazyhouse)
 fn player_pieces_count(game_state: &chess::state::game_state::GameState, player_number: i8) -> usize {
     let mut score: usize = 0;
     for row in game_state.squares.iter() {
         for s in row.iter() {
             if s.player_number == player_number {
-                score += match s.kind {
-                    PieceKind::King => 200,
-                    PieceKind::Queen => 9,
-                    PieceKind::Rook => 5,
-                    PieceKind::Bishop => 3,
-                    PieceKind::Knight => 3,
-                    PieceKind::Pawn => 1,
-                    PieceKind::Empty => 0
-                };
+                score += piece_value(s.kind);
             }
         }
     }
+    for piece_kind in game_state.hands[player_number as usize].iter() {
+        score += piece_value(*piece_kind);
+    }
     score
 }
 
+// material the opponent can win by capturing the player's pieces
+fn player_hanging_value(game_state: &chess::state::game_state::GameState, player_number: i8) -> i32 {
+    let mut value = 0;
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            if s.player_number == player_number {
+                value += hanging_value(game_state, (x as i8, y as i8));
+            }
+        }
+    }
+    value
+}
+
+fn piece_value(piece_kind: PieceKind) -> usize {
+    match piece_kind {
+        PieceKind::King => 200,
+        PieceKind::Queen => 9,
+        PieceKind::Rook => 5,
+        PieceKind::Bishop => 3,
+        PieceKind::Knight => 3,
+        PieceKind::Pawn => 1,
+        PieceKind::Empty => 0
+    }
+}
+
 fn player_double_pawns_count(game_state: &chess::state::game_state::GameState, player_number: i8) -> usize {
     let mut count = 0;
     for x in 0..8 {
@@ -267,4 +390,47 @@ mod tests {
             None => assert!(false, "expected move"),
         }
     }
+
+    #[test]
+    fn static_evaluation_hand_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/4K3[Q] w - - 0 1");
+        let mut game_state = chess::state::game_state::parse_with_variant(&encoded, chess::state::variant::Variant::Crazyhouse).unwrap();
+
+        // queen in hand: 10*9, mobility: (5 king moves + 62 drops) - 5 king moves
+        let result = static_evaluation(&mut game_state);
+        assert_eq!(result, 152);
+    }
+
+    #[test]
+    fn static_evaluation_king_of_the_hill_test() {
+        let encoded = String::from("4k3/8/8/3K4/8/8/8/8 b - - 0 1");
+        let mut game_state = chess::state::game_state::parse_with_variant(&encoded, chess::state::variant::Variant::KingOfTheHill).unwrap();
+
+        // king on the hill: 10000, no moves once the game is won
+        let result = static_evaluation(&mut game_state);
+        assert_eq!(result, 10000);
+    }
+
+    #[test]
+    fn evaluate_quiescence_test() {
+        let encoded = String::from("4k3/8/8/3n4/8/8/8/3RK3 w - - 0 1");
+        let mut game_state = chess::state::game_state::parse(&encoded).unwrap();
+        let stand_pat = static_evaluation(&mut game_state);
+
+        // rook captures the hanging knight
+        match evaluate(&mut game_state, 0, std::i32::MIN, std::i32::MAX, true) {
+            Ok(result) => assert!(result > stand_pat),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn static_evaluation_hanging_test() {
+        let encoded = String::from("4k3/8/8/3n4/8/8/8/3RK3 b - - 0 1");
+        let mut game_state = chess::state::game_state::parse(&encoded).unwrap();
+
+        // rook against knight: 10*2, hanging knight: 5*3, mobility: (7 rook + 4 king) - (8 knight + 5 king)
+        let result = static_evaluation(&mut game_state);
+        assert_eq!(result, 33);
+    }
 }
@@ -0,0 +1,231 @@
+use std::cmp;
+use crate::chess::state::game_state::GameState;
+use crate::chess::state::mov::Move;
+use crate::chess::state::square::PieceKind;
+use crate::chess::state::square::Square;
+use crate::chess::state::square_set::find_by_x_and_y;
+use crate::chess::state::square_set::find_by_x_and_y_mut;
+use super::piece_value;
+
+// Static Exchange Evaluation
+// The material won or lost by a capture once both sides have finished recapturing on the to square.
+// Each side recaptures with its least valuable attacker and may stop when recapturing loses material.
+pub fn static_exchange_evaluation(game_state: &GameState, mov: &Move) -> i32 {
+    let capture_piece_kind = match mov.capture_piece_kind {
+        Some(pk) => pk,
+        None => return 0
+    };
+
+    let player_number = match find_by_x_and_y(&game_state.squares, mov.from) {
+        Some(s) => s.player_number,
+        None => return 0
+    };
+
+    let occupant_kind = match mov.promote_piece_kind {
+        Some(pk) => pk,
+        None => mov.moving_piece_kind
+    };
+
+    let mut state = game_state.clone();
+    capture_on_board(&mut state, mov.from, mov.to, Square { player_number, kind: occupant_kind });
+    if let Some(p) = mov.en_passant_point {
+        clear_square(&mut state, p);
+    }
+    state.en_passant_target = None;
+
+    swap_off(&mut state, mov.to, piece_value_i32(capture_piece_kind), occupant_kind, player_number)
+}
+
+// The material the opponent wins by capturing the piece on the point, 0 if the piece is safe.
+// Only the first capture and recapture are counted, so the attack maps answer it without copying the state.
+pub fn hanging_value(game_state: &GameState, point: (i8, i8)) -> i32 {
+    let target = match find_by_x_and_y(&game_state.squares, point) {
+        Some(s) => *s,
+        None => return 0
+    };
+
+    if target.unoccupied() || target.kind == PieceKind::King {
+        return 0;
+    }
+
+    let other_player_number = opposing_player(target.player_number);
+    let (x, y) = (point.0 as usize, point.1 as usize);
+    if game_state.attacks[other_player_number as usize][y][x] == 0 {
+        return 0;
+    }
+
+    let target_value = piece_value_i32(target.kind);
+    if game_state.attacks[target.player_number as usize][y][x] == 0 {
+        return target_value;
+    }
+
+    match least_valuable_attacker(game_state, point, other_player_number) {
+        Some((_, attacker_kind)) => cmp::max(target_value - piece_value_i32(attacker_kind), 0),
+        None => 0
+    }
+}
+
+// Resolves the captures on the target after the first capture.
+// Attackers are removed from the board as they capture so pieces behind them (x-rays) join in.
+fn swap_off(state: &mut GameState, target: (i8, i8), capture_value: i32, occupant_kind: PieceKind, occupant_player_number: i8) -> i32 {
+    let mut gains = vec![capture_value];
+    let mut occupant_value = piece_value_i32(occupant_kind);
+    let mut side = opposing_player(occupant_player_number);
+
+    while let Some((attacker_point, attacker_kind)) = least_valuable_attacker(state, target, side) {
+        let previous_gain = gains[gains.len() - 1];
+        gains.push(occupant_value - previous_gain);
+        capture_on_board(state, attacker_point, target, Square { player_number: side, kind: attacker_kind });
+        occupant_value = piece_value_i32(attacker_kind);
+        side = opposing_player(side);
+    }
+
+    // each side only recaptures if it doesn't lose material
+    while gains.len() > 1 {
+        if let Some(gain) = gains.pop() {
+            let last = gains.len() - 1;
+            gains[last] = -cmp::max(-gains[last], gain);
+        }
+    }
+
+    gains[0]
+}
+
+fn least_valuable_attacker(state: &GameState, target: (i8, i8), player_number: i8) -> Option<((i8, i8), PieceKind)> {
+    let mut attacker: Option<((i8, i8), PieceKind)> = None;
+
+    for (y, row) in state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            if s.player_number == player_number {
+                let point = (x as i8, y as i8);
+                let cheaper = match attacker {
+                    Some((_, kind)) => piece_value(s.kind) < piece_value(kind),
+                    None => true
+                };
+                if cheaper && s.capture_squares(point, state).contains(&target) {
+                    attacker = Some((point, s.kind));
+                }
+            }
+        }
+    }
+
+    attacker
+}
+
+fn capture_on_board(state: &mut GameState, from: (i8, i8), to: (i8, i8), square: Square) {
+    clear_square(state, from);
+    if let Some(s) = find_by_x_and_y_mut(&mut state.squares, to) {
+        s.player_number = square.player_number;
+        s.kind = square.kind;
+    }
+}
+
+fn clear_square(state: &mut GameState, point: (i8, i8)) {
+    if let Some(s) = find_by_x_and_y_mut(&mut state.squares, point) {
+        s.player_number = 0;
+        s.kind = PieceKind::Empty;
+    }
+}
+
+fn piece_value_i32(piece_kind: PieceKind) -> i32 {
+    piece_value(piece_kind) as i32
+}
+
+fn opposing_player(player_number: i8) -> i8 {
+    match player_number {
+        1 => 2,
+        _ => 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::state::game_state::parse;
+
+    fn find_move(game_state: &mut GameState, from: (i8, i8), to: (i8, i8)) -> Move {
+        let moves = game_state.possible_moves();
+        moves.iter().find(|m| m.from == from && m.to == to).unwrap().clone()
+    }
+
+    #[test]
+    fn static_exchange_evaluation_undefended_test() {
+        let encoded = String::from("4k3/8/8/3p4/8/8/8/3RK3 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (3, 7), (3, 3));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), 1);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_defended_test() {
+        let encoded = String::from("4k3/8/4p3/3p4/8/8/8/3RK3 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (3, 7), (3, 3));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), -4);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_pawn_takes_defended_test() {
+        let encoded = String::from("4k3/8/4p3/3n4/4P3/8/8/4K3 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (4, 4), (3, 3));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), 2);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_x_ray_test() {
+        // second rook behind the first recaptures
+        let encoded = String::from("3rk3/8/4p3/3p4/8/8/3R4/3RK3 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (3, 6), (3, 3));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), -4);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_x_ray_winning_test() {
+        // queen behind the rook wins the recapture
+        let encoded = String::from("3rk3/8/8/3n4/8/8/3R4/3QK3 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (3, 6), (3, 3));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), 3);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_non_capture_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/3RK3 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (3, 7), (3, 3));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), 0);
+    }
+
+    #[test]
+    fn hanging_value_test() {
+        let encoded = String::from("4k3/8/8/3n4/8/8/8/3RK3 b - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+
+        assert_eq!(hanging_value(&game_state, (3, 3)), 3);
+    }
+
+    #[test]
+    fn hanging_value_defended_test() {
+        let encoded = String::from("4k3/8/4p3/3n4/8/8/8/3RK3 b - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+
+        assert_eq!(hanging_value(&game_state, (3, 3)), 0);
+    }
+
+    #[test]
+    fn hanging_value_cheaper_attacker_test() {
+        // the pawn takes the defended knight
+        let encoded = String::from("4k3/8/4p3/3n4/4P3/8/8/4K3 b - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+
+        assert_eq!(hanging_value(&game_state, (3, 3)), 2);
+    }
+}
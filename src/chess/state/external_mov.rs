@@ -20,6 +20,7 @@ pub struct ExternalMove {
     pub en_passant_point: Option<(i8, i8)>, // Undo - add capture piece back next to from
     pub en_passant_target: Option<(i8, i8)>, // Undo - set game state en_passant_target back
     pub castle_move: Option<CastleMove>, // Undo - Move king and rook back to start.
+    pub drop: bool,
     pub file_disambiguation: bool,
     pub rank_disambiguation: bool,
     pub in_check: bool,
@@ -37,6 +38,7 @@ impl ExternalMove {
     //      file and rank of departure. e.g. Qh4e1
     //   promotion e.g. e8Q
     //   castle e.g 0-0 kingside, 0-0-0 queenside
+    //   drop e.g. N@f3
     //   check e.g. + at end
     //   checkmate e.g. # at end
     pub fn format(&self) -> String {
@@ -48,7 +50,11 @@ impl ExternalMove {
                 }
             },
             None => {
-                String::from(format!("{}{}{}{}{}{}", self.piece_format(), self.from_format(), self.capture_format(), self.to_format(), self.en_passant_suffix(), self.check_and_mate_suffix()))
+                if self.drop {
+                    format!("{}@{}{}", self.drop_piece_format(), self.to_format(), self.check_and_mate_suffix())
+                } else {
+                    String::from(format!("{}{}{}{}{}{}", self.piece_format(), self.from_format(), self.capture_format(), self.to_format(), self.en_passant_suffix(), self.check_and_mate_suffix()))
+                }
             }
         }
     }
@@ -77,6 +83,13 @@ impl ExternalMove {
         String::from(format!("{}", piece_letter))
     }
 
+    fn drop_piece_format(&self) -> String {
+        match self.moving_piece_kind {
+            PieceKind::Pawn => String::from("P"),
+            _ => self.piece_format()
+        }
+    }
+
     fn capture_format(&self) -> String {
         match self.capture_piece_kind {
             Some(_) => String::from("x"),
@@ -135,6 +148,7 @@ impl Clone for ExternalMove {
             en_passant_point: self.en_passant_point,
             en_passant_target: self.en_passant_target,
             castle_move: self.castle_move,
+            drop: self.drop,
             file_disambiguation: self.file_disambiguation,
             rank_disambiguation: self.rank_disambiguation,
             in_check: self.in_check,
@@ -161,6 +175,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: false,
             in_check: false,
@@ -184,6 +199,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: false,
             in_check: false,
@@ -208,6 +224,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: false,
             in_check: false,
@@ -233,6 +250,7 @@ mod tests {
             en_passant_point,
             en_passant_target: Some((3, 2)),
             castle_move: None,
+            drop: false,
             file_disambiguation: true,
             rank_disambiguation: false,
             in_check: false,
@@ -256,6 +274,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: true,
             rank_disambiguation: false,
             in_check: false,
@@ -279,6 +298,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: true,
             in_check: false,
@@ -302,6 +322,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: true,
             rank_disambiguation: true,
             in_check: false,
@@ -326,6 +347,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: false,
             in_check: false,
@@ -350,6 +372,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: false,
             in_check: false,
@@ -373,6 +396,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: false,
             in_check: true,
@@ -396,6 +420,7 @@ mod tests {
             en_passant_point: None,
             en_passant_target: None,
             castle_move: None,
+            drop: false,
             file_disambiguation: false,
             rank_disambiguation: false,
             in_check: false,
@@ -404,5 +429,52 @@ mod tests {
 
         assert_eq!("Rd6#", mov.format());
     }
-}
 
+    #[test]
+    fn format_drop_test() {
+        let from = (5, 5);
+        let to = (5, 5);
+        let moving_piece_kind = PieceKind::Knight;
+        let mov = ExternalMove {
+            from,
+            to,
+            moving_piece_kind,
+            capture_piece_kind: None,
+            promote_piece_kind: None,
+            en_passant_point: None,
+            en_passant_target: None,
+            castle_move: None,
+            drop: true,
+            file_disambiguation: false,
+            rank_disambiguation: false,
+            in_check: false,
+            in_checkmate: false
+        };
+
+        assert_eq!("N@f3", mov.format());
+    }
+
+    #[test]
+    fn format_drop_pawn_check_test() {
+        let from = (4, 3);
+        let to = (4, 3);
+        let moving_piece_kind = PieceKind::Pawn;
+        let mov = ExternalMove {
+            from,
+            to,
+            moving_piece_kind,
+            capture_piece_kind: None,
+            promote_piece_kind: None,
+            en_passant_point: None,
+            en_passant_target: None,
+            castle_move: None,
+            drop: true,
+            file_disambiguation: false,
+            rank_disambiguation: false,
+            in_check: true,
+            in_checkmate: false
+        };
+
+        assert_eq!("P@e5+", mov.format());
+    }
+}
@@ -3,6 +3,10 @@ use crate::chess::state::point::valid;
 use crate::chess::state::point::direction_unit_n;
 use crate::chess::state::point::length;
 use crate::chess::state::point::side;
+use crate::chess::state::point::one_step_destination_points;
+use crate::chess::state::point::add;
+use crate::chess::state::point::ORTHOGONAL_DIRECTIONS;
+use crate::chess::state::point::DIAGONAL_DIRECTIONS;
 use crate::chess::state::mov::Move;
 use crate::chess::state::piece_factory::parse as parse_piece;
 use crate::chess::state::castle_move::parse as parse_castle_move;
@@ -12,6 +16,11 @@ use crate::chess::state::square_set::find_by_x_and_y;
 use crate::chess::state::square_set::find_by_x_and_y_mut;
 use crate::chess::state::square::Square;
 use crate::chess::state::square::PieceKind;
+use crate::chess::state::variant::Variant;
+
+const CENTRE_POINTS: [(i8, i8); 4] = [
+  (3, 3), (4, 3), (3, 4), (4, 4)
+];
 
 const PROMOTE_PIECE_KINDS: [PieceKind; 4] = [
   PieceKind::Queen, PieceKind::Bishop, PieceKind::Knight, PieceKind::Rook
@@ -21,7 +30,13 @@ pub struct GameState {
     pub current_player_number: i8,
     pub squares: Vec<Vec<Square>>,
     pub en_passant_target: Option<(i8, i8)>,
-    pub castle_moves: Vec<CastleMove>
+    pub castle_moves: Vec<CastleMove>,
+    pub variant: Variant,
+    pub hands: Vec<Vec<PieceKind>>,
+    pub promoted_points: Vec<(i8, i8)>,
+    pub remaining_checks: Vec<i8>,
+    pub king_points: Vec<Option<(i8, i8)>>,
+    pub attacks: Vec<Vec<Vec<i8>>> // number of each player's pieces attacking each square
 }
 
 impl Clone for GameState {
@@ -30,19 +45,37 @@ impl Clone for GameState {
             current_player_number: self.current_player_number,
             squares: self.squares.clone(),
             en_passant_target: self.en_passant_target,
-            castle_moves: self.castle_moves.clone()
+            castle_moves: self.castle_moves.clone(),
+            variant: self.variant,
+            hands: self.hands.clone(),
+            promoted_points: self.promoted_points.clone(),
+            remaining_checks: self.remaining_checks.clone(),
+            king_points: self.king_points.clone(),
+            attacks: self.attacks.clone()
         }
     }
 }
 
 impl GameState {
+    pub fn unique_piece_kinds_in_hand(&self, subject_player_number: i8) -> Vec<PieceKind> {
+        let mut piece_kinds_in_hand = vec![];
+        for piece_kind in self.hands[subject_player_number as usize].iter() {
+            if !piece_kinds_in_hand.contains(piece_kind) {
+                piece_kinds_in_hand.push(*piece_kind);
+            }
+        }
+        piece_kinds_in_hand
+    }
+
     // pub fn game_over(&mut self) -> bool {
     //     (self.in_checkmate(1) || self.in_stalemate(1)) ||
     //         (self.in_checkmate(2) || self.in_stalemate(1))
     // }
 
     pub fn winner(&mut self) -> Option<i8> {
-        if self.in_checkmate(1) {
+        if let Some(player_number) = self.variant_winner() {
+            Some(player_number)
+        } else if self.in_checkmate(1) {
             Some(2)
         } else if self.in_checkmate(2) {
             Some(1)
@@ -51,6 +84,62 @@ impl GameState {
         }
     }
 
+    // wins specific to the variant, checkmate is handled by winner
+    pub fn variant_winner(&self) -> Option<i8> {
+        match self.variant {
+            Variant::KingOfTheHill => {
+                // king reaches one of the four centre squares
+                let on_hill = |player_number: i8| {
+                    match self.king_point(player_number) {
+                        Some(p) => CENTRE_POINTS.contains(&p),
+                        None => false
+                    }
+                };
+
+                if on_hill(1) {
+                    Some(1)
+                } else if on_hill(2) {
+                    Some(2)
+                } else {
+                    None
+                }
+            },
+            Variant::ThreeCheck => {
+                if self.remaining_checks[1] <= 0 {
+                    Some(1)
+                } else if self.remaining_checks[2] <= 0 {
+                    Some(2)
+                } else {
+                    None
+                }
+            },
+            Variant::Atomic => {
+                // king exploded
+                if self.king_point(1).is_none() {
+                    Some(2)
+                } else if self.king_point(2).is_none() {
+                    Some(1)
+                } else {
+                    None
+                }
+            },
+            Variant::Horde => {
+                // white loses when all pieces are captured
+                let white_has_pieces = self.squares.iter().any(|row| row.iter().any(|s| s.player_number == 1));
+                if white_has_pieces {
+                    None
+                } else {
+                    Some(2)
+                }
+            },
+            _ => None
+        }
+    }
+
+    pub fn king_point(&self, player_number: i8) -> Option<(i8, i8)> {
+        self.king_points[player_number as usize]
+    }
+
     pub fn in_checkmate(&mut self, player_number: i8) -> bool {
         self.in_check(player_number) && self.possible_moves_for_player(player_number).is_empty()
     }
@@ -67,32 +156,83 @@ impl GameState {
             _ => 1
         };
 
-        let mut check = false;
-
-        let mut king_point: (i8, i8) = (0, 0);
+        // horde: white has no king
+        let king_point = match self.king_point(player_number) {
+            Some(p) => p,
+            None => return false
+        };
 
-        for (y, row) in self.squares.iter().enumerate() {
-            for (x, s) in row.iter().enumerate() {
-                if s.kind == PieceKind::King && s.player_number == player_number {
-                   king_point = (x as i8, y as i8);
+        // atomic: kings next to each other can't be checked
+        if self.variant == Variant::Atomic {
+            if let Some(other_king_point) = self.king_point(other_player_number) {
+                if length(king_point, other_king_point) == 1 {
+                    return false;
                 }
             }
         }
 
-        for (y, row) in self.squares.iter().enumerate() {
-            for (x, square) in row.iter().enumerate() {
-                if check {
-                    break;
-                } else {
-                    if square.player_number != 0 {
-                        // if any capture square match king square
-                        check = square.player_number == other_player_number && square.capture_squares((x as i8, y as i8), self).iter().any(|s| *s == king_point );
+        self.attacks[other_player_number as usize][king_point.1 as usize][king_point.0 as usize] > 0
+    }
+
+    // king points and attack maps from scratch, perform_move and undo_move keep them up to date after this
+    pub fn refresh_attacks(&mut self) {
+        self.king_points = vec![None, None, None];
+        self.attacks = vec![vec![vec![0; 8]; 8]; 3];
+        for y in 0..8 {
+            for x in 0..8 {
+                self.update_piece_attacks((x, y), 1);
+            }
+        }
+    }
+
+    // adds (n = 1) or removes (n = -1) the attacks of the piece on the point
+    fn update_piece_attacks(&mut self, point: (i8, i8), n: i8) {
+        let square = match find_by_x_and_y(&self.squares, point) {
+            Some(s) if s.occupied() => *s,
+            _ => return
+        };
+
+        let player_number = square.player_number as usize;
+        if square.kind == PieceKind::King {
+            self.king_points[player_number] = if n > 0 { Some(point) } else { None };
+        }
+
+        for p in square.attack_squares(point, self) {
+            self.attacks[player_number][p.1 as usize][p.0 as usize] += n;
+        }
+    }
+
+    fn update_attacks(&mut self, changed_points: &[(i8, i8)], slider_points: &[(i8, i8)], n: i8) {
+        for point in changed_points.iter().chain(slider_points.iter()) {
+            self.update_piece_attacks(*point, n);
+        }
+    }
+
+    // sliders with a line through the points, their attacks change when the points change
+    fn sliders_through_points(&self, points: &[(i8, i8)]) -> Vec<(i8, i8)> {
+        let mut acc = vec![];
+        for point in points {
+            for direction in ORTHOGONAL_DIRECTIONS.iter().chain(DIAGONAL_DIRECTIONS.iter()) {
+                let orthogonal = direction.0 == 0 || direction.1 == 0;
+                let mut counter = add(*point, *direction);
+                while let Some(s) = find_by_x_and_y(&self.squares, counter) {
+                    if s.occupied() {
+                        let slides = match s.kind {
+                            PieceKind::Queen => true,
+                            PieceKind::Rook => orthogonal,
+                            PieceKind::Bishop => !orthogonal,
+                            _ => false
+                        };
+                        if slides && !points.contains(&counter) && !acc.contains(&counter) {
+                            acc.push(counter);
+                        }
+                        break;
                     }
+                    counter = add(counter, *direction);
                 }
             }
         }
-
-        check
+        acc
     }
 
     pub fn possible_moves(&mut self) -> Vec<Move> {
@@ -102,6 +242,10 @@ impl GameState {
     pub fn possible_moves_for_player(&mut self, subject_player_number: i8) -> Vec<Move> {
         let mut moves = vec![];
 
+        if self.variant_winner().is_some() {
+            return moves;
+        }
+
         for (y, row) in self.squares.iter().enumerate() {
             for (x, from) in row.iter().enumerate() {
                 if from.player_number == subject_player_number {
@@ -139,6 +283,25 @@ impl GameState {
                                 }
                             }
 
+                            // atomic: kings can't capture
+                            if self.variant == Variant::Atomic && from.kind == PieceKind::King && capture_piece_kind.is_some() {
+                                continue;
+                            }
+
+                            // atomic: non pawn pieces around the capture explode
+                            let mut explosion = vec![];
+                            if self.variant == Variant::Atomic && capture_piece_kind.is_some() {
+                                for explosion_point in one_step_destination_points(to_point) {
+                                    if explosion_point != from_point {
+                                        if let Some(s) = find_by_x_and_y(&self.squares, explosion_point) {
+                                            if s.occupied() && s.kind != PieceKind::Pawn {
+                                                explosion.push((explosion_point, *s));
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
                             let mut castle_move: Option<CastleMove> = None;
                             if from.kind == PieceKind::King {
                                 // exclude castle move if in check
@@ -151,6 +314,9 @@ impl GameState {
                                 }
                             }
 
+                            // crazyhouse: a captured promoted piece goes to hand as a pawn
+                            let capture_promoted = capture_piece_kind.is_some() && en_passant_point.is_none() && self.promoted_points.contains(&to_point);
+
                             let promote = from.kind == PieceKind::Pawn && to_point.1 == from.promotion_rank();
 
                             if promote {
@@ -164,7 +330,10 @@ impl GameState {
                                         promote_piece_kind,
                                         en_passant_point,
                                         en_passant_target: self.en_passant_target,
-                                        castle_move
+                                        castle_move,
+                                        drop: false,
+                                        capture_promoted,
+                                        explosion: explosion.clone()
                                     };
                                     moves.push(mov);
                                 };
@@ -178,7 +347,10 @@ impl GameState {
                                     promote_piece_kind,
                                     en_passant_point,
                                     en_passant_target: self.en_passant_target,
-                                    castle_move
+                                    castle_move,
+                                    drop: false,
+                                    capture_promoted,
+                                    explosion
                                 };
                                 moves.push(mov);
                             }
@@ -188,9 +360,55 @@ impl GameState {
             }
         }
 
+        // drops
+        // all piece types in players hands
+        // all unoccupied squares
+        // if pawn, excludes the first and last ranks
+        if self.variant == Variant::Crazyhouse {
+            for piece_kind in self.unique_piece_kinds_in_hand(subject_player_number) {
+                for (y, row) in self.squares.iter().enumerate() {
+                    if piece_kind == PieceKind::Pawn && (y == 0 || y == 7) {
+                        continue;
+                    }
+
+                    for (x, square) in row.iter().enumerate() {
+                        if square.unoccupied() {
+                            let to_point = (x as i8, y as i8);
+                            let mov = Move {
+                                from: to_point,
+                                to: to_point,
+                                moving_piece_kind: piece_kind,
+                                capture_piece_kind: None,
+                                promote_piece_kind: None,
+                                en_passant_point: None,
+                                en_passant_target: self.en_passant_target,
+                                castle_move: None,
+                                drop: true,
+                                capture_promoted: false,
+                                explosion: vec![]
+                            };
+                            moves.push(mov);
+                        }
+                    }
+                }
+            }
+        }
+
+        let other_player_number = match subject_player_number {
+            1 => 2,
+            _ => 1
+        };
+
         moves.retain(|m| {
             let perform_result = self.perform_move(&m);
-            let in_check = self.in_check(subject_player_number);
+            let in_check = match self.variant {
+                // atomic: can't explode own king, exploding the other king ends the game
+                Variant::Atomic => {
+                    self.king_point(subject_player_number).is_none() ||
+                        (self.king_point(other_player_number).is_some() && self.in_check(subject_player_number))
+                },
+                _ => self.in_check(subject_player_number)
+            };
             let undo_result = self.undo_move(&m);
             perform_result.is_ok() && undo_result.is_ok() && !in_check
         });
@@ -199,6 +417,57 @@ impl GameState {
     }
 
     pub fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        let changed_points = changed_points(mov);
+        let slider_points = self.sliders_through_points(&changed_points);
+        self.update_attacks(&changed_points, &slider_points, -1);
+
+        let result = if mov.drop {
+            self.perform_drop(mov)
+        } else {
+            self.perform_board_move(mov)
+        };
+
+        self.update_attacks(&changed_points, &slider_points, 1);
+
+        // three check: count checks given
+        if result.is_ok() && self.variant == Variant::ThreeCheck && self.in_check(self.current_player_number) {
+            let mover_player_number = match self.current_player_number {
+                1 => 2,
+                _ => 1
+            };
+            self.remaining_checks[mover_player_number as usize] -= 1;
+        }
+
+        result
+    }
+
+    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        // three check: restore check given by this move
+        if self.variant == Variant::ThreeCheck && self.in_check(self.current_player_number) {
+            let mover_player_number = match self.current_player_number {
+                1 => 2,
+                _ => 1
+            };
+            self.remaining_checks[mover_player_number as usize] += 1;
+        }
+
+        let changed_points = changed_points(mov);
+        let slider_points = self.sliders_through_points(&changed_points);
+        self.update_attacks(&changed_points, &slider_points, -1);
+
+        let result = if mov.drop {
+            self.undo_drop(mov)
+        } else {
+            self.undo_board_move(mov)
+        };
+
+        self.update_attacks(&changed_points, &slider_points, 1);
+
+        result
+    }
+
+    fn perform_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+
         let piece_player_number: i8;
         let piece_kind: PieceKind;
 
@@ -273,6 +542,43 @@ impl GameState {
            }
         }
 
+        if self.variant == Variant::Crazyhouse {
+            // add captured piece to hand, promoted pieces revert to pawns
+            if let Some(pk) = mov.capture_piece_kind {
+                let hand_piece_kind = if mov.capture_promoted {
+                    PieceKind::Pawn
+                } else {
+                    pk
+                };
+                self.hands[self.current_player_number as usize].push(hand_piece_kind);
+                self.promoted_points.retain(|p| *p != mov.to);
+            }
+
+            if let Some(pos) = self.promoted_points.iter().position(|p| *p == mov.from) {
+                self.promoted_points[pos] = mov.to;
+            } else if mov.promote_piece_kind.is_some() {
+                self.promoted_points.push(mov.to);
+            }
+        }
+
+        if self.variant == Variant::Atomic && mov.capture_piece_kind.is_some() {
+            // capturing piece and surrounding pieces explode
+            if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.to) {
+                s.player_number = 0;
+                s.kind = PieceKind::Empty;
+            }
+
+            for (point, _) in mov.explosion.iter() {
+                match find_by_x_and_y_mut(&mut self.squares, *point) {
+                    Some(s) => {
+                        s.player_number = 0;
+                        s.kind = PieceKind::Empty;
+                    },
+                    None => return Err("Invalid Explosion Square")
+                }
+            }
+        }
+
         // set en passant target
         if mov.moving_piece_kind == PieceKind::Pawn && length(mov.from, mov.to) == 2 {
             let backwards = direction_unit_n(mov.from.1, mov.to.1)*-1;
@@ -310,7 +616,38 @@ impl GameState {
         Ok(())
     }
 
-    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+    fn undo_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+
+        if self.variant == Variant::Atomic && mov.capture_piece_kind.is_some() {
+            // place capturing piece and exploded pieces back
+            let mover_player_number = match self.current_player_number {
+                1 => 2,
+                _ => 1
+            };
+            let mover_piece_kind = match mov.promote_piece_kind {
+                Some(pk) => pk,
+                None => mov.moving_piece_kind
+            };
+
+            match find_by_x_and_y_mut(&mut self.squares, mov.to) {
+                Some(s) => {
+                    s.player_number = mover_player_number;
+                    s.kind = mover_piece_kind;
+                },
+                None => return Err("Invalid To Square")
+            }
+
+            for (point, square) in mov.explosion.iter() {
+                match find_by_x_and_y_mut(&mut self.squares, *point) {
+                    Some(s) => {
+                        s.player_number = square.player_number;
+                        s.kind = square.kind;
+                    },
+                    None => return Err("Invalid Explosion Square")
+                }
+            }
+        }
+
         if let Some(cm) = &mov.castle_move {
             let from = cm.from();
             let to = cm.to();
@@ -407,6 +744,33 @@ impl GameState {
             }
         };
 
+        if self.variant == Variant::Crazyhouse {
+            // remove captured piece from hand
+            if let Some(pk) = mov.capture_piece_kind {
+                let hand_piece_kind = if mov.capture_promoted {
+                    PieceKind::Pawn
+                } else {
+                    pk
+                };
+                let hand = &mut self.hands[moving_piece_player_number as usize];
+                if let Some(idx) = hand.iter().position(|hpk| *hpk == hand_piece_kind) {
+                    hand.remove(idx);
+                } else {
+                    return Err("game_state::undo_move - No piece matches in hand");
+                }
+            }
+
+            if mov.promote_piece_kind.is_some() {
+                self.promoted_points.retain(|p| *p != mov.to);
+            } else if let Some(pos) = self.promoted_points.iter().position(|p| *p == mov.to) {
+                self.promoted_points[pos] = mov.from;
+            }
+
+            if mov.capture_promoted {
+                self.promoted_points.push(mov.to);
+            }
+        }
+
         // castle moves
         if mov.moving_piece_kind == PieceKind::King {
             if let Some(from) = find_by_x_and_y(&self.squares, mov.from) {
@@ -472,16 +836,92 @@ impl GameState {
 
         Ok(())
     }
+
+    fn perform_drop(&mut self, mov: &Move) -> Result<(), &'static str> {
+        match find_by_x_and_y(&self.squares, mov.to) {
+            Some(s) => {
+                if s.occupied() {
+                    return Err("game_state::perform_move - Drop on occupied square");
+                }
+            },
+            None => return Err("Invalid To Square")
+        }
+
+        // remove piece from hand
+        let player_hand = &mut self.hands[self.current_player_number as usize];
+        if let Some(idx) = player_hand.iter().position(|pk| *pk == mov.moving_piece_kind) {
+            player_hand.remove(idx);
+        } else {
+            return Err("game_state::perform_move - No piece matches in hand");
+        }
+
+        if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.to) {
+            s.player_number = self.current_player_number;
+            s.kind = mov.moving_piece_kind;
+        }
+
+        self.en_passant_target = None;
+
+        self.current_player_number = match self.current_player_number {
+            1 => 2,
+            _ => 1
+        };
+
+        Ok(())
+    }
+
+    fn undo_drop(&mut self, mov: &Move) -> Result<(), &'static str> {
+        let piece_player_number: i8;
+        let piece_kind: PieceKind;
+
+        match find_by_x_and_y_mut(&mut self.squares, mov.to) {
+            Some(s) => {
+                if s.unoccupied() {
+                    return Err("game_state::undo_move - No piece on to");
+                }
+                piece_player_number = s.player_number;
+                piece_kind = s.kind;
+                s.player_number = 0;
+                s.kind = PieceKind::Empty;
+            },
+            None => return Err("Invalid To Square")
+        }
+
+        // return piece to hand
+        self.hands[piece_player_number as usize].push(piece_kind);
+
+        // set en passant target
+        self.en_passant_target = mov.en_passant_target;
+
+        self.current_player_number = match self.current_player_number {
+            1 => 2,
+            _ => 1
+        };
+
+        Ok(())
+    }
 }
 
 // state player castle en_passant
 // rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1
 // rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1
 pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
+    parse_with_variant(encoded, Variant::Standard)
+}
+
+// state hand player castle en_passant
+// crazyhouse: pieces in hand are in brackets, promoted pieces are followed by ~
+// rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1
+// r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R[Pp] w KQkq - 0 1
+// three check: remaining checks for white and black follow en passant
+// rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1
+pub fn parse_with_variant(encoded: &String, variant: Variant) -> Result<GameState, &'static str> {
     let mut read_board = true;
+    let mut read_hand = false;
     let mut read_player_number = false;
     let mut read_castle_moves = false;
     let mut read_en_passant = false;
+    let mut read_remaining_checks = false;
     let mut parse_error = false;
 
     let mut y: i8 = 0;
@@ -573,6 +1013,14 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
     let mut castle_moves = vec![];
     let mut en_passant_target = None;
     let mut en_passant_x: i8 = 0;
+    let mut hands: Vec<Vec<PieceKind>> = vec![
+        vec![],
+        vec![],
+        vec![]
+    ];
+    let mut promoted_points = vec![];
+    let mut remaining_checks: Vec<i8> = vec![0, 3, 3];
+    let mut remaining_checks_player_number: usize = 1;
 
     for c in encoded.chars() {
         match c {
@@ -591,6 +1039,11 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                         }
                     };
                     x += 1; // increment column
+                } else if read_hand {
+                    match parse_piece(c) {
+                        Ok(square) => hands[square.player_number as usize].push(square.kind),
+                        Err(_) => parse_error = true
+                    };
                 }
             },
             'q' | 'Q' | 'k' | 'K' => {
@@ -608,6 +1061,17 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                         }
                     };
                     x += 1; // increment column
+                } else if read_hand {
+                    match parse_piece(c) {
+                        Ok(square) => {
+                            if square.kind == PieceKind::King {
+                                parse_error = true;
+                            } else {
+                                hands[square.player_number as usize].push(square.kind);
+                            }
+                        },
+                        Err(_) => parse_error = true
+                    };
                 } else if read_castle_moves {
                     if let Some(cm) = parse_castle_move(c) {
                         castle_moves.push(cm);
@@ -631,6 +1095,8 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                     } else {
                         parse_error = true;
                     }
+                } else if read_remaining_checks {
+                    parse_remaining_checks(c, &mut remaining_checks, remaining_checks_player_number, &mut parse_error);
                 } else if read_en_passant {
                     if let Some(unwrapped) = c.to_digit(10) {
                         // row/y/ reverse
@@ -651,10 +1117,33 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                     x = 0; // reset column
                 }
             },
-            ' ' => {
-                if read_board {
+            '[' => {
+                if read_board && variant == Variant::Crazyhouse {
                     // board reading finished
                     read_board = false;
+                    read_hand = true;
+                } else {
+                    parse_error = true;
+                }
+            },
+            ']' => {
+                if !read_hand {
+                    parse_error = true;
+                }
+            },
+            '~' => {
+                if read_board && variant == Variant::Crazyhouse && valid((x - 1, y)) {
+                    // previous piece is promoted
+                    promoted_points.push((x - 1, y));
+                } else {
+                    parse_error = true;
+                }
+            },
+            ' ' => {
+                if read_board || read_hand {
+                    // board and hand reading finished
+                    read_board = false;
+                    read_hand = false;
                     read_player_number = true;
                 } else if read_player_number {
                     // player reading finished
@@ -665,7 +1154,11 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                     read_castle_moves = false;
                     read_en_passant = true;
                 } else if read_en_passant {
+                    // en passant reading finished
                     read_en_passant = false;
+                    read_remaining_checks = variant == Variant::ThreeCheck;
+                } else if read_remaining_checks {
+                    read_remaining_checks = false;
                 }
             },
             'w' => {
@@ -688,6 +1181,11 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                         }
                     };
                     x += 1; // increment column
+                } else if read_hand {
+                    match parse_piece(c) {
+                        Ok(square) => hands[square.player_number as usize].push(square.kind),
+                        Err(_) => parse_error = true
+                    };
                 } else if read_player_number {
                     current_player_number = 2;
                 } else if read_en_passant {
@@ -702,14 +1200,24 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                 }
             },
             '-' => {
-                if read_castle_moves {
+                if read_castle_moves || read_hand {
                     ()
                 } else if read_en_passant {
                     en_passant_target = None;
                 }
             }
             '0' | '9' => {
-                () //ignore for now
+                if read_remaining_checks {
+                    parse_remaining_checks(c, &mut remaining_checks, remaining_checks_player_number, &mut parse_error);
+                }
+                //ignore counters for now
+            },
+            '+' => {
+                if read_remaining_checks && remaining_checks_player_number == 1 {
+                    remaining_checks_player_number = 2;
+                } else {
+                    parse_error = true;
+                }
             }
             _ => {
                 parse_error = true;
@@ -720,12 +1228,49 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
     if parse_error {
         Err("Error parsing state")
     } else {
-        Ok(GameState {
+        let mut game_state = GameState {
             current_player_number,
             squares,
             castle_moves,
-            en_passant_target
-        })
+            en_passant_target,
+            variant,
+            hands,
+            promoted_points,
+            remaining_checks,
+            king_points: vec![None, None, None],
+            attacks: vec![]
+        };
+        game_state.refresh_attacks();
+        Ok(game_state)
+    }
+}
+
+// points whose occupant changes when the move is performed or undone
+fn changed_points(mov: &Move) -> Vec<(i8, i8)> {
+    let mut acc = vec![mov.from];
+    let mut push = |point: (i8, i8)| {
+        if !acc.contains(&point) {
+            acc.push(point);
+        }
+    };
+    push(mov.to);
+    if let Some(p) = mov.en_passant_point {
+        push(p);
+    }
+    if let Some(cm) = &mov.castle_move {
+        push(cm.from());
+        push(cm.to());
+    }
+    for (point, _) in mov.explosion.iter() {
+        push(*point);
+    }
+    acc
+}
+
+fn parse_remaining_checks(c: char, remaining_checks: &mut [i8], player_number: usize, parse_error: &mut bool) {
+    match c.to_digit(10) {
+        Some(n) if n <= 3 => remaining_checks[player_number] = n as i8,
+        _ => *parse_error = true
     }
 }
 
@@ -995,7 +1540,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1023,7 +1571,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1047,7 +1598,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
         let result = state.perform_move(&mov);
         let expected = vec![
@@ -1071,7 +1625,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1099,7 +1656,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1127,7 +1687,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1154,7 +1717,10 @@ mod tests {
             promote_piece_kind: Some(PieceKind::Queen),
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1181,7 +1747,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: Some((1, 3)),
             en_passant_target: Some((1, 2)),
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1211,7 +1780,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: Some((0, 2)),
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1232,7 +1804,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1253,7 +1828,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: Some((0, 5)),
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1274,7 +1852,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: Some(CastleMove { player_number: 1, side: Side::King })
+            castle_move: Some(CastleMove { player_number: 1, side: Side::King }),
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.perform_move(&mov);
@@ -1311,7 +1892,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1338,7 +1922,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1364,7 +1951,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
         let result = state.undo_move(&mov);
         let expected = vec![
@@ -1389,7 +1979,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: Some((1, 3)),
             en_passant_target: Some((1, 2)),
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let mut new_state = state.clone();
@@ -1457,7 +2050,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1485,7 +2081,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1513,7 +2112,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1540,7 +2142,10 @@ mod tests {
             promote_piece_kind: Some(PieceKind::Queen),
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1567,7 +2172,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: Some((1, 3)),
             en_passant_target: Some((1, 2)),
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1598,7 +2206,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: Some(CastleMove { player_number: 1, side: Side::King })
+            castle_move: Some(CastleMove { player_number: 1, side: Side::King }),
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = state.undo_move(&mov);
@@ -1664,4 +2275,463 @@ mod tests {
 
         assert_eq!(result.en_passant_target, None);
     }
+
+    #[test]
+    fn parse_crazyhouse_test() {
+        let encoded = String::from("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R[Pnp] w KQkq - 0 1");
+        let result = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.variant, Variant::Crazyhouse);
+        assert_eq!(result.squares[0][0].kind, PieceKind::Rook);
+        assert_eq!(result.castle_moves.len(), 4);
+
+        let expected: Vec<Vec<PieceKind>> = vec![
+           vec![],
+           vec![PieceKind::Pawn],
+           vec![PieceKind::Knight, PieceKind::Pawn]
+        ];
+        assert_eq!(result.hands, expected);
+    }
+
+    #[test]
+    fn parse_crazyhouse_promoted_test() {
+        let encoded = String::from("4k2Q~/8/8/8/8/8/8/4K3[] b - - 0 1");
+        let result = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        assert_eq!(result.squares[0][7].kind, PieceKind::Queen);
+        assert_eq!(result.promoted_points, vec![(7, 0)]);
+    }
+
+    #[test]
+    fn parse_crazyhouse_hand_in_standard_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/4K3[Pp] w - - 0 1");
+        let result = parse(&encoded);
+        assert_eq!(result.is_err(), true);
+    }
+
+    #[test]
+    fn parse_crazyhouse_king_in_hand_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/4K3[K] w - - 0 1");
+        let result = parse_with_variant(&encoded, Variant::Crazyhouse);
+        assert_eq!(result.is_err(), true);
+    }
+
+    #[test]
+    fn possible_moves_crazyhouse_drops_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/4K3[Pn] w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let result = state.possible_moves();
+
+        // 5 king moves, 48 pawn drops, not on first or last rank
+        assert_eq!(result.len(), 53);
+        assert_eq!(result.iter().filter(|m| m.drop).count(), 48);
+        assert_eq!(result.iter().any(|m| m.drop && (m.to.1 == 0 || m.to.1 == 7)), false);
+    }
+
+    #[test]
+    fn possible_moves_crazyhouse_drop_blocks_check_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/r3K3[N] w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let result = state.possible_moves();
+
+        // 3 king moves, 3 knight drops between the rook and the king
+        assert_eq!(result.len(), 6);
+        assert_eq!(result.iter().filter(|m| m.drop).count(), 3);
+    }
+
+    #[test]
+    fn perform_move_crazyhouse_drop_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/4K3[N] w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let mov = Move {
+            from: (5, 5),
+            to: (5, 5),
+            moving_piece_kind: PieceKind::Knight,
+            capture_piece_kind: None,
+            promote_piece_kind: None,
+            en_passant_point: None,
+            en_passant_target: None,
+            castle_move: None,
+            drop: true,
+            capture_promoted: false,
+            explosion: vec![]
+        };
+
+        let result = state.perform_move(&mov);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.current_player_number, 2);
+        let to = find_by_x_and_y(&state.squares, (5, 5)).unwrap();
+        assert_eq!(to.player_number, 1);
+        assert_eq!(to.kind, PieceKind::Knight);
+        assert_eq!(state.hands[1].len(), 0);
+    }
+
+    #[test]
+    fn perform_move_crazyhouse_capture_test() {
+        let encoded = String::from("4k3/8/8/8/8/1n6/P7/4K3[] w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let mov = Move {
+            from: (0, 6),
+            to: (1, 5),
+            moving_piece_kind: PieceKind::Pawn,
+            capture_piece_kind: Some(PieceKind::Knight),
+            promote_piece_kind: None,
+            en_passant_point: None,
+            en_passant_target: None,
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
+        };
+
+        let result = state.perform_move(&mov);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.hands[1], vec![PieceKind::Knight]);
+        assert_eq!(state.hands[2].len(), 0);
+    }
+
+    #[test]
+    fn perform_move_crazyhouse_capture_promoted_test() {
+        let encoded = String::from("4k3/8/8/8/8/1q~6/P7/4K3[] w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let moves = state.possible_moves();
+        let mov = moves.iter().find(|m| m.to == (1, 5)).unwrap();
+
+        assert_eq!(mov.capture_promoted, true);
+
+        let result = state.perform_move(mov);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.hands[1], vec![PieceKind::Pawn]);
+        assert_eq!(state.promoted_points.len(), 0);
+    }
+
+    #[test]
+    fn perform_move_crazyhouse_promote_test() {
+        let encoded = String::from("4k3/P7/8/8/8/8/8/4K3[] w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let mov = Move {
+            from: (0, 1),
+            to: (0, 0),
+            moving_piece_kind: PieceKind::Pawn,
+            capture_piece_kind: None,
+            promote_piece_kind: Some(PieceKind::Queen),
+            en_passant_point: None,
+            en_passant_target: None,
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
+        };
+
+        let result = state.perform_move(&mov);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.promoted_points, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn undo_move_crazyhouse_drop_test() {
+        let encoded = String::from("4k3/8/8/8/8/5N2/8/4K3[] b - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let mov = Move {
+            from: (5, 5),
+            to: (5, 5),
+            moving_piece_kind: PieceKind::Knight,
+            capture_piece_kind: None,
+            promote_piece_kind: None,
+            en_passant_point: None,
+            en_passant_target: None,
+            castle_move: None,
+            drop: true,
+            capture_promoted: false,
+            explosion: vec![]
+        };
+
+        let result = state.undo_move(&mov);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.current_player_number, 1);
+        let to = find_by_x_and_y(&state.squares, (5, 5)).unwrap();
+        assert_eq!(to.player_number, 0);
+        assert_eq!(to.kind, PieceKind::Empty);
+        assert_eq!(state.hands[1], vec![PieceKind::Knight]);
+    }
+
+    #[test]
+    fn undo_move_crazyhouse_capture_promoted_test() {
+        let encoded = String::from("4k3/8/8/8/8/1P6/8/4K3[P] b - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Crazyhouse).unwrap();
+        let mov = Move {
+            from: (0, 6),
+            to: (1, 5),
+            moving_piece_kind: PieceKind::Pawn,
+            capture_piece_kind: Some(PieceKind::Queen),
+            promote_piece_kind: None,
+            en_passant_point: None,
+            en_passant_target: None,
+            castle_move: None,
+            drop: false,
+            capture_promoted: true,
+            explosion: vec![]
+        };
+
+        let result = state.undo_move(&mov);
+
+        assert_eq!(result, Ok(()));
+        let to = find_by_x_and_y(&state.squares, (1, 5)).unwrap();
+        assert_eq!(to.player_number, 2);
+        assert_eq!(to.kind, PieceKind::Queen);
+        assert_eq!(state.hands[1].len(), 0);
+        assert_eq!(state.promoted_points, vec![(1, 5)]);
+    }
+
+    #[test]
+    fn possible_moves_castle_move_missing_rook_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/4K3 w K - 0 1");
+        let mut state = parse(&encoded).unwrap();
+        let result = state.possible_moves();
+
+        assert!(result.iter().all(|m| m.castle_move.is_none()));
+    }
+
+    #[test]
+    fn winner_king_of_the_hill_test() {
+        let encoded = String::from("4k3/8/8/3K4/8/8/8/8 b - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::KingOfTheHill).unwrap();
+
+        assert_eq!(state.winner(), Some(1));
+        assert_eq!(state.possible_moves().len(), 0);
+    }
+
+    #[test]
+    fn winner_king_of_the_hill_standard_test() {
+        let encoded = String::from("4k3/8/8/3K4/8/8/8/8 b - - 0 1");
+        let mut state = parse(&encoded).unwrap();
+
+        assert_eq!(state.winner(), None);
+    }
+
+    #[test]
+    fn parse_three_check_test() {
+        let encoded = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 2+1 0 1");
+        let result = parse_with_variant(&encoded, Variant::ThreeCheck).unwrap();
+
+        assert_eq!(result.variant, Variant::ThreeCheck);
+        assert_eq!(result.remaining_checks, vec![0, 2, 1]);
+        assert_eq!(result.castle_moves.len(), 4);
+    }
+
+    #[test]
+    fn parse_three_check_in_standard_test() {
+        let encoded = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 2+1 0 1");
+        let result = parse(&encoded);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn winner_three_check_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/4K3 b - - 0+3 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::ThreeCheck).unwrap();
+
+        assert_eq!(state.winner(), Some(1));
+    }
+
+    #[test]
+    fn perform_move_three_check_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/R3K3 w - - 3+3 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::ThreeCheck).unwrap();
+        let moves = state.possible_moves();
+        let mov = moves.iter().find(|m| m.from == (0, 7) && m.to == (0, 0)).unwrap();
+
+        let result = state.perform_move(mov);
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.remaining_checks, vec![0, 2, 3]);
+
+        let result = state.undo_move(mov);
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.remaining_checks, vec![0, 3, 3]);
+    }
+
+    #[test]
+    fn perform_move_atomic_explosion_test() {
+        let encoded = String::from("4k3/8/8/3n1p2/4p3/8/3N4/4K3 w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Atomic).unwrap();
+        let moves = state.possible_moves();
+        let mov = moves.iter().find(|m| m.from == (3, 6) && m.to == (4, 4)).unwrap();
+
+        assert_eq!(mov.explosion, vec![((3, 3), Square { player_number: 2, kind: PieceKind::Knight })]);
+
+        let result = state.perform_move(mov);
+        assert_eq!(result, Ok(()));
+        assert_eq!(find_by_x_and_y(&state.squares, (3, 6)).unwrap().kind, PieceKind::Empty);
+        assert_eq!(find_by_x_and_y(&state.squares, (4, 4)).unwrap().kind, PieceKind::Empty);
+        assert_eq!(find_by_x_and_y(&state.squares, (3, 3)).unwrap().kind, PieceKind::Empty);
+        assert_eq!(find_by_x_and_y(&state.squares, (5, 3)).unwrap().kind, PieceKind::Pawn);
+
+        let result = state.undo_move(mov);
+        assert_eq!(result, Ok(()));
+        let from = find_by_x_and_y(&state.squares, (3, 6)).unwrap();
+        assert_eq!(from.kind, PieceKind::Knight);
+        assert_eq!(from.player_number, 1);
+        let to = find_by_x_and_y(&state.squares, (4, 4)).unwrap();
+        assert_eq!(to.kind, PieceKind::Pawn);
+        assert_eq!(to.player_number, 2);
+        let exploded = find_by_x_and_y(&state.squares, (3, 3)).unwrap();
+        assert_eq!(exploded.kind, PieceKind::Knight);
+        assert_eq!(exploded.player_number, 2);
+    }
+
+    #[test]
+    fn possible_moves_atomic_king_capture_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/4p3/4K3 w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Atomic).unwrap();
+        let result = state.possible_moves();
+
+        assert!(result.iter().all(|m| m.capture_piece_kind.is_none()));
+    }
+
+    #[test]
+    fn possible_moves_atomic_own_king_explosion_test() {
+        let encoded = String::from("8/8/8/8/8/8/3qK3/k2R4 w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Atomic).unwrap();
+        let result = state.possible_moves();
+
+        assert!(!result.iter().any(|m| m.from == (3, 7) && m.to == (3, 6)));
+    }
+
+    #[test]
+    fn perform_move_atomic_explode_king_test() {
+        let encoded = String::from("4k3/4q3/8/8/8/8/8/4R1K1 w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Atomic).unwrap();
+        let moves = state.possible_moves();
+        let mov = moves.iter().find(|m| m.from == (4, 7) && m.to == (4, 1)).unwrap();
+
+        let result = state.perform_move(mov);
+        assert_eq!(result, Ok(()));
+        assert_eq!(state.king_point(2), None);
+        assert_eq!(state.winner(), Some(1));
+    }
+
+    #[test]
+    fn in_check_atomic_adjacent_kings_test() {
+        let encoded = String::from("4r3/8/8/8/8/8/3kK3/8 w - - 0 1");
+        let atomic_state = parse_with_variant(&encoded, Variant::Atomic).unwrap();
+        let standard_state = parse(&encoded).unwrap();
+
+        assert!(!atomic_state.in_check(1));
+        assert!(standard_state.in_check(1));
+    }
+
+    #[test]
+    fn possible_moves_horde_first_rank_pawn_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/P7 w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Horde).unwrap();
+        let result = state.possible_moves();
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().any(|m| m.to == (0, 5)));
+        assert!(result.iter().any(|m| m.to == (0, 6)));
+    }
+
+    #[test]
+    fn winner_horde_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/8 w - - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Horde).unwrap();
+
+        assert!(!state.in_check(1));
+        assert_eq!(state.winner(), Some(2));
+    }
+
+    #[test]
+    fn not_winner_horde_test() {
+        let encoded = String::from("rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::Horde).unwrap();
+
+        assert_eq!(state.winner(), None);
+        assert_eq!(state.possible_moves().len(), 8);
+    }
+
+    fn assert_attacks_match_refresh(state: &GameState) {
+        let mut refreshed = state.clone();
+        refreshed.refresh_attacks();
+        assert_eq!(state.attacks, refreshed.attacks);
+        assert_eq!(state.king_points, refreshed.king_points);
+    }
+
+    // every move and its undo leaves the same attacks as computing them from scratch
+    fn assert_incremental_attacks(encoded: &str, variant: Variant) {
+        let mut state = parse_with_variant(&String::from(encoded), variant).unwrap();
+        let moves = state.possible_moves();
+        assert!(!moves.is_empty());
+
+        for mov in moves.iter() {
+            state.perform_move(mov).unwrap();
+            assert_attacks_match_refresh(&state);
+
+            for reply in state.possible_moves().iter() {
+                state.perform_move(reply).unwrap();
+                assert_attacks_match_refresh(&state);
+                state.undo_move(reply).unwrap();
+            }
+
+            state.undo_move(mov).unwrap();
+            assert_attacks_match_refresh(&state);
+        }
+    }
+
+    #[test]
+    fn attacks_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+        let state = parse(&encoded).unwrap();
+
+        assert_eq!(state.king_points, vec![None, Some((4, 7)), Some((4, 0))]);
+        assert_eq!(state.attacks[1][0][0], 1);
+        assert_eq!(state.attacks[1][7][3], 2);
+        assert_eq!(state.attacks[1][7][5], 1);
+        assert_eq!(state.attacks[1][7][6], 0);
+        assert_eq!(state.attacks[2][1][4], 1);
+    }
+
+    #[test]
+    fn incremental_attacks_standard_test() {
+        assert_incremental_attacks("r3k2r/pPppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", Variant::Standard);
+    }
+
+    #[test]
+    fn incremental_attacks_en_passant_test() {
+        assert_incremental_attacks("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", Variant::Standard);
+    }
+
+    #[test]
+    fn incremental_attacks_promotion_test() {
+        assert_incremental_attacks("1r2k3/P7/8/8/8/8/7p/4K3 w - - 0 1", Variant::Standard);
+    }
+
+    #[test]
+    fn incremental_attacks_crazyhouse_test() {
+        assert_incremental_attacks("r3k3/8/8/8/8/8/8/R3K3[Nb] w - - 0 1", Variant::Crazyhouse);
+    }
+
+    #[test]
+    fn incremental_attacks_atomic_test() {
+        assert_incremental_attacks("rnbqkbnr/pppp1ppp/8/4p1N1/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 2", Variant::Atomic);
+    }
+
+    #[test]
+    fn incremental_attacks_three_check_test() {
+        let encoded = String::from("4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1");
+        let mut state = parse_with_variant(&encoded, Variant::ThreeCheck).unwrap();
+        let mov = state.possible_moves().into_iter().find(|m| m.to == (0, 0)).unwrap();
+
+        state.perform_move(&mov).unwrap();
+        assert_eq!(state.remaining_checks, vec![0, 0, 3]);
+        assert_attacks_match_refresh(&state);
+
+        state.undo_move(&mov).unwrap();
+        assert_eq!(state.remaining_checks, vec![0, 1, 3]);
+        assert_attacks_match_refresh(&state);
+    }
 }
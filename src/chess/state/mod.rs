@@ -2,7 +2,8 @@ pub mod point;
 mod castle_move;
 mod piece_factory;
 pub mod square;
-mod square_set;
+pub mod square_set;
 pub mod external_mov;
 pub mod mov;
+pub mod variant;
 pub mod game_state;
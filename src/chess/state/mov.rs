@@ -1,4 +1,5 @@
 use crate::chess::state::square::PieceKind;
+use crate::chess::state::square::Square;
 use crate::chess::state::castle_move::CastleMove;
 
 pub struct Move {
@@ -9,7 +10,10 @@ pub struct Move {
     pub promote_piece_kind: Option<PieceKind>, // Undo -> revert promotion
     pub en_passant_point: Option<(i8, i8)>, // Undo - add capture piece back next to from
     pub en_passant_target: Option<(i8, i8)>, // Undo - set game state en_passant_target back
-    pub castle_move: Option<CastleMove> // Undo - Move king and rook back to start.
+    pub castle_move: Option<CastleMove>, // Undo - Move king and rook back to start.
+    pub drop: bool, // Undo - return piece to hand. from is the same as to.
+    pub capture_promoted: bool, // Undo - return pawn from hand and mark capture piece as promoted.
+    pub explosion: Vec<((i8, i8), Square)> // Undo - place exploded pieces back around to.
 }
 
 impl Clone for Move {
@@ -22,7 +26,10 @@ impl Clone for Move {
             promote_piece_kind: self.promote_piece_kind,
             en_passant_point: self.en_passant_point,
             en_passant_target: self.en_passant_target,
-            castle_move: self.castle_move
+            castle_move: self.castle_move,
+            drop: self.drop,
+            capture_promoted: self.capture_promoted,
+            explosion: self.explosion.clone()
         }
     }
 }
@@ -14,6 +14,9 @@ pub const PLAYER_ONE_QUEEN_SIDE_ROOK: (i8, i8) = (0, 7);
 pub const PLAYER_TWO_KING_SIDE_ROOK: (i8, i8) = (7, 0);
 pub const PLAYER_TWO_QUEEN_SIDE_ROOK: (i8, i8) = (0, 0);
 
+pub const ORTHOGONAL_DIRECTIONS: [(i8, i8); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub const DIAGONAL_DIRECTIONS: [(i8, i8); 4] = [(-1, -1), (1, -1), (1, 1), (-1, 1)];
+
 pub fn valid(point: (i8, i8)) -> bool {
     point.0 >= MIN_N && point.0 <= MAX_N && point.1 >= MIN_N && point.1 <= MAX_N
 }
@@ -6,10 +6,15 @@ use crate::chess::state::point::one_step_destination_points;
 use crate::chess::state::point::king_castle_destination_points;
 use crate::chess::state::point::forward_diagonal_step_destination_points;
 use crate::chess::state::point::pawn_destination_points;
+use crate::chess::state::point::valid;
+use crate::chess::state::point::add;
+use crate::chess::state::point::ORTHOGONAL_DIRECTIONS;
+use crate::chess::state::point::DIAGONAL_DIRECTIONS;
 use crate::chess::state::square_set::find_by_x_and_y;
 use crate::chess::state::square_set::between_unoccupied;
 use crate::chess::state::game_state::GameState;
 use crate::chess::state::castle_move::Side;
+use crate::chess::state::variant::Variant;
 
 use crate::chess::state::point::PLAYER_ONE_CASTLE_KING_SIDE;
 use crate::chess::state::point::PLAYER_ONE_CASTLE_QUEEN_SIDE;
@@ -68,6 +73,16 @@ impl Square {
                     }
                 }
 
+                // horde: white pawns on the first rank can also move two squares
+                if game_state.variant == Variant::Horde && self.player_number == 1 && point.1 == 7 {
+                    let to_point = (point.0, 5);
+                    if let Some(to) = find_by_x_and_y(&game_state.squares, to_point) {
+                        if to.unoccupied() && between_unoccupied(&game_state.squares, point, to_point) {
+                            acc.push(to_point);
+                        }
+                    }
+                }
+
                 for to_point in forward_diagonal_step_destination_points(point, self.player_number) {
                     if let Some(to) = find_by_x_and_y(&game_state.squares, to_point) {
                         if to.occupied_by_opponent(self.player_number) || self.en_passant_condition(point, to_point, game_state) {
@@ -164,17 +179,42 @@ impl Square {
         acc
     }
 
+    // squares the piece attacks whatever occupies them, sliders stop at the first piece in the way
+    pub fn attack_squares(&self, point: (i8, i8), game_state: &GameState) -> Vec<(i8, i8)> {
+        match self.kind {
+            PieceKind::Pawn => {
+                forward_diagonal_step_destination_points(point, self.player_number).into_iter().filter(|p| valid(*p)).collect()
+            },
+            PieceKind::Knight => {
+                l_shape_destination_points(point).into_iter().filter(|p| valid(*p)).collect()
+            },
+            PieceKind::King => {
+                one_step_destination_points(point).into_iter().filter(|p| valid(*p)).collect()
+            },
+            PieceKind::Rook => ray_attack_squares(point, &ORTHOGONAL_DIRECTIONS, game_state),
+            PieceKind::Bishop => ray_attack_squares(point, &DIAGONAL_DIRECTIONS, game_state),
+            PieceKind::Queen => {
+                let mut acc = ray_attack_squares(point, &ORTHOGONAL_DIRECTIONS, game_state);
+                acc.append(&mut ray_attack_squares(point, &DIAGONAL_DIRECTIONS, game_state));
+                acc
+            },
+            PieceKind::Empty => vec![]
+        }
+    }
+
     fn castle_conditions(&self, point: (i8, i8), to_point: (i8, i8), game_state: &GameState) -> bool {
         match self.player_number {
             1 => {
                 match to_point {
                     PLAYER_ONE_CASTLE_KING_SIDE => {
                         game_state.castle_moves.iter().any(|cm| cm.player_number == 1 && cm.side == Side::King) &&
-                            between_unoccupied(&game_state.squares, point, PLAYER_ONE_KING_SIDE_ROOK)
+                            between_unoccupied(&game_state.squares, point, PLAYER_ONE_KING_SIDE_ROOK) &&
+                            self.rook_present(PLAYER_ONE_KING_SIDE_ROOK, game_state)
                     },
                     PLAYER_ONE_CASTLE_QUEEN_SIDE => {
                         game_state.castle_moves.iter().any(|cm| cm.player_number == 1 && cm.side == Side::Queen) &&
-                            between_unoccupied(&game_state.squares, point, PLAYER_ONE_QUEEN_SIDE_ROOK)
+                            between_unoccupied(&game_state.squares, point, PLAYER_ONE_QUEEN_SIDE_ROOK) &&
+                            self.rook_present(PLAYER_ONE_QUEEN_SIDE_ROOK, game_state)
                     },
                     _ => false
                 }
@@ -183,11 +223,13 @@ impl Square {
                 match to_point {
                     PLAYER_TWO_CASTLE_KING_SIDE => {
                         game_state.castle_moves.iter().any(|cm| cm.player_number == 2 && cm.side == Side::King) &&
-                            between_unoccupied(&game_state.squares, point, PLAYER_TWO_KING_SIDE_ROOK)
+                            between_unoccupied(&game_state.squares, point, PLAYER_TWO_KING_SIDE_ROOK) &&
+                            self.rook_present(PLAYER_TWO_KING_SIDE_ROOK, game_state)
                     },
                     PLAYER_TWO_CASTLE_QUEEN_SIDE => {
                         game_state.castle_moves.iter().any(|cm| cm.player_number == 2 && cm.side == Side::Queen) &&
-                            between_unoccupied(&game_state.squares, point, PLAYER_TWO_QUEEN_SIDE_ROOK)
+                            between_unoccupied(&game_state.squares, point, PLAYER_TWO_QUEEN_SIDE_ROOK) &&
+                            self.rook_present(PLAYER_TWO_QUEEN_SIDE_ROOK, game_state)
                     },
                     _ => false
                 }
@@ -196,6 +238,14 @@ impl Square {
         }
     }
 
+    // the rook may have been removed without moving (e.g. atomic explosions)
+    fn rook_present(&self, rook_point: (i8, i8), game_state: &GameState) -> bool {
+        match find_by_x_and_y(&game_state.squares, rook_point) {
+            Some(s) => s.kind == PieceKind::Rook && s.player_number == self.player_number,
+            None => false
+        }
+    }
+
     fn en_passant_condition(&self, point: (i8, i8), to_point: (i8, i8), game_state: &GameState) -> bool {
         let mut result = false;
         if let Some(target) = game_state.en_passant_target {
@@ -223,6 +273,21 @@ impl Square {
     }
 }
 
+fn ray_attack_squares(point: (i8, i8), directions: &[(i8, i8)], game_state: &GameState) -> Vec<(i8, i8)> {
+    let mut acc = vec![];
+    for direction in directions {
+        let mut counter = add(point, *direction);
+        while valid(counter) {
+            acc.push(counter);
+            match find_by_x_and_y(&game_state.squares, counter) {
+                Some(s) if s.occupied() => break,
+                _ => counter = add(counter, *direction)
+            }
+        }
+    }
+    acc
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
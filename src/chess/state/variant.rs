@@ -0,0 +1,9 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    Standard,
+    Crazyhouse,
+    KingOfTheHill,
+    ThreeCheck,
+    Atomic,
+    Horde
+}
@@ -61,11 +61,11 @@ pub fn mcts(game_data: &String) -> HttpResponse {
     }
 }
 
-fn build_external_move(game_state: &chess::state::game_state::GameState, mov: chess::state::mov::Move) -> chess::state::external_mov::ExternalMove {
+pub fn build_external_move(game_state: &chess::state::game_state::GameState, mov: chess::state::mov::Move) -> chess::state::external_mov::ExternalMove {
     let mut new_state = game_state.clone();
     let _result = new_state.perform_move(&mov);
 
-    let file_disambiguation = match mov.moving_piece_kind {
+    let file_disambiguation = !mov.drop && match mov.moving_piece_kind {
         chess::state::square::PieceKind::Pawn => mov.capture_piece_kind.is_some(),
         _ => {
             let mut count: i8 = 0;
@@ -82,7 +82,7 @@ fn build_external_move(game_state: &chess::state::game_state::GameState, mov: ch
         }
     };
 
-    let rank_disambiguation = match mov.moving_piece_kind {
+    let rank_disambiguation = !mov.drop && match mov.moving_piece_kind {
         chess::state::square::PieceKind::Pawn => false,
         _ => {
             let mut count: i8 = 0;
@@ -111,6 +111,7 @@ fn build_external_move(game_state: &chess::state::game_state::GameState, mov: ch
         en_passant_point: mov.en_passant_point,
         en_passant_target: mov.en_passant_target,
         castle_move: mov.castle_move,
+        drop: mov.drop,
         file_disambiguation,
         rank_disambiguation,
         in_check,
@@ -156,7 +157,7 @@ mod tests {
 
          assert_eq!(result.status(), 200);
          match result.into_body().try_into_bytes() {
-            Ok(bytes) => assert_eq!(bytes, "e3\n"),
+            Ok(bytes) => assert_eq!(bytes, "e4\n"),
             Err(_) => assert!(false, "unexpected body")
          };
      }
@@ -221,7 +222,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
         let result = build_external_move(&state, mov);
 
@@ -240,7 +244,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = build_external_move(&state, mov);
@@ -260,7 +267,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = build_external_move(&state, mov);
@@ -280,7 +290,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = build_external_move(&state, mov);
@@ -301,7 +314,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = build_external_move(&state, mov);
@@ -320,7 +336,10 @@ mod tests {
             promote_piece_kind: None,
             en_passant_point: None,
             en_passant_target: None,
-            castle_move: None
+            castle_move: None,
+            drop: false,
+            capture_promoted: false,
+            explosion: vec![]
         };
 
         let result = build_external_move(&state, mov);
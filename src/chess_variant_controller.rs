@@ -0,0 +1,313 @@
+use std::env;
+use actix_web::HttpResponse;
+use super::chess;
+use super::chess_controller::build_external_move;
+use chess::state::variant::Variant;
+
+// environment variables are prefixed with the game type, e.g. CRAZYHOUSE_MINIMAX_DEPTH
+fn env_prefix(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Crazyhouse => "CRAZYHOUSE",
+        Variant::KingOfTheHill => "KING_OF_THE_HILL",
+        Variant::ThreeCheck => "THREE_CHECK",
+        Variant::Atomic => "ATOMIC",
+        Variant::Horde => "HORDE",
+        Variant::Standard => "CHESS"
+    }
+}
+
+fn env_number<T: std::str::FromStr>(variant: Variant, name: &str, default: &str) -> T {
+    let key = format!("{}_{}", env_prefix(variant), name);
+    env::var(&key)
+        .unwrap_or_else(|_| default.to_string())
+        .parse()
+        .unwrap_or_else(|_| panic!("{} must be a number", key))
+}
+
+pub fn minimax(game_data: &String, variant: Variant) -> HttpResponse {
+    let mut game_state = match chess::state::game_state::parse_with_variant(game_data, variant) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let minimax_depth: i8 = env_number(variant, "MINIMAX_DEPTH", "2");
+
+    let recommended_move = chess::minimax::recommended_move(&mut game_state, minimax_depth);
+
+    match recommended_move {
+        Some(m) => {
+            let external_move = build_external_move(&game_state, m);
+            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
+        },
+        None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    }
+}
+
+pub fn mcts(game_data: &String, variant: Variant) -> HttpResponse {
+    let mut game_state = match chess::state::game_state::parse_with_variant(game_data, variant) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n"),
+    };
+
+    let mcts_simulation_count: i16 = env_number(variant, "MCTS_SIMULATION_COUNT", "120");
+    let mcts_simulation_depth: i16 = env_number(variant, "MCTS_SIMULATION_DEPTH", "60");
+
+    let recommended_move = chess::mcts::recommended_move(&mut game_state, mcts_simulation_count, mcts_simulation_depth);
+
+    match recommended_move {
+        Ok(m) => {
+            let external_move = build_external_move(&game_state, m);
+            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
+        },
+        Err(e) => {
+            println!("{}", e);
+            HttpResponse::NotFound().body("404 Not Found\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::MessageBody;
+
+    #[test]
+    fn crazyhouse_minimax_valid_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1");
+        let result = minimax(&game_state, Variant::Crazyhouse);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "Ngh3\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn crazyhouse_minimax_drop_test() {
+        let game_state = String::from("1r2k3/8/8/8/8/p7/P7/K7[N] w - - 0 1");
+        let result = minimax(&game_state, Variant::Crazyhouse);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "N@h1\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn crazyhouse_minimax_invalid_game_state_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[X] w KQkq - 0 1");
+        let result = minimax(&game_state, Variant::Crazyhouse);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn crazyhouse_mcts_invalid_game_state_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[X] w KQkq - 0 1");
+        let result = mcts(&game_state, Variant::Crazyhouse);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn king_of_the_hill_minimax_valid_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let result = minimax(&game_state, Variant::KingOfTheHill);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "e3\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn king_of_the_hill_minimax_hill_test() {
+        let game_state = String::from("4k3/8/8/8/8/4K3/8/8 w - - 0 1");
+        let result = minimax(&game_state, Variant::KingOfTheHill);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "Ke4\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn king_of_the_hill_minimax_invalid_game_state_test() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let result = minimax(&game_state, Variant::KingOfTheHill);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn king_of_the_hill_mcts_invalid_game_state_test() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let result = mcts(&game_state, Variant::KingOfTheHill);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn three_check_minimax_valid_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1");
+        let result = minimax(&game_state, Variant::ThreeCheck);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "e3\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn three_check_minimax_check_test() {
+        let game_state = String::from("4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1");
+        let result = minimax(&game_state, Variant::ThreeCheck);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "Ra8+\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn three_check_minimax_invalid_game_state_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+4 0 1");
+        let result = minimax(&game_state, Variant::ThreeCheck);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn three_check_mcts_invalid_game_state_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+4 0 1");
+        let result = mcts(&game_state, Variant::ThreeCheck);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn atomic_minimax_valid_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let result = minimax(&game_state, Variant::Atomic);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "a4\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn atomic_minimax_explosion_test() {
+        let game_state = String::from("rnbqkbnr/pppp1ppp/8/4p1N1/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 2");
+        let result = minimax(&game_state, Variant::Atomic);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "Nxf7\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn atomic_minimax_invalid_game_state_test() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let result = minimax(&game_state, Variant::Atomic);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn atomic_mcts_invalid_game_state_test() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let result = mcts(&game_state, Variant::Atomic);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn horde_minimax_valid_test() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1");
+        let result = minimax(&game_state, Variant::Horde);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "g6\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn horde_minimax_first_rank_test() {
+        let game_state = String::from("4k3/8/8/8/8/8/8/P7 w - - 0 1");
+        let result = minimax(&game_state, Variant::Horde);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "a3\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn horde_minimax_invalid_game_state_test() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1");
+        let result = minimax(&game_state, Variant::Horde);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn horde_mcts_invalid_game_state_test() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1");
+        let result = mcts(&game_state, Variant::Horde);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+}
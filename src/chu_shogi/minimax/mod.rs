@@ -0,0 +1,174 @@
+use std::cmp;
+use crate::chu_shogi::state::square::PieceKind;
+use crate::chu_shogi;
+
+const GAME_END_VALUE: i32 = 10000;
+
+pub fn recommended_move(game_state: &mut chu_shogi::state::game_state::GameState, depth: i8) -> Option<chu_shogi::state::mov::Move> {
+    let mut new_game_state = game_state.clone();
+    let moves = game_state.possible_moves();
+    match moves.len() {
+        0 => None,
+        1 => moves.first().cloned(),
+        _ => {
+            let maximizing_player = new_game_state.current_player_number == 1;
+
+            let moves_with_value = moves.iter().map(|mov| {
+                match new_game_state.perform_move(mov) {
+                    Ok(_) => (),
+                    Err(_) => return (mov, 0),
+                };
+
+                // the opponent moves next
+                let value = evaluate(&mut new_game_state, depth, i32::MIN, i32::MAX, !maximizing_player).unwrap_or(0);
+
+                match new_game_state.undo_move(mov) {
+                    Ok(_) => (),
+                    Err(_) => return (mov, 0)
+                }
+
+                (mov, value)
+            });
+
+            let best_move = match maximizing_player {
+                true => moves_with_value.max_by(|a,b| (a.1).cmp(&b.1) ),
+                false => moves_with_value.min_by(|a,b| (a.1).cmp(&b.1) ),
+            };
+
+            best_move.map(|h| (h.0).clone())
+        }
+    }
+}
+
+pub fn evaluate(game_state: &mut chu_shogi::state::game_state::GameState, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
+    match game_state.winner() {
+        Some(1) => return Ok(GAME_END_VALUE),
+        Some(_) => return Ok(-GAME_END_VALUE),
+        None => ()
+    }
+
+    if depth == 0 {
+        return Ok(static_evaluation(game_state));
+    }
+
+    let moves = game_state.possible_moves();
+
+    if moves.is_empty() {
+        return Ok(static_evaluation(game_state));
+    }
+
+    if maximizing_player {
+        let mut max_eval = i32::MIN;
+        for mov in moves {
+            game_state.perform_move(&mov)?;
+            let eval = evaluate(game_state, depth - 1, alpha, beta, false)?;
+            game_state.undo_move(&mov)?;
+
+            max_eval = cmp::max(max_eval, eval);
+            alpha = cmp::max(alpha, eval);
+            if beta <= alpha {
+                break;
+            }
+        }
+        Ok(max_eval)
+    } else {
+        let mut min_eval = i32::MAX;
+        for mov in moves {
+            game_state.perform_move(&mov)?;
+            let eval = evaluate(game_state, depth - 1, alpha, beta, true)?;
+            game_state.undo_move(&mov)?;
+
+            min_eval = cmp::min(min_eval, eval);
+            beta = cmp::min(beta, eval);
+            if beta <= alpha {
+                break;
+            }
+        }
+        Ok(min_eval)
+    }
+}
+
+// positive -> sente
+// negative -> gote
+pub fn static_evaluation(game_state: &chu_shogi::state::game_state::GameState) -> i32 {
+    player_pieces_value(game_state, 1) - player_pieces_value(game_state, 2)
+}
+
+// no drops in chu shogi, so only the pieces on the board count
+fn player_pieces_value(game_state: &chu_shogi::state::game_state::GameState, player_number: i8) -> i32 {
+    game_state.squares.iter().flatten().filter(|s| s.player_number == player_number).map(|s| piece_value(s.kind)).sum()
+}
+
+fn piece_value(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::King => 0,
+        PieceKind::Prince => 30,
+        PieceKind::Pawn | PieceKind::GoBetween => 10,
+        PieceKind::Copper | PieceKind::Silver => 20,
+        PieceKind::Gold | PieceKind::Leopard | PieceKind::DrunkElephant | PieceKind::BlindTiger => 30,
+        PieceKind::Lance | PieceKind::ReverseChariot | PieceKind::Kirin | PieceKind::Phoenix => 30,
+        PieceKind::SideMover | PieceKind::VerticalMover => 40,
+        PieceKind::Bishop | PieceKind::Whale => 50,
+        PieceKind::Rook | PieceKind::WhiteHorse | PieceKind::FlyingStag => 60,
+        PieceKind::DragonHorse | PieceKind::FreeBoar | PieceKind::FlyingOx => 70,
+        PieceKind::DragonKing => 80,
+        PieceKind::HornedFalcon | PieceKind::SoaringEagle => 100,
+        PieceKind::Queen => 120,
+        PieceKind::Lion => 150,
+        PieceKind::Empty => 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_evaluation_starting_position_test() {
+        let encoded = String::from("lfcsgekgscfl/a1b1txot1b1a/mvrhdqndhrvm/pppppppppppp/3i4i3/12/12/3I4I3/PPPPPPPPPPPP/MVRHDNQDHRVM/A1B1TOXT1B1A/LFCSGKEGSCFL b -");
+        let game_state = chu_shogi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(static_evaluation(&game_state), 0);
+    }
+
+    #[test]
+    fn evaluate_king_captured_test() {
+        let encoded = String::from("12/12/12/12/12/12/12/12/12/12/12/5K6 w -");
+        let mut game_state = chu_shogi::state::game_state::parse(&encoded).unwrap();
+
+        let result = evaluate(&mut game_state, 2, i32::MIN, i32::MAX, false);
+
+        assert_eq!(result, Ok(10000));
+    }
+
+    #[test]
+    fn recommended_move_captures_king_test() {
+        let encoded = String::from("5k6/12/12/12/12/5R6/12/12/12/12/12/K11 b -");
+        let mut game_state = chu_shogi::state::game_state::parse(&encoded).unwrap();
+
+        let mov = recommended_move(&mut game_state, 0).unwrap();
+
+        assert_eq!(mov.to, (5, 0));
+    }
+
+    #[test]
+    fn recommended_move_lion_double_capture_test() {
+        let encoded = String::from("k11/12/12/5g6/5p6/5N6/12/12/12/12/12/K11 b -");
+        let mut game_state = chu_shogi::state::game_state::parse(&encoded).unwrap();
+
+        let mov = recommended_move(&mut game_state, 0).unwrap();
+
+        assert_eq!(mov.via, Some((5, 4)));
+        assert_eq!(mov.to, (5, 3));
+    }
+
+    #[test]
+    fn recommended_move_gote_test() {
+        let encoded = String::from("k11/12/12/12/12/12/12/12/12/12/12/Kq10 w -");
+        let mut game_state = chu_shogi::state::game_state::parse(&encoded).unwrap();
+
+        let mov = recommended_move(&mut game_state, 1).unwrap();
+
+        assert_eq!(mov.to, (0, 11));
+    }
+}
@@ -0,0 +1,194 @@
+use crate::chu_shogi::state::game_state::GameState;
+use crate::chu_shogi::state::mov::Move;
+use crate::chu_shogi::state::square::PieceKind;
+use crate::chu_shogi::state::square::demotes_to;
+use crate::chu_shogi::state::square_set::find_by_x_and_y;
+
+pub struct ExternalMove {
+    pub from: (i8, i8),
+    pub to: (i8, i8),
+    pub via: Option<(i8, i8)>,
+    pub moving_piece_kind: PieceKind,
+    pub moving_piece_promoted: bool,
+    pub capture: bool,
+    pub promote: bool,
+    pub promotion_possible: bool
+}
+
+impl ExternalMove {
+    // Reference:
+    //   piece, square of departure, then - or x for capture, and the destination e.g. P8i-8h, Ln6jx6h
+    //   squares are the file number, 12 to 1, and the rank letter, a to l
+    //   promoted pieces are written with + and their unpromoted abbreviation e.g. +P
+    //   lion double moves add the square captured on the first step e.g. Ln6jx6ix6h
+    //   igui returns to the square of departure e.g. Ln6jx6i-6j
+    //   promotion declined =
+    //   promotion accepted +
+    pub fn format(&self) -> String {
+        let via = match self.via {
+            Some(v) => format!("x{}", format_point(v)),
+            None => String::from("")
+        };
+        let separator = if self.capture { "x" } else { "-" };
+
+        format!("{}{}{}{}{}{}", self.piece_format(), format_point(self.from), via, separator, format_point(self.to), self.promotion_suffix())
+    }
+
+    fn piece_format(&self) -> String {
+        if self.moving_piece_promoted {
+            let kind = demotes_to(self.moving_piece_kind).unwrap_or(self.moving_piece_kind);
+            format!("+{}", piece_abbreviation(kind))
+        } else {
+            piece_abbreviation(self.moving_piece_kind).to_string()
+        }
+    }
+
+    fn promotion_suffix(&self) -> String {
+        if self.promote {
+            String::from("+")
+        } else if self.promotion_possible {
+            String::from("=")
+        } else {
+            String::from("")
+        }
+    }
+}
+
+pub fn piece_abbreviation(kind: PieceKind) -> &'static str {
+    match kind {
+        PieceKind::King => "K",
+        PieceKind::Prince => "CP",
+        PieceKind::DrunkElephant => "DE",
+        PieceKind::Gold => "G",
+        PieceKind::Silver => "S",
+        PieceKind::Copper => "C",
+        PieceKind::Leopard => "FL",
+        PieceKind::Lance => "L",
+        PieceKind::ReverseChariot => "RC",
+        PieceKind::BlindTiger => "BT",
+        PieceKind::Phoenix => "Ph",
+        PieceKind::Kirin => "Kr",
+        PieceKind::Lion => "Ln",
+        PieceKind::Queen => "FK",
+        PieceKind::Rook => "R",
+        PieceKind::Bishop => "B",
+        PieceKind::DragonKing => "DK",
+        PieceKind::DragonHorse => "DH",
+        PieceKind::SideMover => "SM",
+        PieceKind::VerticalMover => "VM",
+        PieceKind::GoBetween => "GB",
+        PieceKind::Pawn => "P",
+        PieceKind::WhiteHorse => "WH",
+        PieceKind::Whale => "W",
+        PieceKind::FlyingStag => "FS",
+        PieceKind::FreeBoar => "FB",
+        PieceKind::FlyingOx => "FO",
+        PieceKind::HornedFalcon => "HF",
+        PieceKind::SoaringEagle => "SE",
+        PieceKind::Empty => ""
+    }
+}
+
+// file 12 is on sente's left
+fn format_point(point: (i8, i8)) -> String {
+    let rank = (b'a' + point.1 as u8) as char;
+    format!("{}{}", 12 - point.0, rank)
+}
+
+pub fn build(game_state: &GameState, mov: Move) -> ExternalMove {
+    let moving_piece_promoted = find_by_x_and_y(&game_state.squares, mov.from).is_some_and(|s| s.promoted);
+    let promotion_possible = match find_by_x_and_y(&game_state.squares, mov.from) {
+        Some(s) => mov.via.is_none() && game_state.can_promote(s, mov.from, mov.to, mov.capture_piece.is_some()),
+        None => false
+    };
+
+    ExternalMove {
+        from: mov.from,
+        to: mov.to,
+        via: mov.via,
+        moving_piece_kind: mov.moving_piece_kind,
+        moving_piece_promoted,
+        capture: mov.capture_piece.is_some(),
+        promote: mov.promote,
+        promotion_possible
+    }
+}
+
+// the possible move with this notation
+pub fn parse(encoded: &str, game_state: &mut GameState) -> Result<Move, &'static str> {
+    let moves = game_state.possible_moves();
+    match moves.into_iter().find(|m| build(game_state, m.clone()).format() == encoded) {
+        Some(m) => Ok(m),
+        None => Err("external_mov::parse - Invalid move")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chu_shogi::state::game_state::parse as parse_game_state;
+
+    const STARTING_POSITION: &str = "lfcsgekgscfl/a1b1txot1b1a/mvrhdqndhrvm/pppppppppppp/3i4i3/12/12/3I4I3/PPPPPPPPPPPP/MVRHDNQDHRVM/A1B1TOXT1B1A/LFCSGKEGSCFL b -";
+
+    #[test]
+    fn format_move_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.from == (4, 8) && m.to == (4, 7)).unwrap();
+        let result = build(&game_state, mov).format();
+        assert_eq!(result, "P8i-8h");
+    }
+
+    #[test]
+    fn format_lion_jump_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.from == (5, 9) && m.to == (5, 7)).unwrap();
+        let result = build(&game_state, mov).format();
+        assert_eq!(result, "Ln7j-7h");
+    }
+
+    #[test]
+    fn format_igui_test() {
+        let mut game_state = parse_game_state(&String::from("5k6/12/12/12/5p6/5N6/12/12/12/12/12/5K6 b -")).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.via == Some((5, 4)) && m.to == (5, 5)).unwrap();
+        let result = build(&game_state, mov).format();
+        assert_eq!(result, "Ln7fx7e-7f");
+    }
+
+    #[test]
+    fn format_double_capture_test() {
+        let mut game_state = parse_game_state(&String::from("5k6/12/12/5g6/5p6/5N6/12/12/12/12/12/5K6 b -")).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.via == Some((5, 4)) && m.to == (5, 3)).unwrap();
+        let result = build(&game_state, mov).format();
+        assert_eq!(result, "Ln7fx7ex7d");
+    }
+
+    #[test]
+    fn format_promotion_test() {
+        let mut game_state = parse_game_state(&String::from("5k6/12/12/12/2O9/12/12/12/12/12/12/5K6 b -")).unwrap();
+        let moves: Vec<String> = game_state.possible_moves().into_iter().filter(|m| m.to == (2, 2)).map(|m| build(&game_state, m).format()).collect();
+        assert_eq!(moves, vec![String::from("Kr10e-10c+"), String::from("Kr10e-10c=")]);
+    }
+
+    #[test]
+    fn format_promoted_piece_test() {
+        let mut game_state = parse_game_state(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/4+OK6 b -")).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.from == (4, 11) && m.to == (4, 10)).unwrap();
+        let result = build(&game_state, mov).format();
+        assert_eq!(result, "+Kr8l-8k");
+    }
+
+    #[test]
+    fn parse_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = parse("P8i-8h", &mut game_state).unwrap();
+        assert_eq!(result.from, (4, 8));
+        assert_eq!(result.to, (4, 7));
+    }
+
+    #[test]
+    fn parse_invalid_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = parse("P8i-8g", &mut game_state);
+        assert!(result.is_err());
+    }
+}
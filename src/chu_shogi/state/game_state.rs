@@ -0,0 +1,558 @@
+use crate::chu_shogi::state::piece_factory::parse as parse_piece;
+use crate::chu_shogi::state::piece_factory::format as format_piece;
+use crate::chu_shogi::state::point::adjacent;
+use crate::chu_shogi::state::square::PieceKind;
+use crate::chu_shogi::state::square::Square;
+use crate::chu_shogi::state::square::EMPTY_SQUARE;
+use crate::chu_shogi::state::square::destinations;
+use crate::chu_shogi::state::square::double_moves;
+use crate::chu_shogi::state::square::promotes_to;
+use crate::chu_shogi::state::square::in_promotion_zone;
+use crate::chu_shogi::state::square::opposing_player;
+use crate::chu_shogi::state::square_set::find_by_x_and_y;
+use crate::chu_shogi::state::square_set::find_by_x_and_y_mut;
+use crate::chu_shogi::state::square_set::royal_count;
+use crate::chu_shogi::state::square_set::any_threats_to_point;
+use crate::chu_shogi::state::mov::Move;
+use crate::chu_shogi::state::external_mov::parse as parse_external_move;
+
+const BOARD_SIZE: usize = 12;
+
+#[derive(Clone)]
+pub struct GameState {
+    pub current_player_number: i8,
+    pub squares: Vec<Vec<Square>>,
+    pub lion_trades: Vec<bool> // whether each move played captured a lion with a piece other than a lion
+}
+
+impl GameState {
+    // there is no check or checkmate, a player loses when the king and the prince are both captured
+    pub fn winner(&self) -> Option<i8> {
+        if royal_count(&self.squares, 1) == 0 {
+            Some(2)
+        } else if royal_count(&self.squares, 2) == 0 {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    pub fn game_over(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    pub fn possible_moves(&mut self) -> Vec<Move> {
+        self.possible_moves_for_player(self.current_player_number)
+    }
+
+    pub fn possible_moves_for_player(&mut self, subject_player_number: i8) -> Vec<Move> {
+        if self.game_over() {
+            return vec![];
+        }
+
+        let mut moves = vec![];
+
+        for (y, row) in self.squares.iter().enumerate() {
+            for (x, from) in row.iter().enumerate() {
+                if from.player_number == subject_player_number {
+                    let from_point = (x as i8, y as i8);
+
+                    for to_point in destinations(from.kind, subject_player_number, from_point, &self.squares) {
+                        let capture_piece = find_by_x_and_y(&self.squares, to_point).filter(|s| s.occupied()).copied();
+                        self.push_moves(&mut moves, from, from_point, to_point, capture_piece);
+                    }
+
+                    // only the lion, horned falcon and soaring eagle make double moves, none of them promote
+                    for (via_point, to_point) in double_moves(from.kind, subject_player_number, from_point, &self.squares) {
+                        let capture_piece = if to_point == from_point {
+                            None
+                        } else {
+                            find_by_x_and_y(&self.squares, to_point).filter(|s| s.occupied()).copied()
+                        };
+                        moves.push(Move {
+                            from: from_point,
+                            to: to_point,
+                            moving_piece_kind: from.kind,
+                            capture_piece,
+                            via: Some(via_point),
+                            via_capture_piece: find_by_x_and_y(&self.squares, via_point).copied(),
+                            promote: false
+                        });
+                    }
+                }
+            }
+        }
+
+        moves.retain(|m| self.lion_trade_allowed(m));
+
+        moves
+    }
+
+    // a move that may promote is added with and without promotion
+    fn push_moves(&self, moves: &mut Vec<Move>, from: &Square, from_point: (i8, i8), to_point: (i8, i8), capture_piece: Option<Square>) {
+        if self.can_promote(from, from_point, to_point, capture_piece.is_some()) {
+            moves.push(Move {
+                from: from_point,
+                to: to_point,
+                moving_piece_kind: from.kind,
+                capture_piece,
+                via: None,
+                via_capture_piece: None,
+                promote: true
+            });
+        }
+
+        moves.push(Move {
+            from: from_point,
+            to: to_point,
+            moving_piece_kind: from.kind,
+            capture_piece,
+            via: None,
+            via_capture_piece: None,
+            promote: false
+        });
+    }
+
+    // entering the promotion zone, capturing inside it,
+    // or a pawn or lance reaching the last rank
+    pub fn can_promote(&self, square: &Square, from: (i8, i8), to: (i8, i8), captures: bool) -> bool {
+        if square.promoted || promotes_to(square.kind).is_none() {
+            return false;
+        }
+
+        let player_number = square.player_number;
+        let from_in_zone = in_promotion_zone(player_number, from.1);
+        let to_in_zone = in_promotion_zone(player_number, to.1);
+        let last_rank = if player_number == 1 { to.1 == 0 } else { to.1 == BOARD_SIZE as i8 - 1 };
+        let pawn_or_lance = square.kind == PieceKind::Pawn || square.kind == PieceKind::Lance;
+
+        (!from_in_zone && to_in_zone) || (from_in_zone && captures) || (pawn_or_lance && last_rank)
+    }
+
+    // A lion may not capture a protected lion that is not adjacent, unless it also captures
+    // a piece other than a pawn or go-between on the way.
+    // After a lion is captured by another piece, the opponent may not capture a lion with another piece on the next move.
+    fn lion_trade_allowed(&mut self, mov: &Move) -> bool {
+        let captures_lion = mov.captured_pieces().iter().any(|s| s.kind == PieceKind::Lion);
+        if !captures_lion {
+            return true;
+        }
+
+        if mov.moving_piece_kind != PieceKind::Lion {
+            return !self.lion_trades.last().copied().unwrap_or(false);
+        }
+
+        let distant_lion = mov.capture_piece.is_some_and(|s| s.kind == PieceKind::Lion) && !adjacent(mov.from, mov.to);
+        if !distant_lion {
+            return true;
+        }
+
+        let bridge = mov.via_capture_piece.is_some_and(|s| s.kind != PieceKind::Pawn && s.kind != PieceKind::GoBetween);
+        if bridge {
+            return true;
+        }
+
+        let player_number = self.current_player_number;
+        if self.perform_move(mov).is_err() {
+            return false;
+        }
+        let protected = any_threats_to_point(&self.squares, mov.to, opposing_player(player_number));
+        let undo_result = self.undo_move(mov);
+
+        !protected && undo_result.is_ok()
+    }
+
+    pub fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        let mut piece = match find_by_x_and_y_mut(&mut self.squares, mov.from) {
+            Some(s) if s.occupied() => {
+                let piece = *s;
+                *s = EMPTY_SQUARE;
+                piece
+            },
+            Some(_) => return Err("game_state::perform_move - No piece on from"),
+            None => return Err("game_state::perform_move - Invalid from square")
+        };
+
+        if let Some(via) = mov.via {
+            match find_by_x_and_y_mut(&mut self.squares, via) {
+                Some(s) => *s = EMPTY_SQUARE,
+                None => return Err("game_state::perform_move - Invalid via square")
+            }
+        }
+
+        if mov.promote {
+            match promotes_to(piece.kind) {
+                Some(kind) => {
+                    piece.kind = kind;
+                    piece.promoted = true;
+                },
+                None => return Err("game_state::perform_move - Invalid promote")
+            }
+        }
+
+        match find_by_x_and_y_mut(&mut self.squares, mov.to) {
+            Some(s) => *s = piece,
+            None => return Err("game_state::perform_move - Invalid to square")
+        }
+
+        let lion_trade = mov.moving_piece_kind != PieceKind::Lion && mov.captured_pieces().iter().any(|s| s.kind == PieceKind::Lion);
+        self.lion_trades.push(lion_trade);
+        self.current_player_number = opposing_player(self.current_player_number);
+
+        Ok(())
+    }
+
+    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        let mut piece = match find_by_x_and_y_mut(&mut self.squares, mov.to) {
+            Some(s) => {
+                let piece = *s;
+                *s = mov.capture_piece.unwrap_or(EMPTY_SQUARE);
+                piece
+            },
+            None => return Err("game_state::undo_move - Invalid to square")
+        };
+
+        if let Some(via) = mov.via {
+            match find_by_x_and_y_mut(&mut self.squares, via) {
+                Some(s) => *s = mov.via_capture_piece.unwrap_or(EMPTY_SQUARE),
+                None => return Err("game_state::undo_move - Invalid via square")
+            }
+        }
+
+        if mov.promote {
+            piece.kind = mov.moving_piece_kind;
+            piece.promoted = false;
+        }
+
+        match find_by_x_and_y_mut(&mut self.squares, mov.from) {
+            Some(s) => *s = piece,
+            None => return Err("game_state::undo_move - Invalid from square")
+        }
+
+        self.lion_trades.pop();
+        self.current_player_number = opposing_player(self.current_player_number);
+
+        Ok(())
+    }
+
+    // SFEN without the move count, there are no pieces in hand
+    pub fn format(&self) -> String {
+        let rows: Vec<String> = self.squares.iter().map(|row| {
+            let mut encoded = String::new();
+            let mut empty_count = 0;
+            for square in row.iter() {
+                if square.occupied() {
+                    if empty_count > 0 {
+                        encoded.push_str(&empty_count.to_string());
+                        empty_count = 0;
+                    }
+                    encoded.push_str(&format_piece(square));
+                } else {
+                    empty_count += 1;
+                }
+            }
+            if empty_count > 0 {
+                encoded.push_str(&empty_count.to_string());
+            }
+            encoded
+        }).collect();
+
+        let player = if self.current_player_number == 2 { "w" } else { "b" };
+
+        format!("{} {} -", rows.join("/"), player)
+    }
+}
+
+// SFEN on a 12x12 board, optionally followed by the moves played since in the external notation
+// lfcsgekgscfl/a1b1txot1b1a/mvrhdqndhrvm/pppppppppppp/3i4i3/12/12/3I4I3/PPPPPPPPPPPP/MVRHDNQDHRVM/A1B1TOXT1B1A/LFCSGKEGSCFL b - 1
+pub fn parse(encoded: &str) -> Result<GameState, &'static str> {
+    let mut parts = encoded.splitn(2, " moves ");
+
+    let mut game_state = match parts.next() {
+        Some(position) => parse_position(position)?,
+        None => return Err("Error parsing state")
+    };
+
+    if let Some(moves) = parts.next() {
+        for encoded_move in moves.split_whitespace() {
+            let mov = parse_external_move(encoded_move, &mut game_state)?;
+            game_state.perform_move(&mov)?;
+        }
+    }
+
+    Ok(game_state)
+}
+
+fn parse_position(encoded: &str) -> Result<GameState, &'static str> {
+    let mut fields = encoded.split_whitespace();
+
+    let board = fields.next().ok_or("Error parsing state")?;
+    let current_player_number = match fields.next() {
+        Some("b") => 1,
+        Some("w") => 2,
+        _ => return Err("Error parsing state")
+    };
+
+    // no drops in chu shogi, the hand is always empty
+    match fields.next() {
+        Some("-") | None => (),
+        _ => return Err("Error parsing state")
+    }
+
+    let rows: Vec<&str> = board.split('/').collect();
+    if rows.len() != BOARD_SIZE {
+        return Err("Error parsing state");
+    }
+
+    let mut squares = vec![];
+    for row in rows {
+        squares.push(parse_row(row)?);
+    }
+
+    Ok(GameState {
+        current_player_number,
+        squares,
+        lion_trades: vec![]
+    })
+}
+
+fn parse_row(encoded: &str) -> Result<Vec<Square>, &'static str> {
+    let mut row = vec![];
+    let mut empty_count: usize = 0;
+    let mut promoted_piece = false;
+
+    for c in encoded.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            empty_count = empty_count * 10 + digit as usize;
+            continue;
+        }
+
+        row.extend(vec![EMPTY_SQUARE; empty_count]);
+        empty_count = 0;
+
+        if c == '+' {
+            promoted_piece = true;
+        } else {
+            row.push(parse_piece(c, promoted_piece).map_err(|_| "Error parsing state")?);
+            promoted_piece = false;
+        }
+    }
+    row.extend(vec![EMPTY_SQUARE; empty_count]);
+
+    if row.len() == BOARD_SIZE && !promoted_piece {
+        Ok(row)
+    } else {
+        Err("Error parsing state")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STARTING_POSITION: &str = "lfcsgekgscfl/a1b1txot1b1a/mvrhdqndhrvm/pppppppppppp/3i4i3/12/12/3I4I3/PPPPPPPPPPPP/MVRHDNQDHRVM/A1B1TOXT1B1A/LFCSGKEGSCFL b -";
+
+    #[test]
+    fn parse_starting_position_test() {
+        let game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        assert_eq!(game_state.current_player_number, 1);
+        assert_eq!(game_state.squares.len(), 12);
+        assert_eq!(game_state.squares[11][5], Square { player_number: 1, kind: PieceKind::King, promoted: false });
+        assert_eq!(game_state.squares[2][6], Square { player_number: 2, kind: PieceKind::Lion, promoted: false });
+        assert_eq!(game_state.squares.iter().flatten().filter(|s| s.occupied()).count(), 92);
+    }
+
+    #[test]
+    fn parse_move_count_test() {
+        let encoded = format!("{} 1", STARTING_POSITION);
+        let result = parse(&encoded);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn parse_promoted_test() {
+        let game_state = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/4+OK6 w -")).unwrap();
+        assert_eq!(game_state.current_player_number, 2);
+        assert_eq!(game_state.squares[11][4], Square { player_number: 1, kind: PieceKind::Lion, promoted: true });
+    }
+
+    #[test]
+    fn parse_wrong_row_length_test() {
+        let result = parse(&String::from("5k5/12/12/12/12/12/12/12/12/12/12/5K6 b -"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_hand_test() {
+        let result = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/5K6 b P"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_with_moves_test() {
+        let encoded = format!("{} 1 moves P8i-8h P8d-8e", STARTING_POSITION);
+        let game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.current_player_number, 1);
+        assert_eq!(game_state.squares[7][4].kind, PieceKind::Pawn);
+        assert_eq!(game_state.squares[4][4].kind, PieceKind::Pawn);
+    }
+
+    #[test]
+    fn format_test() {
+        let game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        assert_eq!(game_state.format(), STARTING_POSITION);
+    }
+
+    #[test]
+    fn format_promoted_test() {
+        let encoded = String::from("5k6/12/12/12/12/12/12/12/12/12/12/4+OK6 w -");
+        let game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.format(), encoded);
+    }
+
+    #[test]
+    fn possible_moves_starting_position_test() {
+        let mut game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        let moves = game_state.possible_moves();
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|m| m.capture_piece.is_none() && !m.promote));
+    }
+
+    #[test]
+    fn possible_moves_no_drops_test() {
+        let mut game_state = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/5K6 b -")).unwrap();
+        let moves = game_state.possible_moves();
+        assert_eq!(moves.len(), 5);
+    }
+
+    #[test]
+    fn possible_moves_igui_test() {
+        let mut game_state = parse(&String::from("5k6/12/12/12/5p6/5N6/12/12/12/12/12/5K6 b -")).unwrap();
+        let moves = game_state.possible_moves();
+        let igui = moves.iter().find(|m| m.via == Some((5, 4)) && m.to == (5, 5));
+        assert!(igui.is_some());
+    }
+
+    #[test]
+    fn perform_and_undo_igui_test() {
+        let encoded = String::from("5k6/12/12/12/5p6/5N6/12/12/12/12/12/5K6 b -");
+        let mut game_state = parse(&encoded).unwrap();
+        let moves = game_state.possible_moves();
+        let igui = moves.iter().find(|m| m.via == Some((5, 4)) && m.to == (5, 5)).unwrap();
+
+        game_state.perform_move(igui).unwrap();
+        assert_eq!(game_state.format(), "5k6/12/12/12/12/5N6/12/12/12/12/12/5K6 w -");
+
+        game_state.undo_move(igui).unwrap();
+        assert_eq!(game_state.format(), encoded);
+    }
+
+    #[test]
+    fn perform_and_undo_double_capture_test() {
+        let encoded = String::from("5k6/12/12/5g6/5p6/5N6/12/12/12/12/12/5K6 b -");
+        let mut game_state = parse(&encoded).unwrap();
+        let moves = game_state.possible_moves();
+        let double_capture = moves.iter().find(|m| m.via == Some((5, 4)) && m.to == (5, 3)).unwrap();
+        assert_eq!(double_capture.captured_pieces().len(), 2);
+
+        game_state.perform_move(double_capture).unwrap();
+        assert_eq!(game_state.format(), "5k6/12/12/5N6/12/12/12/12/12/12/12/5K6 w -");
+
+        game_state.undo_move(double_capture).unwrap();
+        assert_eq!(game_state.format(), encoded);
+    }
+
+    #[test]
+    fn perform_and_undo_promotion_test() {
+        let encoded = String::from("5k6/12/12/12/2O9/12/12/12/12/12/12/5K6 b -");
+        let mut game_state = parse(&encoded).unwrap();
+        let moves = game_state.possible_moves();
+        let promotion = moves.iter().find(|m| m.to == (2, 2) && m.promote).unwrap();
+
+        game_state.perform_move(promotion).unwrap();
+        assert_eq!(game_state.squares[2][2], Square { player_number: 1, kind: PieceKind::Lion, promoted: true });
+
+        game_state.undo_move(promotion).unwrap();
+        assert_eq!(game_state.format(), encoded);
+    }
+
+    #[test]
+    fn promotion_by_capture_in_zone_test() {
+        let game_state = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/5K6 b -")).unwrap();
+        let silver = Square { player_number: 1, kind: PieceKind::Silver, promoted: false };
+        assert!(game_state.can_promote(&silver, (3, 4), (3, 3), false));
+        assert!(!game_state.can_promote(&silver, (3, 3), (3, 2), false));
+        assert!(game_state.can_promote(&silver, (3, 3), (3, 2), true));
+        assert!(!game_state.can_promote(&silver, (3, 5), (3, 4), true));
+    }
+
+    #[test]
+    fn promoted_piece_cannot_promote_test() {
+        let game_state = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/5K6 b -")).unwrap();
+        let tokin = Square { player_number: 1, kind: PieceKind::Gold, promoted: true };
+        assert!(!game_state.can_promote(&tokin, (3, 4), (3, 3), false));
+    }
+
+    #[test]
+    fn lion_cannot_capture_protected_distant_lion_test() {
+        let mut game_state = parse(&String::from("5k6/12/5g6/5n6/12/5N6/12/12/12/12/12/5K6 b -")).unwrap();
+        let moves = game_state.possible_moves();
+        assert!(!moves.iter().any(|m| m.capture_piece.is_some_and(|s| s.kind == PieceKind::Lion)));
+    }
+
+    #[test]
+    fn lion_captures_unprotected_distant_lion_test() {
+        let mut game_state = parse(&String::from("5k6/12/12/5n6/12/5N6/12/12/12/12/12/5K6 b -")).unwrap();
+        let moves = game_state.possible_moves();
+        assert!(moves.iter().any(|m| m.to == (5, 3) && m.via.is_none()));
+    }
+
+    #[test]
+    fn lion_captures_adjacent_protected_lion_test() {
+        let mut game_state = parse(&String::from("5k6/12/12/5g6/5n6/5N6/12/12/12/12/12/5K6 b -")).unwrap();
+        let moves = game_state.possible_moves();
+        assert!(moves.iter().any(|m| m.to == (5, 4) && m.via.is_none()));
+    }
+
+    #[test]
+    fn lion_captures_distant_lion_with_bridge_test() {
+        let mut game_state = parse(&String::from("5k6/12/5g6/5n6/5s6/5N6/12/12/12/12/12/5K6 b -")).unwrap();
+        let moves = game_state.possible_moves();
+        assert!(moves.iter().any(|m| m.via == Some((5, 4)) && m.to == (5, 3)));
+        assert!(!moves.iter().any(|m| m.via.is_none() && m.to == (5, 3)));
+    }
+
+    #[test]
+    fn lion_counter_strike_test() {
+        // without the trade the gote hisha may take the lion
+        let mut game_state = parse(&String::from("5k5r/12/12/12/12/10R1/12/12/12/12/12/5K5N w -")).unwrap();
+        let moves = game_state.possible_moves();
+        assert!(moves.iter().any(|m| m.moving_piece_kind == PieceKind::Rook && m.to == (11, 11)));
+
+        // the sente rook takes a lion, the gote rook may not take the other lion in reply
+        let mut game_state = parse(&String::from("5k5r/12/12/12/12/10n1/12/12/12/12/12/5K4RN b -")).unwrap();
+        let moves = game_state.possible_moves();
+        let lion_capture = moves.iter().find(|m| m.moving_piece_kind == PieceKind::Rook && m.to == (10, 5)).unwrap().clone();
+        game_state.perform_move(&lion_capture).unwrap();
+        assert_eq!(game_state.lion_trades, vec![true]);
+
+        let moves = game_state.possible_moves();
+        assert!(!moves.iter().any(|m| m.moving_piece_kind == PieceKind::Rook && m.to == (11, 11)));
+    }
+
+    #[test]
+    fn winner_test() {
+        let game_state = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/5K6 b -")).unwrap();
+        assert_eq!(game_state.winner(), None);
+
+        let game_state = parse(&String::from("12/12/12/12/12/12/12/12/12/12/12/5K6 w -")).unwrap();
+        assert_eq!(game_state.winner(), Some(1));
+    }
+
+    #[test]
+    fn prince_is_royal_test() {
+        let game_state = parse(&String::from("5+e6/12/12/12/12/12/12/12/12/12/12/5K6 b -")).unwrap();
+        assert_eq!(game_state.winner(), None);
+        assert!(!game_state.game_over());
+    }
+}
@@ -0,0 +1,19 @@
+use crate::chu_shogi::state::square::PieceKind;
+use crate::chu_shogi::state::square::Square;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Move {
+    pub from: (i8, i8),
+    pub to: (i8, i8), // the same as from when the lion captures without moving (igui)
+    pub moving_piece_kind: PieceKind,
+    pub capture_piece: Option<Square>, // Undo -> place piece back
+    pub via: Option<(i8, i8)>, // lion double move: the square captured on the first step
+    pub via_capture_piece: Option<Square>,
+    pub promote: bool
+}
+
+impl Move {
+    pub fn captured_pieces(&self) -> Vec<Square> {
+        self.via_capture_piece.iter().chain(self.capture_piece.iter()).copied().collect()
+    }
+}
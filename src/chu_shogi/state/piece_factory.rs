@@ -0,0 +1,111 @@
+use crate::chu_shogi::state::square::Square;
+use crate::chu_shogi::state::square::PieceKind;
+use crate::chu_shogi::state::square::promotes_to;
+use crate::chu_shogi::state::square::demotes_to;
+
+// SFEN letters of the unpromoted pieces
+const LETTERS: [(char, PieceKind); 21] = [
+    ('K', PieceKind::King),
+    ('E', PieceKind::DrunkElephant),
+    ('G', PieceKind::Gold),
+    ('S', PieceKind::Silver),
+    ('C', PieceKind::Copper),
+    ('F', PieceKind::Leopard),
+    ('L', PieceKind::Lance),
+    ('A', PieceKind::ReverseChariot),
+    ('T', PieceKind::BlindTiger),
+    ('X', PieceKind::Phoenix),
+    ('O', PieceKind::Kirin),
+    ('N', PieceKind::Lion),
+    ('Q', PieceKind::Queen),
+    ('R', PieceKind::Rook),
+    ('B', PieceKind::Bishop),
+    ('D', PieceKind::DragonKing),
+    ('H', PieceKind::DragonHorse),
+    ('M', PieceKind::SideMover),
+    ('V', PieceKind::VerticalMover),
+    ('I', PieceKind::GoBetween),
+    ('P', PieceKind::Pawn)
+];
+
+// uppercase for sente and lowercase for gote, a promoted piece is written with the letter of its unpromoted side
+pub fn parse(encoded: char, promoted_piece: bool) -> Result<Square, &'static str> {
+    let player_number = if encoded.is_ascii_uppercase() { 1 } else { 2 };
+    let letter = encoded.to_ascii_uppercase();
+
+    let kind = match LETTERS.iter().find(|(l, _)| *l == letter) {
+        Some((_, kind)) => *kind,
+        None => return Err("unknown piece")
+    };
+
+    if promoted_piece {
+        match promotes_to(kind) {
+            Some(promoted_kind) => Ok(Square { player_number, kind: promoted_kind, promoted: true }),
+            None => Err("unpromotable piece")
+        }
+    } else {
+        Ok(Square { player_number, kind, promoted: false })
+    }
+}
+
+pub fn format(square: &Square) -> String {
+    let kind = if square.promoted {
+        demotes_to(square.kind).unwrap_or(square.kind)
+    } else {
+        square.kind
+    };
+
+    let letter = match LETTERS.iter().find(|(_, k)| *k == kind) {
+        Some((l, _)) => l.to_string(),
+        None => String::from("")
+    };
+
+    let prefix = if square.promoted { "+" } else { "" };
+
+    if square.player_number == 2 {
+        format!("{}{}", prefix, letter.to_lowercase())
+    } else {
+        format!("{}{}", prefix, letter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sente_test() {
+        let result = parse('N', false).unwrap();
+        assert_eq!(result, Square { player_number: 1, kind: PieceKind::Lion, promoted: false });
+    }
+
+    #[test]
+    fn parse_promoted_gote_test() {
+        let result = parse('o', true).unwrap();
+        assert_eq!(result, Square { player_number: 2, kind: PieceKind::Lion, promoted: true });
+    }
+
+    #[test]
+    fn parse_unpromotable_test() {
+        let result = parse('Q', true);
+        assert_eq!(result, Err("unpromotable piece"));
+    }
+
+    #[test]
+    fn parse_unknown_test() {
+        let result = parse('Z', false);
+        assert_eq!(result, Err("unknown piece"));
+    }
+
+    #[test]
+    fn format_promoted_test() {
+        let square = Square { player_number: 2, kind: PieceKind::Gold, promoted: true };
+        assert_eq!(format(&square), "+p");
+    }
+
+    #[test]
+    fn format_unpromoted_test() {
+        let square = Square { player_number: 1, kind: PieceKind::Gold, promoted: false };
+        assert_eq!(format(&square), "G");
+    }
+}
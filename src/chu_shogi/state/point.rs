@@ -0,0 +1,106 @@
+pub const MIN_N: i8 = 0;
+pub const MAX_N: i8 = 11;
+
+// direction relative to the player, the second value is towards the opposing side
+pub type Direction = (i8, i8);
+
+pub const FORWARD: Direction = (0, 1);
+pub const BACKWARD: Direction = (0, -1);
+pub const LEFT: Direction = (-1, 0);
+pub const RIGHT: Direction = (1, 0);
+pub const FORWARD_LEFT: Direction = (-1, 1);
+pub const FORWARD_RIGHT: Direction = (1, 1);
+pub const BACKWARD_LEFT: Direction = (-1, -1);
+pub const BACKWARD_RIGHT: Direction = (1, -1);
+
+pub const ORTHOGONAL: [Direction; 4] = [FORWARD, BACKWARD, LEFT, RIGHT];
+pub const DIAGONAL: [Direction; 4] = [FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT];
+
+pub fn valid(point: (i8, i8)) -> bool {
+    point.0 >= MIN_N && point.0 <= MAX_N && point.1 >= MIN_N && point.1 <= MAX_N
+}
+
+pub fn add(a: (i8, i8), b: (i8, i8)) -> (i8, i8) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+// the point distance steps away in the direction, sente moves towards y = 0
+pub fn step(point: (i8, i8), direction: Direction, player_number: i8, distance: i8) -> (i8, i8) {
+    let dy = if player_number == 1 { -direction.1 } else { direction.1 };
+    add(point, (direction.0 * distance, dy * distance))
+}
+
+// the squares a king step away, on the board
+pub fn adjacent_points(point: (i8, i8)) -> Vec<(i8, i8)> {
+    let mut acc = vec![];
+    for x in -1..=1 {
+        for y in -1..=1 {
+            let to = add(point, (x, y));
+            if (x != 0 || y != 0) && valid(to) {
+                acc.push(to);
+            }
+        }
+    }
+    acc
+}
+
+// the squares up to two king steps away, on the board
+pub fn lion_points(point: (i8, i8)) -> Vec<(i8, i8)> {
+    let mut acc = vec![];
+    for x in -2..=2 {
+        for y in -2..=2 {
+            let to = add(point, (x, y));
+            if (x != 0 || y != 0) && valid(to) {
+                acc.push(to);
+            }
+        }
+    }
+    acc
+}
+
+pub fn adjacent(a: (i8, i8), b: (i8, i8)) -> bool {
+    a != b && (a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_test() {
+        assert!(valid((11, 0)));
+        assert!(!valid((12, 0)));
+        assert!(!valid((0, -1)));
+    }
+
+    #[test]
+    fn step_sente_test() {
+        let result = step((5, 5), FORWARD, 1, 2);
+        assert_eq!(result, (5, 3));
+    }
+
+    #[test]
+    fn step_gote_test() {
+        let result = step((5, 5), FORWARD_LEFT, 2, 1);
+        assert_eq!(result, (4, 6));
+    }
+
+    #[test]
+    fn adjacent_points_corner_test() {
+        let result = adjacent_points((0, 0));
+        assert_eq!(result.len(), 3);
+    }
+
+    #[test]
+    fn lion_points_test() {
+        let result = lion_points((5, 5));
+        assert_eq!(result.len(), 24);
+    }
+
+    #[test]
+    fn adjacent_test() {
+        assert!(adjacent((5, 5), (6, 6)));
+        assert!(!adjacent((5, 5), (5, 7)));
+        assert!(!adjacent((5, 5), (5, 5)));
+    }
+}
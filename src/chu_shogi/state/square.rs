@@ -0,0 +1,356 @@
+use crate::chu_shogi::state::point::Direction;
+use crate::chu_shogi::state::point::FORWARD;
+use crate::chu_shogi::state::point::BACKWARD;
+use crate::chu_shogi::state::point::LEFT;
+use crate::chu_shogi::state::point::RIGHT;
+use crate::chu_shogi::state::point::FORWARD_LEFT;
+use crate::chu_shogi::state::point::FORWARD_RIGHT;
+use crate::chu_shogi::state::point::BACKWARD_LEFT;
+use crate::chu_shogi::state::point::BACKWARD_RIGHT;
+use crate::chu_shogi::state::point::ORTHOGONAL;
+use crate::chu_shogi::state::point::DIAGONAL;
+use crate::chu_shogi::state::point::step;
+use crate::chu_shogi::state::point::adjacent_points;
+use crate::chu_shogi::state::point::lion_points;
+use crate::chu_shogi::state::square_set::find_by_x_and_y;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PieceKind {
+    King,
+    Prince,
+    DrunkElephant,
+    Gold,
+    Silver,
+    Copper,
+    Leopard,
+    Lance,
+    ReverseChariot,
+    BlindTiger,
+    Phoenix,
+    Kirin,
+    Lion,
+    Queen,
+    Rook,
+    Bishop,
+    DragonKing,
+    DragonHorse,
+    SideMover,
+    VerticalMover,
+    GoBetween,
+    Pawn,
+    WhiteHorse,
+    Whale,
+    FlyingStag,
+    FreeBoar,
+    FlyingOx,
+    HornedFalcon,
+    SoaringEagle,
+    Empty
+}
+
+// how far a piece moves in a direction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Range {
+    Step, // one square
+    Jump, // two squares, leaping over the first
+    Ranging, // any number of squares until blocked
+    Lion // one or two squares, or a double move capturing on the first square
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Square {
+    pub player_number: i8,
+    pub kind: PieceKind,
+    pub promoted: bool
+}
+
+impl Square {
+    pub fn occupied(&self) -> bool {
+        self.player_number != 0
+    }
+
+    pub fn unoccupied(&self) -> bool {
+        self.player_number == 0
+    }
+
+    pub fn occupied_by_opponent(&self, player_number: i8) -> bool {
+        self.player_number != 0 && self.player_number != player_number
+    }
+
+    pub fn unoccupied_or_occupied_by_opponent(&self, player_number: i8) -> bool {
+        self.player_number == 0 || self.player_number != player_number
+    }
+}
+
+pub const EMPTY_SQUARE: Square = Square { player_number: 0, kind: PieceKind::Empty, promoted: false };
+
+const ALL_DIRECTIONS: [Direction; 8] = [FORWARD, BACKWARD, LEFT, RIGHT, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT];
+
+// the directions of a piece and how far it moves in each, the lion moves anywhere within two squares
+pub fn movement(kind: PieceKind) -> Vec<(Direction, Range)> {
+    let with_range = |directions: &[Direction], range: Range| -> Vec<(Direction, Range)> {
+        directions.iter().map(|d| (*d, range)).collect()
+    };
+
+    match kind {
+        PieceKind::King | PieceKind::Prince => with_range(&ALL_DIRECTIONS, Range::Step),
+        PieceKind::DrunkElephant => with_range(&[FORWARD, LEFT, RIGHT, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Step),
+        PieceKind::Gold => with_range(&[FORWARD, BACKWARD, LEFT, RIGHT, FORWARD_LEFT, FORWARD_RIGHT], Range::Step),
+        PieceKind::Silver => with_range(&[FORWARD, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Step),
+        PieceKind::Copper => with_range(&[FORWARD, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD], Range::Step),
+        PieceKind::Leopard => with_range(&[FORWARD, BACKWARD, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Step),
+        PieceKind::BlindTiger => with_range(&[BACKWARD, LEFT, RIGHT, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Step),
+        PieceKind::GoBetween => with_range(&[FORWARD, BACKWARD], Range::Step),
+        PieceKind::Pawn => with_range(&[FORWARD], Range::Step),
+        PieceKind::Lance => with_range(&[FORWARD], Range::Ranging),
+        PieceKind::ReverseChariot => with_range(&[FORWARD, BACKWARD], Range::Ranging),
+        PieceKind::SideMover => [with_range(&[LEFT, RIGHT], Range::Ranging), with_range(&[FORWARD, BACKWARD], Range::Step)].concat(),
+        PieceKind::VerticalMover => [with_range(&[FORWARD, BACKWARD], Range::Ranging), with_range(&[LEFT, RIGHT], Range::Step)].concat(),
+        PieceKind::Rook => with_range(&ORTHOGONAL, Range::Ranging),
+        PieceKind::Bishop => with_range(&DIAGONAL, Range::Ranging),
+        PieceKind::DragonKing => [with_range(&ORTHOGONAL, Range::Ranging), with_range(&DIAGONAL, Range::Step)].concat(),
+        PieceKind::DragonHorse => [with_range(&DIAGONAL, Range::Ranging), with_range(&ORTHOGONAL, Range::Step)].concat(),
+        PieceKind::Queen => with_range(&ALL_DIRECTIONS, Range::Ranging),
+        PieceKind::WhiteHorse => with_range(&[FORWARD, BACKWARD, FORWARD_LEFT, FORWARD_RIGHT], Range::Ranging),
+        PieceKind::Whale => with_range(&[FORWARD, BACKWARD, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Ranging),
+        PieceKind::FlyingStag => [with_range(&[FORWARD, BACKWARD], Range::Ranging), with_range(&[LEFT, RIGHT, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Step)].concat(),
+        PieceKind::FreeBoar => with_range(&[LEFT, RIGHT, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Ranging),
+        PieceKind::FlyingOx => with_range(&[FORWARD, BACKWARD, FORWARD_LEFT, FORWARD_RIGHT, BACKWARD_LEFT, BACKWARD_RIGHT], Range::Ranging),
+        PieceKind::Phoenix => [with_range(&ORTHOGONAL, Range::Step), with_range(&DIAGONAL, Range::Jump)].concat(),
+        PieceKind::Kirin => [with_range(&DIAGONAL, Range::Step), with_range(&ORTHOGONAL, Range::Jump)].concat(),
+        PieceKind::HornedFalcon => [with_range(&[FORWARD], Range::Lion), with_range(&[BACKWARD, LEFT, RIGHT], Range::Ranging), with_range(&DIAGONAL, Range::Ranging)].concat(),
+        PieceKind::SoaringEagle => [with_range(&[FORWARD_LEFT, FORWARD_RIGHT], Range::Lion), with_range(&ORTHOGONAL, Range::Ranging), with_range(&[BACKWARD_LEFT, BACKWARD_RIGHT], Range::Ranging)].concat(),
+        PieceKind::Lion | PieceKind::Empty => vec![]
+    }
+}
+
+// single moves of the piece, including the lion's jumps to any square two away
+pub fn destinations(kind: PieceKind, player_number: i8, point: (i8, i8), squares: &[Vec<Square>]) -> Vec<(i8, i8)> {
+    let mut acc = vec![];
+    let add_if_available = |to_point: (i8, i8), acc: &mut Vec<(i8, i8)>| -> bool {
+        match find_by_x_and_y(squares, to_point) {
+            Some(to) => {
+                if to.unoccupied_or_occupied_by_opponent(player_number) {
+                    acc.push(to_point);
+                }
+                to.unoccupied()
+            },
+            None => false
+        }
+    };
+
+    if kind == PieceKind::Lion {
+        for to_point in lion_points(point) {
+            add_if_available(to_point, &mut acc);
+        }
+        return acc;
+    }
+
+    for (direction, range) in movement(kind) {
+        match range {
+            Range::Step => {
+                add_if_available(step(point, direction, player_number, 1), &mut acc);
+            },
+            Range::Jump => {
+                add_if_available(step(point, direction, player_number, 2), &mut acc);
+            },
+            Range::Lion => {
+                add_if_available(step(point, direction, player_number, 1), &mut acc);
+                add_if_available(step(point, direction, player_number, 2), &mut acc);
+            },
+            Range::Ranging => {
+                let mut distance = 1;
+                while add_if_available(step(point, direction, player_number, distance), &mut acc) {
+                    distance += 1;
+                }
+            }
+        }
+    }
+    acc
+}
+
+// lion double moves as (first square, destination): a capture on the first square followed by a second step,
+// back to the starting square for igui (capturing without moving)
+pub fn double_moves(kind: PieceKind, player_number: i8, point: (i8, i8), squares: &[Vec<Square>]) -> Vec<((i8, i8), (i8, i8))> {
+    let mut acc = vec![];
+    let capturable = |p: (i8, i8)| find_by_x_and_y(squares, p).is_some_and(|s| s.occupied_by_opponent(player_number));
+    let available = |p: (i8, i8)| p == point || find_by_x_and_y(squares, p).is_some_and(|s| s.unoccupied_or_occupied_by_opponent(player_number));
+
+    if kind == PieceKind::Lion {
+        for via in adjacent_points(point).into_iter().filter(|p| capturable(*p)) {
+            for to in adjacent_points(via).into_iter().filter(|p| available(*p)) {
+                acc.push((via, to));
+            }
+        }
+        return acc;
+    }
+
+    for (direction, range) in movement(kind) {
+        if range == Range::Lion {
+            let via = step(point, direction, player_number, 1);
+            if capturable(via) {
+                acc.push((via, point));
+                let to = step(point, direction, player_number, 2);
+                if available(to) {
+                    acc.push((via, to));
+                }
+            }
+        }
+    }
+    acc
+}
+
+pub fn promotes_to(kind: PieceKind) -> Option<PieceKind> {
+    match kind {
+        PieceKind::Pawn => Some(PieceKind::Gold),
+        PieceKind::GoBetween => Some(PieceKind::DrunkElephant),
+        PieceKind::Lance => Some(PieceKind::WhiteHorse),
+        PieceKind::ReverseChariot => Some(PieceKind::Whale),
+        PieceKind::Leopard => Some(PieceKind::Bishop),
+        PieceKind::Copper => Some(PieceKind::SideMover),
+        PieceKind::Silver => Some(PieceKind::VerticalMover),
+        PieceKind::Gold => Some(PieceKind::Rook),
+        PieceKind::DrunkElephant => Some(PieceKind::Prince),
+        PieceKind::BlindTiger => Some(PieceKind::FlyingStag),
+        PieceKind::Phoenix => Some(PieceKind::Queen),
+        PieceKind::Kirin => Some(PieceKind::Lion),
+        PieceKind::SideMover => Some(PieceKind::FreeBoar),
+        PieceKind::VerticalMover => Some(PieceKind::FlyingOx),
+        PieceKind::Bishop => Some(PieceKind::DragonHorse),
+        PieceKind::Rook => Some(PieceKind::DragonKing),
+        PieceKind::DragonHorse => Some(PieceKind::HornedFalcon),
+        PieceKind::DragonKing => Some(PieceKind::SoaringEagle),
+        _ => None
+    }
+}
+
+// every promoted kind comes from exactly one piece
+pub fn demotes_to(kind: PieceKind) -> Option<PieceKind> {
+    match kind {
+        PieceKind::Gold => Some(PieceKind::Pawn),
+        PieceKind::DrunkElephant => Some(PieceKind::GoBetween),
+        PieceKind::WhiteHorse => Some(PieceKind::Lance),
+        PieceKind::Whale => Some(PieceKind::ReverseChariot),
+        PieceKind::Bishop => Some(PieceKind::Leopard),
+        PieceKind::SideMover => Some(PieceKind::Copper),
+        PieceKind::VerticalMover => Some(PieceKind::Silver),
+        PieceKind::Rook => Some(PieceKind::Gold),
+        PieceKind::Prince => Some(PieceKind::DrunkElephant),
+        PieceKind::FlyingStag => Some(PieceKind::BlindTiger),
+        PieceKind::Queen => Some(PieceKind::Phoenix),
+        PieceKind::Lion => Some(PieceKind::Kirin),
+        PieceKind::FreeBoar => Some(PieceKind::SideMover),
+        PieceKind::FlyingOx => Some(PieceKind::VerticalMover),
+        PieceKind::DragonHorse => Some(PieceKind::Bishop),
+        PieceKind::DragonKing => Some(PieceKind::Rook),
+        PieceKind::HornedFalcon => Some(PieceKind::DragonHorse),
+        PieceKind::SoaringEagle => Some(PieceKind::DragonKing),
+        _ => None
+    }
+}
+
+// the last four ranks
+pub fn in_promotion_zone(player_number: i8, y: i8) -> bool {
+    if player_number == 1 {
+        y < 4
+    } else {
+        y > 7
+    }
+}
+
+// a player loses when both the king and the prince are captured
+pub fn royal(kind: PieceKind) -> bool {
+    kind == PieceKind::King || kind == PieceKind::Prince
+}
+
+pub fn opposing_player(player_number: i8) -> i8 {
+    if player_number == 1 {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chu_shogi::state::game_state::parse;
+
+    #[test]
+    fn destinations_lion_test() {
+        let game_state = parse(&String::from("12/12/12/12/12/5N6/12/12/12/12/12/12 b -")).unwrap();
+        let result = destinations(PieceKind::Lion, 1, (5, 5), &game_state.squares);
+        assert_eq!(result.len(), 24);
+    }
+
+    #[test]
+    fn destinations_kirin_test() {
+        let game_state = parse(&String::from("12/12/12/12/12/5O6/12/12/12/12/12/12 b -")).unwrap();
+        let mut result = destinations(PieceKind::Kirin, 1, (5, 5), &game_state.squares);
+        result.sort();
+        assert_eq!(result, vec![(3, 5), (4, 4), (4, 6), (5, 3), (5, 7), (6, 4), (6, 6), (7, 5)]);
+    }
+
+    #[test]
+    fn destinations_ranging_blocked_test() {
+        let game_state = parse(&String::from("12/12/12/5p6/12/5L6/12/12/12/12/12/12 b -")).unwrap();
+        let result = destinations(PieceKind::Lance, 1, (5, 5), &game_state.squares);
+        assert_eq!(result, vec![(5, 4), (5, 3)]);
+    }
+
+    #[test]
+    fn destinations_gote_direction_test() {
+        let game_state = parse(&String::from("12/12/12/12/12/5p6/12/12/12/12/12/12 b -")).unwrap();
+        let result = destinations(PieceKind::Pawn, 2, (5, 5), &game_state.squares);
+        assert_eq!(result, vec![(5, 6)]);
+    }
+
+    #[test]
+    fn destinations_horned_falcon_jump_test() {
+        let game_state = parse(&String::from("12/12/12/5P6/5P6/5+H6/12/12/12/12/12/12 b -")).unwrap();
+        let result = destinations(PieceKind::HornedFalcon, 1, (5, 5), &game_state.squares);
+        assert!(!result.contains(&(5, 4)));
+        assert!(!result.contains(&(5, 3)));
+        assert!(!result.contains(&(5, 2)));
+    }
+
+    #[test]
+    fn double_moves_lion_test() {
+        let game_state = parse(&String::from("12/12/12/12/5p6/5N6/12/12/12/12/12/12 b -")).unwrap();
+        let result = double_moves(PieceKind::Lion, 1, (5, 5), &game_state.squares);
+        // igui and the seven other squares around the pawn
+        assert_eq!(result.len(), 8);
+        assert!(result.contains(&((5, 4), (5, 5))));
+        assert!(result.contains(&((5, 4), (5, 3))));
+    }
+
+    #[test]
+    fn double_moves_soaring_eagle_test() {
+        let game_state = parse(&String::from("12/12/12/12/4p7/5+D6/12/12/12/12/12/12 b -")).unwrap();
+        let result = double_moves(PieceKind::SoaringEagle, 1, (5, 5), &game_state.squares);
+        assert_eq!(result, vec![((4, 4), (5, 5)), ((4, 4), (3, 3))]);
+    }
+
+    #[test]
+    fn double_moves_no_capture_test() {
+        let game_state = parse(&String::from("12/12/12/12/12/5N6/12/12/12/12/12/12 b -")).unwrap();
+        let result = double_moves(PieceKind::Lion, 1, (5, 5), &game_state.squares);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn promotion_round_trip_test() {
+        for kind in [PieceKind::Pawn, PieceKind::Kirin, PieceKind::DragonKing, PieceKind::Leopard].iter() {
+            let promoted = promotes_to(*kind).unwrap();
+            assert_eq!(demotes_to(promoted), Some(*kind));
+        }
+    }
+
+    #[test]
+    fn in_promotion_zone_test() {
+        assert!(in_promotion_zone(1, 3));
+        assert!(!in_promotion_zone(1, 4));
+        assert!(in_promotion_zone(2, 8));
+        assert!(!in_promotion_zone(2, 7));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::chu_shogi::state::point::valid;
+use crate::chu_shogi::state::square::Square;
+use crate::chu_shogi::state::square::royal;
+use crate::chu_shogi::state::square::destinations;
+
+pub fn find_by_x_and_y_mut(squares: &mut [Vec<Square>], point: (i8, i8)) -> Option<&mut Square> {
+    if valid(point) {
+        Some(&mut squares[point.1 as usize][point.0 as usize])
+    } else {
+        None
+    }
+}
+
+pub fn find_by_x_and_y(squares: &[Vec<Square>], point: (i8, i8)) -> Option<&Square> {
+    if valid(point) {
+        Some(&squares[point.1 as usize][point.0 as usize])
+    } else {
+        None
+    }
+}
+
+// king and crown prince
+pub fn royal_count(squares: &[Vec<Square>], player_number: i8) -> usize {
+    squares.iter().flatten().filter(|s| s.player_number == player_number && royal(s.kind)).count()
+}
+
+// a piece of the player could capture on the point
+pub fn any_threats_to_point(squares: &[Vec<Square>], point: (i8, i8), player_number: i8) -> bool {
+    squares.iter().enumerate().any(|(y, row)| {
+        row.iter().enumerate().any(|(x, s)| {
+            s.player_number == player_number && destinations(s.kind, player_number, (x as i8, y as i8), squares).contains(&point)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chu_shogi::state::game_state::parse;
+
+    #[test]
+    fn royal_count_test() {
+        let game_state = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/5K+E5 b -")).unwrap();
+        assert_eq!(royal_count(&game_state.squares, 1), 2);
+        assert_eq!(royal_count(&game_state.squares, 2), 1);
+    }
+
+    #[test]
+    fn any_threats_to_point_test() {
+        let game_state = parse(&String::from("5k6/12/12/12/12/12/12/12/12/12/12/5R6 b -")).unwrap();
+        assert!(any_threats_to_point(&game_state.squares, (5, 0), 1));
+        assert!(!any_threats_to_point(&game_state.squares, (5, 0), 2));
+    }
+}
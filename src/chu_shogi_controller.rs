@@ -0,0 +1,63 @@
+use std::env;
+use actix_web::HttpResponse;
+use super::chu_shogi;
+
+pub fn minimax(game_data: &str) -> HttpResponse {
+    let mut game_state = match chu_shogi::state::game_state::parse(game_data) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let minimax_depth: i8 = env::var("CHU_SHOGI_MINIMAX_DEPTH")
+        .unwrap_or_else(|_| "1".to_string())
+        .parse()
+        .expect("CHU_SHOGI_MINIMAX_DEPTH must be a number");
+
+    let recommended_move = chu_shogi::minimax::recommended_move(&mut game_state, minimax_depth);
+
+    match recommended_move {
+        Some(m) => {
+            let external_move = chu_shogi::state::external_mov::build(&game_state, m);
+            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
+        },
+        None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::MessageBody;
+
+    #[test]
+    fn minimax_valid_test() {
+        let game_state = String::from("lfcsgekgscfl/a1b1txot1b1a/mvrhdqndhrvm/pppppppppppp/3i4i3/12/12/3I4I3/PPPPPPPPPPPP/MVRHDNQDHRVM/A1B1TOXT1B1A/LFCSGKEGSCFL b - 1");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn minimax_capture_test() {
+        let game_state = String::from("k11/12/12/5g6/5p6/5N6/12/12/12/12/12/K11 b -");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "Ln7fx7ex7d\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_invalid_game_state_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+}
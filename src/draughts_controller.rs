@@ -0,0 +1,130 @@
+use std::env;
+
+use actix_web::HttpResponse;
+
+use super::checkers;
+use super::checkers::state::variant::Variant;
+
+pub fn minimax(game_data: &String) -> HttpResponse {
+    let game_state = match checkers::state::game_state::parse_with_variant(game_data, Variant::International) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let minimax_depth: i8 = env::var("DRAUGHTS_MINIMAX_DEPTH")
+        .unwrap_or_else(|_| "4".to_string())
+        .parse()
+        .expect("DRAUGHTS_MINIMAX_DEPTH must be a number");
+
+    let recommended_move = checkers::minimax::recommended_move(game_state, minimax_depth);
+
+    match recommended_move {
+        Some(m) => HttpResponse::Ok().body(format!("{}\n", m.format_with_variant(Variant::International))),
+        None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    }
+}
+
+pub fn mcts(game_data: &String) -> HttpResponse {
+    let game_state = match checkers::state::game_state::parse_with_variant(game_data, Variant::International) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let mcts_simulation_count: i16 = env::var("DRAUGHTS_MCTS_SIMULATION_COUNT")
+        .unwrap_or_else(|_| "200".to_string())
+        .parse()
+        .expect("DRAUGHTS_MCTS_SIMULATION_COUNT must be a number");
+
+    let mcts_simulation_depth: i16 = env::var("DRAUGHTS_MCTS_SIMULATION_DEPTH")
+        .unwrap_or_else(|_| "50".to_string())
+        .parse()
+        .expect("DRAUGHTS_MCTS_SIMULATION_DEPTH must be a number");
+
+    let recommended_move = checkers::mcts::recommended_move(game_state, mcts_simulation_count, mcts_simulation_depth);
+
+    match recommended_move {
+        Ok(m) => HttpResponse::Ok().body(format!("{}\n", m.format_with_variant(Variant::International))),
+        Err(e) => {
+            println!("{}", e);
+            HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::MessageBody;
+
+    #[test]
+    fn minimax_valid_test() {
+        let game_state = String::from("W:W32:B19,27,28");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "32x14\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_opening_test() {
+        let game_state = String::from("W:W31,32,33,34,35,36,37,38,39,40,41,42,43,44,45,46,47,48,49,50:B1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert!(bytes.starts_with(b"3")),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_invalid_game_state_test() {
+        let game_state = String::from("W:W51:B1");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_no_moves_test() {
+        let game_state = String::from("W:W:B1,2,3");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn mcts_valid_test() {
+        let game_state = String::from("W:WK46:B28");
+        let result = mcts(&game_state);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert!(bytes.starts_with(b"46x")),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn mcts_invalid_game_state_test() {
+        let game_state = String::from("X:W32:B19,27,28");
+        let result = mcts(&game_state);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+}
@@ -0,0 +1,79 @@
+use crate::janggi::state::game_state::GameState;
+use crate::janggi::state::mov::Move;
+use crate::xiangqi;
+use crate::xiangqi::state::game_state::Outcome;
+
+// the search is shared with xiangqi, janggi games end in a win on checkmate or on points
+impl xiangqi::mcts::State for GameState {
+    type Move = Move;
+
+    fn current_player_number(&self) -> i8 {
+        self.current_player_number
+    }
+
+    fn possible_moves(&mut self) -> Vec<Move> {
+        GameState::possible_moves(self)
+    }
+
+    fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        GameState::perform_move(self, mov)
+    }
+
+    fn outcome(&mut self) -> Option<Outcome> {
+        self.winner().map(Outcome::Win)
+    }
+}
+
+pub fn recommended_move(game_state: &mut GameState, simulation_count: i16, max_simulation_depth: i16) -> Result<Move, &'static str> {
+    xiangqi::mcts::recommended_move(game_state, simulation_count, max_simulation_depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::janggi::state::game_state;
+    use crate::janggi::state::square::PieceKind;
+    use crate::xiangqi::mcts::State;
+
+    #[test]
+    fn recommended_move_test() {
+        // the king is in check and can only take the chariot
+        let mut game_state = game_state::parse(&String::from("3k5/3R5/9/9/9/9/9/9/9/4RK3 b - - 0 1")).unwrap();
+        let simulation_count: i16 = 10;
+        let max_simulation_depth: i16 = 10;
+
+        match recommended_move(&mut game_state, simulation_count, max_simulation_depth) {
+            Ok(mov) => {
+                assert_eq!(mov.from, (3, 0));
+                assert_eq!(mov.to, (3, 1));
+                assert_eq!(mov.moving_piece_kind, PieceKind::King);
+                assert_eq!(mov.capture_piece_kind, Some(PieceKind::Chariot));
+                assert!(!mov.pass);
+            },
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    #[test]
+    fn recommended_move_searched_test() {
+        let mut game_state = game_state::parse(&String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1")).unwrap();
+        let moves = game_state.possible_moves();
+
+        match recommended_move(&mut game_state, 10, 5) {
+            Ok(mov) => assert!(moves.iter().any(|m| m.from == mov.from && m.to == mov.to && m.pass == mov.pass)),
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    #[test]
+    fn outcome_checkmate_test() {
+        let mut game_state = game_state::parse(&String::from("3kR4/4R4/9/9/9/9/9/9/9/4K4 b - - 0 1")).unwrap();
+        assert_eq!(State::outcome(&mut game_state), Some(Outcome::Win(1)));
+    }
+
+    #[test]
+    fn outcome_in_progress_test() {
+        let mut game_state = game_state::parse(&String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1")).unwrap();
+        assert_eq!(State::outcome(&mut game_state), None);
+    }
+}
@@ -0,0 +1,212 @@
+use std::cmp;
+use std::convert::TryFrom;
+use crate::janggi;
+use crate::janggi::state::square::destinations;
+
+const GAME_END_VALUE: i32 = 100000;
+
+pub fn recommended_move(game_state: &mut janggi::state::game_state::GameState, depth: i8) -> Option<janggi::state::mov::Move> {
+    let mut new_game_state = game_state.clone();
+    let moves = game_state.possible_moves();
+    match moves.len() {
+        0 => None,
+        1 => moves.first().cloned(),
+        _ => {
+            let maximizing_player = new_game_state.current_player_number != 2;
+
+            let moves_with_value = moves.iter().map(|mov| {
+                match new_game_state.perform_move(mov) {
+                    Ok(_) => (),
+                    Err(_) => return (mov, 0),
+                };
+
+                // the other player moves next
+                // TODO: pass error up instead of ignoring
+                let value = evaluate(&mut new_game_state, depth, i32::MIN, i32::MAX, !maximizing_player).unwrap_or(0);
+
+                match new_game_state.undo_move(mov) {
+                    Ok(_) => (),
+                    Err(_) => return (mov, 0)
+                }
+
+                (mov, value)
+            });
+
+            let best_move = match maximizing_player {
+                true => moves_with_value.max_by(|a,b| (a.1).cmp(&b.1) ),
+                false => moves_with_value.min_by(|a,b| (a.1).cmp(&b.1) ),
+            };
+
+            best_move.map(|h| (h.0).clone())
+        }
+    }
+}
+
+pub fn evaluate(game_state: &mut janggi::state::game_state::GameState, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
+    if let Some(value) = game_end_evaluation(game_state) {
+        return Ok(value);
+    }
+
+    let moves = game_state.possible_moves();
+
+    // passing is always possible outside of check, so no moves is checkmate
+    if moves.is_empty() {
+        return match game_state.current_player_number {
+            1 => Ok(-GAME_END_VALUE),
+            _ => Ok(GAME_END_VALUE)
+        };
+    }
+
+    if depth == 0 {
+        return Ok(static_evaluation(game_state));
+    }
+
+    if maximizing_player {
+        let mut max_eval = i32::MIN;
+        for mov in moves {
+            game_state.perform_move(&mov)?;
+
+            match evaluate(game_state, depth - 1, alpha, beta, false) {
+                Ok(eval) => {
+                    max_eval = cmp::max(max_eval, eval);
+                    alpha = cmp::max(alpha, eval);
+                },
+                Err(e) => return Err(e),
+            }
+
+            game_state.undo_move(&mov)?;
+
+            if beta <= alpha {
+                break;
+            }
+        }
+        Ok(max_eval)
+    } else {
+        let mut min_eval = i32::MAX;
+        for mov in moves {
+            game_state.perform_move(&mov)?;
+
+            match evaluate(game_state, depth - 1, alpha, beta, true) {
+                Ok(eval) => {
+                    min_eval = cmp::min(min_eval, eval);
+                    beta = cmp::min(beta, eval);
+                },
+                Err(e) => return Err(e),
+            }
+
+            game_state.undo_move(&mov)?;
+
+            if beta <= alpha {
+                break;
+            }
+        }
+        Ok(min_eval)
+    }
+}
+
+// bikjang and passing end the game, won by the player with the most points
+pub fn game_end_evaluation(game_state: &janggi::state::game_state::GameState) -> Option<i32> {
+    match game_state.points_winner() {
+        Some(1) => Some(GAME_END_VALUE),
+        Some(_) => Some(-GAME_END_VALUE),
+        None => None
+    }
+}
+
+// positive -> Cho
+// negative -> Han
+// Piece points, including deom, and mobility
+pub fn static_evaluation(game_state: &janggi::state::game_state::GameState) -> i32 {
+    let points_value = ((game_state.points(1) - game_state.points(2)) * 100.0) as i32;
+
+    let player_one_destinations_count = player_destinations_count(game_state, 1);
+    let player_two_destinations_count = player_destinations_count(game_state, 2);
+    let destinations_value = u_to_i32(player_one_destinations_count) - u_to_i32(player_two_destinations_count);
+
+    points_value + destinations_value
+}
+
+// destinations of the player's pieces, ignoring whether the moves leave the king in check
+fn player_destinations_count(game_state: &janggi::state::game_state::GameState, player_number: i8) -> usize {
+    let mut count: usize = 0;
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            if s.player_number == player_number {
+                count += destinations(s.kind, s.player_number, (x as i8, y as i8), &game_state.squares).len();
+            }
+        }
+    }
+    count
+}
+
+fn u_to_i32(value: usize) -> i32 {
+    i32::try_from(value).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::janggi::state::square::PieceKind;
+
+    #[test]
+    fn static_evaluation_test() {
+        let encoded = String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1");
+        let game_state = janggi::state::game_state::parse(&encoded).unwrap();
+
+        // deom
+        assert_eq!(static_evaluation(&game_state), -150);
+    }
+
+    #[test]
+    fn evaluate_checkmate_test() {
+        let encoded = String::from("3kR4/4R4/9/9/9/9/9/9/9/4K4 b - - 0 1");
+        let mut game_state = janggi::state::game_state::parse(&encoded).unwrap();
+
+        match evaluate(&mut game_state, 1, i32::MIN, i32::MAX, false) {
+            Ok(result) => assert_eq!(result, GAME_END_VALUE),
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    #[test]
+    fn game_end_evaluation_passed_out_test() {
+        let encoded = String::from("3k5/9/9/9/9/9/9/9/9/4KR3 w - - 0 1 moves 한수쉼 한수쉼");
+        let game_state = janggi::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(game_end_evaluation(&game_state), Some(GAME_END_VALUE));
+    }
+
+    #[test]
+    fn game_end_evaluation_in_progress_test() {
+        let encoded = String::from("3k5/9/9/9/9/9/9/9/9/4KR3 w - - 0 1 moves 한수쉼");
+        let game_state = janggi::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(game_end_evaluation(&game_state), None);
+    }
+
+    #[test]
+    fn recommended_move_capture_test() {
+        let encoded = String::from("3k5/9/9/9/r8/9/9/9/9/R3K4 w - - 0 1");
+        let mut game_state = janggi::state::game_state::parse(&encoded).unwrap();
+
+        match recommended_move(&mut game_state, 0) {
+            Some(m) => {
+                assert_eq!(m.from, (0, 9));
+                assert_eq!(m.to, (0, 4));
+                assert_eq!(m.moving_piece_kind, PieceKind::Chariot);
+                assert_eq!(m.capture_piece_kind, Some(PieceKind::Chariot));
+            },
+            None => panic!("expected move"),
+        }
+    }
+
+    #[test]
+    fn recommended_move_pass_to_win_test() {
+        // after Han passes, passing again ends the game with Cho ahead on points
+        let encoded = String::from("3k5/9/9/9/9/9/9/9/9/4KR3 b - - 0 1 moves 한수쉼");
+        let mut game_state = janggi::state::game_state::parse(&encoded).unwrap();
+
+        match recommended_move(&mut game_state, 0) {
+            Some(m) => assert!(m.pass),
+            None => panic!("expected move"),
+        }
+    }
+}
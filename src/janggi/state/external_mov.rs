@@ -0,0 +1,175 @@
+use crate::janggi::state::square::PieceKind;
+use crate::janggi::state::game_state::GameState;
+use crate::janggi::state::mov::Move;
+
+const PASS: &str = "한수쉼";
+
+pub struct ExternalMove {
+    pub from: (i8, i8),
+    pub to: (i8, i8),
+    pub moving_piece_kind: PieceKind,
+    pub player_number: i8,
+    pub pass: bool
+}
+
+impl ExternalMove {
+    // Korean notation
+    // starting point, piece, destination point, each point the rank then the file
+    // ranks are numbered 1 to 9 then 0 from the top, files 1 to 9 from the left
+    //   02마83 - horse from the bottom rank, second file to the eighth rank, third file
+    //   한수쉼 - pass
+    pub fn format(&self) -> String {
+        if self.pass {
+            return String::from(PASS);
+        }
+        format!("{}{}{}", format_point(self.from), piece_name(self.moving_piece_kind, self.player_number), format_point(self.to))
+    }
+}
+
+pub fn piece_name(kind: PieceKind, player_number: i8) -> &'static str {
+    match kind {
+        PieceKind::King => "장",
+        PieceKind::Chariot => "차",
+        PieceKind::Cannon => "포",
+        PieceKind::Horse => "마",
+        PieceKind::Elephant => "상",
+        PieceKind::Guard => "사",
+        PieceKind::Soldier => if player_number == 1 { "졸" } else { "병" },
+        PieceKind::Empty => ""
+    }
+}
+
+fn format_point(point: (i8, i8)) -> String {
+    format!("{}{}", (point.1 + 1) % 10, point.0 + 1)
+}
+
+fn parse_point(rank: char, file: char) -> Result<(i8, i8), &'static str> {
+    let y = match rank.to_digit(10) {
+        Some(0) => 9,
+        Some(r) => r as i8 - 1,
+        None => return Err("external_mov::parse - Invalid rank")
+    };
+
+    let x = match file.to_digit(10) {
+        Some(f) if f >= 1 => f as i8 - 1,
+        _ => return Err("external_mov::parse - Invalid file")
+    };
+
+    Ok((x, y))
+}
+
+pub fn build(game_state: &GameState, mov: Move) -> ExternalMove {
+    ExternalMove {
+        from: mov.from,
+        to: mov.to,
+        moving_piece_kind: mov.moving_piece_kind,
+        player_number: game_state.current_player_number,
+        pass: mov.pass
+    }
+}
+
+// either soldier name is accepted, and 궁 for the king
+pub fn parse(encoded: &str, game_state: &mut GameState) -> Result<Move, &'static str> {
+    let moves = game_state.possible_moves();
+
+    if encoded == PASS {
+        return moves.into_iter().find(|m| m.pass).ok_or("external_mov::parse - Illegal move");
+    }
+
+    let chars: Vec<char> = encoded.chars().collect();
+    if chars.len() != 5 {
+        return Err("external_mov::parse - Invalid move");
+    }
+
+    let from = parse_point(chars[0], chars[1])?;
+    let to = parse_point(chars[3], chars[4])?;
+    let kind = match chars[2] {
+        '장' | '궁' => PieceKind::King,
+        '차' => PieceKind::Chariot,
+        '포' => PieceKind::Cannon,
+        '마' => PieceKind::Horse,
+        '상' => PieceKind::Elephant,
+        '사' => PieceKind::Guard,
+        '졸' | '병' => PieceKind::Soldier,
+        _ => return Err("external_mov::parse - Invalid piece")
+    };
+
+    match moves.into_iter().find(|m| !m.pass && m.from == from && m.to == to && m.moving_piece_kind == kind) {
+        Some(m) => Ok(m),
+        None => Err("external_mov::parse - Illegal move")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::janggi::state::game_state::parse as parse_game_state;
+
+    const STARTING_POSITION: &str = "rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1";
+
+    #[test]
+    fn format_test() {
+        let mov = ExternalMove {
+            from: (1, 9),
+            to: (2, 7),
+            moving_piece_kind: PieceKind::Horse,
+            player_number: 1,
+            pass: false
+        };
+
+        assert_eq!(mov.format(), "02마83");
+    }
+
+    #[test]
+    fn format_soldier_two_test() {
+        let mov = ExternalMove {
+            from: (4, 3),
+            to: (4, 4),
+            moving_piece_kind: PieceKind::Soldier,
+            player_number: 2,
+            pass: false
+        };
+
+        assert_eq!(mov.format(), "45병55");
+    }
+
+    #[test]
+    fn format_pass_test() {
+        let mov = ExternalMove {
+            from: (4, 8),
+            to: (4, 8),
+            moving_piece_kind: PieceKind::King,
+            player_number: 1,
+            pass: true
+        };
+
+        assert_eq!(mov.format(), "한수쉼");
+    }
+
+    #[test]
+    fn parse_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = parse("02마83", &mut game_state).unwrap();
+
+        assert_eq!(result.from, (1, 9));
+        assert_eq!(result.to, (2, 7));
+        assert_eq!(result.moving_piece_kind, PieceKind::Horse);
+    }
+
+    #[test]
+    fn parse_pass_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = parse("한수쉼", &mut game_state).unwrap();
+
+        assert!(result.pass);
+    }
+
+    #[test]
+    fn parse_illegal_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+
+        assert!(parse("02마84", &mut game_state).is_err());
+        assert!(parse("02차83", &mut game_state).is_err());
+        assert!(parse("0283", &mut game_state).is_err());
+    }
+}
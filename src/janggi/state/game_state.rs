@@ -0,0 +1,470 @@
+use crate::janggi::state::point::valid;
+use crate::janggi::state::piece_factory::parse as parse_piece;
+use crate::janggi::state::square::destinations;
+use crate::janggi::state::square::opposing_player;
+use crate::janggi::state::square::points;
+use crate::janggi::state::square::PieceKind;
+use crate::janggi::state::square::Square;
+use crate::janggi::state::square_set::find_by_x_and_y_mut;
+use crate::janggi::state::square_set::find_by_x_and_y;
+use crate::janggi::state::square_set::find_king_point_for_player;
+use crate::janggi::state::square_set::any_threats_to_point;
+use crate::janggi::state::square_set::kings_facing;
+use crate::janggi::state::mov::Move;
+use crate::janggi::state::external_mov::parse as parse_external_move;
+
+// deom, the points given to Han (player 2) for moving second
+const DEOM: f32 = 1.5;
+
+#[derive(Clone)]
+pub struct GameState {
+    pub current_player_number: i8,
+    pub squares: Vec<Vec<Square>>,
+    pub move_history: Vec<Move>,
+    pub facing_history: Vec<bool> // whether the kings faced each other in every position so far, including the current one
+}
+
+impl GameState {
+    // checkmate wins, a game ended by bikjang or by both players passing is won on points
+    pub fn winner(&mut self) -> Option<i8> {
+        if let Some(player_number) = self.points_winner() {
+            Some(player_number)
+        } else if self.in_checkmate(1) {
+            Some(2)
+        } else if self.in_checkmate(2) {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    // the player with the most points if the game has ended by bikjang or passing
+    pub fn points_winner(&self) -> Option<i8> {
+        if self.bikjang() || self.passed_out() {
+            if self.points(1) > self.points(2) {
+                Some(1)
+            } else {
+                Some(2)
+            }
+        } else {
+            None
+        }
+    }
+
+    // the kings were left facing each other after the player to move was given the chance to break it
+    pub fn bikjang(&self) -> bool {
+        let n = self.facing_history.len();
+        n >= 2 && self.facing_history[n - 1] && self.facing_history[n - 2]
+    }
+
+    // both players passed in succession
+    pub fn passed_out(&self) -> bool {
+        let n = self.move_history.len();
+        n >= 2 && self.move_history[n - 1].pass && self.move_history[n - 2].pass
+    }
+
+    // the value of the player's pieces on the board, with deom for Han
+    pub fn points(&self, player_number: i8) -> f32 {
+        let total: f32 = self.squares.iter().flatten().filter(|s| s.player_number == player_number).map(|s| points(s.kind)).sum();
+
+        if player_number == 2 {
+            total + DEOM
+        } else {
+            total
+        }
+    }
+
+    pub fn in_checkmate(&mut self, player_number: i8) -> bool {
+        self.in_check(player_number) && self.possible_moves_for_player(player_number).is_empty()
+    }
+
+    pub fn in_check(&self, player_number: i8) -> bool {
+        match find_king_point_for_player(&self.squares, player_number) {
+            Some(king_point) => any_threats_to_point(&self.squares, king_point, player_number),
+            None => false
+        }
+    }
+
+    pub fn possible_moves(&mut self) -> Vec<Move> {
+        self.possible_moves_for_player(self.current_player_number)
+    }
+
+    pub fn possible_moves_for_player(&mut self, subject_player_number: i8) -> Vec<Move> {
+        let mut moves = vec![];
+
+        for (y, row) in self.squares.iter().enumerate() {
+            for (x, from) in row.iter().enumerate() {
+                if from.player_number == subject_player_number {
+                    let from_point = (x as i8, y as i8);
+                    for to_point in destinations(from.kind, from.player_number, from_point, &self.squares) {
+                        if let Some(to) = find_by_x_and_y(&self.squares, to_point) {
+                            let capture_piece_kind = if to.occupied_by_opponent(subject_player_number) {
+                                Some(to.kind)
+                            } else {
+                                None
+                            };
+
+                            let mov = Move {
+                                from: from_point,
+                                to: to_point,
+                                moving_piece_kind: from.kind,
+                                capture_piece_kind,
+                                pass: false
+                            };
+                            moves.push(mov);
+                        }
+                    }
+                }
+            }
+        }
+
+        // keep moves that don't result in check for the current player.
+        moves.retain(|m| {
+            let perform_result = self.perform_board_move(m);
+            let in_check = self.in_check(subject_player_number);
+            let undo_result = self.undo_board_move(m);
+            perform_result.is_ok() && undo_result.is_ok() && !in_check
+        });
+
+        // a player may pass instead of moving unless in check
+        if !self.in_check(subject_player_number) {
+            if let Some(king_point) = find_king_point_for_player(&self.squares, subject_player_number) {
+                moves.push(Move {
+                    from: king_point,
+                    to: king_point,
+                    moving_piece_kind: PieceKind::King,
+                    capture_piece_kind: None,
+                    pass: true
+                });
+            }
+        }
+
+        moves
+    }
+
+    pub fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        self.perform_board_move(mov)?;
+        self.move_history.push(mov.clone());
+        self.facing_history.push(kings_facing(&self.squares));
+        Ok(())
+    }
+
+    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        self.undo_board_move(mov)?;
+        self.move_history.pop();
+        self.facing_history.pop();
+        Ok(())
+    }
+
+    // moves the pieces without recording the position, used when testing the legality of moves
+    fn perform_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        if mov.pass {
+            self.current_player_number = opposing_player(self.current_player_number);
+            return Ok(());
+        }
+
+        let piece_player_number: i8;
+        let piece_kind: PieceKind;
+
+        if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.from) {
+            if s.occupied() {
+                piece_player_number = s.player_number;
+                piece_kind = s.kind;
+                s.player_number = 0;
+                s.kind = PieceKind::Empty;
+            } else {
+                return Err("game_state::perform_move - No piece on from");
+            }
+        } else {
+            return Err("game_state::perform_move - Invalid from square");
+        }
+
+        if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.to) {
+            s.player_number = piece_player_number;
+            s.kind = piece_kind;
+        } else {
+            return Err("game_state::perform_move - Invalid to square");
+        }
+
+        self.current_player_number = opposing_player(self.current_player_number);
+
+        Ok(())
+    }
+
+    fn undo_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        if mov.pass {
+            self.current_player_number = opposing_player(self.current_player_number);
+            return Ok(());
+        }
+
+        let moving_piece_player_number: i8;
+        let moving_piece_kind: PieceKind;
+
+        // get piece on to
+        if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.to) {
+            moving_piece_kind = s.kind;
+            moving_piece_player_number = s.player_number;
+            s.kind = PieceKind::Empty;
+            s.player_number = 0;
+        } else {
+            return Err("game_state::undo_move - Invalid to square")
+        };
+
+        // place piece onto from
+        if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.from) {
+            s.kind = moving_piece_kind;
+            s.player_number = moving_piece_player_number;
+        } else {
+            return Err("game_state::undo_move - Invalid from square")
+        }
+
+        // capture
+        if let Some(capture_piece_kind) = mov.capture_piece_kind {
+            if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.to) {
+                s.kind = capture_piece_kind;
+                s.player_number = opposing_player(moving_piece_player_number);
+            } else {
+                return Err("game_state::undo_move - Invalid to square");
+            }
+        }
+
+        self.current_player_number = opposing_player(self.current_player_number);
+
+        Ok(())
+    }
+}
+
+// board player - - half move full move
+// K A C E R H P, N and B are accepted for H and E
+// Cho (w) Capital, moves first from the bottom, Han (b) lower case
+// rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1
+//
+// optionally followed by the moves played since in Korean notation, used to detect bikjang and passing
+// rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1 moves 02마83 한수쉼
+pub fn parse(encoded: &str) -> Result<GameState, &'static str> {
+    let mut parts = encoded.splitn(2, " moves ");
+
+    let mut game_state = match parts.next() {
+        Some(position) => parse_position(position)?,
+        None => return Err("Error parsing state")
+    };
+
+    if let Some(moves) = parts.next() {
+        for encoded_move in moves.split_whitespace() {
+            let mov = parse_external_move(encoded_move, &mut game_state)?;
+            game_state.perform_move(&mov)?;
+        }
+    }
+
+    Ok(game_state)
+}
+
+fn parse_position(encoded: &str) -> Result<GameState, &'static str> {
+    let mut fields = encoded.split_whitespace();
+
+    let board = match fields.next() {
+        Some(b) => b,
+        None => return Err("Error parsing state")
+    };
+
+    let mut squares = vec![vec![Square { player_number: 0, kind: PieceKind::Empty }; 9]; 10];
+    let mut y: i8 = 0;
+    let mut x: i8 = 0;
+
+    for c in board.chars() {
+        match c {
+            '/' => {
+                y += 1; // new row
+                x = 0; // reset column
+            },
+            '1'..='9' => {
+                if let Some(number_of_spaces) = c.to_digit(10) {
+                    x += number_of_spaces as i8;
+                }
+                if x > 9 {
+                    return Err("Error parsing state");
+                }
+            },
+            _ => {
+                let square = match parse_piece(c) {
+                    Ok(s) => s,
+                    Err(_) => return Err("Error parsing state")
+                };
+                if valid((x, y)) {
+                    squares[y as usize][x as usize] = square;
+                } else {
+                    return Err("Error parsing state");
+                }
+                x += 1;
+            }
+        }
+    }
+
+    if y != 9 {
+        return Err("Error parsing state");
+    }
+
+    let current_player_number = match fields.next() {
+        Some("w") | None => 1,
+        Some("b") => 2,
+        Some(_) => return Err("Error parsing state")
+    };
+
+    let facing = kings_facing(&squares);
+
+    Ok(GameState {
+        current_player_number,
+        squares,
+        move_history: vec![],
+        facing_history: vec![facing]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STARTING_POSITION: &str = "rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1";
+
+    #[test]
+    fn parse_test() {
+        let result = parse(&String::from(STARTING_POSITION)).unwrap();
+        assert_eq!(result.current_player_number, 1);
+
+        assert_eq!(result.squares.len(), 10);
+        assert_eq!(result.squares[0][1].kind, PieceKind::Horse);
+        assert_eq!(result.squares[0][1].player_number, 2);
+
+        assert_eq!(result.squares[8][4].kind, PieceKind::King);
+        assert_eq!(result.squares[8][4].player_number, 1);
+
+        assert_eq!(result.squares[4][0].kind, PieceKind::Empty);
+        assert_eq!(result.squares[4][0].player_number, 0);
+    }
+
+    #[test]
+    fn parse_player_two_test() {
+        let result = parse(&String::from("4k4/9/9/9/9/9/9/9/9/3K5 b - - 0 1")).unwrap();
+        assert_eq!(result.current_player_number, 2);
+    }
+
+    #[test]
+    fn parse_invalid_test() {
+        assert!(parse(&String::from("4k4/9/9/9/9/9/9/9/9/3K6 w - - 0 1")).is_err());
+        assert!(parse(&String::from("4k4/9/9/9/9/9/9/9/3K5 w - - 0 1")).is_err());
+        assert!(parse(&String::from("4x4/9/9/9/9/9/9/9/9/3K5 w - - 0 1")).is_err());
+    }
+
+    #[test]
+    fn parse_with_moves_test() {
+        let result = parse(&String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1 moves 02마83 한수쉼")).unwrap();
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.squares[7][2].kind, PieceKind::Horse);
+        assert_eq!(result.move_history.len(), 2);
+        assert_eq!(result.facing_history.len(), 3);
+    }
+
+    #[test]
+    fn parse_with_illegal_move_test() {
+        let result = parse(&String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1 moves 01차61"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn points_test() {
+        let game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        assert_eq!(game_state.points(1), 72.0);
+        assert_eq!(game_state.points(2), 73.5);
+    }
+
+    #[test]
+    fn winner_checkmate_test() {
+        let mut game_state = parse(&String::from("3kR4/4R4/9/9/9/9/9/9/9/4K4 b - - 0 1")).unwrap();
+        assert_eq!(game_state.winner(), Some(1));
+    }
+
+    #[test]
+    fn winner_none_test() {
+        let mut game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        assert_eq!(game_state.winner(), None);
+    }
+
+    #[test]
+    fn winner_passed_out_test() {
+        let mut game_state = parse(&String::from("3k5/9/9/9/9/9/9/9/9/4KR3 w - - 0 1 moves 한수쉼 한수쉼")).unwrap();
+        assert!(game_state.passed_out());
+        assert_eq!(game_state.winner(), Some(1));
+    }
+
+    #[test]
+    fn winner_bikjang_test() {
+        let mut game_state = parse(&String::from("3k5/9/9/9/9/9/9/9/3A5/3K5 w - - 0 1 moves 94사95 한수쉼")).unwrap();
+        assert!(game_state.bikjang());
+        assert_eq!(game_state.winner(), Some(1));
+    }
+
+    #[test]
+    fn bikjang_broken_test() {
+        let game_state = parse(&String::from("3k5/9/9/9/9/9/9/9/3A5/3K5 w - - 0 1 moves 94사95 14장15")).unwrap();
+        assert!(!game_state.bikjang());
+    }
+
+    #[test]
+    fn in_check_test() {
+        let game_state = parse(&String::from("4k4/9/9/9/9/9/9/9/4r4/3K5 w - - 0 1")).unwrap();
+        assert!(game_state.in_check(1));
+        assert!(!game_state.in_check(2));
+    }
+
+    #[test]
+    fn possible_moves_test() {
+        let mut game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        let result = game_state.possible_moves();
+        assert!(result.iter().any(|m| m.pass));
+        assert!(result.iter().any(|m| m.from == (1, 9) && m.to == (2, 7)));
+    }
+
+    #[test]
+    fn possible_moves_in_check_test() {
+        let mut game_state = parse(&String::from("4k4/9/9/9/9/9/9/9/4r4/3K5 w - - 0 1")).unwrap();
+        let result = game_state.possible_moves();
+        assert!(!result.iter().any(|m| m.pass));
+        assert!(result.iter().all(|m| m.to != (3, 9) && m.to != (4, 9)));
+    }
+
+    #[test]
+    fn perform_move_test() {
+        let mut game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        let mov = Move { from: (1, 9), to: (2, 7), moving_piece_kind: PieceKind::Horse, capture_piece_kind: None, pass: false };
+        game_state.perform_move(&mov).unwrap();
+
+        assert_eq!(game_state.squares[9][1].kind, PieceKind::Empty);
+        assert_eq!(game_state.squares[7][2].kind, PieceKind::Horse);
+        assert_eq!(game_state.current_player_number, 2);
+        assert_eq!(game_state.move_history.len(), 1);
+    }
+
+    #[test]
+    fn undo_move_capture_test() {
+        let mut game_state = parse(&String::from("4k4/9/9/9/9/4p4/9/9/9/3KR4 w - - 0 1")).unwrap();
+        let mov = Move { from: (4, 9), to: (4, 5), moving_piece_kind: PieceKind::Chariot, capture_piece_kind: Some(PieceKind::Soldier), pass: false };
+        game_state.perform_move(&mov).unwrap();
+        game_state.undo_move(&mov).unwrap();
+
+        assert_eq!(game_state.squares[9][4].kind, PieceKind::Chariot);
+        assert_eq!(game_state.squares[5][4].kind, PieceKind::Soldier);
+        assert_eq!(game_state.squares[5][4].player_number, 2);
+        assert_eq!(game_state.current_player_number, 1);
+        assert_eq!(game_state.facing_history.len(), 1);
+    }
+
+    #[test]
+    fn pass_test() {
+        let mut game_state = parse(&String::from(STARTING_POSITION)).unwrap();
+        let mov = Move { from: (4, 8), to: (4, 8), moving_piece_kind: PieceKind::King, capture_piece_kind: None, pass: true };
+        game_state.perform_move(&mov).unwrap();
+
+        assert_eq!(game_state.current_player_number, 2);
+        assert_eq!(game_state.squares[8][4].kind, PieceKind::King);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod point;
+mod piece_factory;
+pub mod square;
+pub mod square_set;
+pub mod mov;
+pub mod external_mov;
+pub mod game_state;
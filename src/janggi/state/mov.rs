@@ -0,0 +1,10 @@
+use crate::janggi::state::square::PieceKind;
+
+#[derive(Clone)]
+pub struct Move {
+    pub from: (i8, i8),
+    pub to: (i8, i8),
+    pub moving_piece_kind: PieceKind,
+    pub capture_piece_kind: Option<PieceKind>, // Undo -> place piece back
+    pub pass: bool // passing the turn, from and to are the king point
+}
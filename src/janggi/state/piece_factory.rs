@@ -0,0 +1,45 @@
+use crate::janggi::state::square::Square;
+use crate::janggi::state::square::PieceKind;
+
+// letters follow xiangqi FEN, n and b are also accepted for the horse and elephant
+pub fn parse(encoded: char) -> Result<Square, &'static str> {
+    let player_number = if encoded.is_ascii_uppercase() { 1 } else { 2 };
+
+    let kind = match encoded.to_ascii_lowercase() {
+        'k' => PieceKind::King,
+        'r' => PieceKind::Chariot,
+        'c' => PieceKind::Cannon,
+        'h' | 'n' => PieceKind::Horse,
+        'e' | 'b' => PieceKind::Elephant,
+        'a' => PieceKind::Guard,
+        'p' => PieceKind::Soldier,
+        _ => return Err("unknown piece")
+    };
+
+    Ok(Square { kind, player_number })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_guard_one_test() {
+       let expected = Ok(Square { kind: PieceKind::Guard, player_number: 1 });
+       let result = parse('A');
+       assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_horse_two_test() {
+       let expected = Ok(Square { kind: PieceKind::Horse, player_number: 2 });
+       assert_eq!(parse('h'), expected);
+       assert_eq!(parse('n'), expected);
+    }
+
+    #[test]
+    fn parse_unknown_test() {
+       let result = parse('x');
+       assert_eq!(result, Err("unknown piece"));
+    }
+}
@@ -0,0 +1,239 @@
+pub const MIN_X: i8 = 0;
+pub const MAX_X: i8 = 8;
+pub const MIN_Y: i8 = 0;
+pub const MAX_Y: i8 = 9;
+
+// the centres of the palaces of player 2 (top) and player 1 (bottom)
+const PALACE_CENTERS: [(i8, i8); 2] = [(4, 1), (4, 8)];
+
+pub fn valid(point: (i8, i8)) -> bool {
+    point.0 >= MIN_X && point.0 <= MAX_X && point.1 >= MIN_Y && point.1 <= MAX_Y
+}
+
+pub fn add(a: (i8, i8), b: (i8, i8)) -> (i8, i8) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+pub fn palace_center(point: (i8, i8)) -> Option<(i8, i8)> {
+    PALACE_CENTERS.iter().find(|c| (point.0 - c.0).abs() <= 1 && (point.1 - c.1).abs() <= 1).copied()
+}
+
+pub fn in_palace(point: (i8, i8)) -> bool {
+    palace_center(point).is_some()
+}
+
+pub fn in_own_palace(point: (i8, i8), player_number: i8) -> bool {
+    match player_number {
+        1 => palace_center(point) == Some(PALACE_CENTERS[1]),
+        _ => palace_center(point) == Some(PALACE_CENTERS[0])
+    }
+}
+
+// the palace lines run through the centre and the four corners
+fn on_palace_diagonal(point: (i8, i8)) -> bool {
+    match palace_center(point) {
+        Some(center) => (point.0 - center.0).abs() == (point.1 - center.1).abs(),
+        None => false
+    }
+}
+
+// lines a chariot or cannon moves along: the four orthogonal directions,
+// and the palace diagonals from the centre or a corner of a palace
+pub fn lines(from: (i8, i8)) -> Vec<Vec<(i8, i8)>> {
+    let mut acc = vec![];
+
+    for direction in [(0, -1), (1, 0), (0, 1), (-1, 0)].iter() {
+        let mut line = vec![];
+        let mut counter = add(from, *direction);
+        while valid(counter) {
+            line.push(counter);
+            counter = add(counter, *direction);
+        }
+        acc.push(line);
+    }
+
+    if on_palace_diagonal(from) {
+        for direction in [(-1, -1), (1, -1), (1, 1), (-1, 1)].iter() {
+            let mut line = vec![];
+            let mut counter = add(from, *direction);
+            while on_palace_diagonal(counter) && palace_center(counter) == palace_center(from) {
+                line.push(counter);
+                counter = add(counter, *direction);
+            }
+            if !line.is_empty() {
+                acc.push(line);
+            }
+        }
+    }
+
+    acc
+}
+
+// one step along the lines of the board, including the palace diagonals
+pub fn step_points(from: (i8, i8)) -> Vec<(i8, i8)> {
+    lines(from).iter().filter_map(|line| line.first().copied()).collect()
+}
+
+// kings and guards stay inside their own palace
+pub fn palace_step_points(from: (i8, i8), player_number: i8) -> Vec<(i8, i8)> {
+    step_points(from).into_iter().filter(|p| in_own_palace(*p, player_number)).collect()
+}
+
+// forwards or sideways, and diagonally forwards along the lines of the opposing palace
+pub fn soldier_destination_points(from: (i8, i8), player_number: i8) -> Vec<(i8, i8)> {
+    let direction = forwards_direction(player_number);
+
+    step_points(from).into_iter().filter(|p| {
+        let dy = p.1 - from.1;
+        dy == direction || (dy == 0 && p.0 != from.0)
+    }).collect()
+}
+
+// a destination with the points that block the move to it
+pub type BlockedPoint = (Vec<(i8, i8)>, (i8, i8));
+
+// each destination with the points that block the move
+//  H H
+// H   H
+//   O
+// H   H
+//  H H
+pub fn horse_destination_points(from: (i8, i8)) -> Vec<BlockedPoint> {
+    let mut acc = vec![];
+
+    for (step, diagonals) in orthogonal_steps().iter() {
+        let first = add(from, *step);
+        for diagonal in diagonals.iter() {
+            let to = add(first, *diagonal);
+            if valid(to) {
+                acc.push((vec![first], to));
+            }
+        }
+    }
+
+    acc
+}
+
+// one step orthogonally then two diagonally, blocked on either of the first two points
+// E     E
+//
+//   E   E
+pub fn elephant_destination_points(from: (i8, i8)) -> Vec<BlockedPoint> {
+    let mut acc = vec![];
+
+    for (step, diagonals) in orthogonal_steps().iter() {
+        let first = add(from, *step);
+        for diagonal in diagonals.iter() {
+            let second = add(first, *diagonal);
+            let to = add(second, *diagonal);
+            if valid(to) {
+                acc.push((vec![first, second], to));
+            }
+        }
+    }
+
+    acc
+}
+
+// an orthogonal step with the diagonal steps that continue away from the start
+type Steps = ((i8, i8), [(i8, i8); 2]);
+
+fn orthogonal_steps() -> [Steps; 4] {
+    [
+        ((0, -1), [(-1, -1), (1, -1)]),
+        ((1, 0), [(1, -1), (1, 1)]),
+        ((0, 1), [(1, 1), (-1, 1)]),
+        ((-1, 0), [(-1, 1), (-1, -1)])
+    ]
+}
+
+pub fn forwards_direction(player_number: i8) -> i8 {
+    match player_number {
+        1 => -1,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palace_center_test() {
+        assert_eq!(palace_center((3, 9)), Some((4, 8)));
+        assert_eq!(palace_center((5, 2)), Some((4, 1)));
+        assert_eq!(palace_center((2, 8)), None);
+    }
+
+    #[test]
+    fn in_own_palace_test() {
+        assert!(in_own_palace((4, 8), 1));
+        assert!(!in_own_palace((4, 1), 1));
+        assert!(in_own_palace((3, 0), 2));
+    }
+
+    #[test]
+    fn lines_outside_palace_test() {
+        let result = lines((0, 0));
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[1].len(), 8);
+        assert_eq!(result[2].len(), 9);
+    }
+
+    #[test]
+    fn lines_palace_corner_test() {
+        let result = lines((3, 7));
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[4], vec![(4, 8), (5, 9)]);
+    }
+
+    #[test]
+    fn lines_palace_edge_test() {
+        let result = lines((4, 7));
+        assert_eq!(result.len(), 4);
+    }
+
+    #[test]
+    fn step_points_palace_center_test() {
+        let result = step_points((4, 1));
+        assert_eq!(result, vec![(4, 0), (5, 1), (4, 2), (3, 1), (3, 0), (5, 0), (5, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn palace_step_points_test() {
+        let result = palace_step_points((3, 7), 1);
+        assert_eq!(result, vec![(4, 7), (3, 8), (4, 8)]);
+    }
+
+    #[test]
+    fn soldier_destination_points_test() {
+        let result = soldier_destination_points((0, 6), 1);
+        assert_eq!(result, vec![(0, 5), (1, 6)]);
+    }
+
+    #[test]
+    fn soldier_destination_points_palace_test() {
+        let result = soldier_destination_points((3, 2), 1);
+        assert_eq!(result, vec![(3, 1), (4, 2), (2, 2), (4, 1)]);
+    }
+
+    #[test]
+    fn horse_destination_points_test() {
+        let result = horse_destination_points((1, 9));
+        assert_eq!(result, vec![
+            (vec![(1, 8)], (0, 7)),
+            (vec![(1, 8)], (2, 7)),
+            (vec![(2, 9)], (3, 8))
+        ]);
+    }
+
+    #[test]
+    fn elephant_destination_points_test() {
+        let result = elephant_destination_points((2, 9));
+        assert_eq!(result, vec![
+            (vec![(2, 8), (1, 7)], (0, 6)),
+            (vec![(2, 8), (3, 7)], (4, 6)),
+            (vec![(3, 9), (4, 8)], (5, 7))
+        ]);
+    }
+}
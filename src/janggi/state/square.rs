@@ -0,0 +1,238 @@
+use crate::janggi::state::point::lines;
+use crate::janggi::state::point::palace_step_points;
+use crate::janggi::state::point::soldier_destination_points;
+use crate::janggi::state::point::horse_destination_points;
+use crate::janggi::state::point::elephant_destination_points;
+use crate::janggi::state::square_set::find_by_x_and_y;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PieceKind {
+    King,
+    Chariot,
+    Cannon,
+    Horse,
+    Elephant,
+    Guard,
+    Soldier,
+    Empty
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Square {
+    pub player_number: i8,
+    pub kind: PieceKind
+}
+
+impl Square {
+    pub fn unoccupied(&self) -> bool {
+        self.player_number == 0
+    }
+
+    pub fn occupied(&self) -> bool {
+        self.player_number != 0
+    }
+
+    pub fn unoccupied_or_occupied_by_opponent(&self, player_number: i8) -> bool {
+        self.player_number == 0 || self.player_number != player_number
+    }
+
+    pub fn occupied_by_opponent(&self, player_number: i8) -> bool {
+        self.player_number != 0 && self.player_number != player_number
+    }
+}
+
+// points scored for the pieces left on the board
+pub fn points(kind: PieceKind) -> f32 {
+    match kind {
+        PieceKind::Chariot => 13.0,
+        PieceKind::Cannon => 7.0,
+        PieceKind::Horse => 5.0,
+        PieceKind::Elephant => 3.0,
+        PieceKind::Guard => 3.0,
+        PieceKind::Soldier => 2.0,
+        PieceKind::King | PieceKind::Empty => 0.0
+    }
+}
+
+pub fn destinations(piece_kind: PieceKind, player_number: i8, point: (i8, i8), squares: &[Vec<Square>]) -> Vec<(i8, i8)> {
+    let mut acc = vec![];
+
+    let open = |to_point: &(i8, i8)| -> bool {
+        match find_by_x_and_y(squares, *to_point) {
+            Some(to) => to.unoccupied_or_occupied_by_opponent(player_number),
+            None => false
+        }
+    };
+
+    let unblocked = |blocks: &Vec<(i8, i8)>| -> bool {
+        blocks.iter().all(|b| {
+            match find_by_x_and_y(squares, *b) {
+                Some(s) => s.unoccupied(),
+                None => true
+            }
+        })
+    };
+
+    match piece_kind {
+        PieceKind::Empty => (),
+        PieceKind::King | PieceKind::Guard => {
+            acc = palace_step_points(point, player_number).into_iter().filter(open).collect();
+        },
+        PieceKind::Soldier => {
+            acc = soldier_destination_points(point, player_number).into_iter().filter(open).collect();
+        },
+        PieceKind::Horse => {
+            acc = horse_destination_points(point).into_iter().filter(|(blocks, to)| unblocked(blocks) && open(to)).map(|(_, to)| to).collect();
+        },
+        PieceKind::Elephant => {
+            acc = elephant_destination_points(point).into_iter().filter(|(blocks, to)| unblocked(blocks) && open(to)).map(|(_, to)| to).collect();
+        },
+        PieceKind::Chariot => {
+            for line in lines(point) {
+                for to_point in line {
+                    match find_by_x_and_y(squares, to_point) {
+                        Some(to) if to.unoccupied() => acc.push(to_point),
+                        Some(to) => {
+                            if to.player_number != player_number {
+                                acc.push(to_point);
+                            }
+                            break;
+                        },
+                        None => break
+                    }
+                }
+            }
+        },
+        PieceKind::Cannon => {
+            // jumps exactly one piece that is not a cannon, and can't capture a cannon
+            for line in lines(point) {
+                let mut screened = false;
+                for to_point in line {
+                    let to = match find_by_x_and_y(squares, to_point) {
+                        Some(s) => s,
+                        None => break
+                    };
+
+                    if !screened {
+                        if to.kind == PieceKind::Cannon {
+                            break;
+                        } else if to.occupied() {
+                            screened = true;
+                        }
+                    } else if to.unoccupied() {
+                        acc.push(to_point);
+                    } else {
+                        if to.player_number != player_number && to.kind != PieceKind::Cannon {
+                            acc.push(to_point);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    acc
+}
+
+pub fn opposing_player(player_number: i8) -> i8 {
+    if player_number == 1 {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::janggi::state::game_state::parse as parse_game_state;
+
+    const STARTING_POSITION: &str = "rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1";
+
+    #[test]
+    fn occupied_by_opponent_test() {
+        let square = Square { player_number: 2, kind: PieceKind::Soldier };
+        assert!(square.occupied_by_opponent(1));
+        assert!(!square.occupied_by_opponent(2));
+    }
+
+    #[test]
+    fn destinations_king_test() {
+        let game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = destinations(PieceKind::King, 1, (4, 8), &game_state.squares);
+        assert_eq!(result, vec![(4, 7), (5, 8), (4, 9), (3, 8), (3, 7), (5, 7)]);
+    }
+
+    #[test]
+    fn destinations_soldier_test() {
+        let game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = destinations(PieceKind::Soldier, 1, (0, 6), &game_state.squares);
+        assert_eq!(result, vec![(0, 5), (1, 6)]);
+    }
+
+    #[test]
+    fn destinations_horse_blocked_test() {
+        let game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = destinations(PieceKind::Horse, 1, (1, 9), &game_state.squares);
+        assert_eq!(result, vec![(0, 7), (2, 7)]);
+    }
+
+    #[test]
+    fn destinations_elephant_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/9/9/9/9/9/9/9/2E1K4 w - - 0 1")).unwrap();
+        let result = destinations(PieceKind::Elephant, 1, (2, 9), &game_state.squares);
+        assert_eq!(result, vec![(0, 6), (4, 6), (5, 7)]);
+    }
+
+    #[test]
+    fn destinations_elephant_blocked_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/9/9/9/9/9/1P7/9/2E1K4 w - - 0 1")).unwrap();
+        let result = destinations(PieceKind::Elephant, 1, (2, 9), &game_state.squares);
+        assert_eq!(result, vec![(4, 6), (5, 7)]);
+    }
+
+    #[test]
+    fn destinations_chariot_palace_diagonal_test() {
+        let game_state = parse_game_state(&String::from("3k5/9/9/9/9/9/9/3R5/9/4K4 w - - 0 1")).unwrap();
+        let result = destinations(PieceKind::Chariot, 1, (3, 7), &game_state.squares);
+        assert!(result.contains(&(4, 8)));
+        assert!(result.contains(&(5, 9)));
+        assert!(result.contains(&(3, 0)));
+    }
+
+    #[test]
+    fn destinations_cannon_no_screen_test() {
+        let game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let result = destinations(PieceKind::Cannon, 1, (1, 7), &game_state.squares);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn destinations_cannon_jump_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/1r7/9/9/9/1P7/1C7/9/4K4 w - - 0 1")).unwrap();
+        let result = destinations(PieceKind::Cannon, 1, (1, 7), &game_state.squares);
+        assert_eq!(result, vec![(1, 5), (1, 4), (1, 3), (1, 2)]);
+    }
+
+    #[test]
+    fn destinations_cannon_cannot_jump_cannon_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/1r7/9/9/9/1c7/1C7/9/4K4 w - - 0 1")).unwrap();
+        let result = destinations(PieceKind::Cannon, 1, (1, 7), &game_state.squares);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn destinations_cannon_cannot_capture_cannon_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/1c7/9/9/9/1P7/1C7/9/4K4 w - - 0 1")).unwrap();
+        let result = destinations(PieceKind::Cannon, 1, (1, 7), &game_state.squares);
+        assert_eq!(result, vec![(1, 5), (1, 4), (1, 3)]);
+    }
+
+    #[test]
+    fn destinations_cannon_palace_diagonal_test() {
+        let game_state = parse_game_state(&String::from("3k5/9/9/9/9/9/9/3C5/4A4/4K4 w - - 0 1")).unwrap();
+        let result = destinations(PieceKind::Cannon, 1, (3, 7), &game_state.squares);
+        assert_eq!(result, vec![(5, 9)]);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::janggi::state::point::valid;
+use crate::janggi::state::square::opposing_player;
+use crate::janggi::state::square::destinations;
+use crate::janggi::state::square::PieceKind;
+use crate::janggi::state::square::Square;
+
+pub fn find_by_x_and_y_mut(squares: &mut [Vec<Square>], point: (i8, i8)) -> Option<&mut Square> {
+    if valid(point) {
+        Some(&mut squares[point.1 as usize][point.0 as usize])
+    } else {
+        None
+    }
+}
+
+pub fn find_by_x_and_y(squares: &[Vec<Square>], point: (i8, i8)) -> Option<&Square> {
+    if valid(point) {
+        Some(&squares[point.1 as usize][point.0 as usize])
+    } else {
+        None
+    }
+}
+
+pub fn find_king_point_for_player(squares: &[Vec<Square>], player_number: i8) -> Option<(i8, i8)> {
+    for (y, row) in squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            if s.kind == PieceKind::King && s.player_number == player_number {
+                return Some((x as i8, y as i8));
+            }
+        }
+    }
+
+    None
+}
+
+// Returns true if any piece of the opponent of player_number can move to the point.
+pub fn any_threats_to_point(squares: &[Vec<Square>], point: (i8, i8), player_number: i8) -> bool {
+    let opposing_player_number = opposing_player(player_number);
+
+    squares.iter().enumerate().any(|(y, row)| {
+        row.iter().enumerate().any(|(x, s)| {
+            s.player_number == opposing_player_number && destinations(s.kind, s.player_number, (x as i8, y as i8), squares).contains(&point)
+        })
+    })
+}
+
+// Bikjang, the kings face each other on a file with nothing between them.
+pub fn kings_facing(squares: &[Vec<Square>]) -> bool {
+    let (one, two) = match (find_king_point_for_player(squares, 1), find_king_point_for_player(squares, 2)) {
+        (Some(one), Some(two)) => (one, two),
+        _ => return false
+    };
+
+    if one.0 != two.0 {
+        return false;
+    }
+
+    let (top, bottom) = if one.1 < two.1 { (one.1, two.1) } else { (two.1, one.1) };
+
+    ((top + 1)..bottom).all(|y| {
+        match find_by_x_and_y(squares, (one.0, y)) {
+            Some(s) => s.unoccupied(),
+            None => true
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::janggi::state::game_state::parse as parse_game_state;
+
+    #[test]
+    fn find_king_point_for_player_test() {
+        let game_state = parse_game_state(&String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1")).unwrap();
+        assert_eq!(find_king_point_for_player(&game_state.squares, 1), Some((4, 8)));
+        assert_eq!(find_king_point_for_player(&game_state.squares, 2), Some((4, 1)));
+    }
+
+    #[test]
+    fn any_threats_to_point_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/9/9/9/9/9/9/4r4/3K5 w - - 0 1")).unwrap();
+        // along the palace diagonal
+        assert!(any_threats_to_point(&game_state.squares, (3, 9), 1));
+        assert!(!any_threats_to_point(&game_state.squares, (2, 9), 1));
+    }
+
+    #[test]
+    fn kings_facing_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/9/9/9/9/9/9/9/4K4 w - - 0 1")).unwrap();
+        assert!(kings_facing(&game_state.squares));
+    }
+
+    #[test]
+    fn kings_facing_blocked_test() {
+        let game_state = parse_game_state(&String::from("4k4/9/9/9/4p4/9/9/9/9/4K4 w - - 0 1")).unwrap();
+        assert!(!kings_facing(&game_state.squares));
+    }
+}
@@ -0,0 +1,113 @@
+use std::env;
+use actix_web::HttpResponse;
+use super::janggi;
+
+pub fn minimax(game_data: &str) -> HttpResponse {
+    let mut game_state = match janggi::state::game_state::parse(game_data) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let minimax_depth: i8 = env::var("JANGGI_MINIMAX_DEPTH")
+        .unwrap_or_else(|_| "1".to_string())
+        .parse()
+        .expect("JANGGI_MINIMAX_DEPTH must be a number");
+
+    let recommended_move = janggi::minimax::recommended_move(&mut game_state, minimax_depth);
+
+    match recommended_move {
+        Some(m) => {
+            let external_move = janggi::state::external_mov::build(&game_state, m);
+            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
+        },
+        None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    }
+}
+
+pub fn mcts(game_data: &str) -> HttpResponse {
+    let mut game_state = match janggi::state::game_state::parse(game_data) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let mcts_simulation_count: i16 = env::var("JANGGI_MCTS_SIMULATION_COUNT")
+        .unwrap_or_else(|_| "100".to_string())
+        .parse()
+        .expect("JANGGI_MCTS_SIMULATION_COUNT must be a number");
+
+    let mcts_simulation_depth: i16 = env::var("JANGGI_MCTS_SIMULATION_DEPTH")
+        .unwrap_or_else(|_| "50".to_string())
+        .parse()
+        .expect("JANGGI_MCTS_SIMULATION_DEPTH must be a number");
+
+    let recommended_move = janggi::mcts::recommended_move(&mut game_state, mcts_simulation_count, mcts_simulation_depth);
+
+    match recommended_move {
+        Ok(m) => {
+            let external_move = janggi::state::external_mov::build(&game_state, m);
+            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
+        },
+        Err(e) => {
+            println!("{}", e);
+            HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::MessageBody;
+
+    #[test]
+    fn minimax_valid_test() {
+        let game_state = String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn minimax_capture_test() {
+        let game_state = String::from("3k5/9/9/9/r8/9/9/9/9/R3K4 w - - 0 1");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "01차51\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_invalid_game_state_test() {
+        let game_state = String::from("xhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1");
+        let result = minimax(&game_state);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn mcts_valid_test() {
+        let game_state = String::from("rhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1");
+        let result = mcts(&game_state);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn mcts_invalid_game_state_test() {
+        let game_state = String::from("xhea1aehr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RHEA1AEHR w - - 0 1");
+        let result = mcts(&game_state);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+}
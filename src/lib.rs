@@ -0,0 +1,8 @@
+pub mod checkers;
+pub mod backgammon;
+pub mod chess;
+pub mod go;
+pub mod shogi;
+pub mod chu_shogi;
+pub mod xiangqi;
+pub mod janggi;
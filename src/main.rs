@@ -2,30 +2,41 @@ use actix_web::{web, App, HttpResponse, HttpServer, Responder};
 use actix_cors::Cors;
 
 use std::env;
+use std::collections::HashMap;
 
-mod checkers;
+use salieri::checkers;
 mod checkers_controller;
 
-mod backgammon;
+use salieri::backgammon;
 mod backgammon_controller;
 
-mod chess;
+use salieri::chess;
 mod chess_controller;
+mod chess_variant_controller;
 
-mod go;
+use salieri::go;
 mod go_controller;
 
-mod shogi;
+use salieri::shogi;
 mod shogi_controller;
+mod shogi_variant_controller;
 
-mod xiangqi;
+use salieri::chu_shogi;
+mod chu_shogi_controller;
+
+use salieri::xiangqi;
 mod xiangqi_controller;
 
+use salieri::janggi;
+mod janggi_controller;
+
+mod draughts_controller;
+
 async fn index() -> impl Responder {
     HttpResponse::Ok().body("200 OK\n")
 }
 
-async fn post_game_move(info: web::Path<String>, req_body: String) -> impl Responder {
+async fn post_game_move(info: web::Path<String>, query: web::Query<HashMap<String, String>>, req_body: String) -> impl Responder {
     let game_type = &info.into_inner();
 
     match game_type.as_str() {
@@ -47,6 +58,11 @@ async fn post_game_move(info: web::Path<String>, req_body: String) -> impl Respo
                 None => chess_controller::minimax(&req_body)
             }
         },
+        "crazyhouse" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::Crazyhouse),
+        "king_of_the_hill" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::KingOfTheHill),
+        "three_check" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::ThreeCheck),
+        "atomic" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::Atomic),
+        "horde" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::Horde),
         "go" => {
             match go::openings::recommended_move(&req_body) {
                 Some(m) => HttpResponse::Ok().body(format!("{}\n", m)),
@@ -54,22 +70,39 @@ async fn post_game_move(info: web::Path<String>, req_body: String) -> impl Respo
             }
         },
         "shogi" => {
+            let req_body = match shogi_controller::game_data(&query, &req_body) {
+                Ok(d) => d,
+                Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            };
+            let notation = match shogi_controller::notation(&query) {
+                Ok(n) => n,
+                Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            };
             match shogi::openings::recommended_move(&req_body) {
-                Some(m) => HttpResponse::Ok().body(format!("{}\n", m)),
-                None => shogi_controller::minimax(&req_body)
+                Some(_) => shogi_controller::opening(&req_body, notation),
+                None => shogi_controller::minimax(&req_body, notation)
             }
         },
+        "minishogi" => shogi_variant_controller::minimax(&req_body, shogi::state::variant::Variant::Minishogi),
+        "judkins_shogi" => shogi_variant_controller::minimax(&req_body, shogi::state::variant::Variant::Judkins),
+        "chu_shogi" => chu_shogi_controller::minimax(&req_body),
         "xiangqi" => {
+            let notation = match xiangqi_controller::notation(&query) {
+                Ok(n) => n,
+                Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            };
             match xiangqi::openings::recommended_move(&req_body) {
-                Some(m) => HttpResponse::Ok().body(format!("{}\n", m)),
-                None => xiangqi_controller::minimax(&req_body)
+                Some(_) => xiangqi_controller::opening(&req_body, notation),
+                None => xiangqi_controller::minimax(&req_body, notation)
             }
         },
+        "janggi" => janggi_controller::minimax(&req_body),
+        "draughts" => draughts_controller::minimax(&req_body),
         _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     }
 }
 
-async fn post_game_algorithm_move(info: web::Path<(String, String)>, req_body: String) -> impl Responder {
+async fn post_game_algorithm_move(info: web::Path<(String, String)>, query: web::Query<HashMap<String, String>>, req_body: String) -> impl Responder {
     let (game_type, algorithm) = &info.into_inner();
 
     match game_type.as_str() {
@@ -97,6 +130,41 @@ async fn post_game_algorithm_move(info: web::Path<(String, String)>, req_body: S
                 _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
             }
         },
+        "crazyhouse" => {
+            match algorithm.as_str() {
+                "minimax" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::Crazyhouse),
+                "mcts" => chess_variant_controller::mcts(&req_body, chess::state::variant::Variant::Crazyhouse),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "king_of_the_hill" => {
+            match algorithm.as_str() {
+                "minimax" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::KingOfTheHill),
+                "mcts" => chess_variant_controller::mcts(&req_body, chess::state::variant::Variant::KingOfTheHill),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "three_check" => {
+            match algorithm.as_str() {
+                "minimax" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::ThreeCheck),
+                "mcts" => chess_variant_controller::mcts(&req_body, chess::state::variant::Variant::ThreeCheck),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "atomic" => {
+            match algorithm.as_str() {
+                "minimax" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::Atomic),
+                "mcts" => chess_variant_controller::mcts(&req_body, chess::state::variant::Variant::Atomic),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "horde" => {
+            match algorithm.as_str() {
+                "minimax" => chess_variant_controller::minimax(&req_body, chess::state::variant::Variant::Horde),
+                "mcts" => chess_variant_controller::mcts(&req_body, chess::state::variant::Variant::Horde),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
         "go" => {
             match algorithm.as_str() {
                 "openings_db" => go_controller::opening(&req_body),
@@ -106,18 +174,65 @@ async fn post_game_algorithm_move(info: web::Path<(String, String)>, req_body: S
             }
         },
         "shogi" => {
+            let req_body = match shogi_controller::game_data(&query, &req_body) {
+                Ok(d) => d,
+                Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            };
+            let notation = match shogi_controller::notation(&query) {
+                Ok(n) => n,
+                Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            };
             match algorithm.as_str() {
-                "openings_db" => shogi_controller::opening(&req_body),
-                "minimax" => shogi_controller::minimax(&req_body),
-                "mcts" => shogi_controller::mcts(&req_body),
+                "openings_db" => shogi_controller::opening(&req_body, notation),
+                "minimax" => shogi_controller::minimax(&req_body, notation),
+                "mcts" => shogi_controller::mcts(&req_body, notation),
+                "tsume" => shogi_controller::tsume(&req_body, notation),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "minishogi" => {
+            match algorithm.as_str() {
+                "minimax" => shogi_variant_controller::minimax(&req_body, shogi::state::variant::Variant::Minishogi),
+                "mcts" => shogi_variant_controller::mcts(&req_body, shogi::state::variant::Variant::Minishogi),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "judkins_shogi" => {
+            match algorithm.as_str() {
+                "minimax" => shogi_variant_controller::minimax(&req_body, shogi::state::variant::Variant::Judkins),
+                "mcts" => shogi_variant_controller::mcts(&req_body, shogi::state::variant::Variant::Judkins),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "chu_shogi" => {
+            match algorithm.as_str() {
+                "minimax" => chu_shogi_controller::minimax(&req_body),
                 _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
             }
         },
         "xiangqi" => {
+            let notation = match xiangqi_controller::notation(&query) {
+                Ok(n) => n,
+                Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            };
             match algorithm.as_str() {
-                "openings_db" => xiangqi_controller::opening(&req_body),
-                "minimax" => xiangqi_controller::minimax(&req_body),
-                "mcts" => xiangqi_controller::mcts(&req_body),
+                "openings_db" => xiangqi_controller::opening(&req_body, notation),
+                "minimax" => xiangqi_controller::minimax(&req_body, notation),
+                "mcts" => xiangqi_controller::mcts(&req_body, notation),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "janggi" => {
+            match algorithm.as_str() {
+                "minimax" => janggi_controller::minimax(&req_body),
+                "mcts" => janggi_controller::mcts(&req_body),
+                _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+            }
+        },
+        "draughts" => {
+            match algorithm.as_str() {
+                "minimax" => draughts_controller::minimax(&req_body),
+                "mcts" => draughts_controller::mcts(&req_body),
                 _ => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
             }
         },
@@ -132,6 +247,9 @@ async fn main() -> std::io::Result<()> {
         .parse()
         .expect("PORT must be a number");
 
+    xiangqi::openings::load_book();
+    checkers::endgame::load_database();
+
     HttpServer::new(|| {
         let allowed_origin = env::var("ALLOWED_ORIGIN")
             .unwrap_or_else(|_| "http://localhost:5173".to_string());
@@ -347,6 +465,146 @@ mod tests {
         assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
     }
 
+    // crazyhouse with valid params
+    #[actix_rt::test]
+    async fn test_crazyhouse_status_with_valid_params() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/crazyhouse")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // crazyhouse with invalid params
+    #[actix_rt::test]
+    async fn test_crazyhouse_body_with_invalid_params() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[X] w KQkq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/crazyhouse")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // king of the hill with valid params
+    #[actix_rt::test]
+    async fn test_king_of_the_hill_status_with_valid_params() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/king_of_the_hill")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // king of the hill with invalid params
+    #[actix_rt::test]
+    async fn test_king_of_the_hill_body_with_invalid_params() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/king_of_the_hill")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // three check with valid params
+    #[actix_rt::test]
+    async fn test_three_check_status_with_valid_params() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/three_check")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // three check with invalid params
+    #[actix_rt::test]
+    async fn test_three_check_body_with_invalid_params() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+4 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/three_check")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // atomic with valid params
+    #[actix_rt::test]
+    async fn test_atomic_status_with_valid_params() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/atomic")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // atomic with invalid params
+    #[actix_rt::test]
+    async fn test_atomic_body_with_invalid_params() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/atomic")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // horde with valid params
+    #[actix_rt::test]
+    async fn test_horde_status_with_valid_params() {
+        let game_state = String::from("rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/horde")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+    }
+
+    // horde with invalid params
+    #[actix_rt::test]
+    async fn test_horde_body_with_invalid_params() {
+        let game_state = String::from("znbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/horde")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
     // go with valid params
     #[actix_rt::test]
     async fn test_go_status_with_valid_params() {
@@ -425,7 +683,7 @@ mod tests {
             .set_payload(game_state)
             .to_request();
         let res = test::call_and_read_body(&app, req).await;
-        assert_eq!(res, Bytes::from_static(b"B*41\n"));
+        assert_eq!(res, Bytes::from_static(b"B*42\n"));
     }
 
     // shogi with invalid params
@@ -455,6 +713,141 @@ mod tests {
         assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
     }
 
+    // shogi with handicap params
+    #[actix_rt::test]
+    async fn test_shogi_body_with_handicap_params() {
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/shogi?handicap=two_piece")
+            .set_payload(String::from(""))
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"S-62\n"));
+    }
+
+    #[actix_rt::test]
+    async fn test_shogi_body_with_invalid_handicap_params() {
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/shogi?handicap=queen")
+            .set_payload(String::from(""))
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // shogi with notation params
+    #[actix_rt::test]
+    async fn test_shogi_body_with_notation_params() {
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/shogi?handicap=two_piece&notation=japanese")
+            .set_payload(String::from(""))
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from(String::from("６二銀\n")));
+    }
+
+    #[actix_rt::test]
+    async fn test_shogi_body_with_invalid_notation_params() {
+        let game_state = String::from("lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/shogi?notation=kanji")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // minishogi with valid params
+    #[actix_rt::test]
+    async fn test_minishogi_body_with_valid_params() {
+        let game_state = String::from("2k2/5/5/r4/K4 b -");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/minishogi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"Kx54\n"));
+    }
+
+    // minishogi with invalid params
+    #[actix_rt::test]
+    async fn test_minishogi_body_with_invalid_params() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/minishogi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // judkins_shogi with valid params
+    #[actix_rt::test]
+    async fn test_judkins_shogi_body_with_valid_params() {
+        let game_state = String::from("3k2/6/6/6/r5/K5 b -");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/judkins_shogi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"Kx65\n"));
+    }
+
+    // judkins_shogi with invalid params
+    #[actix_rt::test]
+    async fn test_judkins_shogi_body_with_invalid_params() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/judkins_shogi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // chu_shogi with valid params
+    #[actix_rt::test]
+    async fn test_chu_shogi_body_with_valid_params() {
+        let game_state = String::from("k11/12/12/5g6/5p6/5N6/12/12/12/12/12/K11 b -");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/chu_shogi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"Ln7fx7ex7d\n"));
+    }
+
+    // chu_shogi with invalid params
+    #[actix_rt::test]
+    async fn test_chu_shogi_body_with_invalid_params() {
+        let game_state = String::from("asdf");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/chu_shogi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
     // xiangqi with valid params
     #[actix_rt::test]
     async fn test_xiangqi_status_with_valid_params() {
@@ -509,6 +902,89 @@ mod tests {
         assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
     }
 
+    // xiangqi with notation params
+    #[actix_rt::test]
+    async fn test_xiangqi_body_with_notation_params() {
+        let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b - - 1 0");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/xiangqi?notation=iccs")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"h7e7\n"));
+    }
+
+    #[actix_rt::test]
+    async fn test_xiangqi_body_with_invalid_notation_params() {
+        let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b - - 1 0");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/xiangqi?notation=ucci")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // janggi with valid params
+    #[actix_rt::test]
+    async fn test_janggi_body_with_valid_params() {
+        let game_state = String::from("3k5/9/9/9/r8/9/9/9/9/R3K4 w - - 0 1");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/janggi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static("01차51\n".as_bytes()));
+    }
+
+    // janggi with invalid params
+    #[actix_rt::test]
+    async fn test_janggi_body_with_invalid_params() {
+        let game_state = String::from("asdf");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/janggi")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
+    // draughts with valid params
+    #[actix_rt::test]
+    async fn test_draughts_body_with_valid_params() {
+        let game_state = String::from("W:W32:B19,27,28");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/draughts")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"32x14\n"));
+    }
+
+    // draughts with invalid params
+    #[actix_rt::test]
+    async fn test_draughts_body_with_invalid_params() {
+        let game_state = String::from("asdf");
+        let app = test::init_service(App::new().route("/api/v0/{game_type}", web::post().to(post_game_move))).await;
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::plaintext())
+            .uri("/api/v0/draughts")
+            .set_payload(game_state)
+            .to_request();
+        let res = test::call_and_read_body(&app, req).await;
+        assert_eq!(res, Bytes::from_static(b"422 Unprocessable Entity\n"));
+    }
+
     // invalid game type
     #[actix_rt::test]
     async fn test_invalid_game_type_status() {
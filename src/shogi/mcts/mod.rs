@@ -155,6 +155,8 @@ fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<boo
             if let Some(w) = current_game_state.winner() {
                 end_game = true;
                 winner = Some(w);
+            } else if current_game_state.draw() {
+                end_game = true;
             } else {
                 simulation_depth = simulation_depth + 1;
             }
@@ -229,7 +231,8 @@ mod tests {
             to: (7, 6),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let child_node_a = Node {
             id: 2,
@@ -247,7 +250,8 @@ mod tests {
             to: (3, 6),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let child_node_b = Node {
             id: 3,
@@ -288,7 +292,8 @@ mod tests {
             to: (3, 6),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let node = Node {
             id: 2,
@@ -366,7 +371,8 @@ mod tests {
             to: (3, 6),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let node = Node {
             id: 2,
@@ -1,8 +1,26 @@
 use std::cmp;
 use std::convert::TryFrom;
 use crate::shogi::state::square::PieceKind;
+use crate::shogi::state::square::opposing_player;
+use crate::shogi::state::square::ou_kind;
+use crate::shogi::state::square::demotes_to;
+use crate::shogi::state::variant::Variant;
 use crate::shogi;
 
+const GAME_END_VALUE: i32 = 100000;
+
+const MOBILITY_WEIGHT: i32 = 5;
+const ENTERING_KING_WEIGHT: i32 = 100;
+
+const KING_DEFENDERS: [PieceKind; 4] = [PieceKind::Kinshou, PieceKind::Ginshou, PieceKind::Narigin, PieceKind::Tokin];
+const KING_DEFENDER_ADJACENT_VALUE: i32 = 40;
+const KING_DEFENDER_VALUE: i32 = 20;
+const KING_ATTACKER_VALUE: i32 = 30;
+const KING_HAND_ATTACKER_VALUE: i32 = 10;
+
+// an ou with at most this many opposing pieces ahead of it is unlikely to be mated
+const ENTERING_KING_MAX_PIECES_AHEAD: usize = 2;
+
 pub fn recommended_move(game_state: &mut shogi::state::game_state::GameState, depth: i8) -> Option<shogi::state::mov::Move> {
     let mut new_game_state = game_state.clone();
     let moves = game_state.possible_moves();
@@ -54,6 +72,10 @@ pub fn recommended_move(game_state: &mut shogi::state::game_state::GameState, de
 }
 
 pub fn evaluate(game_state: &mut shogi::state::game_state::GameState, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
+    if let Some(value) = game_end_evaluation(game_state) {
+        return Ok(value);
+    }
+
     let moves = game_state.possible_moves();
 
     if depth == 0 || moves.len() == 0 {
@@ -103,48 +125,198 @@ pub fn evaluate(game_state: &mut shogi::state::game_state::GameState, depth: i8,
     }
 }
 
-// positive -> w
-// negative -> b
-// Piece Scores
+// declaration, sennichite and impasse end the game with a win for one player or a draw
+pub fn game_end_evaluation(game_state: &shogi::state::game_state::GameState) -> Option<i32> {
+    if !game_state.game_over() {
+        return None;
+    }
+
+    let winner = match game_state.declared_player_number {
+        Some(_) => game_state.declaration_winner(),
+        None => game_state.perpetual_check_player().map(opposing_player)
+    };
+
+    match winner {
+        Some(1) => Some(GAME_END_VALUE),
+        Some(_) => Some(-GAME_END_VALUE),
+        None => Some(0)
+    }
+}
+
+// positive -> sente
+// negative -> gote
+// material on the board and in hand, piece-square tables, king safety, mobility and entering king
 pub fn static_evaluation(game_state: &mut shogi::state::game_state::GameState) -> i32 {
-    let player_one_pieces_count = player_pieces_count(game_state, 1);
-    let player_two_pieces_count = player_pieces_count(game_state, 2);
-    let pieces_count_value = u_to_i32(player_one_pieces_count) - u_to_i32(player_two_pieces_count);
+    let material_value = player_material_value(game_state, 1) - player_material_value(game_state, 2) - handicap_material_value(game_state);
+
+    let piece_square_value = player_piece_square_value(game_state, 1) - player_piece_square_value(game_state, 2);
+
+    let king_safety_value = king_safety_value(game_state, 1) - king_safety_value(game_state, 2);
 
     let player_one_possible_moves_count = game_state.possible_moves_for_player(1).len();
     let player_two_possible_moves_count = game_state.possible_moves_for_player(2).len();
     let possible_moves_value = u_to_i32(player_one_possible_moves_count) - u_to_i32(player_two_possible_moves_count);
 
-    10*pieces_count_value + 1*possible_moves_value
+    let entering_king_value = entering_king_value(game_state, 1) - entering_king_value(game_state, 2);
+
+    material_value + piece_square_value + king_safety_value + MOBILITY_WEIGHT*possible_moves_value + ENTERING_KING_WEIGHT*entering_king_value
+}
+
+// nyuugyoku: ranks the ou has advanced once few opposing pieces are left ahead of it to mate it,
+// plus the declaration points of the pieces in hand once it is in the enemy camp
+fn entering_king_value(game_state: &shogi::state::game_state::GameState, player_number: i8) -> i32 {
+    if !game_state.variant.entering_king() {
+        return 0;
+    }
+
+    let ou_point = match shogi::state::square_set::find_ou_point_for_player(&game_state.squares, player_number) {
+        Some(p) => p,
+        None => return 0
+    };
+
+    let ahead = |y: i8| if player_number == 1 { y < ou_point.1 } else { y > ou_point.1 };
+    let opposing_player_number = opposing_player(player_number);
+    let pieces_ahead = game_state.squares.iter().enumerate().filter(|(y, _)| ahead(*y as i8)).map(|(_, row)| {
+        row.iter().filter(|s| s.player_number == opposing_player_number && s.kind != ou_kind(opposing_player_number)).count()
+    }).sum::<usize>();
+
+    if pieces_ahead > ENTERING_KING_MAX_PIECES_AHEAD {
+        return 0;
+    }
+
+    let ranks_advanced = if player_number == 1 { 8 - ou_point.1 } else { ou_point.1 };
+
+    let hand_points = if shogi::state::square::in_promotion_zone(player_number, ou_point.1, game_state.variant) {
+        game_state.hands[player_number as usize].iter().map(|pk| shogi::state::square::impasse_points(*pk)).sum::<i8>()
+    } else {
+        0
+    };
+
+    i32::from(ranks_advanced) + i32::from(hand_points)
+}
+
+// pieces on the board, promoted ones with their bonus, and pieces in hand at the value they are dropped with
+fn player_material_value(game_state: &shogi::state::game_state::GameState, player_number: i8) -> i32 {
+    let board_value: i32 = game_state.squares.iter().flatten().filter(|s| s.player_number == player_number).map(|s| {
+        match demotes_to(s.kind) {
+            Some(kind) => piece_value(kind) + promotion_bonus(kind),
+            None => piece_value(s.kind)
+        }
+    }).sum();
+
+    let hand_value: i32 = game_state.hands[player_number as usize].iter().map(|kind| piece_value(*kind)).sum();
+
+    board_value + hand_value
 }
 
-fn player_pieces_count(game_state: &shogi::state::game_state::GameState, player_number: i8) -> usize {
-    let mut score: usize = 0;
-    for row in game_state.squares.iter() {
-        for s in row.iter() {
+// pieces gote gave up in a handicap game, so the starting position evaluates as even
+fn handicap_material_value(game_state: &shogi::state::game_state::GameState) -> i32 {
+    match game_state.handicap {
+        Some(handicap) => handicap.pieces().iter().map(|kind| piece_value(*kind)).sum(),
+        None => 0
+    }
+}
+
+fn piece_value(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Hisha => 950,
+        PieceKind::Kakugyou => 800,
+        PieceKind::Kinshou => 550,
+        PieceKind::Ginshou => 500,
+        PieceKind::Keima => 350,
+        PieceKind::Kyousha => 300,
+        PieceKind::Fuhyou => 100,
+        _ => 0
+    }
+}
+
+// added to the value of the unpromoted piece, the small pieces all move like a kinshou once promoted
+fn promotion_bonus(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Hisha => 300,
+        PieceKind::Kakugyou => 250,
+        PieceKind::Ginshou => 50,
+        PieceKind::Keima => 200,
+        PieceKind::Kyousha => 250,
+        PieceKind::Fuhyou => 450,
+        _ => 0
+    }
+}
+
+// Piece-square tables from the player's side, by ranks from the enemy back rank and files from the left.
+// The tables are for the full board, smaller variants don't use them.
+fn player_piece_square_value(game_state: &shogi::state::game_state::GameState, player_number: i8) -> i32 {
+    if game_state.variant != Variant::Standard {
+        return 0;
+    }
+
+    let mut value = 0;
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
             if s.player_number == player_number {
-                score += match s.kind {
-                    PieceKind::Oushou => 200,
-                    PieceKind::Gyokushou => 200,
-                    PieceKind::Hisha => 9,
-                    PieceKind::Kakugyou => 8,
-                    PieceKind::Kinshou => 6,
-                    PieceKind::Ginshou => 5,
-                    PieceKind::Keima => 4,
-                    PieceKind::Kyousha => 3,
-                    PieceKind::Fuhyou => 1,
-                    PieceKind::Ryuuou => 11,
-                    PieceKind::Ryuuma => 10,
-                    PieceKind::Narigin => 6,
-                    PieceKind::Narikei => 6,
-                    PieceKind::Narikyou => 6,
-                    PieceKind::Tokin => 7,
-                    PieceKind::Empty => 0
-                };
+                let (rank, file) = if player_number == 1 { (y, x) } else { (8 - y, 8 - x) };
+                value += piece_square_value(s.kind, rank, file);
             }
         }
     }
-    score
+    value
+}
+
+fn piece_square_value(kind: PieceKind, rank: usize, file: usize) -> i32 {
+    let (rank_table, file_table) = match kind {
+        PieceKind::Fuhyou => (FUHYOU_RANKS, FLAT_FILES),
+        PieceKind::Keima => (KEIMA_RANKS, CENTRE_FILES),
+        PieceKind::Ginshou => (GINSHOU_RANKS, CENTRE_FILES),
+        PieceKind::Kinshou => (KINSHOU_RANKS, FLAT_FILES),
+        PieceKind::Oushou | PieceKind::Gyokushou => (OU_RANKS, OU_FILES),
+        PieceKind::Tokin | PieceKind::Narikyou | PieceKind::Narikei | PieceKind::Narigin => (PROMOTED_RANKS, FLAT_FILES),
+        _ => (FLAT_RANKS, FLAT_FILES)
+    };
+    rank_table[rank] + file_table[file]
+}
+
+const FLAT_RANKS: [i32; 9] = [0, 0, 0, 0, 0, 0, 0, 0, 0];
+const FLAT_FILES: [i32; 9] = [0, 0, 0, 0, 0, 0, 0, 0, 0];
+const CENTRE_FILES: [i32; 9] = [-10, 0, 5, 10, 10, 10, 5, 0, -10];
+
+// fuhyou gain from advancing, the rest from reaching the enemy camp without leaving the ou bare
+const FUHYOU_RANKS: [i32; 9] = [0, 40, 30, 20, 15, 10, 0, 0, 0];
+const KEIMA_RANKS: [i32; 9] = [0, 0, 20, 25, 15, 5, 0, -5, -10];
+const GINSHOU_RANKS: [i32; 9] = [0, 10, 20, 25, 20, 10, 5, 0, -5];
+const KINSHOU_RANKS: [i32; 9] = [-20, -15, -10, -5, 0, 5, 10, 10, 5];
+const PROMOTED_RANKS: [i32; 9] = [20, 25, 20, 10, 5, 0, 0, 0, 0];
+
+// the ou is safest castled on the back ranks away from the centre
+const OU_RANKS: [i32; 9] = [0, 0, 0, 0, 0, 0, 0, 5, 10];
+const OU_FILES: [i32; 9] = [15, 15, 10, 0, -10, 0, 10, 15, 15];
+
+// kinshou and ginshou around the ou defend it, opposing pieces near it and in hand attack it
+fn king_safety_value(game_state: &shogi::state::game_state::GameState, player_number: i8) -> i32 {
+    let ou_point = match shogi::state::square_set::find_ou_point_for_player(&game_state.squares, player_number) {
+        Some(p) => p,
+        None => return 0
+    };
+
+    let opposing_player_number = opposing_player(player_number);
+    let mut value = 0;
+
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            let distance = cmp::max((x as i8 - ou_point.0).abs(), (y as i8 - ou_point.1).abs());
+            if distance > 2 {
+                continue;
+            }
+
+            if s.player_number == player_number && KING_DEFENDERS.contains(&s.kind) {
+                value += if distance == 1 { KING_DEFENDER_ADJACENT_VALUE } else { KING_DEFENDER_VALUE };
+            } else if s.player_number == opposing_player_number {
+                value -= KING_ATTACKER_VALUE;
+            }
+        }
+    }
+
+    let hand_attackers = game_state.hands[opposing_player_number as usize].iter().filter(|kind| **kind != PieceKind::Fuhyou).count();
+    value - KING_HAND_ATTACKER_VALUE * u_to_i32(hand_attackers)
 }
 
 fn u_to_i32(value: usize) -> i32 {
@@ -164,7 +336,7 @@ mod tests {
         let mut game_state = shogi::state::game_state::parse(&encoded).unwrap();
 
         match evaluate(&mut game_state, 0, std::i32::MIN, std::i32::MAX, false) {
-            Ok(result) => assert_eq!(result, 6),
+            Ok(result) => assert_eq!(result, 10),
             Err(e) => assert!(false, "{}", e)
         }
     }
@@ -179,7 +351,7 @@ mod tests {
         match mov {
             Some(m) => {
                 assert_eq!(m.from, None);
-                assert_eq!(m.to, (5, 0));
+                assert_eq!(m.to, (5, 1));
                 assert_eq!(m.moving_piece_kind, PieceKind::Kakugyou);
                 assert_eq!(m.capture_piece_kind, None);
                 assert_eq!(m.promote, false);
@@ -187,4 +359,137 @@ mod tests {
             None => assert!(false, "expected move"),
         }
     }
+
+    #[test]
+    fn evaluate_repetition_draw_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 b - 1 moves 5i5h 5a5b 5h5i 5b5a 5i5h 5a5b 5h5i 5b5a 5i5h 5a5b 5h5i 5b5a");
+        let mut game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        let result = evaluate(&mut game_state, 2, i32::MIN, i32::MAX, true);
+
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn evaluate_perpetual_check_test() {
+        let encoded = String::from("k8/9/9/9/9/9/9/9/1R2K4 b - 1 moves 8i9i 9a8a 9i8i 8a9a 8i9i 9a8a 9i8i 8a9a 8i9i 9a8a 9i8i 8a9a");
+        let mut game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        let result = evaluate(&mut game_state, 2, i32::MIN, i32::MAX, true);
+
+        assert_eq!(result, Ok(-GAME_END_VALUE));
+    }
+
+    #[test]
+    fn recommended_move_declare_test() {
+        let encoded = String::from("RR2K4/BB7/+P+P+P+P+P+P3/9/9/k8/9/9/9 b 2P");
+        let mut game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        let mov = recommended_move(&mut game_state, 0);
+
+        assert!(mov.unwrap().declare);
+    }
+
+    #[test]
+    fn evaluate_impasse_test() {
+        let encoded = String::from("4K4/9/9/9/9/9/9/9/4k4 b 2R2B4P9p");
+        let mut game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        let result = evaluate(&mut game_state, 2, i32::MIN, i32::MAX, true);
+
+        assert_eq!(result, Ok(GAME_END_VALUE));
+    }
+
+    #[test]
+    fn entering_king_value_test() {
+        // the ou has advanced 6 ranks with one opposing piece ahead of it
+        let encoded = String::from("4k4/9/2K1p4/9/9/9/9/9/9 b 2P");
+        let game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(entering_king_value(&game_state, 1), 8);
+        assert_eq!(entering_king_value(&game_state, 2), 0);
+    }
+
+    #[test]
+    fn entering_king_value_blocked_test() {
+        let encoded = String::from("4k4/ppp6/2K1p4/9/9/9/9/9/9 b 2P");
+        let game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(entering_king_value(&game_state, 1), 0);
+    }
+
+    #[test]
+    fn recommended_move_capture_over_drop_test() {
+        // counting only the board, dropping the hisha looked better than taking the kinshou for free
+        let encoded = String::from("4k4/9/9/9/4g4/4S4/9/9/4K4 b R");
+        let mut game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        let mov = recommended_move(&mut game_state, 0).unwrap();
+
+        assert_eq!(mov.from, Some((4, 5)));
+        assert_eq!(mov.to, (4, 4));
+        assert_eq!(mov.capture_piece_kind, Some(PieceKind::Kinshou));
+    }
+
+    #[test]
+    fn handicap_material_value_test() {
+        let encoded = String::from("lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1 moves 7a6b");
+        let game_state = shogi::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(handicap_material_value(&game_state), 1750);
+        assert_eq!(player_material_value(&game_state, 1) - player_material_value(&game_state, 2), 1750);
+    }
+
+    #[test]
+    fn handicap_material_value_even_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1");
+        let game_state = shogi::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(handicap_material_value(&game_state), 0);
+    }
+
+    #[test]
+    fn player_material_value_hand_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 b Rp");
+        let game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(player_material_value(&game_state, 1), 950);
+        assert_eq!(player_material_value(&game_state, 2), 100);
+    }
+
+    #[test]
+    fn player_material_value_promoted_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/+P8/4K4 b -");
+        let game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(player_material_value(&game_state, 1), 550);
+    }
+
+    #[test]
+    fn king_safety_value_test() {
+        let castled = shogi::state::game_state::parse(&String::from("4k4/9/9/9/9/9/9/2GS5/1K7 b -")).unwrap();
+        let bare = shogi::state::game_state::parse(&String::from("4k4/9/9/9/2GS5/9/9/9/1K7 b -")).unwrap();
+
+        assert_eq!(king_safety_value(&castled, 1), 60);
+        assert_eq!(king_safety_value(&bare, 1), 0);
+    }
+
+    #[test]
+    fn king_safety_value_attackers_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/1p7/9/1K7 b BP");
+        let game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(king_safety_value(&game_state, 1), -30);
+        assert_eq!(king_safety_value(&game_state, 2), -10);
+    }
+
+    #[test]
+    fn recommended_move_keeps_castle_test() {
+        // on mobility alone the ou walked to the edge, away from the ginshou and kinshou defending it
+        let encoded = String::from("4k4/9/ppppppppp/9/9/9/9/2S6/1KG6 b -");
+        let mut game_state = shogi::state::game_state::parse(&encoded).unwrap();
+
+        let mov = recommended_move(&mut game_state, 0).unwrap();
+        game_state.perform_move(&mov).unwrap();
+
+        assert_eq!(king_safety_value(&game_state, 1), 2 * KING_DEFENDER_ADJACENT_VALUE);
+    }
 }
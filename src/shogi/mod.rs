@@ -1,5 +1,8 @@
 pub mod state;
 pub mod mcts;
 pub mod minimax;
+pub mod notation;
 pub mod openings;
+pub mod record;
 
+pub mod tsume;
@@ -0,0 +1,95 @@
+use crate::shogi::record::csa;
+use crate::shogi::record::ki2;
+use crate::shogi::state::external_mov;
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::mov::Move;
+use crate::shogi::state::usi_mov;
+
+// styles a move can be written in
+//   western - P-76, Bx22+, S*45
+//   japanese - ７六歩, ２二角成, 同　銀, ４五銀打, ５八金右
+//   usi - 7g7f, 8h2b+, S*4e
+//   csa - +7776FU, +8822UM, -0045GI
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Notation {
+    Western,
+    Japanese,
+    Usi,
+    Csa
+}
+
+pub fn parse(name: &str) -> Result<Notation, &'static str> {
+    match name {
+        "western" => Ok(Notation::Western),
+        "japanese" => Ok(Notation::Japanese),
+        "usi" => Ok(Notation::Usi),
+        "csa" => Ok(Notation::Csa),
+        _ => Err("notation::parse - Unknown notation")
+    }
+}
+
+// the move by the current player, previous_to is the destination of the move before, written 同 in japanese notation
+pub fn format(game_state: &mut GameState, mov: Move, notation: Notation, previous_to: Option<(i8, i8)>) -> String {
+    match notation {
+        Notation::Western => external_mov::build(game_state, mov).format(),
+        Notation::Japanese => ki2::format_move(game_state, &mov, previous_to),
+        Notation::Usi => usi_mov::format(&mov, game_state.variant.size()),
+        Notation::Csa => csa::format_move(&mov, game_state.current_player_number)
+    }
+}
+
+// the destination of the last of the USI moves following the position, if any
+pub fn previous_to(encoded: &str, size: i8) -> Option<(i8, i8)> {
+    let (_, moves) = encoded.split_once(" moves ")?;
+    let chars: Vec<char> = moves.split_whitespace().last()?.chars().collect();
+    if chars.len() < 4 {
+        return None;
+    }
+    usi_mov::parse_point(chars[2], chars[3], size).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::state::game_state::parse as parse_game_state;
+
+    const POSITION: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f 3c3d";
+
+    fn format_bishop_exchange(notation: Notation) -> String {
+        let mut game_state = parse_game_state(&String::from(POSITION)).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.from == Some((1, 7)) && m.to == (7, 1) && m.promote).unwrap();
+        format(&mut game_state, mov, notation, previous_to(POSITION, 9))
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(parse("japanese"), Ok(Notation::Japanese));
+        assert_eq!(parse("kanji"), Err("notation::parse - Unknown notation"));
+    }
+
+    #[test]
+    fn format_western_test() {
+        assert_eq!(format_bishop_exchange(Notation::Western), "Bx22+");
+    }
+
+    #[test]
+    fn format_japanese_test() {
+        assert_eq!(format_bishop_exchange(Notation::Japanese), "２二角成");
+    }
+
+    #[test]
+    fn format_usi_test() {
+        assert_eq!(format_bishop_exchange(Notation::Usi), "8h2b+");
+    }
+
+    #[test]
+    fn format_csa_test() {
+        assert_eq!(format_bishop_exchange(Notation::Csa), "+8822UM");
+    }
+
+    #[test]
+    fn previous_to_test() {
+        assert_eq!(previous_to(POSITION, 9), Some((6, 3)));
+        assert_eq!(previous_to("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -", 9), None);
+    }
+}
@@ -1,9 +1,35 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
 use rand::prelude::*;
+use crate::shogi::record::GameRecord;
+use crate::shogi::record::kif;
+use crate::shogi::record::csa;
+use crate::shogi::state::external_mov;
+use crate::shogi::state::game_state::parse as parse_game_state;
 
+// number of moves from the start of each record added to the mined book
+const MINED_MOVE_COUNT: usize = 16;
+
+const KIF_RECORDS: [&str; 3] = [
+    include_str!("records/yagura.kif"),
+    include_str!("records/shikenbisha.kif"),
+    include_str!("records/aigakari.kif")
+];
+
+const CSA_RECORDS: [&str; 1] = [
+    include_str!("records/nakabisha.csa")
+];
+
+static MINED_BOOK: OnceLock<HashMap<String, Vec<String>>> = OnceLock::new();
+
+// positions are keyed by SFEN without the move count, after replaying any moves
 pub fn recommended_move(game_state: &String) -> Option<&'static str> {
-    let game_state_string = game_state.as_str();
+    let game_state_string = match parse_game_state(game_state) {
+        Ok(gs) => gs.format(),
+        Err(_) => game_state.to_string()
+    };
 
-    match game_state_string {
+    match game_state_string.as_str() {
         "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -" => {
             // starting state
             let mut rng = rand::rng();
@@ -112,7 +138,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
             // b: S-3h/S-38 w: P-6d/P-64 b: R-7f/R-76
             Some("Bx88+")
         },
-        "ln1g1gsnl/1r1s2k2/p1p1pp1pp/3p2p2/1pP6/2R6/PP1PPPPPP/1b+4SK1/LNSG1G1NL b b" => {
+        "ln1g1gsnl/1r1s2k2/p1p1pp1pp/3p2p2/1pP6/2R6/PP1PPPPPP/1+b4SK1/LNSG1G1NL b b" => {
             // b: P-7f/P-76 w: P-3d/P-34 b: P-7e/P-75 w: P-8d/P-84 b: R-7h/R-78  w: P-8e/P-85
             // b: K-4h/K-48 w: S-6b/S-62 b: K-3h/K-38 w: K-4b/K-42 b: K-2h/K-28  w: K-3b/K-32
             // b: S-3h/S-38 w: P-6d/P-64 b: R-7f/R-76 w: Bx8h+/Bx88+
@@ -1083,8 +1109,109 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
             // b: B-7g/B-77 w: P-3d/P-34 b: S-8h/S-88 w: Bx7g+/Bx77+ b: Sx7g+/Sx77+
             Some("S-42")
         },
-        _ => None
+        // handicap games, uwate (w) moves first
+        "lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w -" => {
+            // two piece handicap starting state, nifu tsukkiri joseki
+            Some("S-62")
+        },
+        "ln1gkgsnl/3s5/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -" => {
+            // two piece w: S-6b/S-62
+            Some("P-76")
+        },
+        "ln1gkgsnl/3s5/ppppppppp/9/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL w -" => {
+            // two piece w: S-6b/S-62 b: P-7f/P-76
+            Some("P-54")
+        },
+        "ln1gkgsnl/3s5/pppp1pppp/4p4/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL b -" => {
+            // two piece w: S-6b/S-62 b: P-7f/P-76 w: P-5d/P-54
+            Some("P-26")
+        },
+        "ln1gkgsnl/3s5/pppp1pppp/4p4/9/2P4P1/PP1PPPP1P/1B5R1/LNSGKGSNL w -" => {
+            // two piece w: S-6b/S-62 b: P-7f/P-76 w: P-5d/P-54 b: P-2f/P-26
+            Some("S-53")
+        },
+        "ln1gkgsnl/9/ppppspppp/4p4/9/2P4P1/PP1PPPP1P/1B5R1/LNSGKGSNL b -" => {
+            // two piece w: S-6b/S-62 b: P-7f/P-76 w: P-5d/P-54 b: P-2f/P-26 w: S-5c/S-53
+            Some("P-25")
+        },
+        "lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w -" => {
+            // lance handicap starting state
+            let mut rng = rand::rng();
+            let mut possible_values = ["P-34", "P-84"];
+            possible_values.shuffle(&mut rng);
+            Some(possible_values[0])
+        },
+        "lnsgkgsn1/1r5b1/pppppp1pp/6p2/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -" => {
+            // lance w: P-3d/P-34
+            Some("P-76")
+        },
+        "lnsgkgsnl/1r7/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w -" => {
+            // bishop handicap starting state
+            Some("P-34")
+        },
+        "lnsgkgsnl/1r7/pppppp1pp/6p2/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -" => {
+            // bishop w: P-3d/P-34
+            Some("P-76")
+        },
+        "lnsgkgsnl/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w -" => {
+            // rook handicap starting state
+            Some("P-34")
+        },
+        "lnsgkgsnl/7b1/pppppp1pp/6p2/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -" => {
+            // rook w: P-3d/P-34
+            Some("P-76")
+        },
+        "lnsgkgsnl/7b1/pppppp1pp/6p2/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL w -" => {
+            // rook w: P-3d/P-34 b: P-7f/P-76, closes the kakugyou diagonal
+            Some("P-44")
+        },
+        _ => mined_move(&game_state_string)
+    }
+}
+
+// moves played in each position of the records, keyed by SFEN without the move count
+pub fn mine(records: &[GameRecord], move_count: usize) -> Result<HashMap<String, Vec<String>>, &'static str> {
+    let mut book: HashMap<String, Vec<String>> = HashMap::new();
+
+    for record in records.iter() {
+        let mut game_state = parse_game_state(&record.starting_position().to_string())?;
+
+        for mov in record.moves.iter().take(move_count) {
+            // only name the departure point when another piece of the same kind can reach the destination
+            let disambiguation = game_state.possible_moves().iter().any(|m| {
+                m.from.is_some() && m.from != mov.from && m.to == mov.to && m.moving_piece_kind == mov.moving_piece_kind
+            });
+            let mut external_move = external_mov::build(&game_state, mov.clone());
+            external_move.disambiguation = disambiguation;
+            let external_move = external_move.format();
+            let moves = book.entry(game_state.format()).or_default();
+            if !moves.contains(&external_move) {
+                moves.push(external_move);
+            }
+            game_state.perform_move(mov)?;
+        }
     }
+
+    Ok(book)
+}
+
+fn mined_book() -> &'static HashMap<String, Vec<String>> {
+    MINED_BOOK.get_or_init(|| {
+        let mut records: Vec<GameRecord> = vec![];
+        for encoded in KIF_RECORDS.iter() {
+            records.push(kif::parse(encoded).expect("invalid shogi opening record"));
+        }
+        for encoded in CSA_RECORDS.iter() {
+            records.push(csa::parse(encoded).expect("invalid shogi opening record"));
+        }
+        mine(&records, MINED_MOVE_COUNT).expect("invalid shogi opening record")
+    })
+}
+
+fn mined_move(game_state: &str) -> Option<&'static str> {
+    let moves = mined_book().get(game_state)?;
+    let mut rng = rand::rng();
+    moves.choose(&mut rng).map(|m| m.as_str())
 }
 
 #[cfg(test)]
@@ -1104,4 +1231,52 @@ mod tests {
             None => assert!(false, "expected move"),
         }
     }
+
+    #[test]
+    fn fetch_two_piece_handicap_test() {
+        let game_state = String::from("lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w -");
+        let result = recommended_move(&game_state);
+        assert_eq!(result, Some("S-62"));
+    }
+
+    #[test]
+    fn fetch_two_piece_handicap_with_moves_test() {
+        let game_state = String::from("lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1 moves 7a6b 7g7f");
+        let result = recommended_move(&game_state);
+        assert_eq!(result, Some("P-54"));
+    }
+
+    #[test]
+    fn fetch_handicap_positions_test() {
+        for handicap in ["lance", "bishop", "rook", "two_piece"].iter() {
+            let position = crate::shogi::state::handicap::parse(handicap).unwrap().position();
+            let game_state = parse_game_state(&position.to_string()).unwrap();
+            let result = recommended_move(&game_state.format());
+            assert!(result.is_some());
+        }
+    }
+
+    #[test]
+    fn fetch_mined_test() {
+        // aigakari after P-26 P-84 P-25 P-85 G-78 G-32 P-24 Px24
+        let game_state = String::from("lnsgk1snl/1r4gb1/p1ppppp1p/7p1/1p7/9/PPPPPPP1P/1BG4R1/LNS1KGSNL b p");
+        let result = recommended_move(&game_state);
+        assert_eq!(result, Some("Rx24"));
+    }
+
+    #[test]
+    fn mine_test() {
+        let record = kif::parse("   1 ７六歩(77)\n   2 ３四歩(33)\n   3 ６六歩(67)\n").unwrap();
+        let other_record = kif::parse("   1 ７六歩(77)\n   2 ８四歩(83)\n").unwrap();
+        let result = mine(&[record, other_record], 2).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result["lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -"], vec!["P-76"]);
+        assert_eq!(result["lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL w -"], vec!["P-34", "P-84"]);
+    }
+
+    #[test]
+    fn mined_book_test() {
+        let result = mined_book();
+        assert!(result.len() > 40);
+    }
 }
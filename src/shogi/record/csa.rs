@@ -0,0 +1,407 @@
+use crate::shogi::record::GameRecord;
+use crate::shogi::record::Ending;
+use crate::shogi::record::point_from_numbers;
+use crate::shogi::record::file_number;
+use crate::shogi::record::rank_number;
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::mov::Move;
+use crate::shogi::state::square::demotes_to;
+use crate::shogi::state::square::ou_kind;
+use crate::shogi::state::square::promotes_to;
+use crate::shogi::state::square::PieceKind;
+use crate::shogi::state::square::Square;
+use crate::shogi::state::variant::Variant;
+
+const STARTING_ROWS: [&str; 9] = [
+    "-KY-KE-GI-KI-OU-KI-GI-KE-KY",
+    " * -HI *  *  *  *  * -KA * ",
+    "-FU-FU-FU-FU-FU-FU-FU-FU-FU",
+    " *  *  *  *  *  *  *  *  * ",
+    " *  *  *  *  *  *  *  *  * ",
+    " *  *  *  *  *  *  *  *  * ",
+    "+FU+FU+FU+FU+FU+FU+FU+FU+FU",
+    " * +KA *  *  *  *  * +HI * ",
+    "+KY+KE+GI+KI+OU+KI+GI+KE+KY"
+];
+
+// CSA game record
+//   V2.2
+//   N+Sente
+//   N-Gote
+//   PI
+//   +
+//   +7776FU
+//   T1
+//   -3334FU
+//   T2
+//   %TORYO
+pub fn parse(encoded: &str) -> Result<GameRecord, &'static str> {
+    let mut sente_name = None;
+    let mut gote_name = None;
+    let mut squares = empty_squares();
+    let mut hands: Vec<Vec<PieceKind>> = vec![vec![], vec![], vec![]];
+    let mut replay: Option<(GameRecord, GameState)> = None;
+
+    let statements = encoded.lines()
+        .filter(|line| !line.starts_with('\''))
+        .flat_map(|line| line.split(','))
+        .map(|statement| statement.trim_end());
+
+    for statement in statements {
+        if statement.is_empty() {
+            continue;
+        }
+
+        if let Some((ref mut record, ref mut game_state)) = replay {
+            if statement.starts_with('+') || statement.starts_with('-') {
+                let mov = parse_move(statement, game_state)?;
+                record.push_move(game_state, mov, None)?;
+            } else if let Some(time) = statement.strip_prefix('T') {
+                let time = time.parse().map_err(|_| "csa::parse - Invalid time")?;
+                if let Some(last) = record.times.last_mut() {
+                    *last = Some(time);
+                }
+            } else if let Some(ending) = statement.strip_prefix('%') {
+                record.ending = parse_ending(ending);
+                break;
+            }
+            continue;
+        }
+
+        if let Some(name) = statement.strip_prefix("N+") {
+            sente_name = Some(name.to_string());
+        } else if let Some(name) = statement.strip_prefix("N-") {
+            gote_name = Some(name.to_string());
+        } else if let Some(removed) = statement.strip_prefix("PI") {
+            // starting position, optionally followed by removed pieces e.g. PI82HI22KA
+            squares = parse_rows(&STARTING_ROWS)?;
+            for chunk in removed.as_bytes().chunks(4) {
+                let chunk = std::str::from_utf8(chunk).map_err(|_| "csa::parse - Invalid position")?;
+                let point = parse_point(&chunk[0..2])?;
+                squares[point.1 as usize][point.0 as usize] = Square { player_number: 0, kind: PieceKind::Empty };
+            }
+        } else if statement.starts_with("P+") || statement.starts_with("P-") {
+            let player_number = if statement.starts_with("P+") { 1 } else { 2 };
+            parse_placements(&statement[2..], player_number, &mut squares, &mut hands)?;
+        } else if statement.starts_with('P') && statement.len() > 2 && statement.as_bytes()[1].is_ascii_digit() {
+            let y = (statement.as_bytes()[1] - b'1') as usize;
+            if y > 8 {
+                return Err("csa::parse - Invalid position");
+            }
+            squares[y] = parse_row(&statement[2..])?;
+        } else if statement == "+" || statement == "-" {
+            let current_player_number = if statement == "+" { 1 } else { 2 };
+            let game_state = GameState {
+                current_player_number,
+                squares: squares.clone(),
+                hands: hands.clone(),
+                position_hashes: vec![],
+                check_history: vec![],
+                declared_player_number: None,
+                handicap: None,
+                variant: Variant::Standard
+            };
+            replay = Some(GameRecord::new(&format!("{} 1", game_state.format()))?);
+        }
+    }
+
+    match replay {
+        Some((mut record, _)) => {
+            record.sente_name = sente_name;
+            record.gote_name = gote_name;
+            Ok(record)
+        },
+        None => Err("csa::parse - Missing player to move")
+    }
+}
+
+// +7776FU - player, from, to and the piece after the move, from is 00 for drops
+fn parse_move(encoded: &str, game_state: &mut GameState) -> Result<Move, &'static str> {
+    if encoded.len() != 7 || !encoded.is_ascii() {
+        return Err("csa::parse - Invalid move");
+    }
+
+    let player_number = if encoded.starts_with('+') { 1 } else { 2 };
+    if player_number != game_state.current_player_number {
+        return Err("csa::parse - Move out of turn");
+    }
+
+    let from = if &encoded[1..3] == "00" {
+        None
+    } else {
+        Some(parse_point(&encoded[1..3])?)
+    };
+    let to = parse_point(&encoded[3..5])?;
+    let piece_kind = parse_piece_kind(&encoded[5..7], player_number)?;
+
+    let mov = game_state.possible_moves().into_iter().find(|m| {
+        if m.declare || m.from != from || m.to != to {
+            return false;
+        }
+        if m.promote {
+            demotes_to(piece_kind) == Some(m.moving_piece_kind)
+        } else {
+            m.moving_piece_kind == piece_kind
+        }
+    });
+
+    match mov {
+        Some(m) => Ok(m),
+        None => Err("csa::parse - Illegal move")
+    }
+}
+
+// +7776FU, +2822UM for a promotion, +0055KA for a drop and %KACHI for an entering king declaration
+pub fn format_move(mov: &Move, player_number: i8) -> String {
+    if mov.declare {
+        return String::from("%KACHI");
+    }
+
+    let sign = if player_number == 1 { "+" } else { "-" };
+    let from = match mov.from {
+        Some(f) => format_point(f),
+        None => String::from("00")
+    };
+    let piece_kind = if mov.promote {
+        promotes_to(mov.moving_piece_kind).unwrap_or(mov.moving_piece_kind)
+    } else {
+        mov.moving_piece_kind
+    };
+
+    format!("{}{}{}{}", sign, from, format_point(mov.to), format_piece_kind(piece_kind))
+}
+
+fn parse_ending(encoded: &str) -> Option<Ending> {
+    match encoded {
+        "TORYO" => Some(Ending::Resignation),
+        "TSUMI" => Some(Ending::Checkmate),
+        "SENNICHITE" | "OUTE_SENNICHITE" => Some(Ending::Repetition),
+        "JISHOGI" | "HIKIWAKE" => Some(Ending::Impasse),
+        "KACHI" => Some(Ending::Declaration),
+        "TIME_UP" => Some(Ending::Timeout),
+        "ILLEGAL_MOVE" | "+ILLEGAL_ACTION" | "-ILLEGAL_ACTION" => Some(Ending::IllegalMove),
+        "CHUDAN" => Some(Ending::Abort),
+        _ => None
+    }
+}
+
+// 77 - file then rank
+fn parse_point(encoded: &str) -> Result<(i8, i8), &'static str> {
+    let digits: Vec<u32> = encoded.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() != 2 {
+        return Err("csa::parse - Invalid point");
+    }
+    point_from_numbers(digits[0], digits[1])
+}
+
+fn parse_piece_kind(encoded: &str, player_number: i8) -> Result<PieceKind, &'static str> {
+    match encoded {
+        "FU" => Ok(PieceKind::Fuhyou),
+        "KY" => Ok(PieceKind::Kyousha),
+        "KE" => Ok(PieceKind::Keima),
+        "GI" => Ok(PieceKind::Ginshou),
+        "KI" => Ok(PieceKind::Kinshou),
+        "KA" => Ok(PieceKind::Kakugyou),
+        "HI" => Ok(PieceKind::Hisha),
+        "OU" => Ok(ou_kind(player_number)),
+        "TO" => Ok(PieceKind::Tokin),
+        "NY" => Ok(PieceKind::Narikyou),
+        "NK" => Ok(PieceKind::Narikei),
+        "NG" => Ok(PieceKind::Narigin),
+        "UM" => Ok(PieceKind::Ryuuma),
+        "RY" => Ok(PieceKind::Ryuuou),
+        _ => Err("csa::parse - Invalid piece")
+    }
+}
+
+fn format_point(point: (i8, i8)) -> String {
+    format!("{}{}", file_number(point), rank_number(point))
+}
+
+fn format_piece_kind(piece_kind: PieceKind) -> &'static str {
+    match piece_kind {
+        PieceKind::Fuhyou => "FU",
+        PieceKind::Kyousha => "KY",
+        PieceKind::Keima => "KE",
+        PieceKind::Ginshou => "GI",
+        PieceKind::Kinshou => "KI",
+        PieceKind::Kakugyou => "KA",
+        PieceKind::Hisha => "HI",
+        PieceKind::Oushou | PieceKind::Gyokushou => "OU",
+        PieceKind::Tokin => "TO",
+        PieceKind::Narikyou => "NY",
+        PieceKind::Narikei => "NK",
+        PieceKind::Narigin => "NG",
+        PieceKind::Ryuuma => "UM",
+        PieceKind::Ryuuou => "RY",
+        PieceKind::Empty => ""
+    }
+}
+
+fn empty_squares() -> Vec<Vec<Square>> {
+    (0..9).map(|_| {
+        (0..9).map(|_| Square { player_number: 0, kind: PieceKind::Empty }).collect()
+    }).collect()
+}
+
+fn parse_rows(rows: &[&str]) -> Result<Vec<Vec<Square>>, &'static str> {
+    rows.iter().map(|row| parse_row(row)).collect()
+}
+
+// P1-KY-KE-GI-KI-OU-KI-GI-KE-KY - three characters per square, * for empty
+fn parse_row(encoded: &str) -> Result<Vec<Square>, &'static str> {
+    let encoded = format!("{:<27}", encoded);
+    if encoded.len() != 27 || !encoded.is_ascii() {
+        return Err("csa::parse - Invalid row");
+    }
+
+    (0..9).map(|x| {
+        let cell = &encoded[x * 3..x * 3 + 3];
+        match &cell[0..1] {
+            "+" => Ok(Square { player_number: 1, kind: parse_piece_kind(&cell[1..3], 1)? }),
+            "-" => Ok(Square { player_number: 2, kind: parse_piece_kind(&cell[1..3], 2)? }),
+            _ if cell.trim() == "*" => Ok(Square { player_number: 0, kind: PieceKind::Empty }),
+            _ => Err("csa::parse - Invalid row")
+        }
+    }).collect()
+}
+
+// P+00KA00FU - pieces in hand, P-5152OU - pieces on the board, 00AL - all remaining pieces in hand
+fn parse_placements(encoded: &str, player_number: i8, squares: &mut [Vec<Square>], hands: &mut [Vec<PieceKind>]) -> Result<(), &'static str> {
+    if encoded.len() % 4 != 0 || !encoded.is_ascii() {
+        return Err("csa::parse - Invalid placement");
+    }
+
+    for index in (0..encoded.len()).step_by(4) {
+        let point = &encoded[index..index + 2];
+        let piece = &encoded[index + 2..index + 4];
+
+        if point == "00" && piece == "AL" {
+            let mut game_state = GameState {
+                current_player_number: player_number,
+                squares: squares.to_vec(),
+                hands: hands.to_vec(),
+                position_hashes: vec![],
+                check_history: vec![],
+                declared_player_number: None,
+                handicap: None,
+                variant: Variant::Standard
+            };
+            game_state.give_remaining_pieces(player_number);
+            hands[player_number as usize] = game_state.hands[player_number as usize].clone();
+        } else if point == "00" {
+            hands[player_number as usize].push(parse_piece_kind(piece, player_number)?);
+        } else {
+            let (x, y) = parse_point(point)?;
+            squares[y as usize][x as usize] = Square { player_number, kind: parse_piece_kind(piece, player_number)? };
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::record::STARTING_POSITION;
+    use crate::shogi::state::game_state::parse as parse_game_state;
+
+    #[test]
+    fn parse_test() {
+        let encoded = "V2.2
+N+Sente
+N-Gote
+' comment
+PI
++
++7776FU
+T1
+-3334FU
+T2
++8822UM,T3
+-3122GI
+T65
++0045KA
+T5
+%TORYO
+";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.sente_name, Some(String::from("Sente")));
+        assert_eq!(result.gote_name, Some(String::from("Gote")));
+        assert_eq!(result.positions[0], STARTING_POSITION);
+        assert_eq!(result.moves.len(), 5);
+        assert!(result.moves[2].promote);
+        assert_eq!(result.times, vec![Some(1), Some(2), Some(3), Some(65), Some(5)]);
+        assert_eq!(result.ending, Some(Ending::Resignation));
+        assert_eq!(result.positions[5], "lnsgkg1nl/1r5s1/pppppp1pp/6p2/5B3/2P6/PP1PPPPPP/7R1/LNSGKGSNL w b 6");
+    }
+
+    #[test]
+    fn parse_board_test() {
+        let encoded = "P1 *  *  *  *  *  *  *  * -OU
+P2 *  *  *  *  *  *  *  *  *
+P3 *  *  *  *  *  *  *  *  *
+P4 *  *  *  *  *  *  *  *  *
+P5 *  *  *  *  *  *  *  *  *
+P6 *  *  *  *  *  *  *  *  *
+P7 *  *  *  *  *  *  *  *  *
+P8 *  *  *  *  *  *  *  *  *
+P9+OU *  *  *  *  *  *  *  *
+P+00KI
+P-00AL
++
+";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.positions[0], "8k/9/9/9/9/9/9/9/K8 b G2r2b3g4s4n4l18p 1");
+    }
+
+    #[test]
+    fn parse_handicap_test() {
+        let encoded = "PI11KY\n-\n-3334FU\n";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.positions[0], "lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1");
+        assert_eq!(result.moves.len(), 1);
+    }
+
+    #[test]
+    fn parse_placement_test() {
+        let encoded = "P-51OU\nP+59OU\n+\n+5948OU\n";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.positions[1], "4k4/9/9/9/9/9/9/5K3/9 w - 2");
+    }
+
+    #[test]
+    fn parse_illegal_move_test() {
+        let encoded = "PI\n+\n+7775FU\n";
+        let result = parse(encoded);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_out_of_turn_test() {
+        let encoded = "PI\n+\n-3334FU\n";
+        let result = parse(encoded);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn format_move_test() {
+        let mut game_state = parse_game_state(&String::from(STARTING_POSITION)).unwrap();
+        let mov = parse_move("+7776FU", &mut game_state).unwrap();
+        assert_eq!(format_move(&mov, 1), "+7776FU");
+    }
+
+    #[test]
+    fn format_move_promotion_test() {
+        let mut game_state = parse_game_state(&String::from("lnsgkgsnl/1r5b1/pppppp1pp/6p2/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL b - 1")).unwrap();
+        let mov = parse_move("+8822UM", &mut game_state).unwrap();
+        assert_eq!(format_move(&mov, 1), "+8822UM");
+    }
+
+    #[test]
+    fn format_move_drop_test() {
+        let mut game_state = parse_game_state(&String::from("4k4/9/9/9/9/9/9/9/4K4 w b 1")).unwrap();
+        let mov = parse_move("-0055KA", &mut game_state).unwrap();
+        assert_eq!(format_move(&mov, 2), "-0055KA");
+    }
+}
@@ -0,0 +1,419 @@
+use crate::shogi::record::GameRecord;
+use crate::shogi::record::STARTING_POSITION;
+use crate::shogi::record::candidate_moves;
+use crate::shogi::record::parse_japanese_point;
+use crate::shogi::record::parse_japanese_piece;
+use crate::shogi::record::parse_japanese_ending;
+use crate::shogi::record::format_japanese_point;
+use crate::shogi::record::format_japanese_piece;
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::handicap;
+use crate::shogi::state::mov::Move;
+use crate::shogi::state::square::in_promotion_zone;
+use crate::shogi::state::square::PieceKind;
+use crate::shogi::state::square::PROMOTABLE_PIECE_KINDS;
+
+// 直 is only used for the pieces that move like kinshou and ginshou
+const STRAIGHT_PIECE_KINDS: [PieceKind; 6] = [
+    PieceKind::Kinshou,
+    PieceKind::Ginshou,
+    PieceKind::Tokin,
+    PieceKind::Narikyou,
+    PieceKind::Narikei,
+    PieceKind::Narigin
+];
+
+const MOVE_MARKERS: [char; 4] = ['▲', '△', '☗', '☖'];
+
+// KI2 game record, moves without departure points or times
+//   手合割：平手
+//   先手：Sente
+//   後手：Gote
+//   ▲７六歩    △３四歩    ▲２二角成  △同銀
+//   ▲４五角打
+//   まで5手で後手の勝ち
+pub fn parse(encoded: &str) -> Result<GameRecord, &'static str> {
+    let (mut record, mut game_state) = GameRecord::new(STARTING_POSITION)?;
+
+    for line in encoded.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with('*') || line.starts_with('&') {
+            continue;
+        }
+
+        if line.starts_with("変化") {
+            // only the main line is replayed
+            break;
+        }
+
+        if line.starts_with('|') {
+            return Err("ki2::parse - Unsupported position");
+        }
+
+        if line.starts_with(|c: char| MOVE_MARKERS.contains(&c)) {
+            for encoded_move in line.split(|c: char| MOVE_MARKERS.contains(&c)) {
+                let encoded_move = encoded_move.trim();
+                if encoded_move.is_empty() {
+                    continue;
+                }
+                let previous_to = record.moves.last().map(|m| m.to);
+                let mov = parse_move(encoded_move, &mut game_state, previous_to)?;
+                record.push_move(&mut game_state, mov, None)?;
+            }
+            continue;
+        }
+
+        if line.starts_with("まで") {
+            record.ending = parse_japanese_ending(line);
+            break;
+        }
+
+        if let Some((key, value)) = line.split_once('：') {
+            match key {
+                "先手" | "下手" => record.sente_name = Some(value.trim().to_string()),
+                "後手" | "上手" => record.gote_name = Some(value.trim().to_string()),
+                "手合割" => {
+                    // the header comes before any move
+                    if value.trim() != "平手" {
+                        let position = handicap::parse(value).map_err(|_| "ki2::parse - Unsupported handicap")?.position();
+                        let (mut handicap_record, handicap_game_state) = GameRecord::new(position)?;
+                        handicap_record.sente_name = record.sente_name.take();
+                        handicap_record.gote_name = record.gote_name.take();
+                        record = handicap_record;
+                        game_state = handicap_game_state;
+                    }
+                },
+                _ => ()
+            }
+        }
+    }
+
+    Ok(record)
+}
+
+// ７六歩, 同銀, ５八金右, ２二角成, ４五角打
+fn parse_move(encoded: &str, game_state: &mut GameState, previous_to: Option<(i8, i8)>) -> Result<Move, &'static str> {
+    let (to, rest) = if let Some(rest) = encoded.strip_prefix('同') {
+        match previous_to {
+            Some(point) => (point, rest.trim_start()),
+            None => return Err("ki2::parse - Invalid same point move")
+        }
+    } else {
+        let mut chars = encoded.chars();
+        let file = chars.next().ok_or("ki2::parse - Invalid move")?;
+        let rank = chars.next().ok_or("ki2::parse - Invalid move")?;
+        (parse_japanese_point(file, rank)?, chars.as_str())
+    };
+
+    let (piece_kind, rest) = parse_japanese_piece(rest)?;
+
+    let promote = rest.ends_with('成') && !rest.ends_with("不成");
+    let drop = rest.contains('打');
+
+    let mut candidates = candidate_moves(game_state, to, piece_kind, promote, drop);
+
+    // a drop is only marked when a piece on the board could also move there
+    if candidates.is_empty() && !drop {
+        candidates = candidate_moves(game_state, to, piece_kind, promote, true);
+    }
+
+    let player_number = game_state.current_player_number;
+    let candidates = disambiguate(candidates, rest, player_number);
+
+    match candidates.len() {
+        1 => Ok(candidates[0].clone()),
+        0 => Err("ki2::parse - Illegal move"),
+        _ => Err("ki2::parse - Ambiguous move")
+    }
+}
+
+// ７六歩, 同　銀, ５八金右, ２二角成, ４五角打 - the move by the current player, previous_to is the destination of the move before
+pub fn format_move(game_state: &mut GameState, mov: &Move, previous_to: Option<(i8, i8)>) -> String {
+    let to = if previous_to == Some(mov.to) {
+        String::from("同　")
+    } else {
+        format_japanese_point(mov.to)
+    };
+
+    let piece = format_japanese_piece(mov.moving_piece_kind);
+    let player_number = game_state.current_player_number;
+
+    // pieces of the same kind on the board that could also move there
+    let mut origins: Vec<(i8, i8)> = game_state.possible_moves().into_iter().filter(|m| {
+        !m.declare && m.to == mov.to && m.moving_piece_kind == mov.moving_piece_kind
+    }).filter_map(|m| m.from).collect();
+    origins.sort();
+    origins.dedup();
+
+    match mov.from {
+        Some(from) => {
+            let promotion_possible = PROMOTABLE_PIECE_KINDS.contains(&mov.moving_piece_kind)
+                && (in_promotion_zone(player_number, mov.to.1, game_state.variant) || in_promotion_zone(player_number, from.1, game_state.variant));
+
+            let suffix = if mov.promote {
+                "成"
+            } else if promotion_possible {
+                "不成"
+            } else {
+                ""
+            };
+
+            format!("{}{}{}{}", to, piece, relative_position(mov, &origins, player_number), suffix)
+        },
+        None if origins.is_empty() => format!("{}{}", to, piece),
+        None => format!("{}{}打", to, piece)
+    }
+}
+
+// the modifiers disambiguate reads back: 直 for straight up, then 上/引/寄, then 右/左 with the movement if needed
+fn relative_position(mov: &Move, origins: &[(i8, i8)], player_number: i8) -> String {
+    let from = match mov.from {
+        Some(f) => f,
+        None => return String::from("")
+    };
+
+    if origins.len() < 2 {
+        return String::from("");
+    }
+
+    let forward = |f: (i8, i8)| -> i8 {
+        if player_number == 1 { (f.1 - mov.to.1).signum() } else { (mov.to.1 - f.1).signum() }
+    };
+    let rightness = |f: (i8, i8)| -> i8 {
+        if player_number == 1 { f.0 } else { -f.0 }
+    };
+
+    if STRAIGHT_PIECE_KINDS.contains(&mov.moving_piece_kind) && forward(from) > 0 && from.0 == mov.to.0 {
+        return String::from("直");
+    }
+
+    let movement = match forward(from) {
+        1 => "上",
+        -1 => "引",
+        _ => "寄"
+    };
+
+    let same_movement: Vec<(i8, i8)> = origins.iter().cloned().filter(|f| forward(*f) == forward(from)).collect();
+    if same_movement.len() == 1 {
+        return String::from(movement);
+    }
+
+    let side = |points: &[(i8, i8)]| -> Option<&'static str> {
+        let value = rightness(from);
+        let others: Vec<i8> = points.iter().filter(|f| **f != from).map(|f| rightness(*f)).collect();
+        if others.iter().all(|o| *o < value) {
+            Some("右")
+        } else if others.iter().all(|o| *o > value) {
+            Some("左")
+        } else {
+            None
+        }
+    };
+
+    if let Some(s) = side(origins) {
+        return String::from(s);
+    }
+
+    match side(&same_movement) {
+        Some(s) => format!("{}{}", s, movement),
+        None => String::from(movement)
+    }
+}
+
+// relative position and movement: 上 up, 引 down, 寄 sideways, 直 straight up, 右 right, 左 left
+fn disambiguate(candidates: Vec<Move>, modifiers: &str, player_number: i8) -> Vec<Move> {
+    // rank distance towards the opponent
+    let forward = |m: &Move| -> i8 {
+        match m.from {
+            Some(from) if player_number == 1 => from.1 - m.to.1,
+            Some(from) => m.to.1 - from.1,
+            None => 0
+        }
+    };
+
+    let mut candidates: Vec<Move> = candidates.into_iter().filter(|m| {
+        if modifiers.contains('上') || modifiers.contains('行') || modifiers.contains('入') {
+            forward(m) > 0
+        } else if modifiers.contains('引') {
+            forward(m) < 0
+        } else if modifiers.contains('寄') {
+            forward(m) == 0
+        } else if modifiers.contains('直') {
+            forward(m) > 0 && m.from.map(|f| f.0) == Some(m.to.0)
+        } else {
+            true
+        }
+    }).collect();
+
+    let right = modifiers.contains('右');
+    let left = modifiers.contains('左');
+
+    if candidates.len() > 1 && (right || left) {
+        // files from the player's right, x increases towards sente's right
+        let rightness = |m: &Move| -> i8 {
+            let x = m.from.map(|f| f.0).unwrap_or(0);
+            if player_number == 1 {
+                x
+            } else {
+                -x
+            }
+        };
+
+        let target = if right {
+            candidates.iter().map(&rightness).max()
+        } else {
+            candidates.iter().map(&rightness).min()
+        };
+
+        candidates.retain(|m| Some(rightness(m)) == target);
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::record::Ending;
+    use crate::shogi::record::STARTING_POSITION;
+
+    #[test]
+    fn parse_test() {
+        let encoded = "手合割：平手
+先手：Sente
+後手：Gote
+
+▲７六歩    △３四歩    ▲２二角成  △同　銀
+▲４五角打
+まで5手で後手の勝ち
+";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.sente_name, Some(String::from("Sente")));
+        assert_eq!(result.moves.len(), 5);
+        assert_eq!(result.times, vec![None, None, None, None, None]);
+        assert_eq!(result.ending, Some(Ending::Resignation));
+        assert_eq!(result.positions[5], "lnsgkg1nl/1r5s1/pppppp1pp/6p2/5B3/2P6/PP1PPPPPP/7R1/LNSGKGSNL w b 6");
+    }
+
+    #[test]
+    fn parse_handicap_test() {
+        let encoded = "手合割：二枚落ち
+△６二銀    ▲７六歩    △５四歩
+";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.moves.len(), 3);
+        assert_eq!(result.positions[3], "ln1gkgsnl/3s5/pppp1pppp/4p4/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL b - 4");
+    }
+
+    #[test]
+    fn parse_right_left_test() {
+        // both kinshou can move to 5h
+        let encoded = "▲５八金右";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.moves[0].from, Some((5, 8)));
+
+        let encoded = "▲５八金左";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.moves[0].from, Some((3, 8)));
+    }
+
+    #[test]
+    fn parse_ambiguous_test() {
+        let encoded = "▲５八金";
+        let result = parse(encoded);
+        assert_eq!(result.err(), Some("ki2::parse - Ambiguous move"));
+    }
+
+    #[test]
+    fn parse_drop_without_marker_test() {
+        let encoded = "▲７六歩 △３四歩 ▲２二角成 △同銀 ▲５五角";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.moves[4].from, None);
+        assert_eq!(result.moves[4].moving_piece_kind, PieceKind::Kakugyou);
+    }
+
+    #[test]
+    fn disambiguate_up_test() {
+        // gote kinshou on 4a and 5b, 上 picks the one moving towards sente
+        let mut game_state = crate::shogi::state::game_state::parse(&String::from("4kg3/4g4/9/9/9/9/9/9/4K4 w -")).unwrap();
+        let candidates = candidate_moves(&mut game_state, (4, 1), PieceKind::Kinshou, false, false);
+        assert_eq!(candidates.len(), 0);
+
+        let candidates = candidate_moves(&mut game_state, (5, 1), PieceKind::Kinshou, false, false);
+        assert_eq!(candidates.len(), 2);
+        let result = disambiguate(candidates, "上", 2);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].from, Some((5, 0)));
+    }
+
+    fn format_from(position: &str, from: Option<(i8, i8)>, to: (i8, i8), previous_to: Option<(i8, i8)>) -> String {
+        let mut game_state = crate::shogi::state::game_state::parse(&String::from(position)).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.from == from && m.to == to && !m.promote).unwrap();
+        format_move(&mut game_state, &mov, previous_to)
+    }
+
+    #[test]
+    fn format_move_test() {
+        let result = format_from(STARTING_POSITION, Some((2, 6)), (2, 5), None);
+        assert_eq!(result, "７六歩");
+    }
+
+    #[test]
+    fn format_move_same_point_test() {
+        let result = format_from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f 3c3d 8h2b+", Some((6, 0)), (7, 1), Some((7, 1)));
+        assert_eq!(result, "同　銀");
+    }
+
+    #[test]
+    fn format_move_right_left_test() {
+        assert_eq!(format_from(STARTING_POSITION, Some((5, 8)), (4, 7), None), "５八金右");
+        assert_eq!(format_from(STARTING_POSITION, Some((3, 8)), (4, 7), None), "５八金左");
+    }
+
+    #[test]
+    fn format_move_straight_test() {
+        let position = "4k4/9/9/9/9/9/9/9/3GG3K b -";
+        assert_eq!(format_from(position, Some((4, 8)), (4, 7), None), "５八金直");
+        assert_eq!(format_from(position, Some((3, 8)), (4, 7), None), "５八金左");
+    }
+
+    #[test]
+    fn format_move_movement_test() {
+        let position = "4k4/9/9/9/9/9/4G4/3G5/8K b -";
+        assert_eq!(format_from(position, Some((4, 6)), (4, 7), None), "５八金引");
+        assert_eq!(format_from(position, Some((3, 7)), (4, 7), None), "５八金寄");
+    }
+
+    #[test]
+    fn format_move_drop_test() {
+        assert_eq!(format_from("4k4/9/9/9/9/9/9/3G5/8K b G", None, (4, 7), None), "５八金打");
+        assert_eq!(format_from("4k4/9/9/9/9/9/9/9/8K b B", None, (4, 4), None), "５五角");
+    }
+
+    #[test]
+    fn format_move_promotion_test() {
+        let mut game_state = crate::shogi::state::game_state::parse(&String::from("4k4/9/9/4S4/9/9/9/9/8K b -")).unwrap();
+        let moves: Vec<String> = game_state.possible_moves().into_iter().filter(|m| m.to == (4, 2)).collect::<Vec<Move>>().iter().map(|m| format_move(&mut game_state, m, None)).collect();
+        assert_eq!(moves, vec![String::from("５三銀成"), String::from("５三銀不成")]);
+    }
+
+    #[test]
+    fn format_move_round_trip_test() {
+        let positions = [
+            STARTING_POSITION,
+            "4k4/9/9/9/9/9/4G4/3G1G3/4K4 b G",
+            "ln1g1g1nl/1r1s1k1b1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1B1S1G1R1/LNKG3NL w Ss",
+            "4k4/9/2+P1+P4/3+P5/9/9/9/9/4K4 b -"
+        ];
+
+        for position in positions.iter() {
+            let mut game_state = crate::shogi::state::game_state::parse(&String::from(*position)).unwrap();
+            for mov in game_state.possible_moves() {
+                let encoded = format_move(&mut game_state, &mov, None);
+                let result = parse_move(&encoded, &mut game_state, None).unwrap();
+                assert_eq!((result.from, result.to, result.promote), (mov.from, mov.to, mov.promote), "{}", encoded);
+            }
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use crate::shogi::record::GameRecord;
+use crate::shogi::record::Ending;
+use crate::shogi::record::STARTING_POSITION;
+use crate::shogi::record::candidate_moves;
+use crate::shogi::record::parse_japanese_point;
+use crate::shogi::record::parse_japanese_piece;
+use crate::shogi::record::parse_japanese_ending;
+use crate::shogi::record::format_japanese_point;
+use crate::shogi::record::format_japanese_piece;
+use crate::shogi::record::format_japanese_ending;
+use crate::shogi::record::point_from_numbers;
+use crate::shogi::record::file_number;
+use crate::shogi::record::rank_number;
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::handicap;
+use crate::shogi::state::mov::Move;
+use crate::shogi::state::square::in_promotion_zone;
+use crate::shogi::state::square::PROMOTABLE_PIECE_KINDS;
+use crate::shogi::state::variant::Variant;
+
+// width of the move column, full width characters count as two
+const MOVE_COLUMN_WIDTH: usize = 14;
+
+enum MoveLine {
+    Move(Move, Option<u32>),
+    Ending(Ending)
+}
+
+// KIF game record with move times
+//   手合割：平手
+//   先手：Sente
+//   後手：Gote
+//   手数----指手---------消費時間--
+//      1 ７六歩(77)   ( 0:01/00:00:01)
+//      2 ３四歩(33)   ( 0:02/00:00:02)
+//      3 ２二角成(88) ( 0:03/00:00:04)
+//      4 同　銀(31)   ( 0:01/00:00:03)
+//      5 ４五角打     ( 0:05/00:00:09)
+//      6 投了
+pub fn parse(encoded: &str) -> Result<GameRecord, &'static str> {
+    let mut sente_name = None;
+    let mut gote_name = None;
+    let mut starting_position = STARTING_POSITION;
+    let mut replay: Option<(GameRecord, GameState)> = None;
+
+    for line in encoded.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with('*') || line.starts_with('&') {
+            continue;
+        }
+
+        if line.starts_with("変化") {
+            // only the main line is replayed
+            break;
+        }
+
+        if line.starts_with('|') {
+            return Err("kif::parse - Unsupported position");
+        }
+
+        if line.starts_with(|c: char| c.is_ascii_digit()) {
+            let (record, game_state) = match replay {
+                Some(ref mut r) => (&mut r.0, &mut r.1),
+                None => {
+                    replay = Some(GameRecord::new(starting_position)?);
+                    let r = replay.as_mut().unwrap();
+                    (&mut r.0, &mut r.1)
+                }
+            };
+
+            let previous_to = record.moves.last().map(|m| m.to);
+            match parse_move_line(line, game_state, previous_to)? {
+                MoveLine::Move(mov, time) => record.push_move(game_state, mov, time)?,
+                MoveLine::Ending(ending) => {
+                    record.ending = Some(ending);
+                    break;
+                }
+            }
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('：') {
+            match key {
+                "先手" | "下手" => sente_name = Some(value.trim().to_string()),
+                "後手" | "上手" => gote_name = Some(value.trim().to_string()),
+                "手合割" => {
+                    starting_position = match value.trim() {
+                        "平手" => STARTING_POSITION,
+                        name => handicap::parse(name).map_err(|_| "kif::parse - Unsupported handicap")?.position()
+                    };
+                },
+                _ => ()
+            }
+        }
+    }
+
+    let (mut record, _) = match replay {
+        Some(r) => r,
+        None => GameRecord::new(starting_position)?
+    };
+
+    record.sente_name = sente_name;
+    record.gote_name = gote_name;
+
+    Ok(record)
+}
+
+fn parse_move_line(line: &str, game_state: &mut GameState, previous_to: Option<(i8, i8)>) -> Result<MoveLine, &'static str> {
+    let rest = line.trim_start_matches(|c: char| c.is_ascii_digit()).trim_start();
+
+    if let Some(ending) = parse_japanese_ending(rest) {
+        return Ok(MoveLine::Ending(ending));
+    }
+
+    let (to, rest) = if let Some(rest) = rest.strip_prefix('同') {
+        match previous_to {
+            Some(point) => (point, rest.trim_start()),
+            None => return Err("kif::parse - Invalid same point move")
+        }
+    } else {
+        let mut chars = rest.chars();
+        let file = chars.next().ok_or("kif::parse - Invalid move")?;
+        let rank = chars.next().ok_or("kif::parse - Invalid move")?;
+        (parse_japanese_point(file, rank)?, chars.as_str())
+    };
+
+    let (piece_kind, rest) = parse_japanese_piece(rest)?;
+
+    let (promote, drop, rest) = if let Some(rest) = rest.strip_prefix("不成") {
+        (false, false, rest)
+    } else if let Some(rest) = rest.strip_prefix('成') {
+        (true, false, rest)
+    } else if let Some(rest) = rest.strip_prefix('打') {
+        (false, true, rest)
+    } else {
+        (false, false, rest)
+    };
+
+    let (from, rest) = match rest.strip_prefix('(') {
+        Some(coordinates) => {
+            let digits: Vec<u32> = coordinates.chars().take(2).filter_map(|c| c.to_digit(10)).collect();
+            if digits.len() != 2 {
+                return Err("kif::parse - Invalid from point");
+            }
+            let rest = coordinates.split_once(')').map(|(_, r)| r).unwrap_or("");
+            (Some(point_from_numbers(digits[0], digits[1])?), rest)
+        },
+        None => (None, rest)
+    };
+
+    let candidates = candidate_moves(game_state, to, piece_kind, promote, drop || from.is_none());
+    let mov = candidates.into_iter().find(|m| from.is_none() || m.from == from);
+
+    match mov {
+        Some(m) => Ok(MoveLine::Move(m, parse_time(rest))),
+        None => Err("kif::parse - Illegal move")
+    }
+}
+
+// ( 0:01/00:00:01) - time spent on the move followed by the total time
+fn parse_time(encoded: &str) -> Option<u32> {
+    let start = encoded.find('(')?;
+    let move_time = encoded[start + 1..].split(['/', ')']).next()?;
+    let mut parts = move_time.trim().split(':');
+    let minutes: u32 = parts.next()?.trim().parse().ok()?;
+    let seconds: u32 = parts.next()?.trim().parse().ok()?;
+    Some(minutes * 60 + seconds)
+}
+
+// KIF for a game played from the starting or a handicap position, e.g. engine against human
+pub fn format(record: &GameRecord) -> Result<String, &'static str> {
+    // handicap games name the players shitate and uwate instead of sente and gote
+    let (handicap_name, sente_label, gote_label) = if record.starting_position() == STARTING_POSITION {
+        ("平手", "先手", "後手")
+    } else {
+        match handicap::from_position(record.starting_position()) {
+            Some(h) => (h.japanese_name(), "下手", "上手"),
+            None => return Err("kif::format - Unsupported starting position")
+        }
+    };
+
+    let (_, mut game_state) = GameRecord::new(record.starting_position())?;
+    let mut total_times: Vec<u32> = vec![0, 0, 0];
+    let mut previous_to = None;
+
+    let mut lines = vec![
+        String::from("#KIF version=2.0 encoding=UTF-8"),
+        format!("手合割：{}", handicap_name),
+        format!("{}：{}", sente_label, record.sente_name.clone().unwrap_or_default()),
+        format!("{}：{}", gote_label, record.gote_name.clone().unwrap_or_default()),
+        String::from("手数----指手---------消費時間--")
+    ];
+
+    for (index, mov) in record.moves.iter().enumerate() {
+        let move_number = index + 1;
+
+        if mov.declare {
+            lines.push(format!("{:>4} {}", move_number, format_japanese_ending(Ending::Declaration)));
+            return Ok(format!("{}\n", lines.join("\n")));
+        }
+
+        let player_number = game_state.current_player_number;
+        let move_text = format_move(mov, player_number, previous_to);
+
+        let line = match record.times.get(index).cloned().flatten() {
+            Some(time) => {
+                total_times[player_number as usize] += time;
+                let total = total_times[player_number as usize];
+                let padding = MOVE_COLUMN_WIDTH.saturating_sub(display_width(&move_text));
+                format!(
+                    "{:>4} {}{}({:>2}:{:02}/{:02}:{:02}:{:02})",
+                    move_number, move_text, " ".repeat(padding),
+                    time / 60, time % 60,
+                    total / 3600, (total / 60) % 60, total % 60
+                )
+            },
+            None => format!("{:>4} {}", move_number, move_text)
+        };
+        lines.push(line);
+
+        game_state.perform_move(mov)?;
+        previous_to = Some(mov.to);
+    }
+
+    if let Some(ending) = record.ending {
+        lines.push(format!("{:>4} {}", record.moves.len() + 1, format_japanese_ending(ending)));
+    }
+
+    Ok(format!("{}\n", lines.join("\n")))
+}
+
+fn format_move(mov: &Move, player_number: i8, previous_to: Option<(i8, i8)>) -> String {
+    let to = if previous_to == Some(mov.to) {
+        String::from("同　")
+    } else {
+        format_japanese_point(mov.to)
+    };
+
+    let piece = format_japanese_piece(mov.moving_piece_kind);
+
+    match mov.from {
+        Some(from) => {
+            let promotion_possible = PROMOTABLE_PIECE_KINDS.contains(&mov.moving_piece_kind)
+                && (in_promotion_zone(player_number, mov.to.1, Variant::Standard) || in_promotion_zone(player_number, from.1, Variant::Standard));
+
+            let suffix = if mov.promote {
+                "成"
+            } else if promotion_possible {
+                "不成"
+            } else {
+                ""
+            };
+
+            format!("{}{}{}({}{})", to, piece, suffix, file_number(from), rank_number(from))
+        },
+        None => format!("{}{}打", to, piece)
+    }
+}
+
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::state::square::PieceKind;
+
+    const RECORD: &str = "#KIF version=2.0 encoding=UTF-8
+開始日時：2024/01/01 10:00:00
+手合割：平手
+先手：Sente
+後手：Gote
+手数----指手---------消費時間--
+*comment
+   1 ７六歩(77)   ( 0:01/00:00:01)
+   2 ３四歩(33)   ( 0:02/00:00:02)
+   3 ２二角成(88) ( 0:03/00:00:04)
+   4 同　銀(31)   ( 1:05/00:01:07)
+   5 ４五角打     ( 0:05/00:00:09)
+   6 投了
+";
+
+    #[test]
+    fn parse_test() {
+        let result = parse(RECORD).unwrap();
+        assert_eq!(result.sente_name, Some(String::from("Sente")));
+        assert_eq!(result.gote_name, Some(String::from("Gote")));
+        assert_eq!(result.moves.len(), 5);
+        assert_eq!(result.positions.len(), 6);
+        assert_eq!(result.times, vec![Some(1), Some(2), Some(3), Some(65), Some(5)]);
+        assert_eq!(result.ending, Some(Ending::Resignation));
+        assert_eq!(result.positions[5], "lnsgkg1nl/1r5s1/pppppp1pp/6p2/5B3/2P6/PP1PPPPPP/7R1/LNSGKGSNL w b 6");
+    }
+
+    #[test]
+    fn parse_promotion_and_same_point_test() {
+        let result = parse(RECORD).unwrap();
+        assert!(result.moves[2].promote);
+        assert_eq!(result.moves[2].moving_piece_kind, PieceKind::Kakugyou);
+        assert_eq!(result.moves[3].to, (7, 1));
+        assert_eq!(result.moves[3].from, Some((6, 0)));
+        assert_eq!(result.moves[4].from, None);
+    }
+
+    #[test]
+    fn parse_variation_test() {
+        let encoded = format!("{}\n変化：5手\n   5 ６五角打\n", RECORD.replace("   6 投了\n", ""));
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.moves.len(), 5);
+        assert_eq!(result.moves[4].to, (5, 4));
+    }
+
+    #[test]
+    fn parse_illegal_move_test() {
+        let encoded = "手合割：平手\n   1 ７五歩(77)\n";
+        let result = parse(encoded);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_handicap_test() {
+        let encoded = "手合割：香落ち\n下手：Shitate\n上手：Uwate\n   1 ３四歩(33)\n";
+        let result = parse(encoded).unwrap();
+        assert_eq!(result.sente_name, Some(String::from("Shitate")));
+        assert_eq!(result.gote_name, Some(String::from("Uwate")));
+        assert_eq!(result.positions[0], "lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1");
+        assert_eq!(result.positions[1], "lnsgkgsn1/1r5b1/pppppp1pp/6p2/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 2");
+    }
+
+    #[test]
+    fn parse_unknown_handicap_test() {
+        let encoded = "手合割：その他\n   1 ３四歩(33)\n";
+        let result = parse(encoded);
+        assert_eq!(result.err(), Some("kif::parse - Unsupported handicap"));
+    }
+
+    #[test]
+    fn format_handicap_test() {
+        let encoded = "手合割：二枚落ち\n   1 ６二銀(71)\n   2 ７六歩(77)\n";
+        let record = parse(encoded).unwrap();
+        let result = format(&record).unwrap();
+        assert!(result.contains("手合割：二枚落ち\n下手：\n上手：\n"));
+        assert!(result.ends_with("   1 ６二銀(71)\n   2 ７六歩(77)\n"));
+        assert_eq!(parse(&result).unwrap().positions, record.positions);
+    }
+
+    #[test]
+    fn parse_time_test() {
+        assert_eq!(parse_time("   ( 1:05/00:01:07)"), Some(65));
+        assert_eq!(parse_time(""), None);
+    }
+
+    #[test]
+    fn format_test() {
+        let record = parse(RECORD).unwrap();
+        let result = format(&record).unwrap();
+        let expected = "#KIF version=2.0 encoding=UTF-8
+手合割：平手
+先手：Sente
+後手：Gote
+手数----指手---------消費時間--
+   1 ７六歩(77)    ( 0:01/00:00:01)
+   2 ３四歩(33)    ( 0:02/00:00:02)
+   3 ２二角成(88)  ( 0:03/00:00:04)
+   4 同　銀(31)    ( 1:05/00:01:07)
+   5 ４五角打      ( 0:05/00:00:09)
+   6 投了
+";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn format_round_trip_test() {
+        let record = parse(RECORD).unwrap();
+        let result = parse(&format(&record).unwrap()).unwrap();
+        assert_eq!(result.positions, record.positions);
+        assert_eq!(result.times, record.times);
+    }
+
+    #[test]
+    fn format_without_times_test() {
+        let (mut record, mut game_state) = GameRecord::new(STARTING_POSITION).unwrap();
+        let mov = candidate_moves(&mut game_state, (2, 5), PieceKind::Fuhyou, false, false).pop().unwrap();
+        record.push_move(&mut game_state, mov, None).unwrap();
+        let result = format(&record).unwrap();
+        assert!(result.ends_with("手数----指手---------消費時間--\n   1 ７六歩(77)\n"));
+    }
+}
@@ -0,0 +1,225 @@
+pub mod kif;
+pub mod ki2;
+pub mod csa;
+
+use crate::shogi::state::game_state::parse as parse_game_state;
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::mov::Move;
+use crate::shogi::state::square::ou_kind;
+use crate::shogi::state::square::PieceKind;
+
+pub const STARTING_POSITION: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ending {
+    Resignation,
+    Checkmate,
+    Repetition,
+    Impasse,
+    Declaration,
+    Timeout,
+    IllegalMove,
+    Abort
+}
+
+// a game replayed through GameState
+#[derive(Clone)]
+pub struct GameRecord {
+    pub sente_name: Option<String>,
+    pub gote_name: Option<String>,
+    pub moves: Vec<Move>,
+    pub times: Vec<Option<u32>>, // seconds spent on each move
+    pub positions: Vec<String>, // SFEN of the starting position followed by the position after each move
+    pub ending: Option<Ending>
+}
+
+impl GameRecord {
+    pub fn new(starting_position: &str) -> Result<(GameRecord, GameState), &'static str> {
+        let game_state = parse_game_state(&starting_position.to_string())?;
+        let record = GameRecord {
+            sente_name: None,
+            gote_name: None,
+            moves: vec![],
+            times: vec![],
+            positions: vec![format!("{} 1", game_state.format())],
+            ending: None
+        };
+        Ok((record, game_state))
+    }
+
+    // performs the move on the game state and records it with the resulting position
+    pub fn push_move(&mut self, game_state: &mut GameState, mov: Move, time: Option<u32>) -> Result<(), &'static str> {
+        game_state.perform_move(&mov)?;
+        self.moves.push(mov);
+        self.times.push(time);
+        self.positions.push(format!("{} {}", game_state.format(), self.moves.len() + 1));
+        Ok(())
+    }
+
+    pub fn starting_position(&self) -> &str {
+        &self.positions[0]
+    }
+}
+
+// legal moves by the current player of a piece kind to a point, drops included when drop is true
+fn candidate_moves(game_state: &mut GameState, to: (i8, i8), piece_kind: PieceKind, promote: bool, drop: bool) -> Vec<Move> {
+    let piece_kind = match piece_kind {
+        PieceKind::Oushou | PieceKind::Gyokushou => ou_kind(game_state.current_player_number),
+        _ => piece_kind
+    };
+
+    game_state.possible_moves().into_iter().filter(|m| {
+        !m.declare && m.to == to && m.moving_piece_kind == piece_kind && m.promote == promote && m.from.is_none() == drop
+    }).collect()
+}
+
+// files are numbered 9 to 1 from left to right and ranks 1 to 9 from top to bottom
+fn point_from_numbers(file: u32, rank: u32) -> Result<(i8, i8), &'static str> {
+    if (1..=9).contains(&file) && (1..=9).contains(&rank) {
+        Ok((9 - file as i8, rank as i8 - 1))
+    } else {
+        Err("record::point_from_numbers - Invalid point")
+    }
+}
+
+fn file_number(point: (i8, i8)) -> i8 {
+    9 - point.0
+}
+
+fn rank_number(point: (i8, i8)) -> i8 {
+    point.1 + 1
+}
+
+const JAPANESE_FILES: [char; 9] = [
+    '１', '２', '３', '４', '５', '６', '７', '８', '９'
+];
+
+const JAPANESE_RANKS: [char; 9] = [
+    '一', '二', '三', '四', '五', '六', '七', '八', '九'
+];
+
+// longer names first so that promoted pieces are matched before the promotion suffix
+const JAPANESE_PIECES: [(&str, PieceKind); 19] = [
+    ("成香", PieceKind::Narikyou),
+    ("成桂", PieceKind::Narikei),
+    ("成銀", PieceKind::Narigin),
+    ("歩", PieceKind::Fuhyou),
+    ("香", PieceKind::Kyousha),
+    ("桂", PieceKind::Keima),
+    ("銀", PieceKind::Ginshou),
+    ("金", PieceKind::Kinshou),
+    ("角", PieceKind::Kakugyou),
+    ("飛", PieceKind::Hisha),
+    ("玉", PieceKind::Oushou),
+    ("王", PieceKind::Oushou),
+    ("と", PieceKind::Tokin),
+    ("杏", PieceKind::Narikyou),
+    ("圭", PieceKind::Narikei),
+    ("全", PieceKind::Narigin),
+    ("馬", PieceKind::Ryuuma),
+    ("龍", PieceKind::Ryuuou),
+    ("竜", PieceKind::Ryuuou)
+];
+
+// 7六 or ７六 or 76
+fn parse_japanese_point(file: char, rank: char) -> Result<(i8, i8), &'static str> {
+    let file_number = match JAPANESE_FILES.iter().position(|c| *c == file) {
+        Some(index) => index as u32 + 1,
+        None => file.to_digit(10).ok_or("record::parse_japanese_point - Invalid file")?
+    };
+
+    let rank_number = match JAPANESE_RANKS.iter().position(|c| *c == rank) {
+        Some(index) => index as u32 + 1,
+        None => rank.to_digit(10).ok_or("record::parse_japanese_point - Invalid rank")?
+    };
+
+    point_from_numbers(file_number, rank_number)
+}
+
+fn format_japanese_point(point: (i8, i8)) -> String {
+    format!("{}{}", JAPANESE_FILES[(file_number(point) - 1) as usize], JAPANESE_RANKS[point.1 as usize])
+}
+
+// the piece kind and the rest of the text, the ou is returned as Oushou for both players
+fn parse_japanese_piece(encoded: &str) -> Result<(PieceKind, &str), &'static str> {
+    for (name, kind) in JAPANESE_PIECES.iter() {
+        if let Some(rest) = encoded.strip_prefix(name) {
+            return Ok((*kind, rest));
+        }
+    }
+    Err("record::parse_japanese_piece - Invalid piece")
+}
+
+fn format_japanese_piece(kind: PieceKind) -> &'static str {
+    match kind {
+        PieceKind::Oushou | PieceKind::Gyokushou => "玉",
+        _ => match JAPANESE_PIECES.iter().find(|(_, k)| *k == kind) {
+            Some((name, _)) => name,
+            None => ""
+        }
+    }
+}
+
+fn parse_japanese_ending(encoded: &str) -> Option<Ending> {
+    if encoded.contains("中断") {
+        Some(Ending::Abort)
+    } else if encoded.contains("千日手") {
+        Some(Ending::Repetition)
+    } else if encoded.contains("持将棋") {
+        Some(Ending::Impasse)
+    } else if encoded.contains("入玉") {
+        Some(Ending::Declaration)
+    } else if encoded.contains("詰") {
+        Some(Ending::Checkmate)
+    } else if encoded.contains("切れ") {
+        Some(Ending::Timeout)
+    } else if encoded.contains("反則") {
+        Some(Ending::IllegalMove)
+    } else if encoded.contains("投了") || encoded.contains("勝ち") {
+        Some(Ending::Resignation)
+    } else {
+        None
+    }
+}
+
+fn format_japanese_ending(ending: Ending) -> &'static str {
+    match ending {
+        Ending::Resignation => "投了",
+        Ending::Checkmate => "詰み",
+        Ending::Repetition => "千日手",
+        Ending::Impasse => "持将棋",
+        Ending::Declaration => "入玉勝ち",
+        Ending::Timeout => "切れ負け",
+        Ending::IllegalMove => "反則負け",
+        Ending::Abort => "中断"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_test() {
+        let (record, game_state) = GameRecord::new(STARTING_POSITION).unwrap();
+        assert_eq!(record.positions, vec![STARTING_POSITION]);
+        assert_eq!(game_state.current_player_number, 1);
+    }
+
+    #[test]
+    fn push_move_test() {
+        let (mut record, mut game_state) = GameRecord::new(STARTING_POSITION).unwrap();
+        let mov = candidate_moves(&mut game_state, (2, 5), PieceKind::Fuhyou, false, false).pop().unwrap();
+        record.push_move(&mut game_state, mov, Some(3)).unwrap();
+        assert_eq!(record.moves.len(), 1);
+        assert_eq!(record.times, vec![Some(3)]);
+        assert_eq!(record.positions[1], "lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL w - 2");
+    }
+
+    #[test]
+    fn point_from_numbers_test() {
+        assert_eq!(point_from_numbers(7, 7), Ok((2, 6)));
+        assert_eq!(point_from_numbers(1, 1), Ok((8, 0)));
+        assert!(point_from_numbers(0, 1).is_err());
+    }
+}
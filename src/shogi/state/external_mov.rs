@@ -1,9 +1,9 @@
 use std::convert::TryFrom;
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::mov::Move;
 use crate::shogi::state::square::PieceKind;
-
-const X_FORMAT: [char; 9] = [
-    '9', '8', '7', '6', '5', '4', '3', '2', '1'
-];
+use crate::shogi::state::square::PROMOTABLE_PIECE_KINDS;
+use crate::shogi::state::square::in_promotion_zone;
 
 const Y_FORMAT: [char; 9] = [
     '1', '2', '3', '4', '5', '6', '7', '8', '9'
@@ -16,7 +16,9 @@ pub struct ExternalMove {
     pub capture_piece_kind: Option<PieceKind>, // Undo -> place piece back
     pub promote: bool, // Undo -> revert promotion
     pub promotion_possible: bool,
-    pub disambiguation: bool
+    pub disambiguation: bool,
+    pub declare: bool,
+    pub board_size: i8 // files are numbered from the board size down to 1
 }
 
 impl ExternalMove {
@@ -30,6 +32,9 @@ impl ExternalMove {
     //   promotion declined =
     //   promotion accepted +
     pub fn format(&self) -> String {
+        if self.declare {
+            return String::from("Declare");
+        }
         String::from(format!("{}{}{}{}{}", self.piece_format(), self.from_format(), self.capture_format(), self.to_format(), self.promotion_suffix()))
     }
 
@@ -68,9 +73,8 @@ impl ExternalMove {
     fn from_format(&self) -> String {
         if let Some(f) = self.from {
             if self.disambiguation {
-                let x = usize::try_from(f.0).unwrap_or(0);
                 let y = usize::try_from(f.1).unwrap_or(0);
-                String::from(format!("{}{}", X_FORMAT[x], Y_FORMAT[y]))
+                String::from(format!("{}{}", self.board_size - f.0, Y_FORMAT[y]))
             } else {
                 String::from("")
             }
@@ -80,9 +84,8 @@ impl ExternalMove {
     }
 
     fn to_format(&self) -> String {
-        let x = usize::try_from(self.to.0).unwrap_or(0);
         let y = usize::try_from(self.to.1).unwrap_or(0);
-        String::from(format!("{}{}", X_FORMAT[x], Y_FORMAT[y]))
+        String::from(format!("{}{}", self.board_size - self.to.0, Y_FORMAT[y]))
     }
 
     fn promotion_suffix(&self) -> String {
@@ -107,11 +110,71 @@ impl Clone for ExternalMove {
             capture_piece_kind: self.capture_piece_kind,
             promote: self.promote,
             promotion_possible: self.promotion_possible,
-            disambiguation: self.disambiguation
+            disambiguation: self.disambiguation,
+            declare: self.declare,
+            board_size: self.board_size
         }
     }
 }
 
+pub fn build(game_state: &GameState, mov: Move) -> ExternalMove {
+    let mut new_state = game_state.clone();
+    let _result = new_state.perform_move(&mov);
+
+    let file_disambiguation = match mov.from {
+        Some(f) => {
+            let mut file_disambiguation_count: i8 = 0;
+            for (y, row) in game_state.squares.iter().enumerate() {
+                for s in row.iter() {
+                    let s_player_number = s.player_number;
+                    let s_kind = s.kind;
+                    if s_kind == mov.moving_piece_kind && y as i8 == f.1 && s_player_number == game_state.current_player_number {
+                        file_disambiguation_count += 1;
+                    }
+                }
+            }
+            file_disambiguation_count > 1
+        },
+        None => false
+    };
+
+    let rank_disambiguation = match mov.from {
+        Some(f) => {
+            let mut count: i8 = 0;
+            for row in game_state.squares.iter() {
+                for (x, s) in row.iter().enumerate() {
+                    let s_player_number = s.player_number;
+                    let s_kind = s.kind;
+                    if s_kind == mov.moving_piece_kind && x as i8 == f.0 && s_player_number == game_state.current_player_number {
+                        count += 1;
+                    }
+                }
+            }
+            count > 1
+        },
+        None => false
+    };
+
+    let disambiguation = file_disambiguation || rank_disambiguation;
+
+    let promotion_possible = PROMOTABLE_PIECE_KINDS.iter().any(|pk| *pk == mov.moving_piece_kind)
+        && in_promotion_zone(game_state.current_player_number, mov.to.1, game_state.variant);
+
+    let external_mov = ExternalMove {
+        from: mov.from,
+        to: mov.to,
+        moving_piece_kind: mov.moving_piece_kind,
+        capture_piece_kind: mov.capture_piece_kind,
+        promote: mov.promote,
+        promotion_possible,
+        disambiguation,
+        declare: mov.declare,
+        board_size: game_state.variant.size()
+    };
+
+    external_mov
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,7 +191,9 @@ mod tests {
             capture_piece_kind: None,
             promote: false,
             promotion_possible: false,
-            disambiguation: false
+            disambiguation: false,
+            declare: false,
+            board_size: 9
         };
 
         assert_eq!("B-54", mov.format());
@@ -146,7 +211,9 @@ mod tests {
             capture_piece_kind: None,
             promote: false,
             promotion_possible: false,
-            disambiguation: false
+            disambiguation: false,
+            declare: false,
+            board_size: 9
         };
 
         assert_eq!("P-55", mov.format());
@@ -164,7 +231,9 @@ mod tests {
             capture_piece_kind: None,
             promote: false,
             promotion_possible: false,
-            disambiguation: false
+            disambiguation: false,
+            declare: false,
+            board_size: 9
         };
 
         assert_eq!("P*55", mov.format());
@@ -183,7 +252,9 @@ mod tests {
             capture_piece_kind,
             promote: false,
             promotion_possible: false,
-            disambiguation: false
+            disambiguation: false,
+            declare: false,
+            board_size: 9
         };
 
         assert_eq!("Bx54", mov.format());
@@ -201,7 +272,9 @@ mod tests {
             capture_piece_kind: None,
             promote: false,
             promotion_possible: false,
-            disambiguation: true
+            disambiguation: true,
+            declare: false,
+            board_size: 9
         };
 
         assert_eq!("+B25-58", mov.format());
@@ -219,7 +292,9 @@ mod tests {
             capture_piece_kind: None,
             promote: true,
             promotion_possible: true,
-            disambiguation: false
+            disambiguation: false,
+            declare: false,
+            board_size: 9
         };
 
         assert_eq!("P-51+", mov.format());
@@ -237,10 +312,28 @@ mod tests {
             capture_piece_kind: None,
             promote: false,
             promotion_possible: true,
-            disambiguation: false
+            disambiguation: false,
+            declare: false,
+            board_size: 9
         };
 
         assert_eq!("S-51=", mov.format());
     }
-}
 
+    #[test]
+    fn format_declare_test() {
+        let mov = ExternalMove {
+            from: None,
+            to: (4, 0),
+            moving_piece_kind: PieceKind::Oushou,
+            capture_piece_kind: None,
+            promote: false,
+            promotion_possible: false,
+            disambiguation: false,
+            declare: true,
+            board_size: 9
+        };
+
+        assert_eq!("Declare", mov.format());
+    }
+}
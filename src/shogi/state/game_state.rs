@@ -1,17 +1,23 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use crate::shogi::state::point::valid;
 use crate::shogi::state::piece_factory::parse as parse_piece;
+use crate::shogi::state::piece_factory::format as format_piece;
 use crate::shogi::state::square::can_promote_on_y;
 use crate::shogi::state::square::must_promote_on_y;
 use crate::shogi::state::square::promotes_to;
 use crate::shogi::state::square::demotes_to;
 use crate::shogi::state::square::destinations;
 use crate::shogi::state::square::opposing_player;
+use crate::shogi::state::square::ou_kind;
+use crate::shogi::state::square::in_promotion_zone;
+use crate::shogi::state::square::impasse_points;
 use crate::shogi::state::square::PieceKind;
 use crate::shogi::state::square::Square;
 use crate::shogi::state::point::diff;
 use crate::shogi::state::point::between;
 use crate::shogi::state::point::one_step_destination_points;
-use crate::shogi::state::point::forwards_direction;
 use crate::shogi::state::square_set::find_by_x_and_y_mut;
 use crate::shogi::state::square_set::find_by_x_and_y;
 use crate::shogi::state::square_set::find_ou_point_for_player;
@@ -20,12 +26,41 @@ use crate::shogi::state::square_set::any_threats_to_point;
 use crate::shogi::state::square_set::any_threats_to_point_through_pin;
 use crate::shogi::state::square_set::pinned_to_point;
 use crate::shogi::state::mov::Move;
+use crate::shogi::state::usi_mov::parse as parse_usi_move;
+use crate::shogi::state::variant::Variant;
+use crate::shogi::state::handicap;
+use crate::shogi::state::handicap::Handicap;
+
+// sennichite: the game ends when the same position occurs for the fourth time
+const REPETITION_COUNT: usize = 4;
+
+// jishogi: 24 point impasse rule and the CSA 27 point declaration rule
+const IMPASSE_POINTS: i8 = 24;
+const DECLARATION_PIECES_IN_CAMP: usize = 10;
+const DECLARATION_POINTS_SENTE: i8 = 28;
+const DECLARATION_POINTS_GOTE: i8 = 27;
+
+// order of pieces in hand in SFEN
+const HAND_FORMAT_ORDER: [PieceKind; 7] = [
+    PieceKind::Hisha,
+    PieceKind::Kakugyou,
+    PieceKind::Kinshou,
+    PieceKind::Ginshou,
+    PieceKind::Keima,
+    PieceKind::Kyousha,
+    PieceKind::Fuhyou
+];
 
 #[derive(Clone)]
 pub struct GameState {
     pub current_player_number: i8,
     pub squares: Vec<Vec<Square>>,
-    pub hands: Vec<Vec<PieceKind>>
+    pub hands: Vec<Vec<PieceKind>>,
+    pub position_hashes: Vec<u64>, // every position of the game so far, including the current one
+    pub check_history: Vec<bool>, // whether the player to move was in check in each position
+    pub declared_player_number: Option<i8>, // player who declared, ending the game under the entering king rules
+    pub handicap: Option<Handicap>, // handicap the game started from
+    pub variant: Variant
 }
 
 impl GameState {
@@ -37,7 +72,11 @@ impl GameState {
     }
 
     pub fn winner(&mut self) -> Option<i8> {
-        if self.in_checkmate(1) {
+        if self.declared_player_number.is_some() {
+            self.declaration_winner()
+        } else if let Some(player_number) = self.perpetual_check_player() {
+            Some(opposing_player(player_number))
+        } else if self.in_checkmate(1) {
             Some(2)
         } else if self.in_checkmate(2) {
             Some(1)
@@ -46,6 +85,248 @@ impl GameState {
         }
     }
 
+    // sennichite without perpetual check is a draw, as is a declared impasse where both players have 24 points
+    pub fn draw(&self) -> bool {
+        if self.declared_player_number.is_some() {
+            self.declaration_winner().is_none()
+        } else if self.repetition_start().is_some() {
+            self.perpetual_check_player().is_none()
+        } else {
+            false
+        }
+    }
+
+    pub fn game_over(&self) -> bool {
+        self.declared_player_number.is_some() || self.repetition_start().is_some()
+    }
+
+    // the declaring player wins under the 27 point rule, otherwise the 24 point rule decides the impasse
+    pub fn declaration_winner(&self) -> Option<i8> {
+        let player_number = self.declared_player_number?;
+        if self.can_declare(player_number) {
+            Some(player_number)
+        } else {
+            self.impasse_winner()
+        }
+    }
+
+    // a declaration move ends the game under the 27 point rule or, once both ou have entered, the 24 point rule
+    pub fn declaration_allowed(&self, player_number: i8) -> bool {
+        self.can_declare(player_number) || self.impasse()
+    }
+
+    // both ou have entered the enemy camp
+    pub fn impasse(&self) -> bool {
+        if !self.variant.entering_king() {
+            return false;
+        }
+
+        [1, 2].iter().all(|player_number| {
+            match find_ou_point_for_player(&self.squares, *player_number) {
+                Some(ou_point) => in_promotion_zone(*player_number, ou_point.1, self.variant),
+                None => false
+            }
+        })
+    }
+
+    // 24 point rule: in an impasse a player with fewer than 24 points loses
+    pub fn impasse_winner(&self) -> Option<i8> {
+        if !self.impasse() {
+            return None;
+        }
+
+        let player_one_enough = self.impasse_points(1) >= IMPASSE_POINTS;
+        let player_two_enough = self.impasse_points(2) >= IMPASSE_POINTS;
+
+        match (player_one_enough, player_two_enough) {
+            (true, false) => Some(1),
+            (false, true) => Some(2),
+            _ => None
+        }
+    }
+
+    // points of the player's pieces on the board and in hand
+    pub fn impasse_points(&self, player_number: i8) -> i8 {
+        let board_points: i8 = self.squares.iter().flatten().filter(|s| s.player_number == player_number).map(|s| impasse_points(s.kind)).sum();
+        board_points + self.hand_impasse_points(player_number)
+    }
+
+    fn hand_impasse_points(&self, player_number: i8) -> i8 {
+        self.hands[player_number as usize].iter().map(|pk| impasse_points(*pk)).sum()
+    }
+
+    // 27 point declaration rule: the ou and at least 10 other pieces are in the enemy camp, the ou isn't in check,
+    // and the pieces in the enemy camp and in hand are worth 28 points for sente or 27 points for gote
+    pub fn can_declare(&self, player_number: i8) -> bool {
+        if !self.variant.entering_king() {
+            return false;
+        }
+
+        let ou_point = match find_ou_point_for_player(&self.squares, player_number) {
+            Some(p) => p,
+            None => return false
+        };
+
+        if !in_promotion_zone(player_number, ou_point.1, self.variant) || self.in_check(player_number, ou_point) {
+            return false;
+        }
+
+        let mut pieces_in_camp = 0;
+        let mut points = self.hand_impasse_points(player_number);
+
+        for (y, row) in self.squares.iter().enumerate() {
+            for s in row.iter() {
+                if s.player_number == player_number && s.kind != ou_kind(player_number) && in_promotion_zone(player_number, y as i8, self.variant) {
+                    pieces_in_camp += 1;
+                    points += impasse_points(s.kind);
+                }
+            }
+        }
+
+        let required_points = if player_number == 1 {
+            DECLARATION_POINTS_SENTE
+        } else {
+            DECLARATION_POINTS_GOTE
+        };
+
+        pieces_in_camp >= DECLARATION_PIECES_IN_CAMP && points >= required_points
+    }
+
+    // index of the first occurrence of the current position once it has repeated enough to end the game
+    pub fn repetition_start(&self) -> Option<usize> {
+        let current_hash = match self.position_hashes.last() {
+            Some(h) => *h,
+            None => return None
+        };
+
+        let occurrences: Vec<usize> = self.position_hashes.iter().enumerate().filter(|(_, h)| **h == current_hash).map(|(i, _)| i).collect();
+
+        if occurrences.len() >= REPETITION_COUNT {
+            occurrences.first().copied()
+        } else {
+            None
+        }
+    }
+
+    // the player who checked in every position of the repetition, they lose the game
+    pub fn perpetual_check_player(&self) -> Option<i8> {
+        let start = self.repetition_start()?;
+        let last = self.position_hashes.len() - 1;
+
+        [1, 2].iter().copied().find(|player_number| {
+            // positions in the cycle where the opponent of the player is to move
+            let checked_player_number = opposing_player(*player_number);
+            let mut positions = (start..=last).filter(|i| self.player_to_move_at(*i) == checked_player_number).peekable();
+            positions.peek().is_some() && positions.all(|i| self.check_history[i])
+        })
+    }
+
+    fn player_to_move_at(&self, index: usize) -> i8 {
+        let moves_since = self.position_hashes.len() - 1 - index;
+        if moves_since % 2 == 0 {
+            self.current_player_number
+        } else {
+            opposing_player(self.current_player_number)
+        }
+    }
+
+    // hash of the board, hands and player to move
+    pub fn position_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.squares.hash(&mut hasher);
+        for hand in self.hands.iter() {
+            let mut sorted_hand = hand.clone();
+            sorted_hand.sort();
+            sorted_hand.hash(&mut hasher);
+        }
+        self.current_player_number.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // SFEN without the move count, e.g. lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -
+    pub fn format(&self) -> String {
+        let rows: Vec<String> = self.squares.iter().map(|row| {
+            let mut encoded = String::new();
+            let mut empty_count = 0;
+            for square in row.iter() {
+                if square.kind == PieceKind::Empty {
+                    empty_count += 1;
+                } else {
+                    if empty_count > 0 {
+                        encoded.push_str(&empty_count.to_string());
+                        empty_count = 0;
+                    }
+                    encoded.push_str(&format_piece(square));
+                }
+            }
+            if empty_count > 0 {
+                encoded.push_str(&empty_count.to_string());
+            }
+            encoded
+        }).collect();
+
+        let player = if self.current_player_number == 1 {
+            "b"
+        } else {
+            "w"
+        };
+
+        let mut hand = String::new();
+        for player_number in 1..=2 {
+            for kind in HAND_FORMAT_ORDER.iter() {
+                let count = self.hands[player_number as usize].iter().filter(|k| *k == kind).count();
+                if count > 1 {
+                    hand.push_str(&count.to_string());
+                }
+                if count > 0 {
+                    hand.push_str(&format_piece(&Square { player_number, kind: *kind }));
+                }
+            }
+        }
+        if hand.is_empty() {
+            hand.push('-');
+        }
+
+        format!("{} {} {}", rows.join("/"), player, hand)
+    }
+
+    // puts the pieces that are neither on the board nor in a hand into the player's hand
+    pub fn give_remaining_pieces(&mut self, player_number: i8) {
+        for (kind, count) in self.variant.piece_set().iter() {
+            if *kind == PieceKind::Oushou {
+                continue;
+            }
+
+            let on_board = self.squares.iter().flatten().filter(|s| {
+                s.kind != PieceKind::Empty && demotes_to(s.kind).unwrap_or(s.kind) == *kind
+            }).count();
+            let in_hands = self.hands.iter().flatten().filter(|k| *k == kind).count();
+
+            for _ in (on_board + in_hands)..*count {
+                self.hands[player_number as usize].push(*kind);
+            }
+        }
+
+        let hash = self.position_hash();
+        if let Some(last) = self.position_hashes.last_mut() {
+            *last = hash;
+        }
+    }
+
+    pub fn current_player_in_check(&self) -> bool {
+        match find_ou_point_for_player(&self.squares, self.current_player_number) {
+            Some(ou_point) => self.in_check(self.current_player_number, ou_point),
+            None => false
+        }
+    }
+
+    fn record_position(&mut self) {
+        let hash = self.position_hash();
+        let in_check = self.current_player_in_check();
+        self.position_hashes.push(hash);
+        self.check_history.push(in_check);
+    }
+
     pub fn in_checkmate(&self, player_number: i8) -> bool {
         if let Some(ou_point) = find_ou_point_for_player(&self.squares, player_number) {
             self.in_check(player_number, ou_point) && self.ou_cannot_move(player_number, ou_point) && !self.threats_to_ou_can_be_captured_or_blocked(player_number, ou_point)
@@ -103,8 +384,8 @@ impl GameState {
                         let threats_to_between = threats_to_point(&self.squares, *b, opposing_player_number);
                         let has_threats = !diff(&threats_to_between, &pinned_to_ou).is_empty();
                         let can_drop = piece_kinds_in_hand.iter().any(|p| {
-                            // TODO: consider fuhyou drop rules, i.e. in checkmate, 1 fuhyou in file
-                            !must_promote_on_y(*p, player_number, b.1)
+                            // a blocking fuhyou can't give check so only nifu applies
+                            !must_promote_on_y(*p, player_number, b.1, self.variant) && (*p != PieceKind::Fuhyou || !self.fuhyou_in_file(player_number, b.0))
                         });
                         has_threats || can_drop
                     })
@@ -117,9 +398,12 @@ impl GameState {
         }
     }
 
-    pub fn fuhyou_attacks_ou(&self, from: (i8, i8), ou_point: (i8, i8)) -> bool {
-        let fuhyou_destination = (from.0, from.1 + forwards_direction(self.current_player_number));
-        fuhyou_destination == ou_point
+    // nifu: an unpromoted fuhyou of the player is already in the file
+    pub fn fuhyou_in_file(&self, player_number: i8, x: i8) -> bool {
+        self.squares.iter().any(|row| {
+            let square = row[x as usize];
+            square.kind == PieceKind::Fuhyou && square.player_number == player_number
+        })
     }
 
     pub fn possible_moves(&mut self) -> Vec<Move> {
@@ -127,13 +411,48 @@ impl GameState {
     }
 
     pub fn possible_moves_for_player(&mut self, subject_player_number: i8) -> Vec<Move> {
+        if self.game_over() {
+            return vec![];
+        }
+
+        let mut moves = self.board_moves_for_player(subject_player_number);
+        moves.append(&mut self.drop_moves_for_player(subject_player_number));
+
+        if self.declaration_allowed(subject_player_number) {
+            if let Some(ou_point) = find_ou_point_for_player(&self.squares, subject_player_number) {
+                let mov = Move {
+                    from: None,
+                    to: ou_point,
+                    moving_piece_kind: ou_kind(subject_player_number),
+                    capture_piece_kind: None,
+                    promote: false,
+                    declare: true
+                };
+                moves.push(mov);
+            }
+        }
+
+        // keep moves that don't result in check for the current player.
+        // uchifuzume: a fuhyou drop may not give checkmate.
+        // The moves are tried as the subject player so captures go to their hand and back.
+        let current_player_number = self.current_player_number;
+        self.current_player_number = subject_player_number;
+        moves.retain(|m| {
+            self.legal_move(m, subject_player_number) && !self.fuhyou_drop_checkmate(m, subject_player_number)
+        });
+        self.current_player_number = current_player_number;
+
+        moves
+    }
+
+    fn board_moves_for_player(&self, subject_player_number: i8) -> Vec<Move> {
         let mut moves = vec![];
 
         for (y, row) in self.squares.iter().enumerate() {
             for (x, from) in row.iter().enumerate() {
                 if from.player_number == subject_player_number {
                     let from_point = (x as i8, y as i8);
-                    for to_point in destinations(from.kind, from.player_number, from_point, &self, false) {
+                    for to_point in destinations(from.kind, from.player_number, from_point, self, false) {
 
                         if let Some(to) = find_by_x_and_y(&self.squares, to_point) {
                             let capture_piece_kind = if to.player_number != 0 && subject_player_number != to.player_number {
@@ -142,8 +461,8 @@ impl GameState {
                                 None
                             };
 
-                            let promote = can_promote_on_y(from.kind, from.player_number, to_point.1);
-                            let compulsory_promote = must_promote_on_y(from.kind, from.player_number, to_point.1);
+                            let promote = can_promote_on_y(from.kind, from.player_number, to_point.1, self.variant);
+                            let compulsory_promote = must_promote_on_y(from.kind, from.player_number, to_point.1, self.variant);
 
                             // if promote possible add a move that promotes
                             if promote {
@@ -152,7 +471,8 @@ impl GameState {
                                     to: to_point,
                                     moving_piece_kind: from.kind,
                                     capture_piece_kind,
-                                    promote
+                                    promote,
+                                    declare: false
                                 };
                                 moves.push(mov);
                             }
@@ -164,7 +484,8 @@ impl GameState {
                                     to: to_point,
                                     moving_piece_kind: from.kind,
                                     capture_piece_kind,
-                                    promote: false
+                                    promote: false,
+                                    declare: false
                                 };
                                 moves.push(mov);
                             }
@@ -174,92 +495,104 @@ impl GameState {
             }
         }
 
+        moves
+    }
+
+    fn drop_moves_for_player(&self, subject_player_number: i8) -> Vec<Move> {
         // drops
         // all piece types in players hands
         // all unoccupied squares
-        // exclude squares with compulsory promotion
-        // if pawn, excludes squares that put opponents king in checkmate
-        // if pawn, excludes squares that place two fuhyou of the same player in the same file
-        let opposing_player_number = opposing_player(self.current_player_number);
+        // exclude squares where the piece would have no moves (compulsory promotion)
+        // if pawn, excludes files that already have an unpromoted fuhyou of the same player
+        // pawn drops that give checkmate are removed with the legality check
+        let mut moves = vec![];
 
         // get unique piece kinds in hand
         let piece_kinds_in_hand = self.unique_piece_kinds_in_hand(subject_player_number);
 
-        for piece_kind in piece_kinds_in_hand.iter() {
-            if *piece_kind == PieceKind::Fuhyou {
-                // get a list of all files that don't have a fuhyou.
-                let mut files_without_fuhyou = vec![];
-                for x in 0..=8 {
-                    let mut fuhyou_exists_in_file = false;
-                    for y in 0..=8 {
-                        let file_square = self.squares[y][x];
-                        if file_square.kind == PieceKind::Fuhyou && file_square.player_number == self.current_player_number {
-                            fuhyou_exists_in_file = true;
-                            break;
-                        }
-                    }
-                    if !fuhyou_exists_in_file {
-                        files_without_fuhyou.push(x);
-                    }
-                }
+        let size = self.variant.size();
 
-                if let Some(opposing_ou_point) = find_ou_point_for_player(&self.squares, opposing_player_number) {
-                    for y in 0..=8 {
-                        if !must_promote_on_y(*piece_kind, subject_player_number, y as i8) {
-                            for x in files_without_fuhyou.iter() {
-                                let square = self.squares[y][*x];
-                                // exclude if drop puts opponent in checkmate
-                                if square.unoccupied() && !(self.ou_cannot_move(opposing_player_number, opposing_ou_point) && self.fuhyou_attacks_ou((*x as i8, y as i8), opposing_ou_point)) {
-                                    let mov = Move {
-                                        from: None,
-                                        to: (*x as i8, y as i8),
-                                        moving_piece_kind: *piece_kind,
-                                        capture_piece_kind: None,
-                                        promote: false
-                                    };
-                                    moves.push(mov);
-                                }
-                            }
-                        }
-                    }
-                }
-            } else {
-                // not fuhyou
-                for y in 0..=8 {
-                    if !must_promote_on_y(*piece_kind, subject_player_number, y as i8) {
-                        for x in 0..=8 {
-                            let square = self.squares[y][x];
-                            if square.unoccupied() {
-                                let mov = Move {
-                                    from: None,
-                                    to: (x as i8, y as i8),
-                                    moving_piece_kind: *piece_kind,
-                                    capture_piece_kind: None,
-                                    promote: false
-                                };
-                                moves.push(mov);
-                            }
-                        }
+        for piece_kind in piece_kinds_in_hand.iter() {
+            let files: Vec<i8> = (0..size).filter(|x| {
+                *piece_kind != PieceKind::Fuhyou || !self.fuhyou_in_file(subject_player_number, *x)
+            }).collect();
+
+            for y in 0..size {
+                for x in files.iter().copied() {
+                    if !must_promote_on_y(*piece_kind, subject_player_number, y, self.variant) && self.squares[y as usize][x as usize].unoccupied() {
+                        let mov = Move {
+                            from: None,
+                            to: (x, y),
+                            moving_piece_kind: *piece_kind,
+                            capture_piece_kind: None,
+                            promote: false,
+                            declare: false
+                        };
+                        moves.push(mov);
                     }
                 }
             }
         }
 
-        // keep moves that don't result in check for the current player.
-        moves.retain(|m| {
-            let perform_result = self.perform_move(&m);
-            let in_check = match find_ou_point_for_player(&self.squares, subject_player_number) {
-                Some(ou_point) => self.in_check(subject_player_number, ou_point),
-                None => false
-            };
-            let undo_result = self.undo_move(&m);
-            perform_result.is_ok() && undo_result.is_ok() && !in_check
-        });
-
         moves
     }
 
+    // the move doesn't leave the player's ou in check
+    fn legal_move(&mut self, mov: &Move, player_number: i8) -> bool {
+        let perform_result = self.perform_board_move(mov);
+        let in_check = match find_ou_point_for_player(&self.squares, player_number) {
+            Some(ou_point) => self.in_check(player_number, ou_point),
+            None => false
+        };
+        let undo_result = self.undo_board_move(mov);
+        perform_result.is_ok() && undo_result.is_ok() && !in_check
+    }
+
+    // uchifuzume: the move drops a fuhyou that checks the opponent's ou and leaves no escape.
+    // A fuhyou check can't be blocked, so only moves of pieces on the board can escape it.
+    fn fuhyou_drop_checkmate(&mut self, mov: &Move, player_number: i8) -> bool {
+        if mov.from.is_some() || mov.moving_piece_kind != PieceKind::Fuhyou {
+            return false;
+        }
+
+        let opposing_player_number = opposing_player(player_number);
+        if self.perform_board_move(mov).is_err() {
+            return false;
+        }
+
+        let checkmate = match find_ou_point_for_player(&self.squares, opposing_player_number) {
+            Some(ou_point) => {
+                self.in_check(opposing_player_number, ou_point) &&
+                    !self.board_moves_for_player(opposing_player_number).iter().any(|m| self.legal_move(m, opposing_player_number))
+            },
+            None => false
+        };
+
+        let undo_result = self.undo_board_move(mov);
+        checkmate && undo_result.is_ok()
+    }
+
     pub fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        self.perform_board_move(mov)?;
+        self.record_position();
+        Ok(())
+    }
+
+    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        self.undo_board_move(mov)?;
+        self.position_hashes.pop();
+        self.check_history.pop();
+        Ok(())
+    }
+
+    // moves the pieces without recording the position, used when testing the legality of moves
+    fn perform_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        if mov.declare {
+            self.declared_player_number = Some(self.current_player_number);
+            self.current_player_number = opposing_player(self.current_player_number);
+            return Ok(());
+        }
+
         let piece_player_number: i8;
         let piece_kind: PieceKind;
 
@@ -290,10 +623,10 @@ impl GameState {
         }
 
         if let Some(s) = find_by_x_and_y_mut(&mut self.squares, mov.to) {
-            // add captured piece to hand
+            // add captured piece to hand, promoted pieces return to their unpromoted side
             if s.kind != PieceKind::Empty {
                 let hand = &mut self.hands[self.current_player_number as usize];
-                hand.push(s.kind);
+                hand.push(demotes_to(s.kind).unwrap_or(s.kind));
             }
             s.player_number = piece_player_number;
             s.kind = piece_kind;
@@ -319,7 +652,13 @@ impl GameState {
         Ok(())
     }
 
-    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+    fn undo_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        if mov.declare {
+            self.declared_player_number = None;
+            self.current_player_number = opposing_player(self.current_player_number);
+            return Ok(());
+        }
+
         let moving_piece_player_number: i8;
         let moving_piece_kind: PieceKind;
 
@@ -353,8 +692,9 @@ impl GameState {
                 s.player_number = opposing_player(moving_piece_player_number);
 
                 // remove piece from hand
+                let hand_piece_kind = demotes_to(capture_piece_kind).unwrap_or(capture_piece_kind);
                 let hand = &mut self.hands[moving_piece_player_number as usize];
-                if let Some(idx) = hand.iter().position(|pk| *pk == capture_piece_kind) {
+                if let Some(idx) = hand.iter().position(|pk| *pk == hand_piece_kind) {
                     hand.remove(idx);
                 } else {
                     return Err("game_state::undo_move - Invalid drop");
@@ -387,9 +727,37 @@ impl GameState {
     }
 }
 
-// state player hand move count
+// state player hand move count, optionally followed by the moves played since in USI notation
 // lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb
+// lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f 3c3d
 pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
+    parse_with_variant(encoded, Variant::Standard)
+}
+
+// the board size of the variant, e.g. rbsgk/4p/5/P4/KGSBR b - for minishogi
+pub fn parse_with_variant(encoded: &String, variant: Variant) -> Result<GameState, &'static str> {
+    let mut parts = encoded.splitn(2, " moves ");
+
+    let mut game_state = match parts.next() {
+        Some(position) => {
+            let mut game_state = parse_position(position, variant)?;
+            game_state.handicap = handicap::from_position(position);
+            game_state
+        },
+        None => return Err("Error parsing state")
+    };
+
+    if let Some(moves) = parts.next() {
+        for encoded_move in moves.split_whitespace() {
+            let mov = parse_usi_move(encoded_move, &mut game_state)?;
+            game_state.perform_move(&mov)?;
+        }
+    }
+
+    Ok(game_state)
+}
+
+fn parse_position(encoded: &str, variant: Variant) -> Result<GameState, &'static str> {
     let mut read_board = true;
     let mut read_player_number = false;
     let mut read_hand = false;
@@ -400,108 +768,10 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
     let mut x: i8 = 0;
     let mut promoted_piece = false;
     let mut hand_piece_count = 1;
+    let mut hand_piece_count_read = false;
 
-    let mut squares: Vec<Vec<Square>> = vec![
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-        vec![
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty },
-            Square { player_number: 0, kind: PieceKind::Empty }
-        ],
-    ];
+    let size = variant.size() as usize;
+    let mut squares: Vec<Vec<Square>> = vec![vec![Square { player_number: 0, kind: PieceKind::Empty }; size]; size];
 
     let mut current_player_number = 1;
     let mut hands: Vec<Vec<PieceKind>> = vec![
@@ -515,7 +785,7 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
             'p' | 'P' | 'l' | 'L' | 'n' | 'N' | 's' | 'S' | 'g' | 'G' | 'B' | 'r' | 'R' | 'k' | 'K' => {
                 if read_board {
                     if let Ok(square) = parse_piece(c, promoted_piece) {
-                        if valid((x, y)) {
+                        if valid((x, y), variant.size()) {
                             squares[y as usize][x as usize] = square;
                         } else {
                             parse_error = true;
@@ -533,6 +803,7 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                             counter += 1;
                         }
                         hand_piece_count = 1;
+                        hand_piece_count_read = false;
                    } else {
                         parse_error = true;
                     }
@@ -551,7 +822,7 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                         let mut empty_counter = 0;
                         while empty_counter < number_of_spaces {
                             let square = Square { player_number: 0, kind: PieceKind::Empty };
-                            if valid((x, y)) {
+                            if valid((x, y), variant.size()) {
                                 squares[y as usize][x as usize] = square;
                             } else {
                                 parse_error = true;
@@ -564,7 +835,12 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                     }
                 } else if read_hand {
                     if let Some(num) = c.to_digit(10) {
-                        hand_piece_count = num;
+                        hand_piece_count = if hand_piece_count_read {
+                            hand_piece_count * 10 + num
+                        } else {
+                            num
+                        };
+                        hand_piece_count_read = true;
                     } else {
                         parse_error = true;
                     }
@@ -605,7 +881,7 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
             'b' => {
                 if read_board {
                     if let Ok(square) = parse_piece(c, promoted_piece) {
-                        if valid((x, y)) {
+                        if valid((x, y), variant.size()) {
                             squares[y as usize][x as usize] = square;
                         } else {
                             parse_error = true;
@@ -617,7 +893,13 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                     promoted_piece = false;
                 } else if read_hand {
                     if let Ok(p) = parse_piece(c, false) {
-                        hands[p.player_number as usize].push(p.kind);
+                        let mut counter = 0;
+                        while counter < hand_piece_count {
+                            hands[p.player_number as usize].push(p.kind);
+                            counter += 1;
+                        }
+                        hand_piece_count = 1;
+                        hand_piece_count_read = false;
                     } else {
                         parse_error = true;
                     }
@@ -632,7 +914,11 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
                     parse_error = true;
                 }
             },
-            '0' => (), // ignore for now
+            '0' => {
+                if read_hand && hand_piece_count_read {
+                    hand_piece_count *= 10;
+                }
+            },
             _ => parse_error = true
         }
     }
@@ -640,11 +926,18 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
     if parse_error {
         Err("Error parsing state")
     } else {
-        Ok(GameState {
+        let mut game_state = GameState {
             current_player_number,
             squares,
-            hands
-        })
+            hands,
+            position_hashes: vec![],
+            check_history: vec![],
+            declared_player_number: None,
+            handicap: None,
+            variant
+        };
+        game_state.record_position();
+        Ok(game_state)
     }
 }
 
@@ -676,11 +969,49 @@ mod tests {
         let expected: Vec<Vec<PieceKind>> = vec![
            vec![],
            vec![PieceKind::Hisha, PieceKind::Kinshou, PieceKind::Fuhyou, PieceKind::Fuhyou, PieceKind::Fuhyou, PieceKind::Fuhyou],
-           vec![PieceKind::Kakugyou, PieceKind::Ginshou, PieceKind::Ginshou, PieceKind::Fuhyou, PieceKind::Fuhyou, PieceKind::Fuhyou]
+           vec![PieceKind::Kakugyou, PieceKind::Kakugyou, PieceKind::Ginshou, PieceKind::Ginshou, PieceKind::Fuhyou, PieceKind::Fuhyou, PieceKind::Fuhyou]
         ];
         assert_eq!(result.hands, expected);
     }
 
+    #[test]
+    fn parse_hand_gote_bishop_count_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 b 2bs");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.hands[2], vec![PieceKind::Kakugyou, PieceKind::Kakugyou, PieceKind::Ginshou]);
+    }
+
+    #[test]
+    fn parse_hand_multiple_digit_count_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 b 10P");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.hands[1].len(), 10);
+    }
+
+    #[test]
+    fn format_test() {
+        let encoded = String::from("lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
+        let game_state = parse(&encoded).unwrap();
+        let result = game_state.format();
+        assert_eq!(result, encoded);
+    }
+
+    #[test]
+    fn format_promoted_and_hand_order_test() {
+        let encoded = String::from("ln1gkg1nl/1r7/pppppp3/9/9/9/PPPPP4/9/LNS1KGS+NL w RG4P2b2s3p");
+        let game_state = parse(&encoded).unwrap();
+        let result = game_state.format();
+        assert_eq!(result, encoded);
+    }
+
+    #[test]
+    fn format_empty_hand_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f");
+        let game_state = parse(&encoded).unwrap();
+        let result = game_state.format();
+        assert_eq!(result, "lnsgkgsnl/1r5b1/ppppppppp/9/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL w -");
+    }
+
     #[test]
     fn winner_test() {
         let encoded = String::from("k8/PG6/G8/9/9/9/9/9/8K b -");
@@ -853,6 +1184,17 @@ mod tests {
         assert_eq!(result[73].promote, false);
     }
 
+    #[test]
+    fn possible_moves_for_player_opponent_keeps_hands_test() {
+        let encoded = String::from("4k4/9/9/9/4g4/4S4/9/9/4K4 b R");
+        let mut game_state = parse(&encoded).unwrap();
+        game_state.possible_moves_for_player(2);
+
+        assert_eq!(game_state.hands[1], vec![PieceKind::Hisha]);
+        assert!(game_state.hands[2].is_empty());
+        assert_eq!(game_state.current_player_number, 1);
+    }
+
     #[test]
     fn possible_moves_for_player_compulsory_promotion_test() {
         let encoded = String::from("k8/9/9/9/9/9/9/9/8K b Pp");
@@ -890,6 +1232,125 @@ mod tests {
         assert_eq!(mov.is_none(), true);
     }
 
+    #[test]
+    fn possible_moves_for_player_fuhyou_drop_not_checkmate_test() {
+        // ou can capture the dropped fuhyou
+        let encoded = String::from("k8/9/9/9/9/9/9/9/8K b P");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(1);
+
+        let mov = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.to == (0, 1));
+
+        assert!(mov.is_some());
+    }
+
+    #[test]
+    fn possible_moves_for_player_fuhyou_drop_captured_by_other_piece_test() {
+        // ou has no escape but the kinshou can capture the dropped fuhyou
+        let encoded = String::from("kg7/2G6/G8/9/9/9/9/9/8K b P");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(1);
+
+        let mov = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.to == (0, 1));
+
+        assert!(mov.is_some());
+    }
+
+    #[test]
+    fn possible_moves_for_player_fuhyou_move_checkmate_test() {
+        // uchifuzume only applies to drops, moving a fuhyou to give checkmate is allowed
+        let encoded = String::from("k8/9/PG7/G8/9/9/9/9/8K b -");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(1);
+
+        let mov = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.from == Some((0, 2)) && s.to == (0, 1));
+
+        assert!(mov.is_some());
+    }
+
+    #[test]
+    fn possible_moves_for_player_fuhyou_in_opponent_file_test() {
+        let encoded = String::from("k8/9/9/9/9/9/9/7p1/8K b P");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(1);
+
+        let mov = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.to == (7, 5));
+
+        assert!(mov.is_some());
+    }
+
+    #[test]
+    fn possible_moves_for_player_tokin_in_file_test() {
+        // a promoted fuhyou doesn't count for nifu
+        let encoded = String::from("k8/9/9/9/9/9/9/7+P1/8K b P");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(1);
+
+        let mov = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.to == (7, 5));
+
+        assert!(mov.is_some());
+    }
+
+    #[test]
+    fn possible_moves_for_player_two_fuhyou_in_file_player_two_test() {
+        let encoded = String::from("k8/1p7/9/9/9/9/9/9/8K w p");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(2);
+
+        let mov = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.from.is_none() && s.to.0 == 1);
+
+        assert!(mov.is_none());
+    }
+
+    #[test]
+    fn possible_moves_for_player_dead_piece_drops_test() {
+        let encoded = String::from("k8/9/9/9/9/9/9/9/8K b NLP");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(1);
+
+        let keima = result.iter().find(|s| s.moving_piece_kind == PieceKind::Keima && s.from.is_none() && s.to.1 <= 1);
+        let kyousha = result.iter().find(|s| s.moving_piece_kind == PieceKind::Kyousha && s.from.is_none() && s.to.1 == 0);
+        let fuhyou = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.from.is_none() && s.to.1 == 0);
+
+        assert!(keima.is_none());
+        assert!(kyousha.is_none());
+        assert!(fuhyou.is_none());
+        assert!(result.iter().any(|s| s.moving_piece_kind == PieceKind::Keima && s.to.1 == 2));
+        assert!(result.iter().any(|s| s.moving_piece_kind == PieceKind::Kyousha && s.to.1 == 1));
+    }
+
+    #[test]
+    fn possible_moves_for_player_dead_piece_drops_player_two_test() {
+        let encoded = String::from("k8/9/9/9/9/9/9/9/8K w nlp");
+        let mut game_state = parse(&encoded).unwrap();
+        let result = game_state.possible_moves_for_player(2);
+
+        let keima = result.iter().find(|s| s.moving_piece_kind == PieceKind::Keima && s.from.is_none() && s.to.1 >= 7);
+        let kyousha = result.iter().find(|s| s.moving_piece_kind == PieceKind::Kyousha && s.from.is_none() && s.to.1 == 8);
+        let fuhyou = result.iter().find(|s| s.moving_piece_kind == PieceKind::Fuhyou && s.from.is_none() && s.to.1 == 8);
+
+        assert!(keima.is_none());
+        assert!(kyousha.is_none());
+        assert!(fuhyou.is_none());
+    }
+
+    #[test]
+    fn in_checkmate_block_prevented_by_nifu_test() {
+        // the only blocks are fuhyou drops into files that already have a fuhyou
+        let encoded = String::from("k2R5/9/G8/1pp6/9/9/9/9/8K w p");
+        let game_state = parse(&encoded).unwrap();
+
+        assert!(game_state.in_checkmate(2));
+    }
+
+    #[test]
+    fn in_checkmate_block_by_fuhyou_drop_test() {
+        let encoded = String::from("k2R5/9/G8/1p7/9/9/9/9/8K w p");
+        let game_state = parse(&encoded).unwrap();
+
+        assert!(!game_state.in_checkmate(2));
+    }
+
     #[test]
     fn perform_move_test() {
         let encoded = String::from("lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
@@ -899,7 +1360,8 @@ mod tests {
             to: (3, 5),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let result = game_state.perform_move(&mov);
 
@@ -922,7 +1384,8 @@ mod tests {
             to: (3, 5),
             moving_piece_kind: PieceKind::Kakugyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let result = game_state.perform_move(&mov);
 
@@ -946,7 +1409,8 @@ mod tests {
             to: (7, 3),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: Some(PieceKind::Fuhyou),
-            promote: false
+            promote: false,
+            declare: false
         };
         let result = game_state.perform_move(&mov);
 
@@ -965,6 +1429,28 @@ mod tests {
         assert_eq!(hand, &vec![PieceKind::Kakugyou, PieceKind::Fuhyou]);
     }
 
+    #[test]
+    fn perform_and_undo_move_capture_promoted_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/4+b4/4K4 b -");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = Move {
+            from: Some((4, 8)),
+            to: (4, 7),
+            moving_piece_kind: PieceKind::Oushou,
+            capture_piece_kind: Some(PieceKind::Ryuuma),
+            promote: false,
+            declare: false
+        };
+
+        game_state.perform_move(&mov).unwrap();
+        assert_eq!(game_state.hands[1], vec![PieceKind::Kakugyou]);
+
+        game_state.undo_move(&mov).unwrap();
+        assert_eq!(game_state.hands[1], vec![]);
+        let to = find_by_x_and_y(&game_state.squares, (4, 7)).unwrap();
+        assert_eq!(to.kind, PieceKind::Ryuuma);
+    }
+
     #[test]
     fn perform_move_promote_test() {
         let encoded = String::from("lnsgk2nl/1r4gs1/p1pppp1pp/1p5P1/9/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b BPb");
@@ -975,7 +1461,8 @@ mod tests {
             to: (7, 2),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: true
+            promote: true,
+            declare: false
         };
         let result = game_state.perform_move(&mov);
 
@@ -1000,7 +1487,8 @@ mod tests {
             to: (3, 5),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let result = game_state.undo_move(&mov);
 
@@ -1023,7 +1511,8 @@ mod tests {
             to: (3, 5),
             moving_piece_kind: PieceKind::Kakugyou,
             capture_piece_kind: None,
-            promote: false
+            promote: false,
+            declare: false
         };
         let result = game_state.undo_move(&mov);
 
@@ -1048,7 +1537,8 @@ mod tests {
             to: (7, 3),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: Some(PieceKind::Fuhyou),
-            promote: false
+            promote: false,
+            declare: false
         };
         let result = game_state.undo_move(&mov);
 
@@ -1077,7 +1567,8 @@ mod tests {
             to: (7, 2),
             moving_piece_kind: PieceKind::Fuhyou,
             capture_piece_kind: None,
-            promote: true
+            promote: true,
+            declare: false
         };
         let result = game_state.undo_move(&mov);
 
@@ -1092,5 +1583,294 @@ mod tests {
         assert_eq!(to.player_number, 0);
         assert_eq!(to.kind, PieceKind::Empty);
     }
-}
 
+    #[test]
+    fn parse_moves_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f 3c3d");
+        let game_state = parse(&encoded).unwrap();
+
+        assert_eq!(game_state.current_player_number, 1);
+        assert_eq!(game_state.squares[5][2], Square { player_number: 1, kind: PieceKind::Fuhyou });
+        assert_eq!(game_state.squares[3][6], Square { player_number: 2, kind: PieceKind::Fuhyou });
+        assert_eq!(game_state.position_hashes.len(), 3);
+        assert_eq!(game_state.check_history, vec![false, false, false]);
+    }
+
+    #[test]
+    fn parse_illegal_moves_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7e");
+        let result = parse(&encoded);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn position_hash_test() {
+        let a = parse(&String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f 3c3d 2g2f")).unwrap();
+        let b = parse(&String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 2g2f 3c3d 7g7f")).unwrap();
+        let c = parse(&String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w -")).unwrap();
+        let d = parse(&String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -")).unwrap();
+
+        assert_eq!(a.position_hash(), b.position_hash());
+        assert_ne!(c.position_hash(), d.position_hash());
+    }
+
+    #[test]
+    fn position_hash_hand_order_test() {
+        let a = parse(&String::from("4k4/9/9/9/9/9/9/9/4K4 b PRb")).unwrap();
+        let b = parse(&String::from("4k4/9/9/9/9/9/9/9/4K4 b bRP")).unwrap();
+        let c = parse(&String::from("4k4/9/9/9/9/9/9/9/4K4 b PRB")).unwrap();
+
+        assert_eq!(a.position_hash(), b.position_hash());
+        assert_ne!(a.position_hash(), c.position_hash());
+    }
+
+    #[test]
+    fn perform_and_undo_move_history_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 b -");
+        let mut game_state = parse(&encoded).unwrap();
+        let initial_hash = game_state.position_hash();
+        let mov = Move {
+            from: Some((4, 8)),
+            to: (4, 7),
+            moving_piece_kind: PieceKind::Gyokushou,
+            capture_piece_kind: None,
+            promote: false,
+            declare: false
+        };
+
+        game_state.perform_move(&mov).unwrap();
+        assert_eq!(game_state.position_hashes.len(), 2);
+        assert_eq!(game_state.check_history.len(), 2);
+
+        game_state.undo_move(&mov).unwrap();
+        assert_eq!(game_state.position_hashes, vec![initial_hash]);
+        assert_eq!(game_state.check_history, vec![false]);
+    }
+
+    #[test]
+    fn sennichite_draw_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 b - 1 moves 5i5h 5a5b 5h5i 5b5a 5i5h 5a5b 5h5i 5b5a 5i5h 5a5b 5h5i 5b5a");
+        let mut game_state = parse(&encoded).unwrap();
+
+        assert_eq!(game_state.repetition_start(), Some(0));
+        assert!(game_state.draw());
+        assert_eq!(game_state.winner(), None);
+        assert!(game_state.possible_moves().is_empty());
+    }
+
+    #[test]
+    fn not_sennichite_test() {
+        // third occurrence only
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 b - 1 moves 5i5h 5a5b 5h5i 5b5a 5i5h 5a5b 5h5i 5b5a");
+        let mut game_state = parse(&encoded).unwrap();
+
+        assert_eq!(game_state.repetition_start(), None);
+        assert!(!game_state.draw());
+        assert!(!game_state.possible_moves().is_empty());
+    }
+
+    #[test]
+    fn perpetual_check_test() {
+        let encoded = String::from("k8/9/9/9/9/9/9/9/1R2K4 b - 1 moves 8i9i 9a8a 9i8i 8a9a 8i9i 9a8a 9i8i 8a9a 8i9i 9a8a 9i8i 8a9a");
+        let mut game_state = parse(&encoded).unwrap();
+
+        assert_eq!(game_state.perpetual_check_player(), Some(1));
+        assert!(!game_state.draw());
+        assert_eq!(game_state.winner(), Some(2));
+    }
+
+    #[test]
+    fn not_perpetual_check_test() {
+        // the rook doesn't check on every move
+        let encoded = String::from("k8/9/9/9/9/9/9/9/1R2K4 b - 1 moves 8i9i 9a8a 9i9h 8a8b 9h9i 8b8a 9i8i 8a9a 8i9i 9a8a 9i9h 8a8b 9h9i 8b8a");
+        let mut game_state = parse(&encoded).unwrap();
+
+        assert_eq!(game_state.perpetual_check_player(), None);
+        assert!(game_state.draw());
+        assert_eq!(game_state.winner(), None);
+    }
+
+    #[test]
+    fn can_declare_sente_test() {
+        // 26 points in the enemy camp and 2 in hand
+        let encoded = String::from("RR2K4/BB7/+P+P+P+P+P+P3/9/9/k8/9/9/9 b 2P");
+        let game_state = parse(&encoded).unwrap();
+
+        assert!(game_state.can_declare(1));
+        assert!(!game_state.can_declare(2));
+    }
+
+    #[test]
+    fn can_declare_sente_not_enough_points_test() {
+        let encoded = String::from("RR2K4/BB7/+P+P+P+P+P+P3/9/9/k8/9/9/9 b P");
+        let game_state = parse(&encoded).unwrap();
+
+        assert!(!game_state.can_declare(1));
+    }
+
+    #[test]
+    fn can_declare_gote_test() {
+        // gote needs 27 points
+        let encoded = String::from("9/9/9/K8/9/9/+p+p+p+p+p+p3/bb7/rr2k4 w p");
+        let game_state = parse(&encoded).unwrap();
+
+        assert!(game_state.can_declare(2));
+    }
+
+    #[test]
+    fn can_declare_not_enough_pieces_test() {
+        let encoded = String::from("RR2K4/BB7/+P+P+P+P+P4/9/9/k8/9/9/9 b 3P");
+        let game_state = parse(&encoded).unwrap();
+
+        assert!(!game_state.can_declare(1));
+    }
+
+    #[test]
+    fn can_declare_in_check_test() {
+        let encoded = String::from("RR2K4/BB2g4/+P+P+P+P+P+P3/9/9/k8/9/9/9 b 2P");
+        let game_state = parse(&encoded).unwrap();
+
+        assert!(!game_state.can_declare(1));
+    }
+
+    #[test]
+    fn possible_moves_declare_test() {
+        let encoded = String::from("RR2K4/BB7/+P+P+P+P+P+P3/9/9/k8/9/9/9 b 2P");
+        let mut game_state = parse(&encoded).unwrap();
+        let moves = game_state.possible_moves();
+        let mov = moves.iter().find(|m| m.declare).unwrap();
+
+        assert_eq!(mov.from, None);
+        assert_eq!(mov.to, (4, 0));
+        assert_eq!(mov.moving_piece_kind, PieceKind::Oushou);
+
+        game_state.perform_move(mov).unwrap();
+        assert_eq!(game_state.declared_player_number, Some(1));
+        assert_eq!(game_state.winner(), Some(1));
+        assert!(game_state.possible_moves().is_empty());
+
+        game_state.undo_move(mov).unwrap();
+        assert_eq!(game_state.declared_player_number, None);
+        assert_eq!(game_state.current_player_number, 1);
+        assert_eq!(game_state.winner(), None);
+    }
+
+    #[test]
+    fn impasse_points_test() {
+        let encoded = String::from("RR2K4/BB7/+P+P+P+P+P+P3/9/9/k8/9/9/9 b 2P");
+        let game_state = parse(&encoded).unwrap();
+
+        assert_eq!(game_state.impasse_points(1), 28);
+        assert_eq!(game_state.impasse_points(2), 0);
+    }
+
+    #[test]
+    fn impasse_winner_test() {
+        let encoded = String::from("4K4/9/9/9/9/9/9/9/4k4 b 2R2B4P9p");
+        let mut game_state = parse(&encoded).unwrap();
+
+        assert!(game_state.impasse());
+        assert_eq!(game_state.impasse_winner(), Some(1));
+        assert_eq!(game_state.winner(), None);
+
+        // the impasse only ends the game once a player declares
+        let moves = game_state.possible_moves();
+        let mov = moves.iter().find(|m| m.declare).unwrap();
+        game_state.perform_move(mov).unwrap();
+
+        assert_eq!(game_state.winner(), Some(1));
+        assert!(!game_state.draw());
+        assert!(game_state.possible_moves().is_empty());
+    }
+
+    #[test]
+    fn impasse_draw_test() {
+        let encoded = String::from("4K4/9/9/9/9/9/9/9/4k4 b 2R9P4GN2b9p4s3n");
+        let mut game_state = parse(&encoded).unwrap();
+
+        assert!(game_state.impasse());
+        assert_eq!(game_state.impasse_winner(), None);
+        assert!(!game_state.draw());
+
+        let moves = game_state.possible_moves();
+        let mov = moves.iter().find(|m| m.declare).unwrap();
+        game_state.perform_move(mov).unwrap();
+
+        assert_eq!(game_state.winner(), None);
+        assert!(game_state.draw());
+    }
+
+    #[test]
+    fn not_impasse_test() {
+        let encoded = String::from("9/9/9/9/4K4/9/9/9/4k4 b -");
+        let mut game_state = parse(&encoded).unwrap();
+
+        assert!(!game_state.impasse());
+        assert!(!game_state.possible_moves().iter().any(|m| m.declare));
+    }
+
+    #[test]
+    fn parse_minishogi_test() {
+        let encoded = String::from("rbsgk/4p/5/P4/KGSBR b -");
+        let mut game_state = parse_with_variant(&encoded, Variant::Minishogi).unwrap();
+
+        assert_eq!(game_state.squares.len(), 5);
+        assert_eq!(game_state.format(), encoded);
+        assert_eq!(game_state.possible_moves().len(), 14);
+    }
+
+    #[test]
+    fn parse_minishogi_wrong_size_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let result = parse_with_variant(&encoded, Variant::Minishogi);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn minishogi_drops_test() {
+        let encoded = String::from("2k2/5/5/5/2K2 b G");
+        let mut game_state = parse_with_variant(&encoded, Variant::Minishogi).unwrap();
+        let drops = game_state.possible_moves().into_iter().filter(|m| m.from.is_none()).count();
+
+        assert_eq!(drops, 23);
+    }
+
+    #[test]
+    fn minishogi_must_promote_test() {
+        let encoded = String::from("2k2/P4/5/5/2K2 b -");
+        let mut game_state = parse_with_variant(&encoded, Variant::Minishogi).unwrap();
+        let pawn_moves: Vec<Move> = game_state.possible_moves().into_iter().filter(|m| m.moving_piece_kind == PieceKind::Fuhyou).collect();
+
+        assert_eq!(pawn_moves.len(), 1);
+        assert!(pawn_moves[0].promote);
+    }
+
+    #[test]
+    fn judkins_promotion_zone_test() {
+        let encoded = String::from("5k/6/S5/6/6/5K b -");
+        let mut game_state = parse_with_variant(&encoded, Variant::Judkins).unwrap();
+        let promotions = game_state.possible_moves().into_iter().filter(|m| m.promote).count();
+
+        assert_eq!(promotions, 2);
+    }
+
+    #[test]
+    fn minishogi_no_impasse_test() {
+        let encoded = String::from("K4/5/5/5/4k b GGSS");
+        let game_state = parse_with_variant(&encoded, Variant::Minishogi).unwrap();
+
+        assert!(!game_state.impasse());
+        assert!(!game_state.can_declare(1));
+    }
+
+    #[test]
+    fn minishogi_give_remaining_pieces_test() {
+        let encoded = String::from("2k2/5/5/5/2K2 b -");
+        let mut game_state = parse_with_variant(&encoded, Variant::Minishogi).unwrap();
+        game_state.give_remaining_pieces(2);
+
+        assert_eq!(game_state.hands[2].len(), 10);
+    }
+}
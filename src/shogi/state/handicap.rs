@@ -0,0 +1,150 @@
+use crate::shogi::state::square::PieceKind;
+
+// komaochi: gote (uwate) removes pieces from the starting position and moves first
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Handicap {
+    Lance,
+    Bishop,
+    Rook,
+    RookLance,
+    TwoPiece,
+    FourPiece,
+    SixPiece,
+    EightPiece,
+    TenPiece
+}
+
+// handicap, API name, KIF name, SFEN starting position
+const HANDICAPS: [(Handicap, &str, &str, &str); 9] = [
+    (Handicap::Lance, "lance", "香落ち", "lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::Bishop, "bishop", "角落ち", "lnsgkgsnl/1r7/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::Rook, "rook", "飛車落ち", "lnsgkgsnl/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::RookLance, "rook_lance", "飛香落ち", "lnsgkgsn1/7b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::TwoPiece, "two_piece", "二枚落ち", "lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::FourPiece, "four_piece", "四枚落ち", "1nsgkgsn1/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::SixPiece, "six_piece", "六枚落ち", "2sgkgs2/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::EightPiece, "eight_piece", "八枚落ち", "3gkg3/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1"),
+    (Handicap::TenPiece, "ten_piece", "十枚落ち", "4k4/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1")
+];
+
+impl Handicap {
+    pub fn name(&self) -> &'static str {
+        self.entry().1
+    }
+
+    pub fn japanese_name(&self) -> &'static str {
+        self.entry().2
+    }
+
+    // SFEN with the move count
+    pub fn position(&self) -> &'static str {
+        self.entry().3
+    }
+
+    // pieces uwate removes from the starting position
+    pub fn pieces(&self) -> &'static [PieceKind] {
+        match self {
+            Handicap::Lance => &[PieceKind::Kyousha],
+            Handicap::Bishop => &[PieceKind::Kakugyou],
+            Handicap::Rook => &[PieceKind::Hisha],
+            Handicap::RookLance => &[PieceKind::Hisha, PieceKind::Kyousha],
+            Handicap::TwoPiece => &[PieceKind::Hisha, PieceKind::Kakugyou],
+            Handicap::FourPiece => &[PieceKind::Hisha, PieceKind::Kakugyou, PieceKind::Kyousha, PieceKind::Kyousha],
+            Handicap::SixPiece => &[
+                PieceKind::Hisha, PieceKind::Kakugyou, PieceKind::Kyousha, PieceKind::Kyousha, PieceKind::Keima, PieceKind::Keima
+            ],
+            Handicap::EightPiece => &[
+                PieceKind::Hisha, PieceKind::Kakugyou, PieceKind::Kyousha, PieceKind::Kyousha, PieceKind::Keima, PieceKind::Keima,
+                PieceKind::Ginshou, PieceKind::Ginshou
+            ],
+            Handicap::TenPiece => &[
+                PieceKind::Hisha, PieceKind::Kakugyou, PieceKind::Kyousha, PieceKind::Kyousha, PieceKind::Keima, PieceKind::Keima,
+                PieceKind::Ginshou, PieceKind::Ginshou, PieceKind::Kinshou, PieceKind::Kinshou
+            ]
+        }
+    }
+
+    fn entry(&self) -> &'static (Handicap, &'static str, &'static str, &'static str) {
+        HANDICAPS.iter().find(|h| h.0 == *self).unwrap_or(&HANDICAPS[0])
+    }
+}
+
+// API name (two_piece) or KIF name (二枚落ち)
+pub fn parse(name: &str) -> Result<Handicap, &'static str> {
+    let name = name.trim();
+    match HANDICAPS.iter().find(|h| h.1 == name || h.2 == name) {
+        Some(h) => Ok(h.0),
+        None => Err("handicap::parse - Unknown handicap")
+    }
+}
+
+// the handicap whose starting position this is, ignoring the move count
+pub fn from_position(position: &str) -> Option<Handicap> {
+    let fields = |p: &'static str| -> Vec<&'static str> { p.split_whitespace().take(3).collect() };
+    let position_fields: Vec<&str> = position.split_whitespace().take(3).collect();
+    HANDICAPS.iter().find(|h| fields(h.3) == position_fields).map(|h| h.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::state::game_state::parse as parse_game_state;
+
+    #[test]
+    fn parse_name_test() {
+        let result = parse("two_piece").unwrap();
+        assert_eq!(result, Handicap::TwoPiece);
+    }
+
+    #[test]
+    fn parse_japanese_name_test() {
+        let result = parse("飛香落ち").unwrap();
+        assert_eq!(result, Handicap::RookLance);
+    }
+
+    #[test]
+    fn parse_unknown_test() {
+        let result = parse("queen");
+        assert_eq!(result, Err("handicap::parse - Unknown handicap"));
+    }
+
+    #[test]
+    fn from_position_test() {
+        let result = from_position("1nsgkgsn1/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w -");
+        assert_eq!(result, Some(Handicap::FourPiece));
+    }
+
+    #[test]
+    fn from_position_even_test() {
+        let result = from_position("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1");
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn from_position_sente_to_move_test() {
+        let result = from_position("lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1");
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn pieces_test() {
+        // the even starting position less the removed pieces is the handicap position
+        let even = parse_game_state(&String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1")).unwrap();
+        let count = |game_state: &crate::shogi::state::game_state::GameState| {
+            game_state.squares.iter().flatten().filter(|s| s.player_number == 2).count()
+        };
+        for (handicap, _, _, position) in HANDICAPS.iter() {
+            let game_state = parse_game_state(&position.to_string()).unwrap();
+            assert_eq!(count(&even) - count(&game_state), handicap.pieces().len());
+        }
+    }
+
+    #[test]
+    fn positions_parse_test() {
+        for (handicap, _, _, position) in HANDICAPS.iter() {
+            let game_state = parse_game_state(&position.to_string()).unwrap();
+            assert_eq!(game_state.current_player_number, 2);
+            assert_eq!(from_position(&game_state.format()), Some(*handicap));
+        }
+    }
+}
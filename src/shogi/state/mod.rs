@@ -1,7 +1,10 @@
 pub mod point;
 mod piece_factory;
 pub mod square;
-mod square_set;
+pub mod square_set;
 pub mod mov;
 pub mod external_mov;
+pub mod usi_mov;
+pub mod handicap;
+pub mod variant;
 pub mod game_state;
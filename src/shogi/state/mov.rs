@@ -6,5 +6,6 @@ pub struct Move {
     pub to: (i8, i8),
     pub moving_piece_kind: PieceKind,
     pub capture_piece_kind: Option<PieceKind>, // Undo -> place piece back
-    pub promote: bool
+    pub promote: bool,
+    pub declare: bool // entering king declaration, from is None and to is the ou point
 }
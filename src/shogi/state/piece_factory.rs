@@ -45,6 +45,33 @@ pub fn parse(encoded: char, promoted_piece: bool) -> Result<Square, &'static str
     }
 }
 
+// SFEN letters, uppercase for sente and lowercase for gote, + for promoted pieces
+pub fn format(square: &Square) -> String {
+    let letter = match square.kind {
+        PieceKind::Fuhyou => "P",
+        PieceKind::Tokin => "+P",
+        PieceKind::Kyousha => "L",
+        PieceKind::Narikyou => "+L",
+        PieceKind::Keima => "N",
+        PieceKind::Narikei => "+N",
+        PieceKind::Ginshou => "S",
+        PieceKind::Narigin => "+S",
+        PieceKind::Kinshou => "G",
+        PieceKind::Hisha => "R",
+        PieceKind::Ryuuou => "+R",
+        PieceKind::Kakugyou => "B",
+        PieceKind::Ryuuma => "+B",
+        PieceKind::Oushou | PieceKind::Gyokushou => "K",
+        PieceKind::Empty => ""
+    };
+
+    if square.player_number == 2 {
+        letter.to_lowercase()
+    } else {
+        letter.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,5 +285,16 @@ mod tests {
        let result = parse('K', true);
        assert_eq!(result, expected);
     }
-}
 
+    #[test]
+    fn format_fuhyou_one_test() {
+        let result = format(&Square { kind: PieceKind::Fuhyou, player_number: 1 });
+        assert_eq!(result, "P");
+    }
+
+    #[test]
+    fn format_ryuuma_two_test() {
+        let result = format(&Square { kind: PieceKind::Ryuuma, player_number: 2 });
+        assert_eq!(result, "+b");
+    }
+}
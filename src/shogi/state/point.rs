@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 
+// ranging destinations reach the edge of the largest board, smaller boards drop the points that aren't valid
 pub const MIN_N: i8 = 0;
 pub const MAX_N: i8 = 8;
 
@@ -9,8 +10,9 @@ pub fn diff<'a>(a: &'a Vec<(i8, i8)>, b: &'a Vec<(i8, i8)>) -> Vec<&'a (i8, i8)>
     }).collect();
 }
 
-pub fn valid(point: (i8, i8)) -> bool {
-    point.0 >= MIN_N && point.0 <= MAX_N && point.1 >= MIN_N && point.1 <= MAX_N
+// on a board of size files and ranks
+pub fn valid(point: (i8, i8), size: i8) -> bool {
+    point.0 >= MIN_N && point.0 < size && point.1 >= MIN_N && point.1 < size
 }
 
 pub fn add(a: (i8, i8), b: (i8, i8)) -> (i8, i8) {
@@ -264,17 +266,24 @@ mod tests {
     #[test]
     fn valid_true_test() {
         let point = (4, 4);
-        let result = valid(point);
+        let result = valid(point, 9);
         assert_eq!(result, true);
     }
 
     #[test]
     fn valid_false_test() {
         let point = (4, 9);
-        let result = valid(point);
+        let result = valid(point, 9);
         assert_eq!(result, false);
     }
 
+    #[test]
+    fn valid_small_board_false_test() {
+        let point = (4, 5);
+        let result = valid(point, 5);
+        assert!(!result);
+    }
+
     #[test]
     fn add_test() {
         let a = (1, 1);
@@ -11,8 +11,9 @@ use crate::shogi::state::point::ryuuou_destination_points;
 use crate::shogi::state::square_set::find_by_x_and_y;
 use crate::shogi::state::square_set::between_unoccupied;
 use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::variant::Variant;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PieceKind {
    Oushou,
    Gyokushou,
@@ -41,7 +42,7 @@ pub const PROMOTABLE_PIECE_KINDS: [PieceKind; 6] = [
     PieceKind::Kakugyou
 ];
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Square {
     pub player_number: i8,
     pub kind: PieceKind
@@ -207,32 +208,44 @@ pub fn threats_matches_point(piece_kind: PieceKind, player_number: i8, from: (i8
     result
 }
 
-pub fn can_promote_on_y(kind: PieceKind, player_number: i8, y: i8) -> bool {
-    if PROMOTABLE_PIECE_KINDS.contains(&kind) {
-        if player_number == 1 {
-            y == 0 || y == 1 || y == 2
-        } else {
-            y == 6 || y == 7 || y == 8
-        }
+// the furthest ranks from the player's side, i.e. the enemy camp, three on the full board
+pub fn in_promotion_zone(player_number: i8, y: i8, variant: Variant) -> bool {
+    if player_number == 1 {
+        y < variant.promotion_ranks()
     } else {
-        false
+        y >= variant.size() - variant.promotion_ranks()
     }
 }
 
-pub fn must_promote_on_y(kind: PieceKind, player_number: i8, y: i8) -> bool {
+pub fn can_promote_on_y(kind: PieceKind, player_number: i8, y: i8, variant: Variant) -> bool {
+    PROMOTABLE_PIECE_KINDS.contains(&kind) && in_promotion_zone(player_number, y, variant)
+}
+
+// jishogi: hisha and kakugyou count 5 points whether promoted or not, the ou doesn't count
+pub fn impasse_points(kind: PieceKind) -> i8 {
+    match kind {
+        PieceKind::Hisha | PieceKind::Ryuuou | PieceKind::Kakugyou | PieceKind::Ryuuma => 5,
+        PieceKind::Oushou | PieceKind::Gyokushou | PieceKind::Empty => 0,
+        _ => 1
+    }
+}
+
+// the piece would have no moves left
+pub fn must_promote_on_y(kind: PieceKind, player_number: i8, y: i8, variant: Variant) -> bool {
+    let last_y = variant.size() - 1;
     match kind {
         PieceKind::Fuhyou | PieceKind::Kyousha => {
             if player_number == 1 {
                 y == 0
             } else {
-                y == 8
+                y == last_y
             }
         },
         PieceKind::Keima => {
             if player_number == 1 {
                 y == 0 || y == 1
             } else {
-                y == 7 || y == 8
+                y == last_y - 1 || y == last_y
             }
         }
         _ => false
@@ -1038,7 +1051,7 @@ mod tests {
     fn can_promote_on_y_promotable_one_true_test() {
         let kind = PieceKind::Fuhyou;
         let player_number = 1;
-        let result = can_promote_on_y(kind, player_number, 1);
+        let result = can_promote_on_y(kind, player_number, 1, Variant::Standard);
         let expected = true;
         assert_eq!(result, expected);
     }
@@ -1047,7 +1060,7 @@ mod tests {
     fn can_promote_on_y_promotable_one_false_test() {
         let kind = PieceKind::Fuhyou;
         let player_number = 1;
-        let result = can_promote_on_y(kind, player_number, 3);
+        let result = can_promote_on_y(kind, player_number, 3, Variant::Standard);
         let expected = false;
         assert_eq!(result, expected);
     }
@@ -1056,7 +1069,7 @@ mod tests {
     fn can_promote_on_y_promotable_two_true_test() {
         let kind = PieceKind::Fuhyou;
         let player_number = 2;
-        let result = can_promote_on_y(kind, player_number, 7);
+        let result = can_promote_on_y(kind, player_number, 7, Variant::Standard);
         let expected = true;
         assert_eq!(result, expected);
     }
@@ -1065,7 +1078,7 @@ mod tests {
     fn can_promote_on_y_promotable_two_false_test() {
         let kind = PieceKind::Fuhyou;
         let player_number = 2;
-        let result = can_promote_on_y(kind, player_number, 5);
+        let result = can_promote_on_y(kind, player_number, 5, Variant::Standard);
         let expected = false;
         assert_eq!(result, expected);
     }
@@ -1074,7 +1087,7 @@ mod tests {
     fn must_promote_on_y_fuhyou_true_test() {
         let kind = PieceKind::Fuhyou;
         let player_number = 1;
-        let result = must_promote_on_y(kind, player_number, 0);
+        let result = must_promote_on_y(kind, player_number, 0, Variant::Standard);
         let expected= true;
         assert_eq!(result, expected);
     }
@@ -1083,7 +1096,7 @@ mod tests {
     fn must_promote_on_y_fuhyou_false_test() {
         let kind = PieceKind::Fuhyou;
         let player_number = 1;
-        let result = must_promote_on_y(kind, player_number, 1);
+        let result = must_promote_on_y(kind, player_number, 1, Variant::Standard);
         let expected= false;
         assert_eq!(result, expected);
     }
@@ -1092,7 +1105,7 @@ mod tests {
     fn must_promote_on_y_kyousha_true_test() {
         let kind = PieceKind::Kyousha;
         let player_number = 1;
-        let result = must_promote_on_y(kind, player_number, 0);
+        let result = must_promote_on_y(kind, player_number, 0, Variant::Standard);
         let expected = true;
         assert_eq!(result, expected);
     }
@@ -1101,7 +1114,7 @@ mod tests {
     fn must_promote_on_y_kyousha_false_test() {
         let kind = PieceKind::Kyousha;
         let player_number = 1;
-        let result = must_promote_on_y(kind, player_number, 1);
+        let result = must_promote_on_y(kind, player_number, 1, Variant::Standard);
         let expected = false;
         assert_eq!(result, expected);
     }
@@ -1110,7 +1123,7 @@ mod tests {
     fn must_promote_on_y_keima_true_test() {
         let kind = PieceKind::Keima;
         let player_number = 1;
-        let result = must_promote_on_y(kind, player_number, 0);
+        let result = must_promote_on_y(kind, player_number, 0, Variant::Standard);
         let expected = true;
         assert_eq!(result, expected);
     }
@@ -1119,7 +1132,7 @@ mod tests {
     fn must_promote_on_y_keima_false_test() {
         let kind = PieceKind::Keima;
         let player_number = 1;
-        let result = must_promote_on_y(kind, player_number, 2);
+        let result = must_promote_on_y(kind, player_number, 2, Variant::Standard);
         let expected = false;
         assert_eq!(result, expected);
     }
@@ -1128,11 +1141,32 @@ mod tests {
     fn must_promote_on_y_ginshou_false_test() {
         let kind = PieceKind::Ginshou;
         let player_number = 1;
-        let result = must_promote_on_y(kind, player_number, 0);
+        let result = must_promote_on_y(kind, player_number, 0, Variant::Standard);
         let expected = false;
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn can_promote_on_y_minishogi_test() {
+        assert!(can_promote_on_y(PieceKind::Ginshou, 1, 0, Variant::Minishogi));
+        assert!(!can_promote_on_y(PieceKind::Ginshou, 1, 1, Variant::Minishogi));
+        assert!(can_promote_on_y(PieceKind::Ginshou, 2, 4, Variant::Minishogi));
+    }
+
+    #[test]
+    fn can_promote_on_y_judkins_test() {
+        assert!(can_promote_on_y(PieceKind::Keima, 1, 1, Variant::Judkins));
+        assert!(!can_promote_on_y(PieceKind::Keima, 2, 3, Variant::Judkins));
+        assert!(can_promote_on_y(PieceKind::Keima, 2, 4, Variant::Judkins));
+    }
+
+    #[test]
+    fn must_promote_on_y_judkins_test() {
+        assert!(must_promote_on_y(PieceKind::Fuhyou, 2, 5, Variant::Judkins));
+        assert!(must_promote_on_y(PieceKind::Keima, 2, 4, Variant::Judkins));
+        assert!(!must_promote_on_y(PieceKind::Keima, 2, 3, Variant::Judkins));
+    }
+
     #[test]
     fn promotes_to_fuhyou_test() {
         let kind = PieceKind::Fuhyou;
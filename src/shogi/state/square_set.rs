@@ -18,7 +18,7 @@ use crate::shogi::state::square::threats_matches_point;
 use crate::shogi::state::game_state::GameState;
 
 pub fn find_by_x_and_y_mut(squares: &mut Vec<Vec<Square>>, point: (i8, i8)) -> Option<&mut Square> {
-    if valid(point) {
+    if valid(point, squares.len() as i8) {
         Some(&mut squares[point.1 as usize][point.0 as usize])
     } else {
         None
@@ -26,7 +26,7 @@ pub fn find_by_x_and_y_mut(squares: &mut Vec<Vec<Square>>, point: (i8, i8)) -> O
 }
 
 pub fn find_by_x_and_y(squares: &Vec<Vec<Square>>, point: (i8, i8)) -> Option<&Square> {
-    if valid(point) {
+    if valid(point, squares.len() as i8) {
         Some(&squares[point.1 as usize][point.0 as usize])
     } else {
         None
@@ -0,0 +1,249 @@
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::mov::Move;
+use crate::shogi::state::piece_factory::parse as parse_piece;
+use crate::shogi::state::square::PieceKind;
+
+const RANK_FORMAT: [char; 9] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i'
+];
+
+// USI move notation
+// files are numbered 9 to 1 from left to right and ranks are lettered a to i from top to bottom,
+// smaller boards number from their size, e.g. 5 to 1 and a to e in minishogi
+//   7g7f - move from 7g to 7f
+//   8h2b+ - move and promote
+//   P*5e - drop
+//   win - entering king declaration
+pub fn parse(encoded: &str, game_state: &mut GameState) -> Result<Move, &'static str> {
+    if encoded == "win" {
+        return match game_state.possible_moves().into_iter().find(|m| m.declare) {
+            Some(m) => Ok(m),
+            None => Err("usi_mov::parse - Illegal declaration")
+        };
+    }
+
+    let chars: Vec<char> = encoded.chars().collect();
+
+    let promote = match chars.len() {
+        4 => false,
+        5 if chars[4] == '+' => true,
+        _ => return Err("usi_mov::parse - Invalid move")
+    };
+
+    let (from, drop_piece_kind) = if chars[1] == '*' {
+        match parse_piece(chars[0], false) {
+            Ok(square) if square.player_number == 1 => (None, Some(square.kind)),
+            _ => return Err("usi_mov::parse - Invalid drop piece")
+        }
+    } else {
+        (Some(parse_point(chars[0], chars[1], game_state.variant.size())?), None)
+    };
+
+    let to = parse_point(chars[2], chars[3], game_state.variant.size())?;
+
+    let moves = game_state.possible_moves();
+    let mov = moves.into_iter().find(|m| {
+        let same_piece = match drop_piece_kind {
+            Some(pk) => m.moving_piece_kind == pk,
+            None => true
+        };
+        !m.declare && m.from == from && m.to == to && m.promote == promote && same_piece
+    });
+
+    match mov {
+        Some(m) => Ok(m),
+        None => Err("usi_mov::parse - Illegal move")
+    }
+}
+
+pub fn format(mov: &Move, size: i8) -> String {
+    if mov.declare {
+        return String::from("win");
+    }
+
+    let from = match mov.from {
+        Some(f) => format_point(f, size),
+        None => format!("{}*", drop_piece_format(mov.moving_piece_kind))
+    };
+
+    let promotion = if mov.promote {
+        "+"
+    } else {
+        ""
+    };
+
+    format!("{}{}{}", from, format_point(mov.to, size), promotion)
+}
+
+fn format_point(point: (i8, i8), size: i8) -> String {
+    format!("{}{}", size - point.0, RANK_FORMAT[point.1 as usize])
+}
+
+fn drop_piece_format(piece_kind: PieceKind) -> char {
+    match piece_kind {
+        PieceKind::Fuhyou => 'P',
+        PieceKind::Kyousha => 'L',
+        PieceKind::Keima => 'N',
+        PieceKind::Ginshou => 'S',
+        PieceKind::Kinshou => 'G',
+        PieceKind::Kakugyou => 'B',
+        PieceKind::Hisha => 'R',
+        _ => '?'
+    }
+}
+
+pub fn parse_point(file: char, rank: char, size: i8) -> Result<(i8, i8), &'static str> {
+    let x = match file.to_digit(10) {
+        Some(n) if n >= 1 && n as i8 <= size => size - n as i8,
+        _ => return Err("usi_mov::parse - Invalid file")
+    };
+
+    let y = match RANK_FORMAT.iter().position(|r| *r == rank) {
+        Some(y) if (y as i8) < size => y as i8,
+        _ => return Err("usi_mov::parse - Invalid rank")
+    };
+
+    Ok((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::state::game_state::parse as parse_game_state;
+
+    #[test]
+    fn parse_move_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+        let result = parse("7g7f", &mut game_state).unwrap();
+
+        assert_eq!(result.from, Some((2, 6)));
+        assert_eq!(result.to, (2, 5));
+        assert_eq!(result.moving_piece_kind, PieceKind::Fuhyou);
+        assert!(!result.promote);
+    }
+
+    #[test]
+    fn parse_promote_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/pppppp1pp/6p2/9/2P6/PP1PPPPPP/1B5R1/LNSGKGSNL b -");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+        let result = parse("8h2b+", &mut game_state).unwrap();
+
+        assert_eq!(result.from, Some((1, 7)));
+        assert_eq!(result.to, (7, 1));
+        assert_eq!(result.capture_piece_kind, Some(PieceKind::Kakugyou));
+        assert!(result.promote);
+    }
+
+    #[test]
+    fn parse_drop_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/4K4 w b");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+        let result = parse("B*5e", &mut game_state).unwrap();
+
+        assert_eq!(result.from, None);
+        assert_eq!(result.to, (4, 4));
+        assert_eq!(result.moving_piece_kind, PieceKind::Kakugyou);
+    }
+
+    #[test]
+    fn parse_illegal_move_test() {
+        let encoded = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+
+        assert!(parse("7g7e", &mut game_state).is_err());
+        assert!(parse("7z7f", &mut game_state).is_err());
+        assert!(parse("P*5e", &mut game_state).is_err());
+    }
+
+    #[test]
+    fn parse_declare_test() {
+        let encoded = String::from("RR2K4/BB7/+P+P+P+P+P+P3/9/9/k8/9/9/9 b 2P");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+        let result = parse("win", &mut game_state).unwrap();
+
+        assert!(result.declare);
+    }
+
+    #[test]
+    fn parse_minishogi_move_test() {
+        let encoded = String::from("rbsgk/4p/5/P4/KGSBR b -");
+        let mut game_state = crate::shogi::state::game_state::parse_with_variant(&encoded, crate::shogi::state::variant::Variant::Minishogi).unwrap();
+        let result = parse("5d5c", &mut game_state).unwrap();
+
+        assert_eq!(result.from, Some((0, 3)));
+        assert_eq!(result.to, (0, 2));
+        assert!(parse("5e5f", &mut game_state).is_err());
+        assert!(parse("6d6c", &mut game_state).is_err());
+    }
+
+    #[test]
+    fn format_minishogi_move_test() {
+        let mov = Move {
+            from: Some((0, 3)),
+            to: (0, 2),
+            moving_piece_kind: PieceKind::Fuhyou,
+            capture_piece_kind: None,
+            promote: false,
+            declare: false
+        };
+
+        assert_eq!(format(&mov, 5), "5d5c");
+    }
+
+    #[test]
+    fn format_move_test() {
+        let mov = Move {
+            from: Some((2, 6)),
+            to: (2, 5),
+            moving_piece_kind: PieceKind::Fuhyou,
+            capture_piece_kind: None,
+            promote: false,
+            declare: false
+        };
+
+        assert_eq!(format(&mov, 9), "7g7f");
+    }
+
+    #[test]
+    fn format_promote_test() {
+        let mov = Move {
+            from: Some((1, 7)),
+            to: (7, 1),
+            moving_piece_kind: PieceKind::Kakugyou,
+            capture_piece_kind: Some(PieceKind::Kakugyou),
+            promote: true,
+            declare: false
+        };
+
+        assert_eq!(format(&mov, 9), "8h2b+");
+    }
+
+    #[test]
+    fn format_drop_test() {
+        let mov = Move {
+            from: None,
+            to: (4, 4),
+            moving_piece_kind: PieceKind::Fuhyou,
+            capture_piece_kind: None,
+            promote: false,
+            declare: false
+        };
+
+        assert_eq!(format(&mov, 9), "P*5e");
+    }
+
+    #[test]
+    fn format_declare_test() {
+        let mov = Move {
+            from: None,
+            to: (4, 0),
+            moving_piece_kind: PieceKind::Oushou,
+            capture_piece_kind: None,
+            promote: false,
+            declare: true
+        };
+
+        assert_eq!(format(&mov, 9), "win");
+    }
+}
@@ -0,0 +1,106 @@
+use crate::shogi::state::square::PieceKind;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    Standard,
+    Minishogi, // 5x5, no keima or kyousha, promotion on the last rank
+    Judkins // 6x6, no kyousha, promotion on the last two ranks
+}
+
+// pieces of a full set by unpromoted kind, Oushou counts both ou
+const STANDARD_PIECE_SET: [(PieceKind, usize); 8] = [
+    (PieceKind::Oushou, 2),
+    (PieceKind::Hisha, 2),
+    (PieceKind::Kakugyou, 2),
+    (PieceKind::Kinshou, 4),
+    (PieceKind::Ginshou, 4),
+    (PieceKind::Keima, 4),
+    (PieceKind::Kyousha, 4),
+    (PieceKind::Fuhyou, 18)
+];
+
+const MINISHOGI_PIECE_SET: [(PieceKind, usize); 6] = [
+    (PieceKind::Oushou, 2),
+    (PieceKind::Hisha, 2),
+    (PieceKind::Kakugyou, 2),
+    (PieceKind::Kinshou, 2),
+    (PieceKind::Ginshou, 2),
+    (PieceKind::Fuhyou, 2)
+];
+
+const JUDKINS_PIECE_SET: [(PieceKind, usize); 7] = [
+    (PieceKind::Oushou, 2),
+    (PieceKind::Hisha, 2),
+    (PieceKind::Kakugyou, 2),
+    (PieceKind::Kinshou, 2),
+    (PieceKind::Ginshou, 2),
+    (PieceKind::Keima, 2),
+    (PieceKind::Fuhyou, 2)
+];
+
+impl Variant {
+    // number of files and ranks
+    pub fn size(&self) -> i8 {
+        match self {
+            Variant::Standard => 9,
+            Variant::Minishogi => 5,
+            Variant::Judkins => 6
+        }
+    }
+
+    // depth of the enemy camp
+    pub fn promotion_ranks(&self) -> i8 {
+        match self {
+            Variant::Standard => 3,
+            Variant::Minishogi => 1,
+            Variant::Judkins => 2
+        }
+    }
+
+    // SFEN with the move count
+    pub fn starting_position(&self) -> &'static str {
+        match self {
+            Variant::Standard => "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1",
+            Variant::Minishogi => "rbsgk/4p/5/P4/KGSBR b - 1",
+            Variant::Judkins => "rbnsgk/5p/6/6/P5/KGSNBR b - 1"
+        }
+    }
+
+    pub fn piece_set(&self) -> &'static [(PieceKind, usize)] {
+        match self {
+            Variant::Standard => &STANDARD_PIECE_SET,
+            Variant::Minishogi => &MINISHOGI_PIECE_SET,
+            Variant::Judkins => &JUDKINS_PIECE_SET
+        }
+    }
+
+    // nyuugyoku impasse and declaration only apply on the full board
+    pub fn entering_king(&self) -> bool {
+        *self == Variant::Standard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::state::game_state::parse_with_variant;
+
+    #[test]
+    fn starting_positions_test() {
+        for variant in [Variant::Standard, Variant::Minishogi, Variant::Judkins].iter() {
+            let game_state = parse_with_variant(&variant.starting_position().to_string(), *variant).unwrap();
+            assert_eq!(game_state.squares.len(), variant.size() as usize);
+            assert!(game_state.squares.iter().all(|row| row.len() == variant.size() as usize));
+        }
+    }
+
+    #[test]
+    fn piece_set_test() {
+        for variant in [Variant::Standard, Variant::Minishogi, Variant::Judkins].iter() {
+            let game_state = parse_with_variant(&variant.starting_position().to_string(), *variant).unwrap();
+            let on_board = game_state.squares.iter().flatten().filter(|s| s.occupied()).count();
+            let in_set: usize = variant.piece_set().iter().map(|(_, count)| count).sum();
+            assert_eq!(on_board, in_set);
+        }
+    }
+}
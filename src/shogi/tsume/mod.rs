@@ -0,0 +1,357 @@
+use std::cmp;
+use std::collections::HashMap;
+use crate::shogi::state::game_state::GameState;
+use crate::shogi::state::mov::Move;
+use crate::shogi::state::square::opposing_player;
+
+const INFINITY: u32 = 1 << 30;
+
+// the longest mating sequence followed when reading the answer from the table
+const MAX_SEQUENCE_LENGTH: usize = 255;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Entry {
+    proof: u32, // proof number, 0 when the position is mate
+    disproof: u32, // disproof number, 0 when there is no mate
+    distance: u32 // moves until mate once proven
+}
+
+const UNKNOWN: Entry = Entry { proof: 1, disproof: 1, distance: 0 };
+const PROVEN: Entry = Entry { proof: 0, disproof: INFINITY, distance: 0 };
+const DISPROVEN: Entry = Entry { proof: INFINITY, disproof: 0, distance: 0 };
+
+struct Solver {
+    attacker: i8,
+    table: HashMap<u64, Entry>,
+    nodes: usize,
+    max_nodes: usize
+}
+
+// Tsume shogi solver using depth-first proof-number search (df-pn).
+// The player to move attacks and may only make checking moves, drops included.
+// As in tsume problems the defender holds every piece that is not on the board or in the attacker's hand.
+// Returns the mating sequence, or None when there is no mate or none is found within max_nodes.
+pub fn solve(game_state: &GameState, max_nodes: usize) -> Option<Vec<Move>> {
+    let mut game_state = game_state.clone();
+    let attacker = game_state.current_player_number;
+    game_state.give_remaining_pieces(opposing_player(attacker));
+
+    let mut solver = Solver {
+        attacker,
+        table: HashMap::new(),
+        nodes: 0,
+        max_nodes
+    };
+
+    let entry = solver.search(&mut game_state, INFINITY, INFINITY);
+
+    if entry.proof == 0 {
+        Some(solver.mating_sequence(&mut game_state))
+    } else {
+        None
+    }
+}
+
+impl Solver {
+    fn search(&mut self, game_state: &mut GameState, proof_threshold: u32, disproof_threshold: u32) -> Entry {
+        let key = position_key(game_state);
+        let attacking = game_state.current_player_number == self.attacker;
+        let moves = self.moves(game_state, attacking);
+
+        if moves.is_empty() {
+            // the attacker has no checks left, or the defender has no way out of check
+            let entry = if attacking { DISPROVEN } else { PROVEN };
+            self.table.insert(key, entry);
+            return entry;
+        }
+
+        let child_keys: Vec<Option<u64>> = moves.iter().map(|mov| child_key(game_state, mov)).collect();
+
+        loop {
+            let children: Vec<Entry> = child_keys.iter().map(|child_key| match child_key {
+                Some(k) => *self.table.get(k).unwrap_or(&UNKNOWN),
+                // repetition does not count as mate
+                None => DISPROVEN
+            }).collect();
+
+            let entry = combine(&children, attacking);
+
+            if entry.proof >= proof_threshold || entry.disproof >= disproof_threshold || self.nodes >= self.max_nodes {
+                self.table.insert(key, entry);
+                return entry;
+            }
+
+            self.nodes += 1;
+
+            // attacker follows the smallest proof number, defender the smallest disproof number
+            let number = |e: &Entry| if attacking { e.proof } else { e.disproof };
+            let mut order: Vec<usize> = (0..children.len()).filter(|i| child_keys[*i].is_some()).collect();
+            order.sort_by_key(|i| number(&children[*i]));
+
+            let best = order[0];
+            let second_number = order.get(1).map(|i| number(&children[*i])).unwrap_or(INFINITY);
+            let child = children[best];
+
+            let (child_proof_threshold, child_disproof_threshold) = if attacking {
+                (
+                    cmp::min(proof_threshold, second_number.saturating_add(1)),
+                    threshold_for_child(disproof_threshold, entry.disproof, child.disproof)
+                )
+            } else {
+                (
+                    threshold_for_child(proof_threshold, entry.proof, child.proof),
+                    cmp::min(disproof_threshold, second_number.saturating_add(1))
+                )
+            };
+
+            let mov = &moves[best];
+            if game_state.perform_move(mov).is_err() {
+                self.table.insert(key, DISPROVEN);
+                return DISPROVEN;
+            }
+            self.search(game_state, child_proof_threshold, child_disproof_threshold);
+            if game_state.undo_move(mov).is_err() {
+                self.table.insert(key, DISPROVEN);
+                return DISPROVEN;
+            }
+        }
+    }
+
+    // checking moves for the attacker, every legal move for the defender
+    fn moves(&self, game_state: &mut GameState, attacking: bool) -> Vec<Move> {
+        let moves: Vec<Move> = game_state.possible_moves().into_iter().filter(|m| !m.declare).collect();
+
+        if attacking {
+            moves.into_iter().filter(|mov| gives_check(game_state, mov)).collect()
+        } else {
+            moves
+        }
+    }
+
+    // attacker plays the shortest mate, defender the longest resistance
+    fn mating_sequence(&mut self, game_state: &mut GameState) -> Vec<Move> {
+        let mut game_state = game_state.clone();
+        let mut sequence = vec![];
+
+        while sequence.len() < MAX_SEQUENCE_LENGTH {
+            let attacking = game_state.current_player_number == self.attacker;
+            let moves = self.moves(&mut game_state, attacking);
+
+            let proven: Vec<(Move, u32)> = moves.into_iter().filter_map(|mov| {
+                let entry = child_key(&mut game_state, &mov).and_then(|k| self.table.get(&k).cloned())?;
+                if entry.proof == 0 {
+                    Some((mov, entry.distance))
+                } else {
+                    None
+                }
+            }).collect();
+
+            let next = if attacking {
+                proven.into_iter().min_by_key(|(_, distance)| *distance)
+            } else {
+                proven.into_iter().max_by_key(|(_, distance)| *distance)
+            };
+
+            match next {
+                Some((mov, _)) => {
+                    if game_state.perform_move(&mov).is_err() {
+                        break;
+                    }
+                    sequence.push(mov);
+                },
+                None => break
+            }
+        }
+
+        sequence
+    }
+}
+
+// proof and disproof numbers of a node from its children
+fn combine(children: &[Entry], attacking: bool) -> Entry {
+    if attacking {
+        let proof = children.iter().map(|c| c.proof).min().unwrap_or(INFINITY);
+        let disproof = cmp::min(children.iter().map(|c| c.disproof).fold(0, u32::saturating_add), INFINITY);
+        let distance = children.iter().filter(|c| c.proof == 0).map(|c| c.distance + 1).min().unwrap_or(0);
+        Entry { proof, disproof, distance }
+    } else {
+        let proof = cmp::min(children.iter().map(|c| c.proof).fold(0, u32::saturating_add), INFINITY);
+        let disproof = children.iter().map(|c| c.disproof).min().unwrap_or(INFINITY);
+        let distance = children.iter().map(|c| c.distance + 1).max().unwrap_or(0);
+        Entry { proof, disproof, distance }
+    }
+}
+
+fn threshold_for_child(threshold: u32, total: u32, child: u32) -> u32 {
+    if threshold >= INFINITY {
+        INFINITY
+    } else {
+        cmp::min(threshold.saturating_sub(total).saturating_add(child), INFINITY)
+    }
+}
+
+fn position_key(game_state: &GameState) -> u64 {
+    match game_state.position_hashes.last() {
+        Some(hash) => *hash,
+        None => game_state.position_hash()
+    }
+}
+
+// key of the position after the move, None when the move repeats an earlier position
+fn child_key(game_state: &mut GameState, mov: &Move) -> Option<u64> {
+    if game_state.perform_move(mov).is_err() {
+        return None;
+    }
+    let key = position_key(game_state);
+    let repeated = game_state.position_hashes.iter().rev().skip(1).any(|h| *h == key);
+    let _ = game_state.undo_move(mov);
+
+    if repeated {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+fn gives_check(game_state: &mut GameState, mov: &Move) -> bool {
+    if game_state.perform_move(mov).is_err() {
+        return false;
+    }
+    let check = game_state.current_player_in_check();
+    let _ = game_state.undo_move(mov);
+    check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shogi::state::game_state::parse;
+    use crate::shogi::state::square::PieceKind;
+
+    const MAX_NODES: usize = 20000;
+
+    #[test]
+    fn solve_mate_in_one_test() {
+        let encoded = String::from("4k4/9/4P4/9/9/9/9/9/9 b G");
+        let game_state = parse(&encoded).unwrap();
+        let result = solve(&game_state, MAX_NODES).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].from, None);
+        assert_eq!(result[0].to, (4, 1));
+        assert_eq!(result[0].moving_piece_kind, PieceKind::Kinshou);
+    }
+
+    #[test]
+    fn solve_mate_in_three_test() {
+        // the hisha on file 2 supports the drops in front of the ou
+        let encoded = String::from("7kl/9/6P1p/9/9/9/9/9/7R1 b GS");
+        let game_state = parse(&encoded).unwrap();
+        let result = solve(&game_state, MAX_NODES).unwrap();
+        assert_eq!(result.len(), 3);
+
+        let mut final_state = parse(&encoded).unwrap();
+        final_state.give_remaining_pieces(2);
+        for mov in result.iter() {
+            final_state.perform_move(mov).unwrap();
+        }
+        assert!(final_state.in_checkmate(2));
+    }
+
+    #[test]
+    fn solve_checks_only_test() {
+        let encoded = String::from("7kl/9/6P1p/9/9/9/9/9/7R1 b GS");
+        let game_state = parse(&encoded).unwrap();
+        let result = solve(&game_state, MAX_NODES).unwrap();
+
+        let mut replay = parse(&encoded).unwrap();
+        replay.give_remaining_pieces(2);
+        for (index, mov) in result.iter().enumerate() {
+            replay.perform_move(mov).unwrap();
+            if index % 2 == 0 {
+                assert!(replay.current_player_in_check());
+            }
+        }
+    }
+
+    #[test]
+    fn solve_no_mate_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9 b P");
+        let game_state = parse(&encoded).unwrap();
+        let result = solve(&game_state, MAX_NODES);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn solve_no_checks_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/8L b -");
+        let game_state = parse(&encoded).unwrap();
+        let result = solve(&game_state, MAX_NODES);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn solve_uchifuzume_test() {
+        // P*1b would be mate, which a fuhyou drop may not give
+        let encoded = String::from("7nk/7n1/8G/9/9/9/9/9/9 b P");
+        let mut game_state = parse(&encoded).unwrap();
+        assert!(!game_state.possible_moves().iter().any(|m| m.from.is_none() && m.to == (8, 1)));
+
+        let result = solve(&game_state, MAX_NODES);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn search_repetition_disproven_test() {
+        // the only check, R9a, returns to a position seen before
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/R3K4 b - 1 moves 9i9a 5a5b 9a9i 5b5a");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = Move { from: Some((0, 8)), to: (0, 0), moving_piece_kind: PieceKind::Hisha, capture_piece_kind: None, promote: false, declare: false };
+        assert_eq!(child_key(&mut game_state, &mov), None);
+
+        let mut first_visit = parse(&String::from("4k4/9/9/9/9/9/9/9/R3K4 b -")).unwrap();
+        assert!(child_key(&mut first_visit, &mov).is_some());
+
+        let mut solver = Solver {
+            attacker: 1,
+            table: HashMap::new(),
+            nodes: 0,
+            max_nodes: MAX_NODES
+        };
+        let result = solver.search(&mut game_state, INFINITY, INFINITY);
+        assert_eq!(result, DISPROVEN);
+    }
+
+    #[test]
+    fn solve_defender_holds_remaining_pieces_test() {
+        // R*1c would be mate if the defender had nothing to block with
+        let encoded = String::from("7lk/7n1/8R/9/9/9/9/9/9 w -");
+        let mut game_state = parse(&encoded).unwrap();
+        assert!(game_state.possible_moves().is_empty());
+
+        let encoded = String::from("7lk/7n1/9/9/9/9/9/9/9 b R");
+        let game_state = parse(&encoded).unwrap();
+        let result = solve(&game_state, MAX_NODES);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn combine_attacking_test() {
+        let children = vec![
+            Entry { proof: 3, disproof: 2, distance: 0 },
+            Entry { proof: 0, disproof: INFINITY, distance: 2 }
+        ];
+        let result = combine(&children, true);
+        assert_eq!(result, Entry { proof: 0, disproof: INFINITY, distance: 3 });
+    }
+
+    #[test]
+    fn combine_defending_test() {
+        let children = vec![
+            Entry { proof: 3, disproof: 2, distance: 0 },
+            Entry { proof: 1, disproof: 4, distance: 0 }
+        ];
+        let result = combine(&children, false);
+        assert_eq!(result.proof, 4);
+        assert_eq!(result.disproof, 2);
+    }
+}
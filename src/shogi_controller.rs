@@ -1,17 +1,79 @@
 use std::env;
+use std::collections::HashMap;
 
 use actix_web::HttpResponse;
 
 use super::shogi;
+use super::shogi::notation::Notation;
+
+// a handicap parameter names the starting position, the body then only holds the moves played from it
+//   ?handicap=two_piece
+//   moves 7a6b 7g7f
+pub fn game_data(params: &HashMap<String, String>, game_data: &String) -> Result<String, &'static str> {
+    let name = match params.get("handicap") {
+        Some(name) => name,
+        None => return Ok(game_data.to_string())
+    };
+
+    let position = shogi::state::handicap::parse(name)?.position();
+    let moves = game_data.trim();
+
+    if moves.is_empty() {
+        Ok(position.to_string())
+    } else if moves.starts_with("moves ") {
+        Ok(format!("{} {}", position, moves))
+    } else {
+        Err("shogi_controller::game_data - Invalid moves")
+    }
+}
+
+// the notation the move is written in, western when not given
+//   ?notation=japanese
+pub fn notation(params: &HashMap<String, String>) -> Result<Notation, &'static str> {
+    match params.get("notation") {
+        Some(name) => shogi::notation::parse(name),
+        None => Ok(Notation::Western)
+    }
+}
 
-pub fn opening(game_data: &String) -> HttpResponse {
-    match shogi::openings::recommended_move(game_data) {
-        Some(m) => HttpResponse::Ok().body(format!("{}\n", m)),
+fn format_move(game_data: &str, game_state: &mut shogi::state::game_state::GameState, mov: shogi::state::mov::Move, notation: Notation) -> String {
+    let previous_to = shogi::notation::previous_to(game_data, game_state.variant.size());
+    shogi::notation::format(game_state, mov, notation, previous_to)
+}
+
+pub fn opening(game_data: &String, notation: Notation) -> HttpResponse {
+    let recommended_move = match shogi::openings::recommended_move(game_data) {
+        Some(m) => m,
+        None => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    if notation == Notation::Western {
+        return HttpResponse::Ok().body(format!("{}\n", recommended_move));
+    }
+
+    // the openings db is written in western notation
+    let mut game_state = match shogi::state::game_state::parse(game_data) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    // written with or without the point of departure
+    let moves = game_state.possible_moves();
+    let mov = moves.iter().find(|m| {
+        shogi::state::external_mov::build(&game_state, (*m).clone()).format() == recommended_move
+    }).or_else(|| moves.iter().find(|m| {
+        let mut external_move = shogi::state::external_mov::build(&game_state, (*m).clone());
+        external_move.disambiguation = false;
+        external_move.format() == recommended_move
+    })).cloned();
+
+    match mov {
+        Some(m) => HttpResponse::Ok().body(format!("{}\n", format_move(game_data, &mut game_state, m, notation))),
         None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     }
 }
 
-pub fn minimax(game_data: &String) -> HttpResponse {
+pub fn minimax(game_data: &String, notation: Notation) -> HttpResponse {
     let mut game_state = match shogi::state::game_state::parse(game_data) {
         Ok(gs) => gs,
         Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
@@ -25,15 +87,12 @@ pub fn minimax(game_data: &String) -> HttpResponse {
     let recommended_move = shogi::minimax::recommended_move(&mut game_state, minimax_depth);
 
     match recommended_move {
-        Some(m) => {
-            let external_move = build_external_move(&game_state, m);
-            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
-        },
+        Some(m) => HttpResponse::Ok().body(format!("{}\n", format_move(game_data, &mut game_state, m, notation))),
         None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     }
 }
 
-pub fn mcts(game_data: &String) -> HttpResponse {
+pub fn mcts(game_data: &String, notation: Notation) -> HttpResponse {
     let mut game_state = match shogi::state::game_state::parse(game_data) {
         Ok(gs) => gs,
         Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
@@ -52,10 +111,7 @@ pub fn mcts(game_data: &String) -> HttpResponse {
     let recommended_move = shogi::mcts::recommended_move(&mut game_state, mcts_simulation_count, mcts_simulation_depth);
 
     match recommended_move {
-        Ok(m) => {
-            let external_move = build_external_move(&game_state, m);
-            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
-        },
+        Ok(m) => HttpResponse::Ok().body(format!("{}\n", format_move(game_data, &mut game_state, m, notation))),
         Err(e) => {
             println!("{}", e);
             HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
@@ -63,67 +119,34 @@ pub fn mcts(game_data: &String) -> HttpResponse {
     }
 }
 
-fn build_external_move(game_state: &shogi::state::game_state::GameState, mov: shogi::state::mov::Move) -> shogi::state::external_mov::ExternalMove {
-    let mut new_state = game_state.clone();
-    let _result = new_state.perform_move(&mov);
-
-    let file_disambiguation = match mov.from {
-        Some(f) => {
-            let mut file_disambiguation_count: i8 = 0;
-            for (y, row) in game_state.squares.iter().enumerate() {
-                for s in row.iter() {
-                    let s_player_number = s.player_number;
-                    let s_kind = s.kind;
-                    if s_kind == mov.moving_piece_kind && y as i8 == f.1 && s_player_number == game_state.current_player_number {
-                        file_disambiguation_count += 1;
-                    }
-                }
-            }
-            file_disambiguation_count > 1
-        },
-        None => false
+pub fn tsume(game_data: &String, notation: Notation) -> HttpResponse {
+    let mut game_state = match shogi::state::game_state::parse(game_data) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     };
 
-    let rank_disambiguation = match mov.from {
-        Some(f) => {
-            let mut count: i8 = 0;
-            for row in game_state.squares.iter() {
-                for (x, s) in row.iter().enumerate() {
-                    let s_player_number = s.player_number;
-                    let s_kind = s.kind;
-                    if s_kind == mov.moving_piece_kind && x as i8 == f.0 && s_player_number == game_state.current_player_number {
-                        count += 1;
-                    }
+    let tsume_max_nodes: usize = env::var("SHOGI_TSUME_MAX_NODES")
+        .unwrap_or_else(|_| "100000".to_string())
+        .parse()
+        .expect("SHOGI_TSUME_MAX_NODES must be a number");
+
+    match shogi::tsume::solve(&game_state, tsume_max_nodes) {
+        Some(moves) => {
+            // moves are described from the position they are played in
+            game_state.give_remaining_pieces(shogi::state::square::opposing_player(game_state.current_player_number));
+            let mut external_moves = vec![];
+            let mut previous_to = shogi::notation::previous_to(game_data, game_state.variant.size());
+            for m in moves {
+                external_moves.push(shogi::notation::format(&mut game_state, m.clone(), notation, previous_to));
+                previous_to = Some(m.to);
+                if game_state.perform_move(&m).is_err() {
+                    return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n");
                 }
             }
-            count > 1
+            HttpResponse::Ok().body(format!("{}\n", external_moves.join(" ")))
         },
-        None => false
-    };
-
-    let disambiguation = file_disambiguation || rank_disambiguation;
-
-    let promotion_possible = if shogi::state::square::PROMOTABLE_PIECE_KINDS.iter().any(|pk| *pk == mov.moving_piece_kind) {
-        if game_state.current_player_number == 1 {
-            mov.to.1 == 0 || mov.to.1 == 1 || mov.to.1 == 2
-        } else {
-            mov.to.1 == 6 || mov.to.1 == 7 || mov.to.1 == 8
-        }
-    } else {
-        false
-    };
-
-    let external_mov = shogi::state::external_mov::ExternalMove {
-        from: mov.from,
-        to: mov.to,
-        moving_piece_kind: mov.moving_piece_kind,
-        capture_piece_kind: mov.capture_piece_kind,
-        promote: mov.promote,
-        promotion_possible,
-        disambiguation
-    };
-
-    external_mov
+        None => HttpResponse::UnprocessableEntity().body("No Mate\n")
+    }
 }
 
 #[cfg(test)]
@@ -131,10 +154,76 @@ mod tests {
     use super::*;
     use actix_web::body::MessageBody;
 
+    #[test]
+    fn game_data_without_handicap_test() {
+        let params = HashMap::new();
+        let result = game_data(&params, &String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -"));
+        assert_eq!(result, Ok(String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -")));
+    }
+
+    #[test]
+    fn game_data_handicap_test() {
+        let mut params = HashMap::new();
+        params.insert(String::from("handicap"), String::from("two_piece"));
+        let result = game_data(&params, &String::from(""));
+        assert_eq!(result, Ok(String::from("lnsgkgsnl/9/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1")));
+    }
+
+    #[test]
+    fn game_data_handicap_with_moves_test() {
+        let mut params = HashMap::new();
+        params.insert(String::from("handicap"), String::from("lance"));
+        let result = game_data(&params, &String::from("moves 3c3d 7g7f\n"));
+        assert_eq!(result, Ok(String::from("lnsgkgsn1/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1 moves 3c3d 7g7f")));
+    }
+
+    #[test]
+    fn game_data_unknown_handicap_test() {
+        let mut params = HashMap::new();
+        params.insert(String::from("handicap"), String::from("queen"));
+        let result = game_data(&params, &String::from(""));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn opening_handicap_test() {
+        let mut params = HashMap::new();
+        params.insert(String::from("handicap"), String::from("two_piece"));
+        let result = opening(&game_data(&params, &String::from("")).unwrap(), Notation::Western);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "S-62\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn opening_handicap_with_moves_test() {
+        let mut params = HashMap::new();
+        params.insert(String::from("handicap"), String::from("two_piece"));
+        let result = opening(&game_data(&params, &String::from("moves 7a6b")).unwrap(), Notation::Western);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "P-76\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_handicap_test() {
+        let mut params = HashMap::new();
+        params.insert(String::from("handicap"), String::from("ten_piece"));
+        let result = minimax(&game_data(&params, &String::from("moves 5a5b")).unwrap(), Notation::Western);
+
+        assert_eq!(result.status(), 200);
+    }
+
     #[test]
     fn opening_valid_test() {
         let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/7P1/PPPPPPP1P/1B5R1/LNSGKGSNL w -");
-        let result = opening(&game_state);
+        let result = opening(&game_state, Notation::Western);
 
         assert_eq!(result.status(), 200);
         match result.into_body().try_into_bytes() {
@@ -146,7 +235,7 @@ mod tests {
     #[test]
     fn opening_no_moves_test() {
         let game_state = String::from("lnsgkgsn1/1r5bl/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
-        let result = opening(&game_state);
+        let result = opening(&game_state, Notation::Western);
 
         assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {
@@ -158,19 +247,68 @@ mod tests {
     #[test]
     fn minimax_valid_test() {
        let game_state = String::from("lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
-       let result = minimax(&game_state);
+       let result = minimax(&game_state, Notation::Western);
 
        assert_eq!(result.status(), 200);
        match result.into_body().try_into_bytes() {
-          Ok(bytes) => assert_eq!(bytes, "B*41\n"),
+          Ok(bytes) => assert_eq!(bytes, "B*42\n"),
           Err(_) => assert!(false, "unexpected body")
        };
     }
 
+    #[test]
+    fn notation_test() {
+        let mut params = HashMap::new();
+        assert_eq!(notation(&params), Ok(Notation::Western));
+
+        params.insert(String::from("notation"), String::from("csa"));
+        assert_eq!(notation(&params), Ok(Notation::Csa));
+    }
+
+    #[test]
+    fn opening_notation_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/7P1/PPPPPPP1P/1B5R1/LNSGKGSNL w -");
+        let result = opening(&game_state, Notation::Csa);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "-8384FU\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_japanese_notation_test() {
+        let game_state = String::from("lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
+        let result = minimax(&game_state, Notation::Japanese);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "４二角\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_with_moves_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7f 3c3d");
+        let result = minimax(&game_state, Notation::Western);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn minimax_with_illegal_moves_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1 moves 7g7e");
+        let result = minimax(&game_state, Notation::Western);
+
+        assert_eq!(result.status(), 422);
+    }
+
     #[test]
     fn minimax_invalid_game_state_test() {
         let game_state = String::from("xnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
-        let result = minimax(&game_state);
+        let result = minimax(&game_state, Notation::Western);
 
         assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {
@@ -182,7 +320,7 @@ mod tests {
     #[test]
     fn minimax_no_moves_test() {
         let game_state = String::from("k7R/8R/9/9/9/9/9/9/8K w -");
-        let result = minimax(&game_state);
+        let result = minimax(&game_state, Notation::Western);
 
         assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {
@@ -194,7 +332,7 @@ mod tests {
     #[test]
     fn mcts_valid_test() {
         let game_state = String::from("lnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
-        let result = mcts(&game_state);
+        let result = mcts(&game_state, Notation::Western);
 
         assert_eq!(result.status(), 200);
         match result.into_body().try_into_bytes() {
@@ -206,7 +344,7 @@ mod tests {
     #[test]
     fn mcts_invalid_game_state_test() {
         let game_state = String::from("xnsgk2nl/1r4gs1/p1pppp1pp/1p4p2/7P1/2P6/PP1PPPP1P/1SG4R1/LN2KGSNL b Bb");
-        let result = mcts(&game_state);
+        let result = mcts(&game_state, Notation::Western);
 
         assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {
@@ -214,5 +352,52 @@ mod tests {
            Err(_) => assert!(false, "unexpected body")
         };
     }
-}
 
+    #[test]
+    fn tsume_valid_test() {
+        let game_state = String::from("4k4/9/4P4/9/9/9/9/9/9 b G");
+        let result = tsume(&game_state, Notation::Western);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "G*52\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn tsume_usi_notation_test() {
+        let game_state = String::from("4k4/9/4P4/9/9/9/9/9/9 b G");
+        let result = tsume(&game_state, Notation::Usi);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "G*5b\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn tsume_no_mate_test() {
+        let game_state = String::from("4k4/9/9/9/9/9/9/9/9 b P");
+        let result = tsume(&game_state, Notation::Western);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "No Mate\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn tsume_invalid_game_state_test() {
+        let game_state = String::from("4x4/9/4P4/9/9/9/9/9/9 b G");
+        let result = tsume(&game_state, Notation::Western);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+}
@@ -0,0 +1,179 @@
+use std::env;
+use actix_web::HttpResponse;
+use super::shogi;
+use shogi::state::variant::Variant;
+
+// environment variables are prefixed with the game type, e.g. MINISHOGI_MINIMAX_DEPTH
+fn env_prefix(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Minishogi => "MINISHOGI",
+        Variant::Judkins => "JUDKINS_SHOGI",
+        Variant::Standard => "SHOGI"
+    }
+}
+
+fn env_number<T: std::str::FromStr>(variant: Variant, name: &str, default: &str) -> T {
+    let key = format!("{}_{}", env_prefix(variant), name);
+    env::var(&key)
+        .unwrap_or_else(|_| default.to_string())
+        .parse()
+        .unwrap_or_else(|_| panic!("{} must be a number", key))
+}
+
+pub fn minimax(game_data: &String, variant: Variant) -> HttpResponse {
+    let mut game_state = match shogi::state::game_state::parse_with_variant(game_data, variant) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let minimax_depth: i8 = env_number(variant, "MINIMAX_DEPTH", "0");
+
+    let recommended_move = shogi::minimax::recommended_move(&mut game_state, minimax_depth);
+
+    match recommended_move {
+        Some(m) => {
+            let external_move = shogi::state::external_mov::build(&game_state, m);
+            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
+        },
+        None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    }
+}
+
+pub fn mcts(game_data: &String, variant: Variant) -> HttpResponse {
+    let mut game_state = match shogi::state::game_state::parse_with_variant(game_data, variant) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    let mcts_simulation_count: i16 = env_number(variant, "MCTS_SIMULATION_COUNT", "100");
+    let mcts_simulation_depth: i16 = env_number(variant, "MCTS_SIMULATION_DEPTH", "50");
+
+    let recommended_move = shogi::mcts::recommended_move(&mut game_state, mcts_simulation_count, mcts_simulation_depth);
+
+    match recommended_move {
+        Ok(m) => {
+            let external_move = shogi::state::external_mov::build(&game_state, m);
+            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
+        },
+        Err(e) => {
+            println!("{}", e);
+            HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::MessageBody;
+
+    #[test]
+    fn env_prefix_test() {
+        assert_eq!(env_prefix(Variant::Minishogi), "MINISHOGI");
+        assert_eq!(env_prefix(Variant::Judkins), "JUDKINS_SHOGI");
+    }
+
+    #[test]
+    fn minishogi_minimax_valid_test() {
+        let game_state = String::from("rbsgk/4p/5/P4/KGSBR b -");
+        let result = minimax(&game_state, Variant::Minishogi);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn minishogi_minimax_capture_test() {
+        let game_state = String::from("2k2/5/5/r4/K4 b -");
+        let result = minimax(&game_state, Variant::Minishogi);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "Kx54\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minishogi_minimax_invalid_game_state_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let result = minimax(&game_state, Variant::Minishogi);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn minishogi_mcts_valid_test() {
+        let game_state = String::from("rbsgk/4p/5/P4/KGSBR b -");
+        let result = mcts(&game_state, Variant::Minishogi);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn minishogi_mcts_invalid_game_state_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let result = mcts(&game_state, Variant::Minishogi);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn judkins_shogi_minimax_valid_test() {
+        let game_state = String::from("rbnsgk/5p/6/6/P5/KGSNBR b -");
+        let result = minimax(&game_state, Variant::Judkins);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn judkins_shogi_minimax_capture_test() {
+        let game_state = String::from("3k2/6/6/6/r5/K5 b -");
+        let result = minimax(&game_state, Variant::Judkins);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "Kx65\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn judkins_shogi_minimax_invalid_game_state_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let result = minimax(&game_state, Variant::Judkins);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+
+    #[test]
+    fn judkins_shogi_mcts_valid_test() {
+        let game_state = String::from("rbnsgk/5p/6/6/P5/KGSNBR b -");
+        let result = mcts(&game_state, Variant::Judkins);
+
+        assert_eq!(result.status(), 200);
+    }
+
+    #[test]
+    fn judkins_shogi_mcts_invalid_game_state_test() {
+        let game_state = String::from("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b -");
+        let result = mcts(&game_state, Variant::Judkins);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => panic!("unexpected body")
+        };
+    }
+}
@@ -4,12 +4,43 @@ use std::cmp::Ordering;
 
 use rand::prelude::*;
 use crate::xiangqi::state::game_state::GameState;
+use crate::xiangqi::state::game_state::Outcome;
 use crate::xiangqi::state::mov::Move;
 use crate::xiangqi::mcts::node::Node;
 
 const EXPLORATION: f32 = 1.4142135623730950; // square root 2
 
-pub fn recommended_move(game_state: &mut GameState, simulation_count: i16, max_simulation_depth: i16) -> Result<Move, &'static str> {
+// the game played out by the search, janggi shares it with xiangqi
+pub trait State: Clone {
+    type Move: Clone;
+
+    fn current_player_number(&self) -> i8;
+    fn possible_moves(&mut self) -> Vec<Self::Move>;
+    fn perform_move(&mut self, mov: &Self::Move) -> Result<(), &'static str>;
+    fn outcome(&mut self) -> Option<Outcome>;
+}
+
+impl State for GameState {
+    type Move = Move;
+
+    fn current_player_number(&self) -> i8 {
+        self.current_player_number
+    }
+
+    fn possible_moves(&mut self) -> Vec<Move> {
+        GameState::possible_moves(self)
+    }
+
+    fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        GameState::perform_move(self, mov)
+    }
+
+    fn outcome(&mut self) -> Option<Outcome> {
+        GameState::outcome(self)
+    }
+}
+
+pub fn recommended_move<S: State>(game_state: &mut S, simulation_count: i16, max_simulation_depth: i16) -> Result<S::Move, &'static str> {
     let moves = game_state.possible_moves();
     match moves.len() {
         0 => Err("No moves possible"),
@@ -25,6 +56,7 @@ pub fn recommended_move(game_state: &mut GameState, simulation_count: i16, max_s
                 mov: None,
                 state: game_state.clone(),
                 wins: 0,
+                draws: 0,
                 simulations: 0
             };
 
@@ -49,7 +81,12 @@ pub fn recommended_move(game_state: &mut GameState, simulation_count: i16, max_s
                 }
             };
 
-            let best_node = nodes.iter().filter(|n| n.parent_id == Some(1)).max_by(|a,b| a.wins.cmp(&b.wins));
+            let best_node = nodes.iter().filter(|n| n.parent_id == Some(1)).max_by(|a,b| {
+                match a.score().partial_cmp(&b.score()) {
+                    Some(c) => c,
+                    None => Ordering::Equal
+                }
+            });
             if let Some(n) = best_node {
                 match n.mov.clone() {
                     Some(m) => Ok(m),
@@ -62,7 +99,7 @@ pub fn recommended_move(game_state: &mut GameState, simulation_count: i16, max_s
     }
 }
 
-fn selection(nodes: &Vec<Node>) -> Result<i32, &'static str> {
+fn selection<S: State>(nodes: &Vec<Node<S>>) -> Result<i32, &'static str> {
     let leaf_nodes = nodes.iter().filter(|n| n.child_ids.len() == 0 );
     let node_scores = leaf_nodes.map(|n| {
         match nodes.iter().find(|p| Some(p.id) == n.parent_id) {
@@ -84,7 +121,7 @@ fn selection(nodes: &Vec<Node>) -> Result<i32, &'static str> {
     }
 }
 
-fn expansion(nodes: &mut Vec<Node>, id: i32) -> Result<(), &'static str> {
+fn expansion<S: State>(nodes: &mut Vec<Node<S>>, id: i32) -> Result<(), &'static str> {
    let mut counter_id = match nodes.iter().map(|n| n.id).max() {
        Some(max_id) => max_id,
        None => 1
@@ -92,7 +129,7 @@ fn expansion(nodes: &mut Vec<Node>, id: i32) -> Result<(), &'static str> {
 
    if let Some(node) = nodes.iter_mut().find(|n| n.id == id) {
        if node.leaf() {
-            let mut child_nodes: Vec<Node> = Vec::new();
+            let mut child_nodes: Vec<Node<S>> = Vec::new();
             for mov in node.state.possible_moves() {
                 counter_id = counter_id + 1;
                 let mut new_game_state = node.state.clone();
@@ -104,6 +141,7 @@ fn expansion(nodes: &mut Vec<Node>, id: i32) -> Result<(), &'static str> {
                     mov: Some(mov),
                     state: new_game_state,
                     wins: 0,
+                    draws: 0,
                     simulations: 0
                 };
                 child_nodes.push(child_node);
@@ -119,10 +157,11 @@ fn expansion(nodes: &mut Vec<Node>, id: i32) -> Result<(), &'static str> {
     }
 }
 
-fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<bool, &'static str> {
+// whether the player to move at the node wins, None for a draw or a simulation cut off before the end
+fn simulate<S: State>(nodes: &Vec<Node<S>>, id: i32, max_simulation_depth: i16) -> Result<Option<bool>, &'static str> {
     if let Some(node) = nodes.iter().find(|n| n.id == id) {
         let mut end_game = false;
-        let mut winner: Option<i8> = None;
+        let mut outcome: Option<Outcome> = None;
         let mut simulation_depth: i16 = 0;
         let mut current_game_state = node.state.clone();
 
@@ -132,7 +171,7 @@ fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<boo
             match moves.len() {
                 0 => {
                     end_game = true;
-                    winner = current_game_state.winner();
+                    outcome = current_game_state.outcome();
                 },
                 1 => {
                     let selected_move = &moves[0];
@@ -152,24 +191,24 @@ fn simulate(nodes: &Vec<Node>, id: i32, max_simulation_depth: i16) -> Result<boo
                 }
             }
 
-            if let Some(w) = current_game_state.winner() {
+            if let Some(o) = current_game_state.outcome() {
                 end_game = true;
-                winner = Some(w);
+                outcome = Some(o);
             } else {
                 simulation_depth = simulation_depth + 1;
             }
         }
 
-        match winner {
-            Some(w) => Ok(w == node.state.current_player_number),
-            None => Ok(false)
+        match outcome {
+            Some(Outcome::Win(w)) => Ok(Some(w == node.state.current_player_number())),
+            _ => Ok(None)
         }
     } else {
         Err("Node not found")
     }
 }
 
-fn backpropagation(nodes: &mut Vec<Node>, selected_node_id: i32, result: bool) -> Result<(), &'static str> {
+fn backpropagation<S: State>(nodes: &mut Vec<Node<S>>, selected_node_id: i32, result: Option<bool>) -> Result<(), &'static str> {
     if let Some(node) = nodes.iter_mut().find(|n| n.id == selected_node_id) {
         node.add_result(result);
         match node.parent_id {
@@ -181,11 +220,11 @@ fn backpropagation(nodes: &mut Vec<Node>, selected_node_id: i32, result: bool) -
     }
 }
 
-fn upper_confidence_bound(parent_node: &Node, node: &Node) -> f32 {
+fn upper_confidence_bound<S: State>(parent_node: &Node<S>, node: &Node<S>) -> f32 {
     if node.simulations == 0 {
         f32::INFINITY
     } else {
-        ( node.wins as f32 / node.simulations as f32 ) + EXPLORATION * ((parent_node.simulations as f32).ln() / node.simulations as f32).sqrt()
+        ( node.score() / node.simulations as f32 ) + EXPLORATION * ((parent_node.simulations as f32).ln() / node.simulations as f32).sqrt()
     }
 }
 
@@ -231,6 +270,7 @@ mod tests {
             mov: Some(mov_a),
             state: child_node_a_state,
             wins: 1,
+            draws: 0,
             simulations: 10
         };
 
@@ -248,6 +288,7 @@ mod tests {
             mov: Some(mov_b),
             state: child_node_b_state,
             wins: 15,
+            draws: 0,
             simulations: 30
         };
 
@@ -260,6 +301,7 @@ mod tests {
             mov: None,
             state: parent_node_state,
             wins: 16,
+            draws: 0,
             simulations: 40
         };
 
@@ -288,6 +330,7 @@ mod tests {
             mov: Some(mov),
             state: node_state,
             wins: 11,
+            draws: 0,
             simulations: 21
         };
 
@@ -300,6 +343,7 @@ mod tests {
             mov: None,
             state: parent_node_state,
             wins: 7,
+            draws: 0,
             simulations: 10
         };
 
@@ -317,6 +361,7 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0,
         };
 
@@ -340,6 +385,7 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0
         };
         let nodes = vec![node];
@@ -350,6 +396,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn simulate_perpetual_check_test() {
+        // the only reply repeats the position for the third time, losing for the player giving perpetual check
+        let node_state = game_state::parse(&String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a9 e9e8 a9a8 e8e9 a8a9 e9e8 a9a8 e8e9 a8a9")).unwrap();
+        let node = Node {
+            id: 1,
+            parent_id: None,
+            child_ids: Vec::new(),
+            mov: None,
+            state: node_state,
+            wins: 0,
+            draws: 0,
+            simulations: 0
+        };
+        let nodes = vec![node];
+        match simulate(&nodes, 1, 5) {
+            Ok(result) => assert_eq!(result, Some(true)),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn simulate_repetition_draw_test() {
+        // the generals step back and forth without checking, the third repetition is a draw
+        let mut node_state = game_state::parse(&String::from("4k4/9/9/9/9/9/9/9/9/3K5 w - - 0 1 moves d0d1 e9e8 d1d0 e8e9 d0d1 e9e8 d1d0 e8e9")).unwrap();
+        assert_eq!(node_state.outcome(), Some(Outcome::Draw));
+        let node = Node {
+            id: 1,
+            parent_id: None,
+            child_ids: Vec::new(),
+            mov: None,
+            state: node_state,
+            wins: 0,
+            draws: 0,
+            simulations: 0
+        };
+        let nodes = vec![node];
+        match simulate(&nodes, 1, 5) {
+            Ok(result) => assert_eq!(result, None),
+            Err(e) => panic!("{}", e)
+        }
+    }
+
     #[test]
     fn backpropagation_test() {
         let node_state = game_state::parse(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b - - 1 0")).unwrap();
@@ -366,6 +455,7 @@ mod tests {
             mov: Some(mov),
             state: node_state,
             wins: 11,
+            draws: 0,
             simulations: 21
         };
 
@@ -378,12 +468,13 @@ mod tests {
             mov: None,
             state: parent_node_state,
             wins: 7,
+            draws: 0,
             simulations: 10
         };
 
         let mut nodes = vec![parent_node, node];
 
-        match backpropagation(&mut nodes, 2, true) {
+        match backpropagation(&mut nodes, 2, Some(true)) {
             Ok(_) => {
                match nodes.iter().find(|n| n.id == 1) {
                     Some(n) => {
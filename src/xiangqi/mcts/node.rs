@@ -1,20 +1,20 @@
-use crate::xiangqi::state::game_state::GameState;
-use crate::xiangqi::state::mov::Move;
+use crate::xiangqi::mcts::State;
 
 // Key: state:move
 // Value: state:wins:simulations
 
-pub struct Node {
+pub struct Node<S: State> {
     pub id: i32,
     pub parent_id: Option<i32>,
     pub child_ids: Vec<i32>,
-    pub mov: Option<Move>,
-    pub state: GameState,
+    pub mov: Option<S::Move>,
+    pub state: S,
     pub wins: i32,
+    pub draws: i32,
     pub simulations: i32,
 }
 
-impl Node {
+impl<S: State> Node<S> {
     pub fn leaf(&self) -> bool {
         self.child_ids.len() == 0
     }
@@ -23,13 +23,20 @@ impl Node {
         self.child_ids = child_ids;
     }
 
-    pub fn add_result(&mut self, result: bool) -> () {
+    // None for a draw
+    pub fn add_result(&mut self, result: Option<bool>) -> () {
         match result {
-            true => self.wins = self.wins + 1,
-            false => (),
+            Some(true) => self.wins = self.wins + 1,
+            Some(false) => (),
+            None => self.draws += 1,
         }
         self.simulations = self.simulations + 1;
     }
+
+    // wins count one and draws a half
+    pub fn score(&self) -> f32 {
+        self.wins as f32 + self.draws as f32 / 2.0
+    }
 }
 
 #[cfg(test)]
@@ -47,14 +54,36 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0,
         };
-        node.add_result(true);
+        node.add_result(Some(true));
 
         assert_eq!(1, node.wins);
         assert_eq!(1, node.simulations);
     }
 
+    #[test]
+    fn add_result_draw_test() {
+        let node_state = game_state::parse(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0")).unwrap();
+        let mut node = Node {
+            id: 1,
+            parent_id: None,
+            child_ids: Vec::new(),
+            mov: None,
+            state: node_state,
+            wins: 1,
+            draws: 0,
+            simulations: 1
+        };
+        node.add_result(None);
+
+        assert_eq!(1, node.wins);
+        assert_eq!(1, node.draws);
+        assert_eq!(2, node.simulations);
+        assert_eq!(1.5, node.score());
+    }
+
     #[test]
     fn add_result_not_win_test() {
         let node_state = game_state::parse(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0")).unwrap();
@@ -65,9 +94,10 @@ mod tests {
             mov: None,
             state: node_state,
             wins: 0,
+            draws: 0,
             simulations: 0
         };
-        node.add_result(false);
+        node.add_result(Some(false));
 
         assert_eq!(0, node.wins);
         assert_eq!(1, node.simulations);
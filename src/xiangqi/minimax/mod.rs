@@ -1,7 +1,30 @@
 use std::cmp;
 use std::convert::TryFrom;
 use crate::xiangqi::state::square::PieceKind;
+use crate::xiangqi::state::square::opposing_player;
+use crate::xiangqi::state::game_state::Outcome;
 use crate::xiangqi;
+use see::static_exchange_evaluation;
+use see::hanging_value;
+use see::attacked_points;
+
+pub mod see;
+
+// how many captures deep the quiescence search goes past the depth
+const QUIESCENCE_DEPTH: i8 = 2;
+
+const GAME_END_VALUE: i32 = 100000;
+
+const MOBILITY_WEIGHT: i32 = 2;
+// hanging pieces count for part of their value, the quiescence search finds most of the captures
+const HANGING_DIVISOR: i32 = 2;
+
+const KING_DEFENDER_COUNT: usize = 4;
+const KING_ATTACKERS: [PieceKind; 3] = [PieceKind::Chariot, PieceKind::Horse, PieceKind::Cannon];
+const MISSING_DEFENDER_VALUE: i32 = 10;
+const KING_RAISED_VALUE: i32 = 20;
+const KING_OPEN_FILE_VALUE: i32 = 30;
+const KING_ATTACKER_VALUE: i32 = 20;
 
 pub fn recommended_move(game_state: &mut xiangqi::state::game_state::GameState, depth: i8) -> Option<xiangqi::state::mov::Move> {
     let mut new_game_state = game_state.clone();
@@ -54,12 +77,22 @@ pub fn recommended_move(game_state: &mut xiangqi::state::game_state::GameState,
 }
 
 pub fn evaluate(game_state: &mut xiangqi::state::game_state::GameState, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
-    let moves = game_state.possible_moves();
+    if let Some(value) = game_end_evaluation(game_state) {
+        return Ok(value);
+    }
 
-    if depth == 0 || moves.len() == 0 {
+    let mut moves = game_state.possible_moves();
+
+    if moves.is_empty() {
         return Ok(static_evaluation(game_state));
     }
 
+    if depth == 0 {
+        return quiescence(game_state, moves, QUIESCENCE_DEPTH, alpha, beta, maximizing_player);
+    }
+
+    order_moves(game_state, &mut moves);
+
     if maximizing_player {
         let mut max_eval = std::i32::MIN;
         for mov in moves {
@@ -103,40 +136,250 @@ pub fn evaluate(game_state: &mut xiangqi::state::game_state::GameState, depth: i
     }
 }
 
+// Searches captures past the depth so positions aren't evaluated in the middle of an exchange.
+// Captures that lose material are pruned.
+fn quiescence(game_state: &mut xiangqi::state::game_state::GameState, moves: Vec<xiangqi::state::mov::Move>, depth: i8, mut alpha: i32, mut beta: i32, maximizing_player: bool) -> Result<i32, &'static str> {
+    let stand_pat = static_evaluation(game_state);
+
+    if depth == 0 {
+        return Ok(stand_pat);
+    }
+
+    let mut captures_with_value: Vec<(xiangqi::state::mov::Move, i32)> = moves.into_iter().filter_map(|mov| {
+        if mov.capture_piece_kind.is_some() {
+            let value = static_exchange_evaluation(game_state, &mov);
+            if value >= 0 {
+                Some((mov, value))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }).collect();
+    captures_with_value.sort_by(|a, b| (b.1).cmp(&a.1));
+
+    if maximizing_player {
+        let mut max_eval = stand_pat;
+        alpha = cmp::max(alpha, stand_pat);
+        for (mov, _) in captures_with_value {
+            if beta <= alpha {
+                break;
+            }
+
+            game_state.perform_move(&mov)?;
+            let next_moves = game_state.possible_moves();
+            let eval = quiescence(game_state, next_moves, depth - 1, alpha, beta, false)?;
+            game_state.undo_move(&mov)?;
+
+            max_eval = cmp::max(max_eval, eval);
+            alpha = cmp::max(alpha, eval);
+        }
+        Ok(max_eval)
+    } else {
+        let mut min_eval = stand_pat;
+        beta = cmp::min(beta, stand_pat);
+        for (mov, _) in captures_with_value {
+            if beta <= alpha {
+                break;
+            }
+
+            game_state.perform_move(&mov)?;
+            let next_moves = game_state.possible_moves();
+            let eval = quiescence(game_state, next_moves, depth - 1, alpha, beta, true)?;
+            game_state.undo_move(&mov)?;
+
+            min_eval = cmp::min(min_eval, eval);
+            beta = cmp::min(beta, eval);
+        }
+        Ok(min_eval)
+    }
+}
+
+// repetition ends the game with a loss for the player perpetually checking or chasing, otherwise a draw
+pub fn game_end_evaluation(game_state: &xiangqi::state::game_state::GameState) -> Option<i32> {
+    match game_state.repetition_outcome() {
+        Some(Outcome::Win(1)) => Some(GAME_END_VALUE),
+        Some(Outcome::Win(_)) => Some(-GAME_END_VALUE),
+        Some(Outcome::Draw) => Some(0),
+        None => None
+    }
+}
+
+// Winning captures first, losing captures last.
+fn order_moves(game_state: &xiangqi::state::game_state::GameState, moves: &mut [xiangqi::state::mov::Move]) {
+    moves.sort_by_cached_key(|mov| -static_exchange_evaluation(game_state, mov));
+}
+
 // positive -> w
 // negative -> b
-// Piece Scores
+// material, piece-square tables, king safety, mobility and hanging pieces
 pub fn static_evaluation(game_state: &mut xiangqi::state::game_state::GameState) -> i32 {
-    let player_one_pieces_count = player_pieces_count(game_state, 1);
-    let player_two_pieces_count = player_pieces_count(game_state, 2);
-    let pieces_count_value = u_to_i32(player_one_pieces_count) - u_to_i32(player_two_pieces_count);
+    let material_value = player_material_value(game_state, 1) - player_material_value(game_state, 2);
+
+    let piece_square_value = player_piece_square_value(game_state, 1) - player_piece_square_value(game_state, 2);
+
+    let king_safety_value = king_safety_value(game_state, 1) - king_safety_value(game_state, 2);
 
     let player_one_possible_moves_count = game_state.possible_moves_for_player(1).len();
     let player_two_possible_moves_count = game_state.possible_moves_for_player(2).len();
     let possible_moves_value = u_to_i32(player_one_possible_moves_count) - u_to_i32(player_two_possible_moves_count);
 
-    10*pieces_count_value + 1*possible_moves_value
+    let player_one_hanging_value = player_hanging_value(game_state, 1);
+    let player_two_hanging_value = player_hanging_value(game_state, 2);
+    let hanging_value = player_one_hanging_value - player_two_hanging_value;
+
+    material_value + piece_square_value + king_safety_value + MOBILITY_WEIGHT*possible_moves_value - hanging_value/HANGING_DIVISOR
+}
+
+fn player_material_value(game_state: &xiangqi::state::game_state::GameState, player_number: i8) -> i32 {
+    game_state.squares.iter().flatten().filter(|s| s.player_number == player_number && s.kind != PieceKind::King).map(|s| u_to_i32(piece_value(s.kind))).sum()
+}
+
+// the king outweighs everything so static exchanges never give it up
+fn piece_value(piece_kind: PieceKind) -> usize {
+    match piece_kind {
+        PieceKind::King => 10000,
+        PieceKind::Chariot => 900,
+        PieceKind::Cannon => 450,
+        PieceKind::Horse => 400,
+        PieceKind::Elephant => 200,
+        PieceKind::Advisor => 200,
+        PieceKind::Soldier => 100,
+        PieceKind::Empty => 0
+    }
 }
 
-fn player_pieces_count(game_state: &xiangqi::state::game_state::GameState, player_number: i8) -> usize {
-    let mut score: usize = 0;
-    for row in game_state.squares.iter() {
-        for s in row.iter() {
+// Piece-square tables from the player's side, by ranks from the enemy back rank and files from the left.
+fn player_piece_square_value(game_state: &xiangqi::state::game_state::GameState, player_number: i8) -> i32 {
+    let mut value = 0;
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
             if s.player_number == player_number {
-                score += match s.kind {
-                    PieceKind::King => 200,
-                    PieceKind::Chariot => 9,
-                    PieceKind::Cannon => 8,
-                    PieceKind::Elephant => 8,
-                    PieceKind::Advisor => 6,
-                    PieceKind::Horse => 4,
-                    PieceKind::Soldier => 7,
-                    PieceKind::Empty => 0
-                };
+                let (rank, file) = if player_number == 1 { (y, x) } else { (9 - y, 8 - x) };
+                value += piece_square_value(s.kind, rank, file);
+            }
+        }
+    }
+    value
+}
+
+fn piece_square_value(kind: PieceKind, rank: usize, file: usize) -> i32 {
+    match kind {
+        PieceKind::Soldier => SOLDIER_TABLE[rank][file],
+        PieceKind::Horse => HORSE_TABLE[rank][file],
+        PieceKind::Cannon => CANNON_TABLE[rank][file],
+        _ => 0
+    }
+}
+
+// soldiers are worth little until they cross the river, most near the opposing palace
+const SOLDIER_TABLE: [[i32; 9]; 10] = [
+    [ 10,  10,  10,  20,  25,  20,  10,  10,  10],
+    [ 50,  70,  90, 110, 120, 110,  90,  70,  50],
+    [ 50,  70,  90, 110, 120, 110,  90,  70,  50],
+    [ 40,  60,  80,  90, 100,  90,  80,  60,  40],
+    [ 30,  40,  60,  70,  80,  70,  60,  40,  30],
+    [  0,   0,   0,   0,   0,   0,   0,   0,   0],
+    [  0,   0,  -5,   0,  10,   0,  -5,   0,   0],
+    [  0,   0,   0,   0,   0,   0,   0,   0,   0],
+    [  0,   0,   0,   0,   0,   0,   0,   0,   0],
+    [  0,   0,   0,   0,   0,   0,   0,   0,   0]
+];
+
+// horses are strongest in the centre and near the opposing palace, weakest on the edges and at home
+const HORSE_TABLE: [[i32; 9]; 10] = [
+    [-10,   0,   0,   0,   0,   0,   0,   0, -10],
+    [  0,  10,  20,  20,  10,  20,  20,  10,   0],
+    [  0,  10,  20,  25,  20,  25,  20,  10,   0],
+    [  0,  10,  20,  25,  25,  25,  20,  10,   0],
+    [  0,  10,  15,  20,  20,  20,  15,  10,   0],
+    [  0,   5,  10,  15,  15,  15,  10,   5,   0],
+    [ -5,   5,  10,  10,  10,  10,  10,   5,  -5],
+    [ -5,   0,   5,   5,   0,   5,   5,   0,  -5],
+    [-10,  -5,   0,   0, -10,   0,   0,  -5, -10],
+    [-15, -10,  -5,  -5, -10,  -5,  -5, -10, -15]
+];
+
+// cannons are strongest on the central file
+const CANNON_TABLE: [[i32; 9]; 10] = [
+    [  0,   0,   0,   5,  10,   5,   0,   0,   0],
+    [  0,   0,   0,   5,  10,   5,   0,   0,   0],
+    [  0,   0,   0,   5,  10,   5,   0,   0,   0],
+    [  0,   0,   0,   5,  10,   5,   0,   0,   0],
+    [  0,   0,   0,   5,  10,   5,   0,   0,   0],
+    [  0,   0,   0,   5,  10,   5,   0,   0,   0],
+    [  0,   0,   0,   5,  10,   5,   0,   0,   0],
+    [  0,   0,   0,   5,  15,   5,   0,   0,   0],
+    [  0,   0,   0,   0,   0,   0,   0,   0,   0],
+    [  0,   0,   0,   0,   0,   0,   0,   0,   0]
+];
+
+// Advisors and elephants missing count against the player for each opposing attacking piece left,
+// the king is exposed when raised from its back rank or with no piece of its own ahead of it on its file,
+// and opposing pieces close to the king attack it.
+fn king_safety_value(game_state: &xiangqi::state::game_state::GameState, player_number: i8) -> i32 {
+    let king_point = match xiangqi::state::square_set::find_king_point_for_player(&game_state.squares, player_number) {
+        Some(p) => p,
+        None => return 0
+    };
+
+    let opposing_player_number = opposing_player(player_number);
+    let pieces = game_state.squares.iter().flatten();
+
+    let defenders = pieces.clone().filter(|s| s.player_number == player_number && (s.kind == PieceKind::Advisor || s.kind == PieceKind::Elephant)).count();
+    let attackers = pieces.filter(|s| s.player_number == opposing_player_number && KING_ATTACKERS.contains(&s.kind)).count();
+    let missing_defenders = KING_DEFENDER_COUNT.saturating_sub(defenders);
+
+    let mut value = -MISSING_DEFENDER_VALUE * u_to_i32(missing_defenders) * u_to_i32(attackers);
+
+    let back_rank = if player_number == 1 { 9 } else { 0 };
+    value -= KING_RAISED_VALUE * i32::from((king_point.1 - back_rank).abs());
+
+    let forwards = if player_number == 1 { -1 } else { 1 };
+    let mut y = king_point.1 + forwards;
+    let mut covered = false;
+    while (0..=9).contains(&y) {
+        if game_state.squares[y as usize][king_point.0 as usize].player_number == player_number {
+            covered = true;
+            break;
+        }
+        y += forwards;
+    }
+    if !covered {
+        value -= KING_OPEN_FILE_VALUE;
+    }
+
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            let distance = cmp::max((x as i8 - king_point.0).abs(), (y as i8 - king_point.1).abs());
+            if distance <= 2 && s.player_number == opposing_player_number && s.kind != PieceKind::King {
+                value -= KING_ATTACKER_VALUE;
+            }
+        }
+    }
+
+    value
+}
+
+// material the opponent can win by capturing the player's pieces
+fn player_hanging_value(game_state: &xiangqi::state::game_state::GameState, player_number: i8) -> i32 {
+    let other_player_number = match player_number {
+        1 => 2,
+        _ => 1
+    };
+    let attacked = attacked_points(game_state, other_player_number);
+
+    let mut value = 0;
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            let point = (x as i8, y as i8);
+            if s.player_number == player_number && attacked.contains(&point) {
+                value += hanging_value(game_state, point);
             }
         }
     }
-    score
+    value
 }
 
 fn u_to_i32(value: usize) -> i32 {
@@ -178,4 +421,125 @@ mod tests {
             None => assert!(false, "expected move"),
         }
     }
+
+    #[test]
+    fn evaluate_quiescence_test() {
+        let encoded = String::from("5k3/9/9/9/R3h4/9/9/9/9/3K5 w - - 0 1");
+        let mut game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+        let stand_pat = static_evaluation(&mut game_state);
+
+        // chariot captures the hanging horse
+        match evaluate(&mut game_state, 0, std::i32::MIN, std::i32::MAX, true) {
+            Ok(result) => assert!(result > stand_pat),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn game_end_evaluation_perpetual_check_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a9 e9e8 a9a8 e8e9 a8a9 e9e8 a9a8 e8e9 a8a9 e9e8");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(game_end_evaluation(&game_state), Some(-GAME_END_VALUE));
+    }
+
+    #[test]
+    fn game_end_evaluation_draw_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a1 e9e8 a1a0 e8e9 a0a1 e9e8 a1a0 e8e9");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(game_end_evaluation(&game_state), Some(0));
+    }
+
+    #[test]
+    fn game_end_evaluation_in_progress_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a9 e9e8");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+        assert_eq!(game_end_evaluation(&game_state), None);
+    }
+
+    #[test]
+    fn evaluate_perpetual_check_test() {
+        // checking again on a9 forces the king back to e8 for the third time
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a9 e9e8 a9a8 e8e9 a8a9 e9e8 a9a8 e8e9 a8a9");
+        let mut game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        match evaluate(&mut game_state, 1, i32::MIN, i32::MAX, false) {
+            Ok(v) => assert_eq!(v, -GAME_END_VALUE),
+            Err(e) => assert!(false, "{}", e)
+        }
+    }
+
+    #[test]
+    fn recommended_move_horse_over_elephant_test() {
+        // valued above a horse, the elephant used to be taken instead
+        let encoded = String::from("3k5/9/9/9/2e1R2h1/9/9/9/9/4K4 w - - 0 1");
+        let mut game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        let mov = recommended_move(&mut game_state, 0).unwrap();
+
+        assert_eq!(mov.from, (4, 4));
+        assert_eq!(mov.to, (7, 4));
+        assert_eq!(mov.capture_piece_kind, Some(PieceKind::Horse));
+    }
+
+    #[test]
+    fn player_material_value_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(player_material_value(&game_state, 1), 4800);
+        assert_eq!(player_material_value(&game_state, 2), 4800);
+    }
+
+    #[test]
+    fn piece_square_value_soldier_test() {
+        assert!(piece_square_value(PieceKind::Soldier, 4, 4) > piece_square_value(PieceKind::Soldier, 5, 4));
+        assert!(piece_square_value(PieceKind::Soldier, 2, 4) > piece_square_value(PieceKind::Soldier, 4, 4));
+    }
+
+    #[test]
+    fn piece_square_value_horse_test() {
+        assert!(piece_square_value(PieceKind::Horse, 4, 4) > piece_square_value(PieceKind::Horse, 4, 0));
+        assert!(piece_square_value(PieceKind::Horse, 7, 2) > piece_square_value(PieceKind::Horse, 9, 1));
+    }
+
+    #[test]
+    fn player_piece_square_value_crossed_soldiers_test() {
+        let encoded = String::from("4k4/9/9/9/4P4/4p4/9/9/9/3K5 w - - 0 1");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(player_piece_square_value(&game_state, 1), 80);
+        assert_eq!(player_piece_square_value(&game_state, 2), 80);
+    }
+
+    #[test]
+    fn king_safety_value_intact_test() {
+        let encoded = String::from("4k4/9/9/9/9/r8/4P4/9/9/2EAKAE2 w - - 0 1");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(king_safety_value(&game_state, 1), 0);
+    }
+
+    #[test]
+    fn king_safety_value_missing_advisor_test() {
+        let encoded = String::from("4k4/9/9/9/9/r8/4P4/9/9/2E1KAE2 w - - 0 1");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(king_safety_value(&game_state, 1), -MISSING_DEFENDER_VALUE);
+    }
+
+    #[test]
+    fn king_safety_value_raised_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/4P4/9/4K4/2EA1AE2 w - - 0 1");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(king_safety_value(&game_state, 1), -KING_RAISED_VALUE);
+    }
+
+    #[test]
+    fn king_safety_value_open_file_and_attacker_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/3h5/9/2EAKAE2 w - - 0 1");
+        let game_state = xiangqi::state::game_state::parse(&encoded).unwrap();
+
+        assert_eq!(king_safety_value(&game_state, 1), -KING_OPEN_FILE_VALUE - KING_ATTACKER_VALUE);
+    }
 }
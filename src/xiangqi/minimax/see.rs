@@ -0,0 +1,212 @@
+use std::cmp;
+use crate::xiangqi::state::game_state::GameState;
+use crate::xiangqi::state::mov::Move;
+use crate::xiangqi::state::point::king_destination_points;
+use crate::xiangqi::state::square::PieceKind;
+use crate::xiangqi::state::square::destinations;
+use crate::xiangqi::state::square::Square;
+use crate::xiangqi::state::square::opposing_player;
+use crate::xiangqi::state::square_set::find_by_x_and_y;
+use crate::xiangqi::state::square_set::find_by_x_and_y_mut;
+use crate::xiangqi::state::square_set::find_king_point_for_player;
+use crate::xiangqi::state::square_set::threats_to_point;
+use super::piece_value;
+
+// Static Exchange Evaluation
+// The material won or lost by a capture once both sides have finished recapturing on the to square.
+// Each side recaptures with its least valuable attacker and may stop when recapturing loses material.
+pub fn static_exchange_evaluation(game_state: &GameState, mov: &Move) -> i32 {
+    let capture_piece_kind = match mov.capture_piece_kind {
+        Some(pk) => pk,
+        None => return 0
+    };
+
+    let player_number = match find_by_x_and_y(&game_state.squares, mov.from) {
+        Some(s) => s.player_number,
+        None => return 0
+    };
+
+    let mut squares = game_state.squares.clone();
+    capture_on_board(&mut squares, mov.from, mov.to, Square { player_number, kind: mov.moving_piece_kind });
+
+    swap_off(&mut squares, mov.to, piece_value_i32(capture_piece_kind), mov.moving_piece_kind, player_number)
+}
+
+// The material the opponent wins by capturing the piece on the point, 0 if the piece is safe.
+// Only the first capture and recapture are counted, so the board isn't copied.
+pub fn hanging_value(game_state: &GameState, point: (i8, i8)) -> i32 {
+    let target = match find_by_x_and_y(&game_state.squares, point) {
+        Some(s) => *s,
+        None => return 0
+    };
+
+    if target.unoccupied() || target.kind == PieceKind::King {
+        return 0;
+    }
+
+    let other_player_number = opposing_player(target.player_number);
+
+    let attacker_kind = match least_valuable_attacker(&game_state.squares, point, other_player_number) {
+        Some((_, kind)) => kind,
+        None => return 0
+    };
+
+    let target_value = piece_value_i32(target.kind);
+    match least_valuable_attacker(&game_state.squares, point, target.player_number) {
+        Some(_) => cmp::max(target_value - piece_value_i32(attacker_kind), 0),
+        None => target_value
+    }
+}
+
+// Points the player's pieces can capture on.
+pub fn attacked_points(game_state: &GameState, player_number: i8) -> Vec<(i8, i8)> {
+    let mut acc = vec![];
+    for (y, row) in game_state.squares.iter().enumerate() {
+        for (x, s) in row.iter().enumerate() {
+            if s.player_number == player_number {
+                let point = (x as i8, y as i8);
+                for to_point in destinations(s.kind, s.player_number, point, game_state, false) {
+                    if !acc.contains(&to_point) {
+                        acc.push(to_point);
+                    }
+                }
+            }
+        }
+    }
+    acc
+}
+
+// Resolves the captures on the target after the first capture.
+// Attackers are removed from the board as they capture so chariots behind them (x-rays) join in
+// and cannons gain or lose their screens.
+fn swap_off(squares: &mut Vec<Vec<Square>>, target: (i8, i8), capture_value: i32, occupant_kind: PieceKind, occupant_player_number: i8) -> i32 {
+    let mut gains = vec![capture_value];
+    let mut occupant_value = piece_value_i32(occupant_kind);
+    let mut side = opposing_player(occupant_player_number);
+
+    while let Some((attacker_point, attacker_kind)) = least_valuable_attacker(squares, target, side) {
+        let previous_gain = gains[gains.len() - 1];
+        gains.push(occupant_value - previous_gain);
+        capture_on_board(squares, attacker_point, target, Square { player_number: side, kind: attacker_kind });
+        occupant_value = piece_value_i32(attacker_kind);
+        side = opposing_player(side);
+    }
+
+    // each side only recaptures if it doesn't lose material
+    while gains.len() > 1 {
+        if let Some(gain) = gains.pop() {
+            let last = gains.len() - 1;
+            gains[last] = -cmp::max(-gains[last], gain);
+        }
+    }
+
+    gains[0]
+}
+
+fn least_valuable_attacker(squares: &Vec<Vec<Square>>, target: (i8, i8), player_number: i8) -> Option<((i8, i8), PieceKind)> {
+    let mut attacker: Option<((i8, i8), PieceKind)> = None;
+
+    // threats_to_point excludes the king
+    for point in threats_to_point(squares, target, opposing_player(player_number)) {
+        if let Some(s) = find_by_x_and_y(squares, point) {
+            let cheaper = match attacker {
+                Some((_, kind)) => piece_value(s.kind) < piece_value(kind),
+                None => true
+            };
+            if cheaper {
+                attacker = Some((point, s.kind));
+            }
+        }
+    }
+
+    if attacker.is_none() {
+        if let Some(king_point) = find_king_point_for_player(squares, player_number) {
+            if king_destination_points(king_point, player_number).contains(&target) {
+                attacker = Some((king_point, PieceKind::King));
+            }
+        }
+    }
+
+    attacker
+}
+
+fn capture_on_board(squares: &mut Vec<Vec<Square>>, from: (i8, i8), to: (i8, i8), square: Square) {
+    if let Some(s) = find_by_x_and_y_mut(squares, from) {
+        s.player_number = 0;
+        s.kind = PieceKind::Empty;
+    }
+    if let Some(s) = find_by_x_and_y_mut(squares, to) {
+        s.player_number = square.player_number;
+        s.kind = square.kind;
+    }
+}
+
+fn piece_value_i32(piece_kind: PieceKind) -> i32 {
+    piece_value(piece_kind) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xiangqi::state::game_state::parse;
+
+    fn find_move(game_state: &mut GameState, from: (i8, i8), to: (i8, i8)) -> Move {
+        let moves = game_state.possible_moves();
+        moves.iter().find(|m| m.from == from && m.to == to).unwrap().clone()
+    }
+
+    #[test]
+    fn static_exchange_evaluation_undefended_test() {
+        let encoded = String::from("4ck3/9/9/9/R3p4/9/9/9/9/3K5 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (0, 4), (4, 4));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), 100);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_cannon_screen_test() {
+        // advisor screens the cannon defending the soldier
+        let encoded = String::from("4ck3/4a4/9/9/R3p4/9/9/9/9/3K5 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (0, 4), (4, 4));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), -800);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_cannon_screen_removed_test() {
+        // chariot screening the cannon moves onto the target so the cannon can't recapture
+        let encoded = String::from("5k3/9/9/9/4h3r/9/4R4/9/4C4/3K5 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (4, 6), (4, 4));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), -500);
+    }
+
+    #[test]
+    fn static_exchange_evaluation_x_ray_test() {
+        // second chariot behind the first recaptures
+        let encoded = String::from("5k3/9/9/9/4p3r/9/4R4/4R4/9/3K5 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let mov = find_move(&mut game_state, (4, 6), (4, 4));
+
+        assert_eq!(static_exchange_evaluation(&game_state, &mov), 100);
+    }
+
+    #[test]
+    fn hanging_value_test() {
+        let encoded = String::from("5k3/9/9/9/R3h4/9/9/9/9/3K5 b - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+
+        assert_eq!(hanging_value(&game_state, (4, 4)), 400);
+    }
+
+    #[test]
+    fn hanging_value_defended_test() {
+        let encoded = String::from("4ck3/4a4/9/9/R3p4/9/9/9/9/3K5 b - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+
+        assert_eq!(hanging_value(&game_state, (4, 4)), 0);
+    }
+}
@@ -2,3 +2,5 @@ pub mod state;
 pub mod mcts;
 pub mod minimax;
 pub mod openings;
+pub mod notation;
+pub mod record;
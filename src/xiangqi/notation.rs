@@ -0,0 +1,97 @@
+use crate::xiangqi::state::external_mov;
+use crate::xiangqi::state::game_state::GameState;
+use crate::xiangqi::state::iccs_mov;
+use crate::xiangqi::state::mov::Move;
+
+// styles a move can be written in
+//   wxf - C2=5, H8+7, R++2
+//   iccs - h2e2, b0c2
+//   chinese - 炮二平五, 馬８進７, 前車進二
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Notation {
+    Wxf,
+    Iccs,
+    Chinese
+}
+
+pub fn parse(name: &str) -> Result<Notation, &'static str> {
+    match name {
+        "wxf" => Ok(Notation::Wxf),
+        "iccs" => Ok(Notation::Iccs),
+        "chinese" => Ok(Notation::Chinese),
+        _ => Err("notation::parse - Unknown notation")
+    }
+}
+
+// the move by the current player
+pub fn format(game_state: &GameState, mov: Move, notation: Notation) -> String {
+    match notation {
+        Notation::Wxf => external_mov::build(game_state, mov).format(),
+        Notation::Iccs => iccs_mov::format(&mov),
+        Notation::Chinese => external_mov::build(game_state, mov).format_chinese()
+    }
+}
+
+// a legal move by the current player
+pub fn parse_move(encoded: &str, game_state: &mut GameState, notation: Notation) -> Result<Move, &'static str> {
+    match notation {
+        Notation::Wxf => external_mov::parse(encoded, game_state),
+        Notation::Iccs => iccs_mov::parse(encoded, game_state),
+        Notation::Chinese => external_mov::parse_chinese(encoded, game_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xiangqi::state::game_state::parse as parse_game_state;
+
+    const POSITION: &str = "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2e2";
+
+    fn format_horse_development(notation: Notation) -> String {
+        let mut game_state = parse_game_state(&String::from(POSITION)).unwrap();
+        let mov = game_state.possible_moves().into_iter().find(|m| m.from == (7, 0) && m.to == (6, 2)).unwrap();
+        format(&game_state, mov, notation)
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(parse("chinese"), Ok(Notation::Chinese));
+        assert_eq!(parse("ucci"), Err("notation::parse - Unknown notation"));
+    }
+
+    #[test]
+    fn format_wxf_test() {
+        assert_eq!(format_horse_development(Notation::Wxf), "H8+7");
+    }
+
+    #[test]
+    fn format_iccs_test() {
+        assert_eq!(format_horse_development(Notation::Iccs), "h9g7");
+    }
+
+    #[test]
+    fn format_chinese_test() {
+        assert_eq!(format_horse_development(Notation::Chinese), "馬８進７");
+    }
+
+    #[test]
+    fn parse_move_round_trip_test() {
+        let positions = [
+            POSITION,
+            "3k5/9/9/9/9/R8/9/R8/9/4K4 w - - 0 1",
+            "3k5/9/4p4/4p4/9/4p4/4p4/4p4/9/5K3 b - - 0 1"
+        ];
+
+        for position in positions.iter() {
+            let mut game_state = parse_game_state(&String::from(*position)).unwrap();
+            for notation in [Notation::Wxf, Notation::Iccs, Notation::Chinese].iter() {
+                for mov in game_state.possible_moves() {
+                    let encoded = format(&game_state, mov.clone(), *notation);
+                    let result = parse_move(&encoded, &mut game_state, *notation).unwrap();
+                    assert_eq!((result.from, result.to), (mov.from, mov.to));
+                }
+            }
+        }
+    }
+}
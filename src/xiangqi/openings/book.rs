@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use crate::xiangqi::notation;
+use crate::xiangqi::notation::Notation;
+use crate::xiangqi::record::GameRecord;
+use crate::xiangqi::state::game_state::Outcome;
+
+// a move played from a position and the results of the games it was played in
+#[derive(Clone, Debug, PartialEq)]
+pub struct BookEntry {
+    pub mov: String, // WXF
+    pub games: u32,
+    pub red_wins: u32,
+    pub black_wins: u32,
+    pub draws: u32
+}
+
+impl BookEntry {
+    // the share of points won by the player making the move, games without a result count as draws
+    pub fn score(&self, player_number: i8) -> f32 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        let wins = if player_number == 1 { self.red_wins } else { self.black_wins };
+        let undecided = self.games - self.red_wins - self.black_wins;
+        (wins as f32 + undecided as f32 / 2.0) / self.games as f32
+    }
+}
+
+// moves by the board and player to move, see GameState::format
+pub type Book = HashMap<String, Vec<BookEntry>>;
+
+// move frequencies and results of the first moves of each record
+pub fn build(records: &[GameRecord], move_count: usize) -> Result<Book, &'static str> {
+    let mut book: Book = HashMap::new();
+
+    for record in records.iter() {
+        let (_, mut game_state) = GameRecord::new(&record.starting_position)?;
+
+        for mov in record.moves.iter().take(move_count) {
+            let key = game_state.format();
+            let encoded_move = notation::format(&game_state, mov.clone(), Notation::Wxf);
+            let entries = book.entry(key).or_default();
+
+            let index = match entries.iter().position(|e| e.mov == encoded_move) {
+                Some(i) => i,
+                None => {
+                    entries.push(BookEntry { mov: encoded_move, games: 0, red_wins: 0, black_wins: 0, draws: 0 });
+                    entries.len() - 1
+                }
+            };
+
+            let entry = &mut entries[index];
+            entry.games += 1;
+            match record.result {
+                Some(Outcome::Win(1)) => entry.red_wins += 1,
+                Some(Outcome::Win(_)) => entry.black_wins += 1,
+                Some(Outcome::Draw) => entry.draws += 1,
+                None => ()
+            }
+
+            game_state.perform_move(mov)?;
+        }
+    }
+
+    for entries in book.values_mut() {
+        entries.sort_by(|a, b| b.games.cmp(&a.games).then(a.mov.cmp(&b.mov)));
+    }
+
+    Ok(book)
+}
+
+// one line per move, sorted by position
+//   rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w C2=5 12 5 4 2
+// board, player to move, move, games, red wins, black wins, draws
+pub fn format(book: &Book) -> String {
+    let mut keys: Vec<&String> = book.keys().collect();
+    keys.sort();
+
+    let mut encoded = String::new();
+    for key in keys {
+        for entry in book[key].iter() {
+            encoded.push_str(&format!("{} {} {} {} {} {}\n", key, entry.mov, entry.games, entry.red_wins, entry.black_wins, entry.draws));
+        }
+    }
+    encoded
+}
+
+// lines starting with # are comments
+pub fn parse(encoded: &str) -> Result<Book, &'static str> {
+    let mut book: Book = HashMap::new();
+
+    for line in encoded.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 7 {
+            return Err("book::parse - Invalid line");
+        }
+
+        let counts = fields[3..].iter().map(|f| f.parse::<u32>()).collect::<Result<Vec<u32>, _>>().map_err(|_| "book::parse - Invalid count")?;
+        let entry = BookEntry {
+            mov: fields[2].to_string(),
+            games: counts[0],
+            red_wins: counts[1],
+            black_wins: counts[2],
+            draws: counts[3]
+        };
+
+        if entry.red_wins + entry.black_wins + entry.draws > entry.games {
+            return Err("book::parse - Invalid count");
+        }
+
+        book.entry(format!("{} {}", fields[0], fields[1])).or_default().push(entry);
+    }
+
+    Ok(book)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xiangqi::record::pgn;
+
+    #[test]
+    fn build_test() {
+        let record = pgn::parse("[Result \"1-0\"]\n1. C2=5 H8+7 2. H2+3 1-0\n").unwrap();
+        let other_record = pgn::parse("[Result \"1/2-1/2\"]\n1. C2=5 H2+3 1/2-1/2\n").unwrap();
+        let another_record = pgn::parse("1. P7+1 *\n").unwrap();
+        let result = build(&[record, other_record, another_record], 2).unwrap();
+
+        assert_eq!(result.len(), 2);
+
+        let start = &result["rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w"];
+        assert_eq!(start.len(), 2);
+        assert_eq!(start[0], BookEntry { mov: String::from("C2=5"), games: 2, red_wins: 1, black_wins: 0, draws: 1 });
+        assert_eq!(start[1], BookEntry { mov: String::from("P7+1"), games: 1, red_wins: 0, black_wins: 0, draws: 0 });
+
+        let central_cannon = &result["rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR b"];
+        assert_eq!(central_cannon.len(), 2);
+    }
+
+    #[test]
+    fn format_and_parse_test() {
+        let record = pgn::parse("[Result \"0-1\"]\n1. C2=5 H8+7 0-1\n").unwrap();
+        let book = build(&[record], 2).unwrap();
+        let encoded = format(&book);
+
+        assert_eq!(encoded, "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR b H8+7 1 0 1 0\nrheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w C2=5 1 0 1 0\n");
+        assert_eq!(parse(&encoded).unwrap(), book);
+    }
+
+    #[test]
+    fn parse_comment_test() {
+        let result = parse("# comment\n\nrheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w C2=5 3 1 1 1\n").unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn parse_invalid_test() {
+        assert_eq!(parse("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w C2=5 3\n"), Err("book::parse - Invalid line"));
+        assert_eq!(parse("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w C2=5 1 1 1 0\n"), Err("book::parse - Invalid count"));
+    }
+
+    #[test]
+    fn score_test() {
+        let entry = BookEntry { mov: String::from("C2=5"), games: 4, red_wins: 2, black_wins: 1, draws: 0 };
+        assert_eq!(entry.score(1), 0.625);
+        assert_eq!(entry.score(2), 0.375);
+    }
+}
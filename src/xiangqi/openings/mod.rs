@@ -1,4 +1,20 @@
+pub mod book;
+
+use std::env;
+use std::fs;
+use std::sync::OnceLock;
 use rand::prelude::*;
+use crate::xiangqi::openings::book::Book;
+use crate::xiangqi::openings::book::BookEntry;
+use crate::xiangqi::state::game_state::parse as parse_game_state;
+
+// built from records/ with the xiangqi_book tool
+const EMBEDDED_BOOK: &str = include_str!("book.txt");
+
+// moves scoring less than this for the player making them are only played when there is nothing better
+const MINIMUM_BOOK_SCORE: f32 = 0.25;
+
+static BOOK: OnceLock<Book> = OnceLock::new();
 
 fn random_move(mut possible_values: Vec<&str>) -> Option<&str> {
     let mut rng = rand::rng();
@@ -6,56 +22,58 @@ fn random_move(mut possible_values: Vec<&str>) -> Option<&str> {
     Some(possible_values[0])
 }
 
+// positions are looked up by board and player to move, so the move counters sent don't matter
 pub fn recommended_move(game_state: &String) -> Option<&'static str> {
-    let game_state_string = game_state.as_str();
+    let parsed = parse_game_state(game_state).ok()?;
+    let position = parsed.format();
 
-    match game_state_string {
+    match position.as_str() {
         // initial state
-        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0" => {
+        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w" => {
             random_move(vec!["C2=5", "P7+1", "E3+5", "H2+3", "C2=4", "C2=6"])
         },
         // 1. C2=6
-        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b - - 1 0" => {
+        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b" => {
             Some("C8=5")
         },
         // 1. C2=6 C8=5
-        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR w - - 2 1" => {
+        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR w" => {
             Some("H2+3")
         },
         // 1. C2=6 C8=5
         // 2. H2+3
-        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE1R b - - 3 1" => {
+        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE1R b" => {
             Some("H8+7")
         },
         // 1. C2=6 C8=5
         // 2. H2+3 H8+7
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE1R w - - 4 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. C2=6 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAER1 b - - 5 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAER1 b" => {
             random_move(vec!["R9+1", "P7+1"])
         },
         // 1. C2=6 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
-        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAER1 w - - 6 3" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAER1 w" => {
             Some("R2+6")
         },
         // 1. C2=6 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. R2+6
-        "2eakaehr/r8/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE2 b - - 7 3" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE2 b" => {
             Some("R9=4")
         },
         // 1. C2=6 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. R2+6 R9=4
-        "2eakaehr/5r3/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE2 w - - 8 4" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE2 w" => {
             Some("A4+5")
         },
         // 1. C2=6 C8=5
@@ -63,51 +81,51 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. R2+6 R9=4
         // 5. A4+5
-        "2eakaehr/5r3/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C1C2H2/4A4/RHEAK1E2 b - - 9 4" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C1C2H2/4A4/RHEAK1E2 b" => {
             Some("H2+3")
         },
         // 1. C2=4
-        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C3C3/9/RHEAKAEHR b - - 1 0" => {
+        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C3C3/9/RHEAKAEHR b" => {
             Some("C2=5")
         },
         // 1. C2=4 C2=5
-        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C3C3/9/RHEAKAEHR w - - 2 1" => {
+        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C3C3/9/RHEAKAEHR w" => {
             Some("H8+7")
         },
         // 1. C2=4 C2=5
         // 2. H8+7
-        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1CH2C3/9/R1EAKAEHR b - - 3 1" => {
+        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1CH2C3/9/R1EAKAEHR b" => {
             Some("H2+3")
         },
         // 1. C2=4 C2=5
         // 2. H8+7 H2+3
-        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2C3/9/R1EAKAEHR w - - 4 2" => {
+        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2C3/9/R1EAKAEHR w" => {
             Some("H2+3")
         },
         // 1. C2=4 C2=5
         // 2. H8+7 H2+3
         // 3. H2+3
-        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAE1R b - - 5 2" => {
+        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAE1R b" => {
             Some("H8+9")
         },
         // 1. C2=4 C2=5
         // 2. H8+7 H2+3
         // 3. H2+3 H8+9
-        "r1eakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAE1R w - - 6 3" => {
+        "r1eakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAE1R w" => {
             Some("R1=2")
         },
         // 1. C2=4 C2=5
         // 2. H8+7 H2+3
         // 3. H2+3 H8+9
         // 4. R1=2
-        "r1eakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAER1 b - - 7 3" => {
+        "r1eakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAER1 b" => {
             Some("R9=8")
         },
         // 1. C2=4 C2=5
         // 2. H8+7 H2+3
         // 3. H2+3 H8+9
         // 4. R1=2 R9=8
-        "1reakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAER1 w - - 8 4" => {
+        "1reakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/R1EAKAER1 w" => {
             Some("R9=8")
         },
         // 1. C2=4 C2=5
@@ -115,7 +133,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. H2+3 H8+9
         // 4. R1=2 R9=8
         // 5. R9=8
-        "1reakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/1REAKAER1 b - - 9 4" => {
+        "1reakae1r/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/1REAKAER1 b" => {
             Some("R1=2")
         },
         // 1. C2=4 C2=5
@@ -123,241 +141,241 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. H2+3 H8+9
         // 4. R1=2 R9=8
         // 5. R9=8 R1=2
-        "1reakaer1/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/1REAKAER1 w - - 10 5" => {
+        "1reakaer1/9/hc2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH2CH2/9/1REAKAER1 w" => {
             random_move(vec!["C4+5", "C8+4"])
         },
         // 1. H2+3
-        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C4HC1/9/RHEAKAE1R b - - 1 0" => {
+        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C4HC1/9/RHEAKAE1R b" => {
             Some("P7+1")
         },
         // 1. H2+3 P7+1
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4HC1/9/RHEAKAE1R w - - 0 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4HC1/9/RHEAKAE1R w" => {
             random_move(vec!["P7+1", "C2=1", "C8=6"])
         },
         // 1. H2+3 P7+1
         // 2. C8=6
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/3C2HC1/9/RHEAKAE1R b - - 1 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/3C2HC1/9/RHEAKAE1R b" => {
             Some("H8+7")
         },
         // 1. H2+3 P7+1
         // 2. C8=6 H8+7
-        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/3C2HC1/9/RHEAKAE1R w - - 2 2" => {
+        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/3C2HC1/9/RHEAKAE1R w" => {
             Some("H8+7")
         },
         // 1. H2+3 P7+1
         // 2. C8=6 H8+7
         // 3. H8+7
-        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/2HC2HC1/9/R1EAKAE1R b - - 3 2" => {
+        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/2HC2HC1/9/R1EAKAE1R b" => {
             Some("H2+1")
         },
         // 1. H2+3 P7+1
         // 2. C2=1
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAE1R b - - 1 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAE1R b" => {
             Some("H8+7")
         },
         // 1. H2+3 P7+1
         // 2. C2=1 H8+7
-        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAE1R w - - 2 2" => {
+        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. H2+3 P7+1
         // 2. C2=1 H8+7
         // 3. R1=2
-        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAER1 b - - 3 2" => {
+        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAER1 b" => {
             Some("R9=8")
         },
         // 1. H2+3 P7+1
         // 2. C2=1 H8+7
         // 3. R1=2 R9=8
-        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAER1 w - - 4 3" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAER1 w" => {
             Some("R2+6")
         },
         // 1. H2+3 P7+1
         // 2. C2=1 H8+7
         // 3. R1=2 R9=8
         // 4. R2+6
-        "1reakaehr/9/1ch4c1/p3p1pRp/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAE2 b - - 5 3" => {
+        "1reakaehr/9/1ch4c1/p3p1pRp/2p6/9/P1P1P1P1P/1C4H1C/9/RHEAKAE2 b" => {
             Some("C8=9")
         },
         // 1. H2+3 P7+1
         // 2. P7+1
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1C4HC1/9/RHEAKAE1R b - - 0 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1C4HC1/9/RHEAKAE1R b" => {
             Some("H8+7")
         },
         // 1. H2+3 P7+1
         // 2. P7+1 H8+7
-        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1C4HC1/9/RHEAKAE1R w - - 1 2" => {
+        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1C4HC1/9/RHEAKAE1R w" => {
             Some("H8+7")
         },
         // 1. H2+3 P7+1
         // 2. P7+1 H8+7
         // 3. H8+7
-        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH3HC1/9/R1EAKAE1R b - - 2 2" => {
+        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH3HC1/9/R1EAKAE1R b" => {
             random_move(vec!["R9+1", "H2+3"])
         },
         // 1. E3+5
-        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR b - - 1 0" => {
+        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR b" => {
             random_move(vec!["P7+1", "C8=5", "C8=4", "C2=4"])
         },
         // 1. E3+5 C2=4
-        "rheakaehr/9/1c3c3/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w - - 2 1" => {
+        "rheakaehr/9/1c3c3/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w" => {
             Some("R9+1")
         },
         // 1. E3+5 C2=4
         // 2. R9+1
-        "rheakaehr/9/1c3c3/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/R8/1HEAKA1HR b - - 3 1" => {
+        "rheakaehr/9/1c3c3/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/R8/1HEAKA1HR b" => {
             Some("H2+3")
         },
         // 1. E3+5 C2=4
         // 2. R9+1 H2+3
-        "rheakae1r/9/1c3ch2/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/R8/1HEAKA1HR w - - 4 2" => {
+        "rheakae1r/9/1c3ch2/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/R8/1HEAKA1HR w" => {
             Some("R9=6")
         },
         // 1. E3+5 C2=4
         // 2. R9+1 H2+3
         // 3. R9=6
-        "rheakae1r/9/1c3ch2/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/3R5/1HEAKA1HR b - - 5 2" => {
+        "rheakae1r/9/1c3ch2/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/3R5/1HEAKA1HR b" => {
             Some("H8+7")
         },
         // 1. E3+5 C2=4
         // 2. R9+1 H2+3
         // 3. R9=6 H8+7
-        "r1eakae1r/9/1ch2ch2/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/3R5/1HEAKA1HR w - - 6 3" => {
+        "r1eakae1r/9/1ch2ch2/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/3R5/1HEAKA1HR w" => {
             Some("H8+9")
         },
         // 1. E3+5 C2=4
         // 2. R9+1 H2+3
         // 3. R9=6 H8+7
         // 4. H8+9
-        "r1eakae1r/9/1ch2ch2/p1p1p1p1p/9/9/P1P1P1P1P/HC2E2C1/3R5/2EAKA1HR b - - 7 3" => {
+        "r1eakae1r/9/1ch2ch2/p1p1p1p1p/9/9/P1P1P1P1P/HC2E2C1/3R5/2EAKA1HR b" => {
             Some("R1=2")
         },
         // 1. E3+5 C2=4
         // 2. R9+1 H2+3
         // 3. R9=6 H8+7
         // 4. H8+9 R1=2
-        "r1eakaer1/9/1ch2ch2/p1p1p1p1p/9/9/P1P1P1P1P/HC2E2C1/3R5/2EAKA1HR w - - 8 4" => {
+        "r1eakaer1/9/1ch2ch2/p1p1p1p1p/9/9/P1P1P1P1P/HC2E2C1/3R5/2EAKA1HR w" => {
             Some("P9+1")
         },
         // 1. E3+5 C8=4
-        "rheakaehr/9/5c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w - - 2 1" => {
+        "rheakaehr/9/5c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w" => {
             Some("H2+3")
         },
         // 1. E3+5 C8=4
         // 2. H2+3
-        "rheakaehr/9/5c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R b - - 3 1" => {
+        "rheakaehr/9/5c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R b" => {
             Some("H8+7")
         },
         // 1. E3+5 C8=4
         // 2. H2+3 H8+7
-        "r1eakaehr/9/2h2c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R w - - 4 2" => {
+        "r1eakaehr/9/2h2c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R w" => {
             Some("R1=2")
         },
         // 1. E3+5 C8=4
         // 2. H2+3 H8+7
         // 3. R1=2
-        "r1eakaehr/9/2h2c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA1R1 b - - 5 2" => {
+        "r1eakaehr/9/2h2c1c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA1R1 b" => {
             random_move(vec!["H2+1", "P7+1"])
         },
         // 1. E3+5 C8=5
-        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w - - 2 1" => {
+        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w" => {
             Some("H2+3")
         },
         // 1. E3+5 C8=5
         // 2. H2+3
-        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R b - - 3 1" => {
+        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R b" => {
             Some("H8+7")
         },
         // 1. E3+5 C8=5
         // 2. H2+3 H8+7
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R w - - 4 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA2R w" => {
             Some("R1=2")
         },
         // 1. E3+5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA1R1 b - - 5 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA1R1 b" => {
             Some("R9=8")
         },
         // 1. E3+5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9=8
-        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA1R1 w - - 6 3" => {
+        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2E1HC1/9/RHEAKA1R1 w" => {
             Some("H8+7")
         },
         // 1. E3+5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9=8
         // 4. H8+7
-        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1E1HC1/9/R1EAKA1R1 b - - 7 3" => {
+        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1E1HC1/9/R1EAKA1R1 b" => {
             random_move(vec!["H2+1", "R8+6", "P7+1"])
         },
         // 1. E3+5 P7+1
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w - - 0 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/9/P1P1P1P1P/1C2E2C1/9/RHEAKA1HR w" => {
             random_move(vec!["H8+9", "P7+1"])
         },
         // 1. P7+1
-        "rheakaehr/9/1c5c1/p1p1p1p1p/9/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR b - - 0 0" => {
+        "rheakaehr/9/1c5c1/p1p1p1p1p/9/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR b" => {
             random_move(vec!["P7+1", "H8+7", "C2=3"])
         },
         // 1. P7+1 C2=3
-        "rheakaehr/9/1c4c2/p1p1p1p1p/9/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR w - - 1 1" => {
+        "rheakaehr/9/1c4c2/p1p1p1p1p/9/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR w" => {
             Some("C2=5")
         },
         // 1. P7+1 C2=3
         // 2. C2=5
-        "rheakaehr/9/1c4c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C4/9/RHEAKAEHR b - - 2 1" => {
+        "rheakaehr/9/1c4c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C4/9/RHEAKAEHR b" => {
             Some("E3+5")
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
-        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C4/9/RHEAKAEHR w - - 3 2" => {
+        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C4/9/RHEAKAEHR w" => {
             random_move(vec!["A6+5", "H8+9", "H2+3"])
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H2+3
-        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R b - - 4 2" => {
+        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R b" => {
             random_move(vec!["R9+1", "P3+1"])
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H2+3 P3+1
-        "rheaka1hr/9/1c2e1c2/p1p1p3p/6p2/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w - - 0 3" => {
+        "rheaka1hr/9/1c2e1c2/p1p1p3p/6p2/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H2+3 P3+1
         // 4. R1=2
-        "rheaka1hr/9/1c2e1c2/p1p1p3p/6p2/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b - - 1 3" => {
+        "rheaka1hr/9/1c2e1c2/p1p1p3p/6p2/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("P3+1")
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H2+3 P3+1
         // 4. R1=2 P3+1
-        "rheaka1hr/9/1c2e1c2/p1p1p3p/9/2P3p2/P3P1P1P/1C2C1H2/9/RHEAKAER1 w - - 0 4" => {
+        "rheaka1hr/9/1c2e1c2/p1p1p3p/9/2P3p2/P3P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+9")
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H2+3 R9+1
-        "1heaka1hr/r8/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w - - 5 3" => {
+        "1heaka1hr/r8/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H2+3 R9+1
         // 4. R1=2
-        "1heaka1hr/r8/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b - - 6 3" => {
+        "1heaka1hr/r8/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("R9=2")
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H2+3 R9+1
         // 4. R1=2 R9=2
-        "1heaka1hr/7r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w - - 7 4" => {
+        "1heaka1hr/7r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. P7+1 C2=3
@@ -365,7 +383,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. H2+3 R9+1
         // 4. R1=2 R9=2
         // 5. H8+7
-        "1heaka1hr/7r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 8 4" => {
+        "1heaka1hr/7r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("H2+4")
         },
         // 1. P7+1 C2=3
@@ -373,7 +391,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. H2+3 R9+1
         // 4. R1=2 R9=2
         // 5. H8+7 H2+4
-        "1heaka2r/5h1r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 9 5" => {
+        "1heaka2r/5h1r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("C8=9")
         },
         // 1. P7+1 C2=3
@@ -382,7 +400,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. R1=2 R9=2
         // 5. H8+7 H2+4
         // 6. C8=9
-        "1heaka2r/5h1r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/C1H1C1H2/9/R1EAKAER1 b - - 10 5" => {
+        "1heaka2r/5h1r1/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/C1H1C1H2/9/R1EAKAER1 b" => {
             Some("H8+9")
         },
         // 1. P7+1 C2=3
@@ -391,114 +409,114 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. R1=2 R9=2
         // 5. H8+7 H2+4
         // 6. C8=9 H8+9
-        "2eaka2r/5h1r1/hc2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/C1H1C1H2/9/R1EAKAER1 w - - 11 6" => {
+        "2eaka2r/5h1r1/hc2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/C1H1C1H2/9/R1EAKAER1 w" => {
             Some("H7+6")
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. H8+9
-        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/HC2C4/9/R1EAKAEHR b - - 4 2" => {
+        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/HC2C4/9/R1EAKAEHR b" => {
             random_move(vec!["R9+1", "H8+7"])
         },
         // 1. P7+1 C2=3
         // 2. C2=5 E3+5
         // 3. A6+5
-        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C4/4A4/RHE1KAEHR b - - 4 2" => {
+        "rheaka1hr/9/1c2e1c2/p1p1p1p1p/9/2P6/P3P1P1P/1C2C4/4A4/RHE1KAEHR b" => {
             random_move(vec!["P7+1", "H8+7"])
         },
         // 1. P7+1 H8+7
-        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR w - - 1 1" => {
+        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR w" => {
             Some("P3+1")
         },
         // 1. P7+1 H8+7
         // 2. P3+1
-        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/2P3P2/P3P3P/1C5C1/9/RHEAKAEHR b - - 0 1" => {
+        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/2P3P2/P3P3P/1C5C1/9/RHEAKAEHR b" => {
             random_move(vec!["C2=3", "C8=9", "R9+1"])
         },
         // 1. P7+1 P7+1
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1C5C1/9/RHEAKAEHR w" => {
             random_move(vec!["C2=3", "H8+7"])
         },
         // 1. P7+1 P7+1
         // 2. H8+7
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH4C1/9/R1EAKAEHR b - - 1 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH4C1/9/R1EAKAEHR b" => {
             Some("H8+7")
         },
         // 1. P7+1 P7+1
         // 2. H8+7 H8+7
-        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH4C1/9/R1EAKAEHR w - - 2 2" => {
+        "r1eakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH4C1/9/R1EAKAEHR w" => {
            random_move(vec!["R9+1", "C8=9"])
         },
         // 1. P7+1 P7+1
         // 2. C2=3
-        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1C4C2/9/RHEAKAEHR b - - 1 1" => {
+        "rheakaehr/9/1c5c1/p3p1p1p/2p6/2P6/P3P1P1P/1C4C2/9/RHEAKAEHR b" => {
             Some("C8=5")
         },
         // 1. C2=5
-        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR b - - 1 0" => {
+        "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR b" => {
             random_move(vec!["H8+7", "H2+3", "C8=5", "C2=5"])
         },
         // 1. C2=5 C2=5
-        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w - - 2 1" => {
+        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w" => {
             Some("H2+3")
         },
         // 1. C2=5 C2=5
         // 2. H2+3
-        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b - - 3 1" => {
+        "rheakaehr/9/1c2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b" => {
             random_move(vec!["H2+3", "H8+9"])
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H8+9
-        "r1eakaehr/9/hc2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 4 2" => {
+        "r1eakaehr/9/hc2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H8+9
         // 3. R1=2
-        "r1eakaehr/9/hc2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 5 2" => {
+        "r1eakaehr/9/hc2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("R9=8")
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H8+9
         // 3. R1=2 R9=8
-        "1reakaehr/9/hc2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "1reakaehr/9/hc2c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["H8+9", "H8+7"])
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H2+3
-        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 4 2" => {
+        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H2+3
         // 3. R1=2
-        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 5 2" => {
+        "rheakae1r/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             random_move(vec!["R1=2", "H8+7"])
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H2+3
         // 3. R1=2 H8+7
-        "r1eakae1r/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "r1eakae1r/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("C8=6")
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H2+3
         // 3. R1=2 R1=2
-        "rheakaer1/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "rheakaer1/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H2+3
         // 3. R1=2 R1=2
         // 4. H8+7
-        "rheakaer1/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 7 3" => {
+        "rheakaer1/9/1c2c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("H8+7")
         },
         // 1. C2=5 C2=5
         // 2. H2+3 H2+3
         // 3. R1=2 R1=2
         // 4. H8+7 H8+7
-        "r1eakaer1/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 8 4" => {
+        "r1eakaer1/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("R9=8")
         },
         // 1. C2=5 C2=5
@@ -506,7 +524,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R1=2
         // 4. H8+7 H8+7
         // 5. R9=8
-        "r1eakaer1/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/1REAKAER1 b - - 9 4" => {
+        "r1eakaer1/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/1REAKAER1 b" => {
             Some("R9=8")
         },
         // 1. C2=5 C2=5
@@ -514,47 +532,47 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R1=2
         // 4. H8+7 H8+7
         // 5. R9=8 R9=8
-        "1reakaer1/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/1REAKAER1 w - - 10 5" => {
+        "1reakaer1/9/1ch1c1h2/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/1REAKAER1 w" => {
             Some("C8+4")
         },
         // 1. C2=5 C8=5
-        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w - - 2 1" => {
+        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w" => {
             Some("H2+3")
         },
         // 1. C2=5 C8=5
         // 2. H2+3
-        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b - - 3 1" => {
+        "rheakaehr/9/4c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b" => {
             Some("H8+7")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 4 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             random_move(vec!["P3+1", "R1+1", "R1=2"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 5 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             random_move(vec!["P7+1", "R9+1"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
-        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["R2+6", "C8=6", "H8+7"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. H8+7
-        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 7 3" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
            random_move(vec!["H2+3", "R9=4"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. H8+7 R9=4
-        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 8 4" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             random_move(vec!["P7+1", "P3+1"])
         },
         // 1. C2=5 C8=5
@@ -562,7 +580,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 R9=4
         // 5. P3+1
-        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 b - - 0 4" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["R4+4", "P3+1", "H2+1", "H2+3"])
         },
         // 1. C2=5 C8=5
@@ -570,7 +588,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 R9=4
         // 5. P3+1 H2+3
-        "2eakae1r/5r3/2h1c1hc1/p1p1p1p1p/9/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w - - 1 5" => {
+        "2eakae1r/5r3/2h1c1hc1/p1p1p1p1p/9/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("P7+1")
         },
         // 1. C2=5 C8=5
@@ -579,7 +597,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. H8+7 R9=4
         // 5. P3+1 H2+3
         // 6. P7+1
-        "2eakae1r/5r3/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3P3P/1CH1C1H2/9/R1EAKAER1 b - - 0 5" => {
+        "2eakae1r/5r3/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3P3P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("R1+1")
         },
         // 1. C2=5 C8=5
@@ -588,7 +606,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. H8+7 R9=4
         // 5. P3+1 H2+3
         // 6. P7+1 R1+1
-        "2eakae2/5r2r/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3P3P/1CH1C1H2/9/R1EAKAER1 w - - 1 6" => {
+        "2eakae2/5r2r/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("A6+5")
         },
         // 1. C2=5 C8=5
@@ -598,7 +616,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. P3+1 H2+3
         // 6. P7+1 R1+1
         // 7. A6+5
-        "2eakae2/5r2r/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3P3P/1CH1C1H2/4A4/R1E1KAER1 b - - 2 6" => {
+        "2eakae2/5r2r/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3P3P/1CH1C1H2/4A4/R1E1KAER1 b" => {
             Some("R4+5")
         },
         // 1. C2=5 C8=5
@@ -608,7 +626,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. P3+1 H2+3
         // 6. P7+1 R1+1
         // 7. A6+5 R4+5
-        "2eakae2/8r/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3Pr2P/1CH1C1H2/4A4/R1E1KAER1 w - - 3 7" => {
+        "2eakae2/8r/2h1c1hc1/p1p1p1p1p/9/2P3P2/P3Pr2P/1CH1C1H2/4A4/R1E1KAER1 w" => {
             Some("E7+9")
         },
         // 1. C2=5 C8=5
@@ -616,7 +634,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 R9=4
         // 5. P3+1 H2+1
-        "2eakae1r/5r3/2h1c2ch/p1p1p1p1p/9/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w - - 1 5" => {
+        "2eakae1r/5r3/2h1c2ch/p1p1p1p1p/9/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("H3+4")
         },
         // 1. C2=5 C8=5
@@ -624,7 +642,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 R9=4
         // 5. P3+1 P3+1
-        "2eakaehr/5r3/2h1c2c1/p1p1p3p/6p2/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w - - 0 5" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p3p/6p2/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("R2+5")
         },
         // 1. C2=5 C8=5
@@ -633,7 +651,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. H8+7 R9=4
         // 5. P3+1 P3+1
         // 6. R2+5
-        "2eakaehr/5r3/2h1c2c1/p1p1p3p/6pR1/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAE2 b - - 1 5" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p3p/6pR1/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAE2 b" => {
             Some("E3+1")
         },
         // 1. C2=5 C8=5
@@ -642,7 +660,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. H8+7 R9=4
         // 5. P3+1 P3+1
         // 6. R2+5 E3+1
-        "2eaka1hr/5r3/2h1c2ce/p1p1p3p/6pR1/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAE2 w - - 2 6" => {
+        "2eaka1hr/5r3/2h1c2ce/p1p1p3p/6pR1/6P2/P1P1P3P/1CH1C1H2/9/R1EAKAE2 w" => {
             Some("C8=9")
         },
         // 1. C2=5 C8=5
@@ -650,7 +668,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 R9=4
         // 5. P3+1 R4+4
-        "2eakaehr/9/2h1c2c1/p1p1p1p1p/9/5rP2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w - - 1 5" => {
+        "2eakaehr/9/2h1c2c1/p1p1p1p1p/9/5rP2/P1P1P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             random_move(vec!["C5=4", "E3+1"])
         },
         // 1. C2=5 C8=5
@@ -658,14 +676,14 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 R9=4
         // 5. P7+1
-        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 0 4" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["H2+1", "H2+3", "R4+5"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. H8+7 H2+3
-        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 8 4" => {
+        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("P7+1")
         },
         // 1. C2=5 C8=5
@@ -673,7 +691,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 H2+3
         // 5. P7+1
-        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 0 4" => {
+        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("R1+1")
         },
         // 1. C2=5 C8=5
@@ -681,21 +699,21 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. H8+7 H2+3
         // 5. P7+1 R1+1
-        "2eakae2/r7r/2h1c1hc1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 1 5" => {
+        "2eakae2/r7r/2h1c1hc1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             random_move(vec!["P3+1", "C8+1"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. C8=6
-        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 b - - 7 3" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 b" => {
             random_move(vec!["H2+3", "R9=4"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. C8=6 R9=4
-        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 w - - 8 4" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 w" => {
             Some("A4+5")
         },
         // 1. C2=5 C8=5
@@ -703,7 +721,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. C8=6 R9=4
         // 5. A4+5
-        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/4A4/RHEAK1ER1 b - - 9 4" => {
+        "2eakaehr/5r3/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/4A4/RHEAK1ER1 b" => {
             Some("H2+1")
         },
         // 1. C2=5 C8=5
@@ -711,7 +729,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. C8=6 R9=4
         // 5. A4+5 H2+1
-        "2eakae1r/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/4A4/RHEAK1ER1 w - - 10 5" => {
+        "2eakae1r/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/4A4/RHEAK1ER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 C8=5
@@ -720,7 +738,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. C8=6 R9=4
         // 5. A4+5 H2+1
         // 6. H8+7
-        "2eakae1r/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/4A4/R1EAK1ER1 b - - 11 5" => {
+        "2eakae1r/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/4A4/R1EAK1ER1 b" => {
             Some("R1=2")
         },
         // 1. C2=5 C8=5
@@ -729,7 +747,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. C8=6 R9=4
         // 5. A4+5 H2+1
         // 6. H8+7 R1=2
-        "2eakaer1/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/4A4/R1EAK1ER1 w - - 12 6" => {
+        "2eakaer1/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/4A4/R1EAK1ER1 w" => {
             Some("R9=8")
         },
         // 1. C2=5 C8=5
@@ -739,7 +757,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. A4+5 H2+1
         // 6. H8+7 R1=2
         // 7. R9=8
-        "2eakaer1/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/4A4/1REAK1ER1 b - - 13 6" => {
+        "2eakaer1/5r3/2h1c2ch/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/4A4/1REAK1ER1 b" => {
             Some("R4+5")
         },
         // 1. C2=5 C8=5
@@ -749,7 +767,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. A4+5 H2+1
         // 6. H8+7 R1=2
         // 7. R9=8 R4+5
-        "2eakaer1/9/2h1c2ch/p1p1p1p1p/9/9/P1P1PrP1P/2HCC1H2/4A4/1REAK1ER1 w - - 14 7" => {
+        "2eakaer1/9/2h1c2ch/p1p1p1p1p/9/9/P1P1PrP1P/2HCC1H2/4A4/1REAK1ER1 w" => {
             Some("R2+6")
         },
         // 1. C2=5 C8=5
@@ -760,14 +778,14 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 6. H8+7 R1=2
         // 7. R9=8 R4+5
         // 8. R2+6
-        "2eakaer1/9/2h1c2ch/p1p1p1pRp/9/9/P1P1PrP1P/2HCC1H2/4A4/1REAK1E2 b - - 15 7" => {
+        "2eakaer1/9/2h1c2ch/p1p1p1pRp/9/9/P1P1PrP1P/2HCC1H2/4A4/1REAK1E2 b" => {
             Some("C5=3")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. C8=6 H2+3
-        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 w - - 8 4" => {
+        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 C8=5
@@ -775,7 +793,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. C8=6 H2+3
         // 5. H8+7
-        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 b - - 9 4" => {
+        "2eakae1r/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 b" => {
             Some("R1=2")
         },
         // 1. C2=5 C8=5
@@ -783,7 +801,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. C8=6 H2+3
         // 5. H8+7 R1=2
-        "2eakaer1/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 w - - 10 5" => {
+        "2eakaer1/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 w" => {
             Some("R9=8")
         },
         // 1. C2=5 C8=5
@@ -792,7 +810,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. C8=6 H2+3
         // 5. H8+7 R1=2
         // 6. R9=8
-        "2eakaer1/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/1REAKAER1 b - - 11 5" => {
+        "2eakaer1/r8/2h1c1hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/1REAKAER1 b" => {
             Some("C2+4")
         },
         // 1. C2=5 C8=5
@@ -801,7 +819,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. C8=6 H2+3
         // 5. H8+7 R1=2
         // 6. R9=8 C2+4
-        "2eakaer1/r8/2h1c1h2/p1p1p1p1p/9/9/P1P1P1PcP/2HCC1H2/9/1REAKAER1 w - - 12 6" => {
+        "2eakaer1/r8/2h1c1h2/p1p1p1p1p/9/9/P1P1P1PcP/2HCC1H2/9/1REAKAER1 w" => {
             Some("C6+5")
         },
         // 1. C2=5 C8=5
@@ -811,21 +829,21 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. H8+7 R1=2
         // 6. R9=8 C2+4
         // 7. C6+5
-        "2eakaer1/r8/2hCc1h2/p1p1p1p1p/9/9/P1P1P1PcP/2H1C1H2/9/1REAKAER1 b - - 13 6" => {
+        "2eakaer1/r8/2hCc1h2/p1p1p1p1p/9/9/P1P1P1PcP/2H1C1H2/9/1REAKAER1 b" => {
             Some("R9=7")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. R2+6
-        "2eakaehr/r8/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 b - - 7 3" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p1pRp/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 b" => {
             Some("P3+1")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 R9+1
         // 4. R2+6 P3+1
-        "2eakaehr/r8/2h1c2c1/p1p1p2Rp/6p2/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 w - - 0 4" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p2Rp/6p2/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             random_move(vec!["R2=3", "C8=7"])
         },
         // 1. C2=5 C8=5
@@ -833,7 +851,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. R2+6 P3+1
         // 5. C8=7
-        "2eakaehr/r8/2h1c2c1/p1p1p2Rp/6p2/9/P1P1P1P1P/2C1C1H2/9/RHEAKAE2 b - - 1 4" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p2Rp/6p2/9/P1P1P1P1P/2C1C1H2/9/RHEAKAE2 b" => {
             Some("H2+3")
         },
         // 1. C2=5 C8=5
@@ -841,7 +859,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. R2+6 P3+1
         // 5. C8=7 H2+3
-        "2eakae1r/r8/2h1c1hc1/p1p1p2Rp/6p2/9/P1P1P1P1P/2C1C1H2/9/RHEAKAE2 w - - 2 5" => {
+        "2eakae1r/r8/2h1c1hc1/p1p1p2Rp/6p2/9/P1P1P1P1P/2C1C1H2/9/RHEAKAE2 w" => {
             Some("P7+1")
         },
         // 1. C2=5 C8=5
@@ -850,7 +868,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. R2+6 P3+1
         // 5. C8=7 H2+3
         // 6. P7+1
-        "2eakae1r/r8/2h1c1hc1/p1p1p2Rp/6p2/2P6/P3P1P1P/2C1C1H2/9/RHEAKAE2 b - - 0 5" => {
+        "2eakae1r/r8/2h1c1hc1/p1p1p2Rp/6p2/2P6/P3P1P1P/2C1C1H2/9/RHEAKAE2 b" => {
             Some("H3+4")
         },
         // 1. C2=5 C8=5
@@ -858,27 +876,27 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 R9+1
         // 4. R2+6 P3+1
         // 5. R2=3
-        "2eakaehr/r8/2h1c2c1/p1p1p1R1p/6p2/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 b - - 1 4" => {
+        "2eakaehr/r8/2h1c2c1/p1p1p1R1p/6p2/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 b" => {
             Some("H2+3")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 P7+1
-        "r1eakaehr/9/2h1c2c1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 0 3" => {
+        "r1eakaehr/9/2h1c2c1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["H8+7", "P7+1"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 P7+1
         // 4. P7+1
-        "r1eakaehr/9/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b - - 0 3" => {
+        "r1eakaehr/9/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             random_move(vec!["C2+4", "R9+1"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 P7+1
         // 4. P7+1 R9+1
-        "2eakaehr/r8/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w - - 1 4" => {
+        "2eakaehr/r8/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 C8=5
@@ -886,7 +904,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 R9+1
         // 5. H8+7
-        "2eakaehr/r8/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 2 4" => {
+        "2eakaehr/r8/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("R9=4")
         },
         // 1. C2=5 C8=5
@@ -894,7 +912,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 R9+1
         // 5. H8+7 R9=4
-        "2eakaehr/5r3/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 3 5" => {
+        "2eakaehr/5r3/2h1c2c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("R2+4")
         },
         // 1. C2=5 C8=5
@@ -903,7 +921,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P7+1 R9+1
         // 5. H8+7 R9=4
         // 6. R2+4
-        "2eakaehr/5r3/2h1c2c1/p3p1p1p/2p6/2P4R1/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b - - 4 5" => {
+        "2eakaehr/5r3/2h1c2c1/p3p1p1p/2p6/2P4R1/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b" => {
             Some("H2+3")
         },
         // 1. C2=5 C8=5
@@ -912,7 +930,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P7+1 R9+1
         // 5. H8+7 R9=4
         // 6. R2+4 H2+3
-        "2eakae1r/5r3/2h1c1hc1/p3p1p1p/2p6/2P4R1/P3P1P1P/1CH1C1H2/9/R1EAKAE2 w - - 5 6" => {
+        "2eakae1r/5r3/2h1c1hc1/p3p1p1p/2p6/2P4R1/P3P1P1P/1CH1C1H2/9/R1EAKAE2 w" => {
             Some("P3+1")
         },
 
@@ -920,7 +938,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 2. H2+3 H8+7
         // 3. R1=2 P7+1
         // 4. P7+1 C2+4
-        "r1eakaehr/9/2h1c4/p3p1p1p/2p6/2P6/P3P1PcP/1C2C1H2/9/RHEAKAER1 w - - 1 4" => {
+        "r1eakaehr/9/2h1c4/p3p1p1p/2p6/2P6/P3P1PcP/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 C8=5
@@ -928,21 +946,21 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 C2+4
         // 5. H8+7
-        "r1eakaehr/9/2h1c4/p3p1p1p/2p6/2P6/P3P1PcP/1CH1C1H2/9/R1EAKAER1 b - - 2 4" => {
+        "r1eakaehr/9/2h1c4/p3p1p1p/2p6/2P6/P3P1PcP/1CH1C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["H2+3", "R9+1"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 P7+1
         // 4. H8+7
-        "r1eakaehr/9/2h1c2c1/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 1 3" => {
+        "r1eakaehr/9/2h1c2c1/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("H2+3")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1=2 P7+1
         // 4. H8+7 H2+3
-        "r1eakae1r/9/2h1c1hc1/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 2 4" => {
+        "r1eakae1r/9/2h1c1hc1/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("P7+1")
         },
         // 1. C2=5 C8=5
@@ -950,33 +968,33 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. H8+7 H2+3
         // 5. P7+1
-        "r1eakae1r/9/2h1c1hc1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 0 4" => {
+        "r1eakae1r/9/2h1c1hc1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("R1+1")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1+1
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 b - - 5 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 b" => {
             Some("R9=8")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1+1 R9=8
-        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 w - - 6 3" => {
+        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 w" => {
             Some("R1=6")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1+1 R9=8
         // 4. R1=6
-        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/3R5/RHEAKAE2 b - - 7 3" => {
+        "1reakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/3R5/RHEAKAE2 b" => {
             Some("R8+4")
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. R1+1 R9=8
         // 4. R1=6 R8+4
-        "2eakaehr/9/2h1c2c1/p1p1p1p1p/1r7/9/P1P1P1P1P/1C2C1H2/3R5/RHEAKAE2 w - - 8 4" => {
+        "2eakaehr/9/2h1c2c1/p1p1p1p1p/1r7/9/P1P1P1P1P/1C2C1H2/3R5/RHEAKAE2 w" => {
             Some("H8+7")
         },
         // 1. C2=5 C8=5
@@ -984,84 +1002,84 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1+1 R9=8
         // 4. R1=6 R8+4
         // 5. H8+7
-        "2eakaehr/9/2h1c2c1/p1p1p1p1p/1r7/9/P1P1P1P1P/1CH1C1H2/3R5/R1EAKAE2 b - - 9 4" => {
+        "2eakaehr/9/2h1c2c1/p1p1p1p1p/1r7/9/P1P1P1P1P/1CH1C1H2/3R5/R1EAKAE2 b" => {
             random_move(vec!["H2+3", "A6+5"])
         },
         // 1. C2=5 C8=5
         // 2. H2+3 H8+7
         // 3. P3+1
-        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/6P2/P1P1P3P/1C2C1H2/9/RHEAKAE1R b - - 0 2" => {
+        "r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/6P2/P1P1P3P/1C2C1H2/9/RHEAKAE1R b" => {
             random_move(vec!["R9=8", "R9+1"])
         },
         // 1.C2=5  H2+3
-        "rheakae1r/9/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w - - 2 1" => {
+        "rheakae1r/9/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w" => {
             Some("H2+3")
         },
         // 1. C2=5 H2+3
         // 2. H2+3
-        "rheakae1r/9/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b - - 3 1" => {
+        "rheakae1r/9/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b" => {
             random_move(vec!["C8=6", "R9+1", "P7+1"])
         },
         // 1. C2=5 H2+3
         // 2. H2+3 P7+1
-        "rheakae1r/9/1c4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 0 2" => {
+        "rheakae1r/9/1c4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 P7+1
         // 3. R1=2
-        "rheakae1r/9/1c4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 1 2" => {
+        "rheakae1r/9/1c4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("R9+2")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 P7+1
         // 3. R1=2 R9+2
-        "1heakae1r/9/rc4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 2 3" => {
+        "1heakae1r/9/rc4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 P7+1
         // 3. R1=2 R9+2
         // 4. H8+7
-        "1heakae1r/9/rc4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 3 3" => {
+        "1heakae1r/9/rc4hc1/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("C2-1")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 P7+1
         // 3. R1=2 R9+2
         // 4. H8+7 C2-1
-        "1heakae1r/7c1/rc4h2/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 4 4" => {
+        "1heakae1r/7c1/rc4h2/p3p1p1p/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("R2+6")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 R9+1
-        "1heakae1r/r8/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 4 2" => {
+        "1heakae1r/r8/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 R9+1
         // 3. R1=2
-        "1heakae1r/r8/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 5 2" => {
+        "1heakae1r/r8/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
            Some("H8+9")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 R9+1
         // 3. R1=2 H8+9
-        "2eakae1r/r8/hc4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "2eakae1r/r8/hc4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("P7+1")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 R9+1
         // 3. R1=2 H8+9
         // 4. P7+1
-        "2eakae1r/r8/hc4hc1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b - - 0 3" => {
+        "2eakae1r/r8/hc4hc1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("C8=7")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 R9+1
         // 3. R1=2 H8+9
         // 4. P7+1 C8=7
-        "2eakae1r/r8/h1c3hc1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w - - 1 4" => {
+        "2eakae1r/r8/h1c3hc1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H2+3
@@ -1069,38 +1087,38 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 H8+9
         // 4. P7+1 C8=7
         // 5. H8+7
-        "2eakae1r/r8/h1c3hc1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 2 4" => {
+        "2eakae1r/r8/h1c3hc1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["E3+5", "R9=4"])
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
-        "rheakae1r/9/3c2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 4 2" => {
+        "rheakae1r/9/3c2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             random_move(vec!["R1+1", "R1=2"])
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2
-        "rheakae1r/9/3c2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 5 2" => {
+        "rheakae1r/9/3c2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("H8+7")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2 H8+7
-        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["C8=6", "P7+1", "P3+1"])
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2 H8+7
         // 4. P3+1
-        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 b - - 0 3" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("P3+1")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2 H8+7
         // 4. P3+1 P3+1
-        "r1eakae1r/9/2hc2hc1/p1p1p3p/6p2/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 w - - 0 4" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p3p/6p2/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+9")
         },
         // 1. C2=5 H2+3
@@ -1108,7 +1126,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 H8+7
         // 4. P3+1 P3+1
         // 5. H8+9
-        "r1eakae1r/9/2hc2hc1/p1p1p3p/6p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 b - - 1 4" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p3p/6p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["E3+5", "E7+5"])
         },
         // 1. C2=5 H2+3
@@ -1116,7 +1134,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 H8+7
         // 4. P3+1 P3+1
         // 5. H8+9 E7+5
-        "r2akae1r/9/2hce1hc1/p1p1p3p/6p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w - - 2 5" => {
+        "r2akae1r/9/2hce1hc1/p1p1p3p/6p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w" => {
             random_move(vec!["C8=6", "C8=7"])
         },
         // 1. C2=5 H2+3
@@ -1124,21 +1142,21 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 H8+7
         // 4. P3+1 P3+1
         // 5. H8+9 E3+5
-        "r1eaka2r/9/2hce1hc1/p1p1p3p/6p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w - - 2 5" => {
+        "r1eaka2r/9/2hce1hc1/p1p1p3p/6p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w" => {
             random_move(vec!["C8=6", "C8=7"])
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2 H8+7
         // 4. P7+1
-        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b - - 0 3" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("P7+1")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2 H8+7
         // 4. P7+1 P7+1
-        "r1eakae1r/9/2hc2hc1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w - - 0 4" => {
+        "r1eakae1r/9/2hc2hc1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("R2+6")
         },
         // 1. C2=5 H2+3
@@ -1146,21 +1164,21 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 H8+7
         // 4. P7+1 P7+1
         // 5. R2+6
-        "r1eakae1r/9/2hc2hc1/p3p1pRp/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE2 b - - 1 4" => {
+        "r1eakae1r/9/2hc2hc1/p3p1pRp/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE2 b" => {
             random_move(vec!["R9+2", "A4+5"])
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2 H8+7
         // 4. C8=6
-        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 b - - 7 3" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 b" => {
             Some("R1=2")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1=2 H8+7
         // 4. C8=6 R1=2
-        "r1eakaer1/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 w - - 8 4" => {
+        "r1eakaer1/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/3CC1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H2+3
@@ -1168,7 +1186,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 H8+7
         // 4. C8=6 R1=2
         // 5. H8+7
-        "r1eakaer1/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 b - - 9 4" => {
+        "r1eakaer1/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 b" => {
             Some("C2=1")
         },
         // 1. C2=5 H2+3
@@ -1176,7 +1194,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 H8+7
         // 4. C8=6 R1=2
         // 5. H8+7 C2=1
-        "r1eakaer1/9/2hc2h1c/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 w - - 10 5" => {
+        "r1eakaer1/9/2hc2h1c/p1p1p1p1p/9/9/P1P1P1P1P/2HCC1H2/9/R1EAKAER1 w" => {
             Some("P7+1")
         },
         // 1. C2=5 H2+3
@@ -1185,7 +1203,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. C8=6 R1=2
         // 5. H8+7 C2=1
         // 6. P7+1
-        "r1eakaer1/9/2hc2h1c/p1p1p1p1p/9/2P6/P3P1P1P/2HCC1H2/9/R1EAKAER1 b - - 0 5" => {
+        "r1eakaer1/9/2hc2h1c/p1p1p1p1p/9/2P6/P3P1P1P/2HCC1H2/9/R1EAKAER1 b" => {
             Some("P7+1")
         },
         // 1. C2=5 H2+3
@@ -1194,7 +1212,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. C8=6 R1=2
         // 5. H8+7 C2=1
         // 6. P7+1 P7+1
-        "r1eakaer1/9/2hc2h1c/p3p1p1p/2p6/2P6/P3P1P1P/2HCC1H2/9/R1EAKAER1 w - - 0 6" => {
+        "r1eakaer1/9/2hc2h1c/p3p1p1p/2p6/2P6/P3P1P1P/2HCC1H2/9/R1EAKAER1 w" => {
             Some("H7+6")
         },
         // 1. C2=5 H2+3
@@ -1204,33 +1222,33 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. H8+7 C2=1
         // 6. P7+1 P7+1
         // 7. H7+6
-        "r1eakaer1/9/2hc2h1c/p3p1p1p/2p6/2PH5/P3P1P1P/3CC1H2/9/R1EAKAER1 b - - 1 6" => {
+        "r1eakaer1/9/2hc2h1c/p3p1p1p/2p6/2PH5/P3P1P1P/3CC1H2/9/R1EAKAER1 b" => {
             Some("A6+5")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1+1
-        "rheakae1r/9/3c2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 b - - 5 2" => {
+        "rheakae1r/9/3c2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 b" => {
             Some("H8+7")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1+1 H8+7 *
-        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 w - - 6 3" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/8R/RHEAKAE2 w" => {
             Some("R1=4")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1+1 H8+7
         // 4. R1=4
-        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/5R3/RHEAKAE2 b - - 7 3" => {
+        "r1eakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/5R3/RHEAKAE2 b" => {
             Some("R9=8")
         },
         // 1. C2=5 H2+3
         // 2. H2+3 C8=6
         // 3. R1+1 H8+7
         // 4. R1=4 R9=8
-        "1reakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/5R3/RHEAKAE2 w - - 8 4" => {
+        "1reakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/5R3/RHEAKAE2 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H2+3
@@ -1238,7 +1256,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1+1 H8+7
         // 4. R1=4 R9=8
         // 5. H8+7
-        "1reakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/5R3/R1EAKAE2 b - - 9 4" => {
+        "1reakae1r/9/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/5R3/R1EAKAE2 b" => {
             Some("A4+5")
         },
         // 1. C2=5 H2+3
@@ -1246,92 +1264,92 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1+1 H8+7
         // 4. R1=4 R9=8
         // 5. H8+7 A4+5
-        "1reak1e1r/4a4/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/5R3/R1EAKAE2 w - - 10 5" => {
+        "1reak1e1r/4a4/2hc2hc1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/5R3/R1EAKAE2 w" => {
             random_move(vec!["P5+1", "R9+1"])
         },
         // 1. C2=5 H8+7
-        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w - - 2 1" => {
+        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR w" => {
             Some("H2+3")
         },
         // 1. C2=5 H8+7
         // 2. H2+3
-        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b - - 3 1" => {
+        "r1eakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b" => {
             random_move(vec!["R9=8", "R9+1"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9+1
-        "2eakaehr/r8/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 4 2" => {
+        "2eakaehr/r8/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("R1=2")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9+1
         // 3. R1=2
-        "2eakaehr/r8/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 5 2" => {
+        "2eakaehr/r8/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("C8-1")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9+1
         // 3. R1=2 C8-1
-        "2eakaehr/rc7/2h4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "2eakaehr/rc7/2h4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["H8+9", "H8+7"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9+1
         // 3. R1=2 C8-1
         // 4. H8+7
-        "2eakaehr/rc7/2h4c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 7 3" => {
+        "2eakaehr/rc7/2h4c1/p1p1p1p1p/9/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("P3+1")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9+1
         // 3. R1=2 C8-1
         // 4. H8+9
-        "2eakaehr/rc7/2h4c1/p1p1p1p1p/9/9/P1P1P1P1P/HC2C1H2/9/R1EAKAER1 b - - 7 3" => {
+        "2eakaehr/rc7/2h4c1/p1p1p1p1p/9/9/P1P1P1P1P/HC2C1H2/9/R1EAKAER1 b" => {
             Some("E3+5")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
-        "1reakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w - - 4 2" => {
+        "1reakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             random_move(vec!["P7+1", "R1=2"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1
-        "1reakaehr/9/1ch4c1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R b - - 0 2" => {
+        "1reakaehr/9/1ch4c1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R b" => {
             random_move(vec!["P7+1", "C8=9"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 C8=9
-        "1reakaehr/9/c1h4c1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w - - 1 3" => {
+        "1reakaehr/9/c1h4c1/p1p1p1p1p/9/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("H8+7")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 C8=9
         // 4. H8+7
-        "1reakaehr/9/c1h4c1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R b - - 2 3" => {
+        "1reakaehr/9/c1h4c1/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R b" => {
             random_move(vec!["C2=5", "P7+1", "R8+5"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 C8=9
         // 4. H8+7 R8+5
-        "2eakaehr/9/c1h4c1/p1p1p1p1p/9/1rP6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w - - 3 4" => {
+        "2eakaehr/9/c1h4c1/p1p1p1p1p/9/1rP6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w" => {
             random_move(vec!["P5+1", "E7+9"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 C8=9
         // 4. H8+7 P7+1
-        "1reakaehr/9/c1h4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w - - 0 4" => {
+        "1reakaehr/9/c1h4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w" => {
             random_move(vec!["R1+1", "C8+2", "H7+6"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 C8=9
         // 4. H8+7 C2=5
-        "1reakaehr/9/c1h1c4/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w - - 3 4" => {
+        "1reakaehr/9/c1h1c4/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w" => {
             Some("R9=8")
         },
         // 1. C2=5 H8+7
@@ -1339,7 +1357,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. P7+1 C8=9
         // 4. H8+7 C2=5
         // 5. R9=8
-        "1reakaehr/9/c1h1c4/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/1REAKAE1R b - - 4 4" => {
+        "1reakaehr/9/c1h1c4/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/1REAKAE1R b" => {
             Some("H2+3")
         },
         // 1. C2=5 H8+7
@@ -1347,53 +1365,53 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. P7+1 C8=9
         // 4. H8+7 C2=5
         // 5. R9=8 H2+3
-        "1reakae1r/9/c1h1c1h2/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/1REAKAE1R w - - 5 5" => {
+        "1reakae1r/9/c1h1c1h2/p1p1p1p1p/9/2P6/P3P1P1P/1CH1C1H2/9/1REAKAE1R w" => {
             random_move(vec!["P3+1", "R1+1"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 P7+1
-        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w - - 0 3" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAE1R w" => {
             Some("H8+7")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 P7+1
         // 4. H8+7
-        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R b - - 1 3" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R b" => {
             Some("H2+3")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. P7+1 P7+1
         // 4. H8+7 H2+3
-        "1reakae1r/9/1ch3hc1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w - - 2 4" => {
+        "1reakae1r/9/1ch3hc1/p3p1p1p/2p6/2P6/P3P1P1P/1CH1C1H2/9/R1EAKAE1R w" => {
             random_move(vec!["R1+1", "C8+2", "C8=9"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2
-        "1reakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b - - 5 2" => {
+        "1reakaehr/9/1ch4c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             random_move(vec!["P7+1", "C2=5", "C8+4"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 C8+4
-        "1reakaehr/9/2h4c1/p1p1p1p1p/9/9/PcP1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "1reakaehr/9/2h4c1/p1p1p1p1p/9/9/PcP1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("P3+1")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 C8+4
         // 4. P3+1
-        "1reakaehr/9/2h4c1/p1p1p1p1p/9/6P2/PcP1P3P/1C2C1H2/9/RHEAKAER1 b - - 0 3" => {
+        "1reakaehr/9/2h4c1/p1p1p1p1p/9/6P2/PcP1P3P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("C2=5")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 C8+4
         // 4. P3+1 C2=5
-        "1reakaehr/9/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1C2C1H2/9/RHEAKAER1 w - - 1 4" => {
+        "1reakaehr/9/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H8+7
@@ -1401,7 +1419,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 C8+4
         // 4. P3+1 C2=5
         // 5. H8+7
-        "1reakaehr/9/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/R1EAKAER1 b - - 2 4" => {
+        "1reakaehr/9/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["R1+1", "H2+3"])
         },
         // 1. C2=5 H8+7
@@ -1409,7 +1427,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 C8+4
         // 4. P3+1 C2=5
         // 5. H8+7 H2+3
-        "1reakae1r/9/2h1c1h2/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/R1EAKAER1 w - - 3 5" => {
+        "1reakae1r/9/2h1c1h2/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             random_move(vec!["P7+1", "R9=8"])
         },
         // 1. C2=5 H8+7
@@ -1418,7 +1436,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P3+1 C2=5
         // 5. H8+7 H2+3
         // 6. R9=8
-        "1reakae1r/9/2h1c1h2/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/1REAKAER1 b - - 4 5" => {
+        "1reakae1r/9/2h1c1h2/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/1REAKAER1 b" => {
             Some("P3+1")
         },
         // 1. C2=5 H8+7
@@ -1427,7 +1445,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P3+1 C2=5
         // 5. H8+7 H2+3
         // 6. R9=8 P3+1
-        "1reakae1r/9/2h1c1h2/p1p1p3p/9/6p2/PcP1P3P/1CH1C1H2/9/1REAKAER1 w - - 0 6" => {
+        "1reakae1r/9/2h1c1h2/p1p1p3p/9/6p2/PcP1P3P/1CH1C1H2/9/1REAKAER1 w" => {
             Some("H3+4")
         },
         // 1. C2=5 H8+7
@@ -1437,7 +1455,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. H8+7 H2+3
         // 6. R9=8 P3+1
         // 7. H3+4
-        "1reakae1r/9/2h1c1h2/p1p1p3p/9/5Hp2/PcP1P3P/1CH1C13/9/1REAKAER1 b - - 1 6" => {
+        "1reakae1r/9/2h1c1h2/p1p1p3p/9/5Hp2/PcP1P3P/1CH1C13/9/1REAKAER1 b" => {
             random_move(vec!["R1=2", "R1+1"])
         },
         // 1. C2=5 H8+7
@@ -1446,7 +1464,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P3+1 C2=5
         // 5. H8+7 H2+3
         // 6. P7+1
-        "1reakae1r/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P3P/1CH1C1H2/9/R1EAKAER1 b - - 0 5" => {
+        "1reakae1r/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P3P/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("R1=2")
         },
         // 1. C2=5 H8+7
@@ -1455,7 +1473,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P3+1 C2=5
         // 5. H8+7 H2+3
         // 6. P7+1 R1=2
-        "1reakaer1/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P3P/1CH1C1H2/9/R1EAKAER1 w - - 1 6" => {
+        "1reakaer1/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("R9=8")
         },
         // 1. C2=5 H8+7
@@ -1465,7 +1483,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. H8+7 H2+3
         // 6. P7+1 R1=2
         // 7. R9=8
-        "1reakaer1/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P3P/1CH1C1H2/9/1REAKAER1 b - - 2 6" => {
+        "1reakaer1/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P3P/1CH1C1H2/9/1REAKAER1 b" => {
             random_move(vec!["R2+6", "R2+4"])
         },
         // 1. C2=5 H8+7
@@ -1475,7 +1493,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. H8+7 H2+3
         // 6. P7+1 R1=2
         // 7. R9=8 R2+4
-        "1reakae2/9/2h1c1h2/p1p1p1p1p/7r1/2P3P2/Pc2P3P/1CH1C1H2/9/1REAKAER1 w - - 3 7" => {
+        "1reakae2/9/2h1c1h2/p1p1p1p1p/7r1/2P3P2/Pc2P3P/1CH1C1H2/9/1REAKAER1 w" => {
             Some("C8=9")
         },
         // 1. C2=5 H8+7
@@ -1486,7 +1504,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 6. P7+1 R1=2
         // 7. R9=8 R2+4
         // 8. C8=9
-        "1reakae2/9/2h1c1h2/p1p1p1p1p/7r1/2P3P2/Pc2P3P/C1H1C1H2/9/1REAKAER1 b - - 4 7" => {
+        "1reakae2/9/2h1c1h2/p1p1p1p1p/7r1/2P3P2/Pc2P3P/C1H1C1H2/9/1REAKAER1 b" => {
             Some("R2=8")
         },
         // 1. C2=5 H8+7
@@ -1496,7 +1514,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. H8+7 H2+3
         // 6. P7+1 R1=2
         // 7. R9=8 R2+6
-        "1reakae2/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P2rP/1CH1C1H2/9/1REAKAER1 w - - 3 7" => {
+        "1reakae2/9/2h1c1h2/p1p1p1p1p/9/2P3P2/Pc2P2rP/1CH1C1H2/9/1REAKAER1 w" => {
             random_move(vec!["H7+6", "C8=9"])
         },
         // 1. C2=5 H8+7
@@ -1504,7 +1522,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 C8+4
         // 4. P3+1 C2=5
         // 5. H8+7 R1+1
-        "1reakaeh1/8r/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/R1EAKAER1 w - - 3 5" => {
+        "1reakaeh1/8r/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("R9=8")
         },
         // 1. C2=5 H8+7
@@ -1513,7 +1531,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P3+1 C2=5
         // 5. H8+7 R1+1
         // 6. R9=8
-        "1reakaeh1/8r/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/1REAKAER1 b - - 4 5" => {
+        "1reakaeh1/8r/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/1REAKAER1 b" => {
             Some("R1=8")
         },
         // 1. C2=5 H8+7
@@ -1522,47 +1540,47 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P3+1 C2=5
         // 5. H8+7 R1+1
         // 6. R9=8 R1=8
-        "1reakaeh1/7r1/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/1REAKAER1 w - - 5 6" => {
+        "1reakaeh1/7r1/2h1c4/p1p1p1p1p/9/6P2/PcP1P3P/1CH1C1H2/9/1REAKAER1 w" => {
             random_move(vec!["H3+4", "P7+1"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 C2=5
-        "1reakaehr/9/1ch1c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 6 3" => {
+        "1reakaehr/9/1ch1c4/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["R2+6", "H8+7"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
-        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w - - 0 3" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["P7+1", "R2+6", "P3+1", "H8+9"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. H8+9
-        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/HC2C1H2/9/R1EAKAER1 b - - 1 3" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/9/P1P1P1P1P/HC2C1H2/9/R1EAKAER1 b" => {
             Some("P7+1")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. H8+9 P7+1
-        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P1P1P1P1P/HC2C1H2/9/R1EAKAER1 w - - 0 4" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P1P1P1P1P/HC2C1H2/9/R1EAKAER1 w" => {
             random_move(vec!["C8=6", "C8=7"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. P3+1
-        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 b - - 0 3" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 b" => {
             Some("P3+1")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. P3+1 P3+1
-        "1reakaehr/9/1ch4c1/p3p3p/2p3p2/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 w - - 0 4" => {
+        "1reakaehr/9/1ch4c1/p3p3p/2p3p2/6P2/P1P1P3P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["C8+4", "H8+9"])
         },
         // 1. C2=5 H8+7
@@ -1570,7 +1588,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P3+1 P3+1
         // 5. H8+9
-        "1reakaehr/9/1ch4c1/p3p3p/2p3p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 b - - 1 4" => {
+        "1reakaehr/9/1ch4c1/p3p3p/2p3p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["E3+5", "E7+5", "P1+1"])
         },
         // 1. C2=5 H8+7
@@ -1578,7 +1596,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P3+1 P3+1
         // 5. H8+9 P1+1
-        "1reakaehr/9/1ch4c1/p3p4/2p3p1p/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w - - 0 5" => {
+        "1reakaehr/9/1ch4c1/p3p4/2p3p1p/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w" => {
             Some("C8=7")
         },
         // 1. C2=5 H8+7
@@ -1586,7 +1604,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P3+1 P3+1
         // 5. H8+9 E7+5
-        "1r1akaehr/9/1ch1e2c1/p3p3p/2p3p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w - - 2 5" => {
+        "1r1akaehr/9/1ch1e2c1/p3p3p/2p3p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w" => {
             Some("R9+1")
         },
         // 1. C2=5 H8+7
@@ -1594,7 +1612,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P3+1 P3+1
         // 5. H8+9 E3+5
-        "1reaka1hr/9/1ch1e2c1/p3p3p/2p3p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w - - 2 5" => {
+        "1reaka1hr/9/1ch1e2c1/p3p3p/2p3p2/6P2/P1P1P3P/HC2C1H2/9/R1EAKAER1 w" => {
             Some("C8+4")
         },
         // 1. C2=5 H8+7
@@ -1602,21 +1620,21 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P3+1 P3+1
         // 5. C8+4
-        "1reakaehr/9/1ch4c1/pC2p3p/2p3p2/6P2/P1P1P3P/4C1H2/9/RHEAKAER1 b - - 1 4" => {
+        "1reakaehr/9/1ch4c1/pC2p3p/2p3p2/6P2/P1P1P3P/4C1H2/9/RHEAKAER1 b" => {
             random_move(vec!["E7+5"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. R2+6
-        "1reakaehr/9/1ch4c1/p3p1pRp/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 b - - 1 3" => {
+        "1reakaehr/9/1ch4c1/p3p1pRp/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 b" => {
             Some("H2+3")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. R2+6 H2+3
-        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 w - - 2 4" => {
+        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             random_move(vec!["P5+1", "C8=6", "H8+7"])
         },
         // 1. C2=5 H8+7
@@ -1624,7 +1642,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. R2+6 H2+3
         // 5. H8+7
-        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAE2 b - - 3 4" => {
+        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAE2 b" => {
             Some("P3+1")
         },
         // 1. C2=5 H8+7
@@ -1632,7 +1650,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. R2+6 H2+3
         // 5. H8+7 P3+1
-        "1reakae1r/9/1ch3hc1/p3p2Rp/2p3p2/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAE2 w - - 0 5" => {
+        "1reakae1r/9/1ch3hc1/p3p2Rp/2p3p2/9/P1P1P1P1P/1CH1C1H2/9/R1EAKAE2 w" => {
             Some("R9+1")
         },
         // 1. C2=5 H8+7
@@ -1640,7 +1658,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. R2+6 H2+3
         // 5. C8=6
-        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/3CC1H2/9/RHEAKAE2 b - - 3 4" => {
+        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/3CC1H2/9/RHEAKAE2 b" => {
             Some("R1=2")
         },
         // 1. C2=5 H8+7
@@ -1648,7 +1666,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. R2+6 H2+3
         // 5. C8=6 R1=2
-        "1reakaer1/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/3CC1H2/9/RHEAKAE2 w - - 4 5" => {
+        "1reakaer1/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/3CC1H2/9/RHEAKAE2 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H8+7
@@ -1657,7 +1675,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. R2+6 H2+3
         // 5. C8=6 R1=2
         // 6. H8+7
-        "1reakaer1/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/2HCC1H2/9/R1EAKAE2 b - - 5 5" => {
+        "1reakaer1/9/1ch3hc1/p3p1pRp/2p6/9/P1P1P1P1P/2HCC1H2/9/R1EAKAE2 b" => {
             Some("C2=1")
         },
         // 1. C2=5 H8+7
@@ -1666,7 +1684,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. R2+6 H2+3
         // 5. C8=6 R1=2
         // 6. H8+7 C2=1
-        "1reakaer1/9/1ch3h1c/p3p1pRp/2p6/9/P1P1P1P1P/2HCC1H2/9/R1EAKAE2 w - - 6 6" => {
+        "1reakaer1/9/1ch3h1c/p3p1pRp/2p6/9/P1P1P1P1P/2HCC1H2/9/R1EAKAE2 w" => {
             Some("P5+1")
         },
         // 1. C2=5 H8+7
@@ -1674,21 +1692,21 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. R2+6 H2+3
         // 5. P5+1
-        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/4P4/P1P3P1P/1C2C1H2/9/RHEAKAE2 b - - 0 4" => {
+        "1reakae1r/9/1ch3hc1/p3p1pRp/2p6/4P4/P1P3P1P/1C2C1H2/9/RHEAKAE2 b" => {
             Some("A4+5")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. P7+1
-        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b - - 0 3" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/2p6/2P6/P3P1P1P/1C2C1H2/9/RHEAKAER1 b" => {
             random_move(vec!["C8+4", "P7+1"])
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
-        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w - - 0 4" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             random_move(vec!["C8=7", "H8+7", "R2+6"])
         },
         // 1. C2=5 H8+7
@@ -1696,7 +1714,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. R2+6
-        "1reakaehr/9/1ch4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 b - - 1 4" => {
+        "1reakaehr/9/1ch4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 b" => {
             random_move(vec!["R1+1", "H7+6", "A4+5", "C8=9"])
         },
         // 1. C2=5 H8+7
@@ -1704,7 +1722,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. R2+6 C8=9
-        "1reakaehr/9/c1h4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w - - 2 5" => {
+        "1reakaehr/9/c1h4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             Some("R2=3")
         },
         // 1. C2=5 H8+7
@@ -1713,7 +1731,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P7+1 P7+1
         // 5. R2+6 C8=9
         // 6. R2=3
-        "1reakaehr/9/c1h4c1/p3p1R1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 b - - 0 5" => {
+        "1reakaehr/9/c1h4c1/p3p1R1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 b" => {
             random_move(vec!["R8+2", "C9-1"])
         },
         // 1. C2=5 H8+7
@@ -1722,7 +1740,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P7+1 P7+1
         // 5. R2+6 C8=9
         // 6. R2=3 C9-1
-        "1reakaehr/c8/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w - - 1 6" => {
+        "1reakaehr/c8/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             random_move(vec!["P5+1", "H8+9", "C8+6", "H8+7"])
         },
         // 1. C2=5 H8+7
@@ -1732,7 +1750,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. R2+6 C8=9
         // 6. R2=3 C9-1
         // 7. H8+7
-        "1reakaehr/c8/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b - - 2 6" => {
+        "1reakaehr/c8/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b" => {
             random_move(vec!["R1+1", "A4+5"])
         },
         // 1. C2=5 H8+7
@@ -1742,7 +1760,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. R2+6 C8=9
         // 6. R2=3 C9-1
         // 7. H8+7 A4+5
-        "1reak1ehr/c3a4/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 w - - 3 7" => {
+        "1reak1ehr/c3a4/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 w" => {
             random_move(vec!["H7+6", "C8=9"])
         },
         // 1. C2=5 H8+7
@@ -1752,7 +1770,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. R2+6 C8=9
         // 6. R2=3 C9-1
         // 7. H8+7 R1+1
-        "1reakaeh1/c7r/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 w - - 3 7" => {
+        "1reakaeh1/c7r/2h4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 w" => {
             Some("C8=9")
         },
         // 1. C2=5 H8+7
@@ -1761,7 +1779,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P7+1 P7+1
         // 5. R2+6 C8=9
         // 6. R2=3 R8+2
-        "2eakaehr/9/crh4c1/p3p1R1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w - - 1 6" => {
+        "2eakaehr/9/crh4c1/p3p1R1p/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H8+7
@@ -1771,7 +1789,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 5. R2+6 C8=9
         // 6. R2=3 R8+2
         // 7. H8+7
-        "2eakaehr/9/crh4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b - - 2 6" => {
+        "2eakaehr/9/crh4c1/p3p1R1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b" => {
             Some("E3+5")
         },
         // 1. C2=5 H8+7
@@ -1779,7 +1797,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. R2+6 A4+5
-        "1reak1ehr/4a4/1ch4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w - - 2 5" => {
+        "1reak1ehr/4a4/1ch4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             random_move(vec!["H8+7", "C8=7"])
         },
         // 1. C2=5 H8+7
@@ -1787,7 +1805,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. R2+6 H7+6
-        "1reakaehr/9/1c5c1/p3p1pRp/3h5/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w - - 2 5" => {
+        "1reakaehr/9/1c5c1/p3p1pRp/3h5/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H8+7
@@ -1796,7 +1814,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P7+1 P7+1
         // 5. R2+6 H7+6
         // 6. H8+7
-        "1reakaehr/9/1c5c1/p3p1pRp/3h5/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b - - 3 5" => {
+        "1reakaehr/9/1c5c1/p3p1pRp/3h5/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAE2 b" => {
             random_move(vec!["E3+5", "R1+1", "P7+1"])
         },
         // 1. C2=5 H8+7
@@ -1804,7 +1822,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. R2+6 R1+1
-        "1reakaeh1/8r/1ch4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w - - 2 5" => {
+        "1reakaeh1/8r/1ch4c1/p3p1pRp/9/2p6/P3P1P1P/1C2C1H2/9/RHEAKAE2 w" => {
             random_move(vec!["H8+7", "C8=7"])
         },
         // 1. C2=5 H8+7
@@ -1812,7 +1830,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. H8+7
-        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 1 4" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["E3+5", "C8+2", "C2+4"])
         },
         // 1. C2=5 H8+7
@@ -1820,7 +1838,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. H8+7 C2+4
-        "1reakaehr/9/1ch6/p3p1p1p/9/2p6/P3P1PcP/1CH1C1H2/9/R1EAKAER1 w - - 2 5" => {
+        "1reakaehr/9/1ch6/p3p1p1p/9/2p6/P3P1PcP/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("P5+1")
         },
         // 1. C2=5 H8+7
@@ -1829,7 +1847,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 4. P7+1 P7+1
         // 5. H8+7 C2+4
         // 6. P5+1
-        "1reakaehr/9/1ch6/p3p1p1p/9/2p1P4/P5PcP/1CH1C1H2/9/R1EAKAER1 b - - 0 5" => {
+        "1reakaehr/9/1ch6/p3p1p1p/9/2p1P4/P5PcP/1CH1C1H2/9/R1EAKAER1 b" => {
             Some("C8+4")
         },
         // 1. C2=5 H8+7
@@ -1837,7 +1855,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. H8+7 C8+2
-        "1reakaehr/9/2h4c1/p3p1p1p/1c7/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 2 5" => {
+        "1reakaehr/9/2h4c1/p3p1p1p/1c7/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("H7+6")
         },
         // 1. C2=5 H8+7
@@ -1845,7 +1863,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. H8+7 E3+5
-        "1reaka1hr/9/1ch1e2c1/p3p1p1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w - - 2 5" => {
+        "1reaka1hr/9/1ch1e2c1/p3p1p1p/9/2p6/P3P1P1P/1CH1C1H2/9/R1EAKAER1 w" => {
             Some("C8+2")
         },
         // 1. C2=5 H8+7
@@ -1853,7 +1871,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. C8=7
-        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P3P1P1P/2C1C1H2/9/RHEAKAER1 b - - 1 4" => {
+        "1reakaehr/9/1ch4c1/p3p1p1p/9/2p6/P3P1P1P/2C1C1H2/9/RHEAKAER1 b" => {
             random_move(vec!["C8+2", "C2+4"])
         },
         // 1. C2=5 H8+7
@@ -1861,7 +1879,7 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. C8=7 C2+4
-        "1reakaehr/9/2h4c1/p3p1p1p/9/2p6/Pc2P1P1P/2C1C1H2/9/RHEAKAER1 w - - 2 5" => {
+        "1reakaehr/9/2h4c1/p3p1p1p/9/2p6/Pc2P1P1P/2C1C1H2/9/RHEAKAER1 w" => {
             Some("R2+4")
         },
         // 1. C2=5 H8+7
@@ -1869,14 +1887,14 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 P7+1
         // 5. C8=7 C8+2
-        "1reakaehr/9/2h4c1/p3p1p1p/1c7/2p6/P3P1P1P/2C1C1H2/9/RHEAKAER1 w - - 2 5" => {
+        "1reakaehr/9/2h4c1/p3p1p1p/1c7/2p6/P3P1P1P/2C1C1H2/9/RHEAKAER1 w" => {
             Some("H8+9")
         },
         // 1. C2=5 H8+7
         // 2. H2+3 R9=8
         // 3. R1=2 P7+1
         // 4. P7+1 C8+4
-        "1reakaehr/9/2h4c1/p3p1p1p/2p6/2P6/Pc2P1P1P/1C2C1H2/9/RHEAKAER1 w - - 1 4" => {
+        "1reakaehr/9/2h4c1/p3p1p1p/2p6/2P6/Pc2P1P1P/1C2C1H2/9/RHEAKAER1 w" => {
             Some("H8+7")
         },
         // 1. C2=5 H8+7
@@ -1884,13 +1902,40 @@ pub fn recommended_move(game_state: &String) -> Option<&'static str> {
         // 3. R1=2 P7+1
         // 4. P7+1 C8+4
         // 5. H8+7
-        "1reakaehr/9/2h4c1/p3p1p1p/2p6/2P6/Pc2P1P1P/1CH1C1H2/9/R1EAKAER1 b - - 2 4" => {
+        "1reakaehr/9/2h4c1/p3p1p1p/2p6/2P6/Pc2P1P1P/1CH1C1H2/9/R1EAKAER1 b" => {
             random_move(vec!["E3+5", "C2=5"])
         },
-        _ => None
+        _ => book_move(&position, parsed.current_player_number)
     }
 }
 
+// the book at XIANGQI_BOOK_PATH, or the embedded book when it isn't set
+// called at startup so that an invalid book is found straight away
+pub fn load_book() -> &'static Book {
+    BOOK.get_or_init(|| {
+        match env::var("XIANGQI_BOOK_PATH") {
+            Ok(path) => {
+                let encoded = fs::read_to_string(path).expect("XIANGQI_BOOK_PATH must be a readable file");
+                book::parse(&encoded).expect("XIANGQI_BOOK_PATH must be a valid book")
+            },
+            Err(_) => book::parse(EMBEDDED_BOOK).expect("invalid xiangqi opening book")
+        }
+    })
+}
+
+// a move weighted by how often it was played
+fn book_move(position: &str, current_player_number: i8) -> Option<&'static str> {
+    let entries = load_book().get(position)?;
+
+    let mut candidates: Vec<&BookEntry> = entries.iter().filter(|e| e.score(current_player_number) >= MINIMUM_BOOK_SCORE).collect();
+    if candidates.is_empty() {
+        candidates = entries.iter().collect();
+    }
+
+    let mut rng = rand::rng();
+    candidates.choose_weighted(&mut rng, |e| e.games).ok().map(|e| e.mov.as_str())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1912,4 +1957,44 @@ mod tests {
             None => assert!(false, "expected move"),
         }
     }
+
+    #[test]
+    fn fetch_book_test() {
+        // 1. E3+5 C2=4 2. R9+1
+        let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves g0e2 b7d7 a0a1");
+        assert_eq!(recommended_move(&game_state), Some("H2+3"));
+    }
+
+    #[test]
+    fn fetch_book_unknown_test() {
+        let game_state = String::from("4k4/9/9/9/9/9/9/9/9/4K4 w - - 0 1");
+        assert_eq!(recommended_move(&game_state), None);
+    }
+
+    #[test]
+    fn embedded_book_test() {
+        let mut records = crate::xiangqi::record::pgn::parse_all(include_str!("records/central_cannon.pgn")).unwrap();
+        records.extend(crate::xiangqi::record::pgn::parse_all(include_str!("records/flank.pgn")).unwrap());
+        let expected = book::build(&records, 20).unwrap();
+
+        assert_eq!(book::parse(EMBEDDED_BOOK).unwrap(), expected);
+    }
+
+
+    #[test]
+    fn fetch_move_counts_test() {
+        // 1. C2=6, sent with standard counters and after the moves played
+        let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b - - 1 1");
+        assert_eq!(recommended_move(&game_state), Some("C8=5"));
+
+        let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2d2");
+        assert_eq!(recommended_move(&game_state), Some("C8=5"));
+    }
+
+    #[test]
+    fn fetch_invalid_test() {
+        let game_state = String::from("rheakaehr/9/1c5c1 x");
+        assert_eq!(recommended_move(&game_state), None);
+    }
+
 }
@@ -0,0 +1,202 @@
+use crate::xiangqi::record::GameRecord;
+use crate::xiangqi::record::STARTING_POSITION;
+use crate::xiangqi::record::find_move;
+use crate::xiangqi::record::position_from_points;
+use crate::xiangqi::record::player_on_point;
+use crate::xiangqi::record::with_player;
+use crate::xiangqi::state::game_state::Outcome;
+
+// the piece is not on the board
+const ABSENT: &str = "99";
+
+// DhtmlXQ game record, as embedded in web pages
+//   [DhtmlXQ]
+//   [DhtmlXQ_red]Red[/DhtmlXQ_red]
+//   [DhtmlXQ_black]Black[/DhtmlXQ_black]
+//   [DhtmlXQ_result]红胜[/DhtmlXQ_result]
+//   [DhtmlXQ_binit]8979695949392919097717866646260600102030405060708012720323436383[/DhtmlXQ_binit]
+//   [DhtmlXQ_movelist]77477062[/DhtmlXQ_movelist]
+//   [/DhtmlXQ]
+//
+// binit lists the points of the 32 pieces, red then black, as a file from the left and a rank from the top,
+// the movelist the from and to points of each move in the same way
+pub fn parse(encoded: &str) -> Result<GameRecord, &'static str> {
+    let points = match field(encoded, "binit") {
+        Some(binit) => Some(parse_points(binit)?),
+        None => None
+    };
+
+    let moves = match field(encoded, "movelist") {
+        Some(movelist) => parse_points(movelist)?,
+        None => vec![]
+    };
+
+    if moves.len() % 2 != 0 {
+        return Err("dhtmlxq::parse - Invalid movelist");
+    }
+
+    // the record doesn't say who moves first, so it is the owner of the first piece moved
+    let starting_position = match points {
+        Some(p) => {
+            let position = position_from_points(&p, 1)?;
+            match moves.first() {
+                Some(Some(from)) => with_player(&position, player_on_point(&position, *from).unwrap_or(1)),
+                _ => position
+            }
+        },
+        None => String::from(STARTING_POSITION)
+    };
+
+    let (mut record, mut game_state) = GameRecord::new(&starting_position)?;
+
+    for pair in moves.chunks(2) {
+        match (pair[0], pair[1]) {
+            (Some(from), Some(to)) => {
+                let mov = find_move(&mut game_state, from, to).map_err(|_| "dhtmlxq::parse - Illegal move")?;
+                record.push_move(&mut game_state, mov)?;
+            },
+            _ => return Err("dhtmlxq::parse - Invalid movelist")
+        }
+    }
+
+    record.red_name = field(encoded, "red").map(|n| n.to_string());
+    record.black_name = field(encoded, "black").map(|n| n.to_string());
+    record.result = field(encoded, "result").and_then(parse_result);
+
+    Ok(record)
+}
+
+// [DhtmlXQ_red]Red[/DhtmlXQ_red] -> Red
+fn field<'a>(encoded: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("[DhtmlXQ_{}]", name);
+    let close = format!("[/DhtmlXQ_{}]", name);
+    let start = encoded.find(&open)? + open.len();
+    let length = encoded[start..].find(&close)?;
+    let value = encoded[start..start + length].trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+// pairs of digits, file then rank
+fn parse_points(encoded: &str) -> Result<Vec<Option<(i8, i8)>>, &'static str> {
+    let chars: Vec<char> = encoded.chars().filter(|c| !c.is_whitespace()).collect();
+    if chars.len() % 2 != 0 {
+        return Err("dhtmlxq::parse - Invalid points");
+    }
+
+    chars.chunks(2).map(|pair| {
+        let encoded_point: String = pair.iter().collect();
+        if encoded_point == ABSENT {
+            return Ok(None);
+        }
+        match (pair[0].to_digit(10), pair[1].to_digit(10)) {
+            (Some(x), Some(y)) if x < 9 => Ok(Some((x as i8, y as i8))),
+            _ => Err("dhtmlxq::parse - Invalid point")
+        }
+    }).collect()
+}
+
+// 红胜 or 黑负 - red wins, 黑胜 or 红负 - black wins, 和棋 - draw
+fn parse_result(value: &str) -> Option<Outcome> {
+    match value {
+        "1-0" => return Some(Outcome::Win(1)),
+        "0-1" => return Some(Outcome::Win(2)),
+        "1/2-1/2" => return Some(Outcome::Draw),
+        _ => ()
+    }
+
+    if value.contains('和') {
+        return Some(Outcome::Draw);
+    }
+
+    // the side named closest before the word
+    let (index, won) = match (value.find('胜'), value.find('负')) {
+        (Some(i), _) => (i, true),
+        (None, Some(i)) => (i, false),
+        (None, None) => return None
+    };
+
+    let before = &value[..index];
+    let red = before.rfind('红');
+    let black = before.rfind('黑');
+    let red_named = match (red, black) {
+        (Some(r), Some(b)) => r > b,
+        (Some(_), None) => true,
+        (None, Some(_)) => false,
+        (None, None) => return None
+    };
+
+    if red_named == won {
+        Some(Outcome::Win(1))
+    } else {
+        Some(Outcome::Win(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xiangqi::state::square::PieceKind;
+
+    #[test]
+    fn parse_test() {
+        let encoded = "[DhtmlXQ]\n[DhtmlXQ_red]Red Player[/DhtmlXQ_red]\n[DhtmlXQ_black]Black Player[/DhtmlXQ_black]\n[DhtmlXQ_result]黑胜[/DhtmlXQ_result]\n[DhtmlXQ_binit]8979695949392919097717866646260600102030405060708012720323436383[/DhtmlXQ_binit]\n[DhtmlXQ_movelist]774770627967[/DhtmlXQ_movelist]\n[/DhtmlXQ]";
+        let result = parse(encoded).unwrap();
+
+        assert_eq!(result.red_name, Some(String::from("Red Player")));
+        assert_eq!(result.black_name, Some(String::from("Black Player")));
+        assert_eq!(result.result, Some(Outcome::Win(2)));
+        assert_eq!(result.starting_position, STARTING_POSITION);
+        assert_eq!(result.moves.len(), 3);
+        assert_eq!(result.moves[0].moving_piece_kind, PieceKind::Cannon);
+        assert_eq!(result.moves[1].moving_piece_kind, PieceKind::Horse);
+        assert_eq!(result.moves[2].to, (6, 7));
+    }
+
+    #[test]
+    fn parse_without_binit_test() {
+        let encoded = "[DhtmlXQ_movelist]7747[/DhtmlXQ_movelist]";
+        let result = parse(encoded).unwrap();
+
+        assert_eq!(result.starting_position, STARTING_POSITION);
+        assert_eq!(result.moves.len(), 1);
+        assert_eq!(result.result, None);
+    }
+
+    #[test]
+    fn parse_black_first_test() {
+        // red king on e0, black king on d9 and cannon on b7
+        let mut binit = String::new();
+        for index in 0..32 {
+            binit.push_str(match index {
+                4 => "49",
+                20 => "30",
+                25 => "12",
+                _ => "99"
+            });
+        }
+        let encoded = format!("[DhtmlXQ_binit]{}[/DhtmlXQ_binit][DhtmlXQ_movelist]1242[/DhtmlXQ_movelist]", binit);
+        let result = parse(&encoded).unwrap();
+
+        assert_eq!(result.starting_position, "3k5/9/1c7/9/9/9/9/9/9/4K4 b - - 0 1");
+        assert_eq!(result.moves.len(), 1);
+    }
+
+    #[test]
+    fn parse_illegal_move_test() {
+        let encoded = "[DhtmlXQ_movelist]8985[/DhtmlXQ_movelist]";
+        assert!(parse(encoded).is_err());
+    }
+
+    #[test]
+    fn parse_result_test() {
+        assert_eq!(parse_result("红胜"), Some(Outcome::Win(1)));
+        assert_eq!(parse_result("红先负"), Some(Outcome::Win(2)));
+        assert_eq!(parse_result("黑负"), Some(Outcome::Win(1)));
+        assert_eq!(parse_result("和棋"), Some(Outcome::Draw));
+        assert_eq!(parse_result("未知"), None);
+    }
+}
@@ -0,0 +1,158 @@
+pub mod pgn;
+pub mod dhtmlxq;
+pub mod xqf;
+
+use crate::xiangqi::state::game_state::parse as parse_game_state;
+use crate::xiangqi::state::game_state::GameState;
+use crate::xiangqi::state::game_state::Outcome;
+use crate::xiangqi::state::mov::Move;
+use crate::xiangqi::state::square::PieceKind;
+use crate::xiangqi::state::square::Square;
+
+pub const STARTING_POSITION: &str = "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1";
+
+const EMPTY_POSITION: &str = "9/9/9/9/9/9/9/9/9/9 w - - 0 1";
+
+// the order pieces are listed in by DhtmlXQ and XQF, red's sixteen followed by black's
+const PIECE_ORDER: [PieceKind; 16] = [
+    PieceKind::Chariot,
+    PieceKind::Horse,
+    PieceKind::Elephant,
+    PieceKind::Advisor,
+    PieceKind::King,
+    PieceKind::Advisor,
+    PieceKind::Elephant,
+    PieceKind::Horse,
+    PieceKind::Chariot,
+    PieceKind::Cannon,
+    PieceKind::Cannon,
+    PieceKind::Soldier,
+    PieceKind::Soldier,
+    PieceKind::Soldier,
+    PieceKind::Soldier,
+    PieceKind::Soldier
+];
+
+// a game replayed through GameState
+#[derive(Clone)]
+pub struct GameRecord {
+    pub red_name: Option<String>,
+    pub black_name: Option<String>,
+    pub starting_position: String, // FEN
+    pub moves: Vec<Move>,
+    pub result: Option<Outcome>
+}
+
+impl GameRecord {
+    pub fn new(starting_position: &str) -> Result<(GameRecord, GameState), &'static str> {
+        let game_state = parse_game_state(&starting_position.to_string())?;
+        let record = GameRecord {
+            red_name: None,
+            black_name: None,
+            starting_position: starting_position.to_string(),
+            moves: vec![],
+            result: None
+        };
+        Ok((record, game_state))
+    }
+
+    // performs the move on the game state and records it
+    pub fn push_move(&mut self, game_state: &mut GameState, mov: Move) -> Result<(), &'static str> {
+        game_state.perform_move(&mov)?;
+        self.moves.push(mov);
+        Ok(())
+    }
+}
+
+// the legal move by the current player between the points
+fn find_move(game_state: &mut GameState, from: (i8, i8), to: (i8, i8)) -> Result<Move, &'static str> {
+    match game_state.possible_moves().into_iter().find(|m| m.from == from && m.to == to) {
+        Some(m) => Ok(m),
+        None => Err("record::find_move - Illegal move")
+    }
+}
+
+// FEN of the pieces listed in PIECE_ORDER, None for a piece that has been captured
+fn position_from_points(points: &[Option<(i8, i8)>], current_player_number: i8) -> Result<String, &'static str> {
+    let mut game_state = parse_game_state(&EMPTY_POSITION.to_string())?;
+
+    for (index, point) in points.iter().enumerate() {
+        if let Some(p) = point {
+            let player_number = if index < PIECE_ORDER.len() { 1 } else { 2 };
+            let kind = match PIECE_ORDER.get(index % PIECE_ORDER.len()) {
+                Some(k) => *k,
+                None => return Err("record::position_from_points - Invalid piece")
+            };
+            match crate::xiangqi::state::square_set::find_by_x_and_y_mut(&mut game_state.squares, *p) {
+                Some(s) => *s = Square { player_number, kind },
+                None => return Err("record::position_from_points - Invalid point")
+            }
+        }
+    }
+
+    game_state.current_player_number = current_player_number;
+
+    Ok(format!("{} - - 0 1", game_state.format()))
+}
+
+// the player whose piece stands on the point, used when a record doesn't say who moves first
+fn player_on_point(starting_position: &str, point: (i8, i8)) -> Option<i8> {
+    let game_state = parse_game_state(&starting_position.to_string()).ok()?;
+    match crate::xiangqi::state::square_set::find_by_x_and_y(&game_state.squares, point) {
+        Some(s) if s.occupied() => Some(s.player_number),
+        _ => None
+    }
+}
+
+// the FEN with the player to move replaced
+fn with_player(starting_position: &str, player_number: i8) -> String {
+    let mut fields: Vec<&str> = starting_position.split_whitespace().collect();
+    if fields.len() > 1 {
+        fields[1] = if player_number == 1 { "w" } else { "b" };
+    }
+    fields.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_move_test() {
+        let (mut record, mut game_state) = GameRecord::new(STARTING_POSITION).unwrap();
+        let mov = find_move(&mut game_state, (7, 7), (4, 7)).unwrap();
+        record.push_move(&mut game_state, mov).unwrap();
+
+        assert_eq!(record.moves.len(), 1);
+        assert_eq!(game_state.current_player_number, 2);
+    }
+
+    #[test]
+    fn find_move_illegal_test() {
+        let mut game_state = parse_game_state(&STARTING_POSITION.to_string()).unwrap();
+        assert!(find_move(&mut game_state, (0, 9), (0, 5)).is_err());
+    }
+
+    #[test]
+    fn position_from_points_test() {
+        let mut points = vec![None; 32];
+        points[4] = Some((4, 9));
+        points[20] = Some((3, 0));
+        points[25] = Some((1, 2));
+
+        let result = position_from_points(&points, 2).unwrap();
+        assert_eq!(result, "3k5/9/1c7/9/9/9/9/9/9/4K4 b - - 0 1");
+    }
+
+    #[test]
+    fn player_on_point_test() {
+        assert_eq!(player_on_point(STARTING_POSITION, (7, 7)), Some(1));
+        assert_eq!(player_on_point(STARTING_POSITION, (7, 2)), Some(2));
+        assert_eq!(player_on_point(STARTING_POSITION, (4, 4)), None);
+    }
+
+    #[test]
+    fn with_player_test() {
+        assert_eq!(with_player(STARTING_POSITION, 2), "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR b - - 0 1");
+    }
+}
@@ -0,0 +1,228 @@
+use crate::xiangqi::notation;
+use crate::xiangqi::notation::Notation;
+use crate::xiangqi::record::GameRecord;
+use crate::xiangqi::record::STARTING_POSITION;
+use crate::xiangqi::state::game_state::Outcome;
+
+// Xiangqi PGN game record, WXF movetext unless the Format tag says otherwise
+//   [Red "Red"]
+//   [Black "Black"]
+//   [Result "1-0"]
+//   [Format "WXF"]
+//   1. C2.5 H8+7 2. H2+3 R9.8 {comment} 3. R1.2 1-0
+pub fn parse(encoded: &str) -> Result<GameRecord, &'static str> {
+    let mut red_name = None;
+    let mut black_name = None;
+    let mut result = None;
+    let mut starting_position = String::from(STARTING_POSITION);
+    let mut move_notation = Notation::Wxf;
+    let mut movetext = String::new();
+
+    for line in encoded.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            if let Some((key, value)) = parse_tag(line) {
+                match key {
+                    "Red" => red_name = Some(value.to_string()),
+                    "Black" => black_name = Some(value.to_string()),
+                    "Result" => result = parse_result(value),
+                    "FEN" => starting_position = value.to_string(),
+                    "Format" => move_notation = parse_format(value)?,
+                    _ => ()
+                }
+            }
+        } else if !line.starts_with('%') {
+            // comments run to the end of the line after a semicolon
+            let text = match line.split_once(';') {
+                Some((text, _)) => text,
+                None => line
+            };
+            movetext.push_str(text);
+            movetext.push(' ');
+        }
+    }
+
+    let (mut record, mut game_state) = GameRecord::new(&starting_position)?;
+
+    for token in main_line(&movetext)?.split_whitespace() {
+        if is_result(token) {
+            if result.is_none() {
+                result = parse_result(token);
+            }
+            break;
+        }
+
+        let encoded_move = token.trim_start_matches(|c: char| c.is_ascii_digit()).trim_start_matches('.');
+        if encoded_move.is_empty() {
+            // move number
+            continue;
+        }
+
+        let mov = notation::parse_move(encoded_move, &mut game_state, move_notation).map_err(|_| "pgn::parse - Illegal move")?;
+        record.push_move(&mut game_state, mov)?;
+    }
+
+    record.red_name = red_name;
+    record.black_name = black_name;
+    record.result = result;
+
+    Ok(record)
+}
+
+// a file of several games, each starting with its tags
+pub fn parse_all(encoded: &str) -> Result<Vec<GameRecord>, &'static str> {
+    let mut games: Vec<String> = vec![];
+    let mut in_movetext = false;
+
+    for line in encoded.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let tag = trimmed.starts_with('[');
+        if games.is_empty() || (tag && in_movetext) {
+            games.push(String::new());
+        }
+        in_movetext = !tag;
+
+        if let Some(game) = games.last_mut() {
+            game.push_str(line);
+            game.push('\n');
+        }
+    }
+
+    games.iter().map(|game| parse(game)).collect()
+}
+
+// [Red "Red"] -> ("Red", "Red")
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (key, value) = inner.split_once(' ')?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((key, value))
+}
+
+fn parse_format(value: &str) -> Result<Notation, &'static str> {
+    match value.to_ascii_uppercase().as_str() {
+        "WXF" => Ok(Notation::Wxf),
+        "ICCS" => Ok(Notation::Iccs),
+        "CHINESE" => Ok(Notation::Chinese),
+        _ => Err("pgn::parse - Unsupported format")
+    }
+}
+
+fn is_result(token: &str) -> bool {
+    matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*")
+}
+
+fn parse_result(value: &str) -> Option<Outcome> {
+    match value {
+        "1-0" => Some(Outcome::Win(1)),
+        "0-1" => Some(Outcome::Win(2)),
+        "1/2-1/2" => Some(Outcome::Draw),
+        _ => None
+    }
+}
+
+// movetext without {comments} and (variations), which may be nested
+fn main_line(movetext: &str) -> Result<String, &'static str> {
+    let mut result = String::new();
+    let mut in_comment = false;
+    let mut variation_depth = 0;
+
+    for c in movetext.chars() {
+        match c {
+            '{' if !in_comment => in_comment = true,
+            '}' if in_comment => in_comment = false,
+            '(' if !in_comment => variation_depth += 1,
+            ')' if !in_comment => {
+                if variation_depth == 0 {
+                    return Err("pgn::parse - Unbalanced variation");
+                }
+                variation_depth -= 1;
+            },
+            _ => {
+                if !in_comment && variation_depth == 0 {
+                    result.push(c);
+                }
+            }
+        }
+        if (c == '}' || c == ')') && !in_comment && variation_depth == 0 {
+            result.push(' ');
+        }
+    }
+
+    if in_comment || variation_depth != 0 {
+        return Err("pgn::parse - Unterminated comment or variation");
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xiangqi::state::square::PieceKind;
+
+    #[test]
+    fn parse_test() {
+        let encoded = "[Event \"Test\"]\n[Red \"Red Player\"]\n[Black \"Black Player\"]\n[Result \"0-1\"]\n[Format \"WXF\"]\n\n1. C2.5 H8+7 2. H2+3 {development} R9.8 0-1\n";
+        let result = parse(encoded).unwrap();
+
+        assert_eq!(result.red_name, Some(String::from("Red Player")));
+        assert_eq!(result.black_name, Some(String::from("Black Player")));
+        assert_eq!(result.result, Some(Outcome::Win(2)));
+        assert_eq!(result.moves.len(), 4);
+        assert_eq!(result.moves[0].from, (7, 7));
+        assert_eq!(result.moves[0].to, (4, 7));
+        assert_eq!(result.moves[3].moving_piece_kind, PieceKind::Chariot);
+    }
+
+    #[test]
+    fn parse_variation_test() {
+        let encoded = "1. C2=5 (1. P7+1 P7+1 (1... C2=5)) H8+7 ; alternative\n2. H2+3 *\n";
+        let result = parse(encoded).unwrap();
+
+        assert_eq!(result.moves.len(), 3);
+        assert_eq!(result.moves[1].to, (6, 2));
+        assert_eq!(result.result, None);
+    }
+
+    #[test]
+    fn parse_iccs_test() {
+        let encoded = "[Format \"ICCS\"]\n1. H2-E2 H9-G7 1/2-1/2\n";
+        let result = parse(encoded).unwrap();
+
+        assert_eq!(result.moves.len(), 2);
+        assert_eq!(result.result, Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn parse_fen_test() {
+        let encoded = "[FEN \"3k5/9/9/9/9/9/9/9/9/4K3R b - - 0 1\"]\n1... K4+1 1-0\n";
+        let result = parse(encoded).unwrap();
+
+        assert_eq!(result.starting_position, "3k5/9/9/9/9/9/9/9/9/4K3R b - - 0 1");
+        assert_eq!(result.moves.len(), 1);
+        assert_eq!(result.result, Some(Outcome::Win(1)));
+    }
+
+    #[test]
+    fn parse_illegal_move_test() {
+        let encoded = "1. R1+5 *\n";
+        assert!(parse(encoded).is_err());
+    }
+
+    #[test]
+    fn parse_all_test() {
+        let encoded = "[Result \"1-0\"]\n1. C2=5 H8+7 1-0\n\n[Result \"0-1\"]\n1. P7+1 0-1\n";
+        let result = parse_all(encoded).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].moves.len(), 2);
+        assert_eq!(result[1].moves.len(), 1);
+        assert_eq!(result[1].result, Some(Outcome::Win(2)));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::xiangqi::record::GameRecord;
+use crate::xiangqi::record::find_move;
+use crate::xiangqi::record::position_from_points;
+use crate::xiangqi::record::player_on_point;
+use crate::xiangqi::record::with_player;
+use crate::xiangqi::state::game_state::Outcome;
+
+const SIGNATURE: &[u8] = b"XQ";
+
+// later versions are encrypted
+const MAX_VERSION: u8 = 10;
+
+const VERSION_OFFSET: usize = 2;
+const PIECES_OFFSET: usize = 0x10;
+const RESULT_OFFSET: usize = 0x33;
+const RED_NAME_OFFSET: usize = 0x130;
+const BLACK_NAME_OFFSET: usize = 0x140;
+const STEPS_OFFSET: usize = 0x400;
+
+const PIECE_COUNT: usize = 32;
+const STEP_LENGTH: usize = 4;
+const COMMENT_LENGTH_LENGTH: usize = 4;
+
+// the piece is not on the board
+const ABSENT: u8 = 0xFF;
+
+// added to the points of each step
+const STEP_POINT_OFFSET: u8 = 24;
+
+// the step is followed by the next move of its line
+const NEXT_STEP_TAG: u8 = 0x80;
+
+// the from and to points of a move
+type Step = ((i8, i8), (i8, i8));
+
+// XQF game record, the binary format of XQStudio
+//   0x000 - "XQ" and the version
+//   0x010 - the points of the 32 pieces, red then black, as file * 10 + rank from red's side, 0xFF when captured
+//   0x033 - result: 1 red wins, 2 black wins, 3 draw
+//   0x130 - red's name, 0x140 - black's name
+//   0x400 - steps of from, to, tag and a reserved byte, each followed by the length of its comment and the comment
+//
+// the first step is the starting position, moves follow in the main line while the tag has the next step bit,
+// variations come after and are ignored
+pub fn parse(encoded: &[u8]) -> Result<GameRecord, &'static str> {
+    if encoded.len() < STEPS_OFFSET || !encoded.starts_with(SIGNATURE) {
+        return Err("xqf::parse - Invalid header");
+    }
+
+    if encoded[VERSION_OFFSET] > MAX_VERSION {
+        return Err("xqf::parse - Unsupported version");
+    }
+
+    let points: Vec<Option<(i8, i8)>> = encoded[PIECES_OFFSET..PIECES_OFFSET + PIECE_COUNT].iter().map(|value| {
+        if *value == ABSENT {
+            Ok(None)
+        } else {
+            parse_point(*value).map(Some)
+        }
+    }).collect::<Result<Vec<Option<(i8, i8)>>, &'static str>>()?;
+
+    let moves = main_line(&encoded[STEPS_OFFSET..])?;
+
+    // the owner of the first piece moved is the player to move
+    let position = position_from_points(&points, 1)?;
+    let starting_position = match moves.first() {
+        Some((from, _)) => with_player(&position, player_on_point(&position, *from).unwrap_or(1)),
+        None => position
+    };
+
+    let (mut record, mut game_state) = GameRecord::new(&starting_position)?;
+
+    for (from, to) in moves {
+        let mov = find_move(&mut game_state, from, to).map_err(|_| "xqf::parse - Illegal move")?;
+        record.push_move(&mut game_state, mov)?;
+    }
+
+    record.red_name = parse_name(encoded, RED_NAME_OFFSET);
+    record.black_name = parse_name(encoded, BLACK_NAME_OFFSET);
+    record.result = match encoded[RESULT_OFFSET] {
+        1 => Some(Outcome::Win(1)),
+        2 => Some(Outcome::Win(2)),
+        3 => Some(Outcome::Draw),
+        _ => None
+    };
+
+    Ok(record)
+}
+
+// the from and to points of the moves following the root step
+fn main_line(steps: &[u8]) -> Result<Vec<Step>, &'static str> {
+    let mut moves = vec![];
+    let mut index = 0;
+    let mut root = true;
+
+    loop {
+        let step = match steps.get(index..index + STEP_LENGTH) {
+            Some(s) => s,
+            None => return Err("xqf::parse - Truncated step")
+        };
+
+        if !root {
+            let from = parse_point(step[0].wrapping_sub(STEP_POINT_OFFSET))?;
+            let to = parse_point(step[1].wrapping_sub(STEP_POINT_OFFSET))?;
+            moves.push((from, to));
+        }
+        root = false;
+
+        let comment_length = match steps.get(index + STEP_LENGTH..index + STEP_LENGTH + COMMENT_LENGTH_LENGTH) {
+            Some(bytes) => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+            None => return Err("xqf::parse - Truncated step")
+        };
+
+        if step[2] & NEXT_STEP_TAG == 0 {
+            break;
+        }
+
+        index += STEP_LENGTH + COMMENT_LENGTH_LENGTH + comment_length;
+    }
+
+    Ok(moves)
+}
+
+// file * 10 + rank, both from red's bottom left
+fn parse_point(value: u8) -> Result<(i8, i8), &'static str> {
+    let x = value / 10;
+    let y = value % 10;
+    if x < 9 {
+        Ok((x as i8, 9 - y as i8))
+    } else {
+        Err("xqf::parse - Invalid point")
+    }
+}
+
+// a length byte followed by the name
+fn parse_name(encoded: &[u8], offset: usize) -> Option<String> {
+    let length = *encoded.get(offset)? as usize;
+    let bytes = encoded.get(offset + 1..offset + 1 + length)?;
+    let name = String::from_utf8_lossy(bytes).trim().to_string();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xiangqi::record::STARTING_POSITION;
+    use crate::xiangqi::state::square::PieceKind;
+
+    const STARTING_POINTS: [u8; 32] = [
+        0, 10, 20, 30, 40, 50, 60, 70, 80, 12, 72, 3, 23, 43, 63, 83,
+        9, 19, 29, 39, 49, 59, 69, 79, 89, 17, 77, 6, 26, 46, 66, 86
+    ];
+
+    fn header(points: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![0; STEPS_OFFSET];
+        encoded[0] = b'X';
+        encoded[1] = b'Q';
+        encoded[VERSION_OFFSET] = MAX_VERSION;
+        encoded[PIECES_OFFSET..PIECES_OFFSET + PIECE_COUNT].copy_from_slice(points);
+        encoded
+    }
+
+    fn push_step(encoded: &mut Vec<u8>, from: u8, to: u8, tag: u8, comment: &str) {
+        encoded.extend_from_slice(&[from + STEP_POINT_OFFSET, to + STEP_POINT_OFFSET, tag, 0]);
+        encoded.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+        encoded.extend_from_slice(comment.as_bytes());
+    }
+
+    #[test]
+    fn parse_test() {
+        let mut encoded = header(&STARTING_POINTS);
+        encoded[RESULT_OFFSET] = 1;
+        encoded[RED_NAME_OFFSET] = 3;
+        encoded[RED_NAME_OFFSET + 1..RED_NAME_OFFSET + 4].copy_from_slice(b"Red");
+        push_step(&mut encoded, 0, 0, NEXT_STEP_TAG, "");
+        // h2e2 h9g7, with a variation after h9g7
+        push_step(&mut encoded, 72, 42, NEXT_STEP_TAG, "central cannon");
+        push_step(&mut encoded, 79, 67, 0x40, "");
+        // variation h9i7, ignored
+        push_step(&mut encoded, 79, 87, 0, "");
+
+        let result = parse(&encoded).unwrap();
+
+        assert_eq!(result.starting_position, STARTING_POSITION);
+        assert_eq!(result.red_name, Some(String::from("Red")));
+        assert_eq!(result.black_name, None);
+        assert_eq!(result.result, Some(Outcome::Win(1)));
+        assert_eq!(result.moves.len(), 2);
+        assert_eq!(result.moves[0].from, (7, 7));
+        assert_eq!(result.moves[0].to, (4, 7));
+        assert_eq!(result.moves[1].moving_piece_kind, PieceKind::Horse);
+        assert_eq!(result.moves[1].to, (6, 2));
+    }
+
+    #[test]
+    fn parse_black_first_test() {
+        let mut points = [ABSENT; 32];
+        points[4] = 40;
+        points[20] = 39;
+        points[25] = 17;
+        let mut encoded = header(&points);
+        push_step(&mut encoded, 0, 0, NEXT_STEP_TAG, "");
+        push_step(&mut encoded, 17, 47, 0, "");
+
+        let result = parse(&encoded).unwrap();
+
+        assert_eq!(result.starting_position, "3k5/9/1c7/9/9/9/9/9/9/4K4 b - - 0 1");
+        assert_eq!(result.moves.len(), 1);
+        assert_eq!(result.result, None);
+    }
+
+    #[test]
+    fn parse_invalid_signature_test() {
+        let mut encoded = header(&STARTING_POINTS);
+        encoded[0] = b'Q';
+        push_step(&mut encoded, 0, 0, 0, "");
+        assert_eq!(parse(&encoded).err(), Some("xqf::parse - Invalid header"));
+    }
+
+    #[test]
+    fn parse_encrypted_test() {
+        let mut encoded = header(&STARTING_POINTS);
+        encoded[VERSION_OFFSET] = 18;
+        push_step(&mut encoded, 0, 0, 0, "");
+        assert_eq!(parse(&encoded).err(), Some("xqf::parse - Unsupported version"));
+    }
+
+    #[test]
+    fn parse_truncated_test() {
+        let mut encoded = header(&STARTING_POINTS);
+        push_step(&mut encoded, 0, 0, NEXT_STEP_TAG, "");
+        assert_eq!(parse(&encoded).err(), Some("xqf::parse - Truncated step"));
+    }
+}
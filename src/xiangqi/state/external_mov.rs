@@ -1,8 +1,13 @@
-use std::convert::TryFrom;
 use crate::xiangqi::state::square::PieceKind;
+use crate::xiangqi::state::game_state::GameState;
+use crate::xiangqi::state::mov::Move;
 
-const X_FORMAT: [char; 9] = [
-    '9', '8', '7', '6', '5', '4', '3', '2', '1'
+const CHINESE_RED_NUMBERS: [char; 9] = [
+    '一', '二', '三', '四', '五', '六', '七', '八', '九'
+];
+
+const CHINESE_BLACK_NUMBERS: [char; 9] = [
+    '１', '２', '３', '４', '５', '６', '７', '８', '９'
 ];
 
 pub struct ExternalMove {
@@ -12,7 +17,8 @@ pub struct ExternalMove {
     pub player_number: i8,
     pub capture_piece_kind: Option<PieceKind>, // Undo -> place piece back
     pub rank_disambiguation: Option<char>,
-    pub pawn_disambiguation: Option<usize>
+    pub pawn_disambiguation: Option<usize>,
+    pub file_piece_count: usize // pieces of the same kind on the starting file, including the moving piece
 }
 
 impl ExternalMove {
@@ -27,6 +33,68 @@ impl ExternalMove {
         String::from(format!("{}{}{}{}", self.piece_format(), self.starting_file_format(), self.direction_format(), self.movement_format()))
     }
 
+    // Traditional Chinese notation, numbered from each player's right
+    //   red uses chinese numerals: 炮二平五, 馬八進七, 前車進二
+    //   black uses arabic numerals: 砲８平５, 馬２進３, 中卒平４
+    pub fn format_chinese(&self) -> String {
+        let piece = self.chinese_piece();
+
+        let prefix = if let Some(pd) = self.pawn_disambiguation {
+            Some(match (pd, self.file_piece_count) {
+                (1, _) => '前',
+                (_, 3) if pd == 2 => '中',
+                (p, c) if p == c => '後',
+                (p, _) => self.chinese_number(p)
+            })
+        } else {
+            match self.rank_disambiguation {
+                Some('+') => Some('前'),
+                Some(_) => Some('後'),
+                None => None
+            }
+        };
+
+        let direction = match self.direction_format().as_str() {
+            "+" => '進',
+            "-" => '退',
+            _ => '平'
+        };
+
+        let movement = if self.from.0 == self.to.0 {
+            self.chinese_number((self.to.1 - self.from.1).unsigned_abs() as usize)
+        } else {
+            self.chinese_number(file_number(self.to.0, self.player_number))
+        };
+
+        match prefix {
+            Some(p) => format!("{}{}{}{}", p, piece, direction, movement),
+            None => format!("{}{}{}{}", piece, self.chinese_number(file_number(self.from.0, self.player_number)), direction, movement)
+        }
+    }
+
+    fn chinese_piece(&self) -> char {
+        match (self.moving_piece_kind, self.player_number) {
+            (PieceKind::Soldier, 1) => '兵',
+            (PieceKind::Soldier, _) => '卒',
+            (PieceKind::Chariot, _) => '車',
+            (PieceKind::Horse, _) => '馬',
+            (PieceKind::Elephant, 1) => '相',
+            (PieceKind::Elephant, _) => '象',
+            (PieceKind::Advisor, 1) => '仕',
+            (PieceKind::Advisor, _) => '士',
+            (PieceKind::King, 1) => '帥',
+            (PieceKind::King, _) => '將',
+            (PieceKind::Cannon, 1) => '炮',
+            (PieceKind::Cannon, _) => '砲',
+            (PieceKind::Empty, _) => ' '
+        }
+    }
+
+    fn chinese_number(&self, number: usize) -> char {
+        let numbers = if self.player_number == 1 { CHINESE_RED_NUMBERS } else { CHINESE_BLACK_NUMBERS };
+        numbers[number.clamp(1, 9) - 1]
+    }
+
     fn piece_format(&self) -> String {
         if let Some(pd) = self.pawn_disambiguation {
             String::from(format!("{}", pd))
@@ -49,8 +117,7 @@ impl ExternalMove {
         if let Some(fd) = self.rank_disambiguation {
             format!("{}", fd)
         } else {
-            let x = usize::try_from(self.from.0).unwrap_or(0);
-            format!("{}", X_FORMAT[x])
+            format!("{}", file_number(self.from.0, self.player_number))
         }
     }
 
@@ -82,8 +149,7 @@ impl ExternalMove {
             format!("{}", distance)
         } else {
             // use the to x
-            let x = usize::try_from(self.to.0).unwrap_or(0);
-            format!("{}", X_FORMAT[x])
+            format!("{}", file_number(self.to.0, self.player_number))
         }
     }
 }
@@ -97,14 +163,127 @@ impl Clone for ExternalMove {
             player_number: self.player_number,
             capture_piece_kind: self.capture_piece_kind,
             rank_disambiguation: self.rank_disambiguation,
-            pawn_disambiguation: self.pawn_disambiguation
+            pawn_disambiguation: self.pawn_disambiguation,
+            file_piece_count: self.file_piece_count
+        }
+    }
+}
+
+// files are numbered from 1 to 9 from the right of the player
+fn file_number(x: i8, player_number: i8) -> usize {
+    if player_number == 1 {
+        (9 - x) as usize
+    } else {
+        (x + 1) as usize
+    }
+}
+
+// the move by the current player, with the pieces of the same kind on its file put in order from the front
+pub fn build(game_state: &GameState, mov: Move) -> ExternalMove {
+    let player_number = game_state.current_player_number;
+
+    // ranks from the top of the board
+    let mut ranks_on_file: Vec<i8> = game_state.squares.iter().enumerate().filter_map(|(y, row)| {
+        match row.get(mov.from.0 as usize) {
+            Some(s) if s.kind == mov.moving_piece_kind && s.player_number == player_number => Some(y as i8),
+            _ => None
+        }
+    }).collect();
+
+    // player 1 advances up the board, player 2 down it
+    if player_number == 2 {
+        ranks_on_file.reverse();
+    }
+
+    let position_from_front = ranks_on_file.iter().position(|y| *y == mov.from.1).unwrap_or(0) + 1;
+
+    let rank_disambiguation = if ranks_on_file.len() == 2 {
+        if position_from_front == 1 {
+            Some('+')
+        } else {
+            Some('-')
         }
+    } else {
+        None
+    };
+
+    let pawn_disambiguation = if ranks_on_file.len() > 2 && mov.moving_piece_kind == PieceKind::Soldier {
+        Some(position_from_front)
+    } else {
+        None
+    };
+
+    ExternalMove {
+        from: mov.from,
+        to: mov.to,
+        moving_piece_kind: mov.moving_piece_kind,
+        player_number,
+        capture_piece_kind: mov.capture_piece_kind,
+        rank_disambiguation,
+        pawn_disambiguation,
+        file_piece_count: ranks_on_file.len()
     }
 }
 
+// WXF notation, e.g. C2=5, H8+7, R++2
+// letters are also accepted in lower case, with N for the horse and B for the elephant
+pub fn parse(encoded: &str, game_state: &mut GameState) -> Result<Move, &'static str> {
+    let normalized: String = encoded.trim().chars().map(|c| {
+        match c.to_ascii_uppercase() {
+            'N' => 'H',
+            'B' => 'E',
+            '.' => '=',
+            other => other
+        }
+    }).collect();
+
+    match game_state.possible_moves().into_iter().find(|m| build(game_state, m.clone()).format() == normalized) {
+        Some(m) => Ok(m),
+        None => Err("external_mov::parse - Invalid move")
+    }
+}
+
+// Traditional Chinese notation, e.g. 炮二平五, 馬８進７, 前車進二
+// simplified characters, the other player's piece names and either style of numerals are also accepted
+pub fn parse_chinese(encoded: &str, game_state: &mut GameState) -> Result<Move, &'static str> {
+    let normalized = normalize_chinese(encoded.trim());
+
+    match game_state.possible_moves().into_iter().find(|m| normalize_chinese(&build(game_state, m.clone()).format_chinese()) == normalized) {
+        Some(m) => Ok(m),
+        None => Err("external_mov::parse_chinese - Invalid move")
+    }
+}
+
+fn normalize_chinese(encoded: &str) -> String {
+    encoded.chars().map(|c| {
+        match c {
+            '卒' => '兵',
+            '车' | '俥' | '伡' => '車',
+            '马' | '傌' | '㐷' => '馬',
+            '象' => '相',
+            '士' => '仕',
+            '將' | '将' | '帅' => '帥',
+            '砲' | '包' => '炮',
+            '进' => '進',
+            '后' => '後',
+            '一' | '１' => '1',
+            '二' | '２' => '2',
+            '三' | '３' => '3',
+            '四' | '４' => '4',
+            '五' | '５' => '5',
+            '六' | '６' => '6',
+            '七' | '７' => '7',
+            '八' | '８' => '8',
+            '九' | '９' => '9',
+            other => other
+        }
+    }).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::xiangqi::state::game_state::parse as parse_game_state;
 
     #[test]
     fn format_piece_test() {
@@ -119,7 +298,8 @@ mod tests {
             player_number,
             capture_piece_kind: None,
             rank_disambiguation: None,
-            pawn_disambiguation: None
+            pawn_disambiguation: None,
+            file_piece_count: 1
         };
 
         assert_eq!("E7+9", mov.format());
@@ -138,7 +318,8 @@ mod tests {
             player_number,
             capture_piece_kind: None,
             rank_disambiguation: None,
-            pawn_disambiguation: None
+            pawn_disambiguation: None,
+            file_piece_count: 1
         };
 
         assert_eq!("P9+1", mov.format());
@@ -157,7 +338,8 @@ mod tests {
             player_number,
             capture_piece_kind: None,
             rank_disambiguation: Some('+'),
-            pawn_disambiguation: None
+            pawn_disambiguation: None,
+            file_piece_count: 2
         };
 
         assert_eq!("R++2", mov.format());
@@ -176,7 +358,8 @@ mod tests {
             player_number,
             capture_piece_kind: None,
             rank_disambiguation: None,
-            pawn_disambiguation: Some(1)
+            pawn_disambiguation: Some(1),
+            file_piece_count: 3
         };
 
         assert_eq!("19+1", mov.format());
@@ -195,7 +378,8 @@ mod tests {
             player_number,
             capture_piece_kind: None,
             rank_disambiguation: None,
-            pawn_disambiguation: None
+            pawn_disambiguation: None,
+            file_piece_count: 1
         };
 
         assert_eq!("R1+2", mov.format());
@@ -214,7 +398,8 @@ mod tests {
             player_number,
             capture_piece_kind: None,
             rank_disambiguation: None,
-            pawn_disambiguation: None
+            pawn_disambiguation: None,
+            file_piece_count: 1
         };
 
         assert_eq!("R1-2", mov.format());
@@ -233,10 +418,165 @@ mod tests {
             player_number,
             capture_piece_kind: None,
             rank_disambiguation: None,
-            pawn_disambiguation: None
+            pawn_disambiguation: None,
+            file_piece_count: 1
         };
 
         assert_eq!("R1=3", mov.format());
     }
-}
 
+    #[test]
+    fn format_black_test() {
+        let mov = ExternalMove {
+            from: (7, 2),
+            to: (4, 2),
+            moving_piece_kind: PieceKind::Cannon,
+            player_number: 2,
+            capture_piece_kind: None,
+            rank_disambiguation: None,
+            pawn_disambiguation: None,
+            file_piece_count: 1
+        };
+
+        assert_eq!("C8=5", mov.format());
+    }
+
+    #[test]
+    fn format_chinese_red_test() {
+        let mov = ExternalMove {
+            from: (7, 7),
+            to: (4, 7),
+            moving_piece_kind: PieceKind::Cannon,
+            player_number: 1,
+            capture_piece_kind: None,
+            rank_disambiguation: None,
+            pawn_disambiguation: None,
+            file_piece_count: 1
+        };
+
+        assert_eq!("炮二平五", mov.format_chinese());
+    }
+
+    #[test]
+    fn format_chinese_black_test() {
+        let mov = ExternalMove {
+            from: (1, 0),
+            to: (2, 2),
+            moving_piece_kind: PieceKind::Horse,
+            player_number: 2,
+            capture_piece_kind: None,
+            rank_disambiguation: None,
+            pawn_disambiguation: None,
+            file_piece_count: 1
+        };
+
+        assert_eq!("馬２進３", mov.format_chinese());
+    }
+
+    #[test]
+    fn format_chinese_disambiguation_test() {
+        let mov = ExternalMove {
+            from: (8, 5),
+            to: (8, 3),
+            moving_piece_kind: PieceKind::Chariot,
+            player_number: 1,
+            capture_piece_kind: None,
+            rank_disambiguation: Some('+'),
+            pawn_disambiguation: None,
+            file_piece_count: 2
+        };
+
+        assert_eq!("前車進二", mov.format_chinese());
+    }
+
+    #[test]
+    fn format_chinese_pawn_disambiguation_test() {
+        let mov = ExternalMove {
+            from: (4, 5),
+            to: (3, 5),
+            moving_piece_kind: PieceKind::Soldier,
+            player_number: 2,
+            capture_piece_kind: None,
+            rank_disambiguation: None,
+            pawn_disambiguation: Some(2),
+            file_piece_count: 3
+        };
+
+        assert_eq!("中卒平４", mov.format_chinese());
+    }
+
+    #[test]
+    fn build_test() {
+        let game_state = parse_game_state(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1")).unwrap();
+        let mov = Move { from: (7, 7), to: (4, 7), moving_piece_kind: PieceKind::Cannon, capture_piece_kind: None };
+        let result = build(&game_state, mov);
+
+        assert_eq!(result.rank_disambiguation, None);
+        assert_eq!(result.pawn_disambiguation, None);
+        assert_eq!(result.format(), "C2=5");
+    }
+
+    #[test]
+    fn build_rank_disambiguation_test() {
+        let game_state = parse_game_state(&String::from("3k5/9/9/9/9/R8/9/R8/9/4K4 w - - 0 1")).unwrap();
+        let mov = Move { from: (0, 7), to: (0, 6), moving_piece_kind: PieceKind::Chariot, capture_piece_kind: None };
+        let result = build(&game_state, mov);
+
+        assert_eq!(result.format(), "R-+1");
+        assert_eq!(result.format_chinese(), "後車進一");
+    }
+
+    #[test]
+    fn build_pawn_disambiguation_test() {
+        let game_state = parse_game_state(&String::from("3k5/9/9/9/9/4p4/4p4/4p4/9/5K3 b - - 0 1")).unwrap();
+        let mov = Move { from: (4, 7), to: (4, 8), moving_piece_kind: PieceKind::Soldier, capture_piece_kind: None };
+        let result = build(&game_state, mov);
+
+        assert_eq!(result.format(), "15+1");
+        assert_eq!(result.format_chinese(), "前卒進１");
+    }
+
+    #[test]
+    fn parse_test() {
+        let mut game_state = parse_game_state(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1")).unwrap();
+        let result = parse("C2=5", &mut game_state).unwrap();
+
+        assert_eq!(result.from, (7, 7));
+        assert_eq!(result.to, (4, 7));
+    }
+
+    #[test]
+    fn parse_alternative_letters_test() {
+        let mut game_state = parse_game_state(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1")).unwrap();
+        let result = parse("n2+3", &mut game_state).unwrap();
+
+        assert_eq!(result.from, (7, 9));
+        assert_eq!(result.to, (6, 7));
+    }
+
+    #[test]
+    fn parse_invalid_test() {
+        let mut game_state = parse_game_state(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1")).unwrap();
+        let result = parse("C2+9", &mut game_state);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_chinese_test() {
+        let mut game_state = parse_game_state(&String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR b - - 0 1")).unwrap();
+        let result = parse_chinese("马2进3", &mut game_state).unwrap();
+
+        assert_eq!(result.from, (1, 0));
+        assert_eq!(result.to, (2, 2));
+    }
+
+    #[test]
+    fn parse_chinese_disambiguation_test() {
+        let mut game_state = parse_game_state(&String::from("3k5/9/9/9/9/R8/9/R8/9/4K4 w - - 0 1")).unwrap();
+        let result = parse_chinese("前车平五", &mut game_state).unwrap();
+
+        assert_eq!(result.from, (0, 5));
+        assert_eq!(result.to, (4, 5));
+    }
+}
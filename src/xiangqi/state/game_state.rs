@@ -1,5 +1,9 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use crate::xiangqi::state::point::valid;
 use crate::xiangqi::state::piece_factory::parse as parse_piece;
+use crate::xiangqi::state::piece_factory::format as format_piece;
 use crate::xiangqi::state::square::destinations;
 use crate::xiangqi::state::square::opposing_player;
 use crate::xiangqi::state::square::PieceKind;
@@ -15,24 +19,204 @@ use crate::xiangqi::state::square_set::any_threats_to_point;
 use crate::xiangqi::state::square_set::any_threats_to_point_through_pin;
 use crate::xiangqi::state::square_set::pinned_to_point;
 use crate::xiangqi::state::mov::Move;
+use crate::xiangqi::state::iccs_mov::parse as parse_iccs_move;
+
+// the third occurrence of a position ends the game
+const REPETITION_COUNT: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Win(i8),
+    Draw
+}
 
 #[derive(Clone)]
 pub struct GameState {
     pub current_player_number: i8,
-    pub squares: Vec<Vec<Square>>
+    pub squares: Vec<Vec<Square>>,
+    pub position_hashes: Vec<u64>, // every position of the game so far, including the current one
+    pub move_history: Vec<Move>, // the moves played between the positions
+    pub halfmove_clock: u16, // moves since the last capture
+    pub fullmove_number: u16, // incremented after black moves
+    pub halfmove_clock_history: Vec<u16> // the clock before each move, restored by undo
 }
 
 impl GameState {
     pub fn winner(&mut self) -> Option<i8> {
-        if self.in_checkmate(1) || self.in_stalemate(1) {
-            Some(2)
+        match self.outcome() {
+            Some(Outcome::Win(player_number)) => Some(player_number),
+            _ => None
+        }
+    }
+
+    pub fn draw(&mut self) -> bool {
+        self.outcome() == Some(Outcome::Draw)
+    }
+
+    // checkmate and stalemate lose, a repetition is lost by the player who perpetually checks or chases, otherwise drawn
+    pub fn outcome(&mut self) -> Option<Outcome> {
+        if let Some(outcome) = self.repetition_outcome() {
+            Some(outcome)
+        } else if self.in_checkmate(1) || self.in_stalemate(1) {
+            Some(Outcome::Win(2))
         } else if self.in_checkmate(2) || self.in_stalemate(2) {
-            Some(1)
+            Some(Outcome::Win(1))
+        } else {
+            None
+        }
+    }
+
+    // index of the first occurrence of the current position once it has repeated enough to end the game
+    pub fn repetition_start(&self) -> Option<usize> {
+        let current_hash = *self.position_hashes.last()?;
+
+        let occurrences: Vec<usize> = self.position_hashes.iter().enumerate().filter(|(_, h)| **h == current_hash).map(|(i, _)| i).collect();
+
+        if occurrences.len() >= REPETITION_COUNT {
+            occurrences.first().copied()
         } else {
             None
         }
     }
 
+    // Asian rules: a player who checks or chases with every move of the repetition loses.
+    // When both do, perpetual check loses to perpetual chase and anything else is a draw.
+    pub fn repetition_outcome(&self) -> Option<Outcome> {
+        let start = self.repetition_start()?;
+        let cycle = self.repetition_moves(start);
+
+        let offending = |player_number: i8| -> bool {
+            let mut moves = cycle.iter().filter(|(p, _, _)| *p == player_number).peekable();
+            moves.peek().is_some() && moves.all(|(_, check, chase)| *check || *chase)
+        };
+        let perpetual_check = |player_number: i8| -> bool {
+            cycle.iter().filter(|(p, _, _)| *p == player_number).all(|(_, check, _)| *check)
+        };
+
+        let outcome = match (offending(1), offending(2)) {
+            (true, false) => Outcome::Win(2),
+            (false, true) => Outcome::Win(1),
+            (true, true) => match (perpetual_check(1), perpetual_check(2)) {
+                (true, false) => Outcome::Win(2),
+                (false, true) => Outcome::Win(1),
+                _ => Outcome::Draw
+            },
+            _ => Outcome::Draw
+        };
+
+        Some(outcome)
+    }
+
+    // the player, whether it checked and whether it chased, for each move since the position at start
+    fn repetition_moves(&self, start: usize) -> Vec<(i8, bool, bool)> {
+        let mut game_state = self.clone();
+        let mut acc = vec![];
+
+        for mov in self.move_history[start..].iter().rev() {
+            let player_number = opposing_player(game_state.current_player_number);
+            let check = game_state.player_in_check(game_state.current_player_number);
+            let chased_after = game_state.chased_points(player_number);
+
+            if game_state.undo_board_move(mov).is_err() {
+                break;
+            }
+
+            let chased_before = game_state.chased_points(player_number);
+            let chase = chased_after.iter().any(|p| !chased_before.contains(p));
+
+            acc.push((player_number, check, chase));
+        }
+
+        acc
+    }
+
+    // Opposing pieces the player attacks that can't safely take back: unprotected pieces, and chariots
+    // attacked by a horse or cannon. Kings and soldiers may attack freely, soldiers that haven't crossed
+    // the river may be attacked freely, and pieces of the same kind attacking each other aren't chased.
+    pub fn chased_points(&self, player_number: i8) -> Vec<(i8, i8)> {
+        let opposing_player_number = opposing_player(player_number);
+        let mut acc = vec![];
+
+        for (y, row) in self.squares.iter().enumerate() {
+            for (x, s) in row.iter().enumerate() {
+                let point = (x as i8, y as i8);
+                if s.player_number != opposing_player_number || s.kind == PieceKind::King || (s.kind == PieceKind::Soldier && !crossed_river(point, opposing_player_number)) {
+                    continue;
+                }
+
+                let protected = any_threats_to_point(&self.squares, point, player_number);
+
+                let chased = threats_to_point(&self.squares, point, opposing_player_number).iter().any(|attacker| {
+                    match find_by_x_and_y(&self.squares, *attacker) {
+                        Some(a) if a.kind == PieceKind::King || a.kind == PieceKind::Soldier || a.kind == s.kind => false,
+                        Some(a) => !protected || (s.kind == PieceKind::Chariot && (a.kind == PieceKind::Horse || a.kind == PieceKind::Cannon)),
+                        None => false
+                    }
+                });
+
+                if chased {
+                    acc.push(point);
+                }
+            }
+        }
+
+        acc
+    }
+
+    fn player_in_check(&self, player_number: i8) -> bool {
+        match find_king_point_for_player(&self.squares, player_number) {
+            Some(king_point) => self.in_check(player_number, king_point),
+            None => false
+        }
+    }
+
+    // the board and player to move of the FEN
+    //   rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w
+    pub fn format(&self) -> String {
+        let rows: Vec<String> = self.squares.iter().map(|row| {
+            let mut encoded = String::new();
+            let mut empty_count = 0;
+            for square in row.iter() {
+                match format_piece(square) {
+                    Some(c) => {
+                        if empty_count > 0 {
+                            encoded.push_str(&empty_count.to_string());
+                            empty_count = 0;
+                        }
+                        encoded.push(c);
+                    },
+                    None => empty_count += 1
+                }
+            }
+            if empty_count > 0 {
+                encoded.push_str(&empty_count.to_string());
+            }
+            encoded
+        }).collect();
+
+        let player = if self.current_player_number == 1 {
+            "w"
+        } else {
+            "b"
+        };
+
+        format!("{} {}", rows.join("/"), player)
+    }
+
+    // FEN with the move counters, which parse reads back
+    //   rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR b - - 1 1
+    pub fn to_fen(&self) -> String {
+        format!("{} - - {} {}", self.format(), self.halfmove_clock, self.fullmove_number)
+    }
+
+    // hash of the board and player to move
+    pub fn position_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.squares.hash(&mut hasher);
+        self.current_player_number.hash(&mut hasher);
+        hasher.finish()
+    }
+
      pub fn in_stalemate(&self, player_number: i8) -> bool {
          if let Some(king_point) = find_king_point_for_player(&self.squares, player_number) {
              self.king_cannot_move(player_number, king_point) && !self.threats_to_king_can_be_captured_or_blocked(player_number, king_point)
@@ -141,12 +325,9 @@ impl GameState {
 
         // keep moves that don't result in check for the current player.
         moves.retain(|m| {
-            let perform_result = self.perform_move(&m);
-            let in_check = match find_king_point_for_player(&self.squares, subject_player_number) {
-                Some(king_point) => self.in_check(subject_player_number, king_point),
-                None => false
-            };
-            let undo_result = self.undo_move(&m);
+            let perform_result = self.perform_board_move(&m);
+            let in_check = self.player_in_check(subject_player_number);
+            let undo_result = self.undo_board_move(&m);
             perform_result.is_ok() && undo_result.is_ok() && !in_check
         });
 
@@ -154,6 +335,41 @@ impl GameState {
     }
 
     pub fn perform_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        self.perform_board_move(mov)?;
+        self.move_history.push(mov.clone());
+        self.position_hashes.push(self.position_hash());
+
+        self.halfmove_clock_history.push(self.halfmove_clock);
+        if mov.capture_piece_kind.is_some() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        if self.current_player_number == 1 {
+            self.fullmove_number += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+        self.undo_board_move(mov)?;
+        self.move_history.pop();
+        self.position_hashes.pop();
+
+        self.halfmove_clock = match self.halfmove_clock_history.pop() {
+            Some(clock) => clock,
+            None => self.halfmove_clock.saturating_sub(1)
+        };
+        if self.current_player_number == 2 {
+            self.fullmove_number = self.fullmove_number.saturating_sub(1);
+        }
+
+        Ok(())
+    }
+
+    // moves the pieces without recording the position, used when testing the legality of moves
+    fn perform_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
         let piece_player_number: i8;
         let piece_kind: PieceKind;
 
@@ -182,7 +398,7 @@ impl GameState {
         Ok(())
     }
 
-    pub fn undo_move(&mut self, mov: &Move) -> Result<(), &'static str> {
+    fn undo_board_move(&mut self, mov: &Move) -> Result<(), &'static str> {
         let moving_piece_player_number: i8;
         let moving_piece_kind: PieceKind;
 
@@ -228,7 +444,28 @@ impl GameState {
 // half move
 // full move
 // 4kaR2/4a4/3hR4/7H1/9/9/9/9/4Ap1r1/3AK3c w - - 0 1
+//
+// optionally followed by the moves played since in ICCS notation, used to detect repetition
+// rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2e2 h9g7
 pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
+    let mut parts = encoded.splitn(2, " moves ");
+
+    let mut game_state = match parts.next() {
+        Some(position) => parse_position(position)?,
+        None => return Err("Error parsing state")
+    };
+
+    if let Some(moves) = parts.next() {
+        for encoded_move in moves.split_whitespace() {
+            let mov = parse_iccs_move(encoded_move, &mut game_state)?;
+            game_state.perform_move(&mov)?;
+        }
+    }
+
+    Ok(game_state)
+}
+
+fn parse_position(encoded: &str) -> Result<GameState, &'static str> {
     let mut read_board = true;
     let mut read_player_number = false;
     let mut read_move_count = false;
@@ -448,10 +685,44 @@ pub fn parse(encoded: &String) -> Result<GameState, &'static str> {
     if parse_error {
         Err("Error parsing state")
     } else {
-        Ok(GameState {
+        let (halfmove_clock, fullmove_number) = parse_move_counts(encoded)?;
+        let mut game_state = GameState {
             current_player_number,
-            squares
-        })
+            squares,
+            position_hashes: vec![],
+            move_history: vec![],
+            halfmove_clock,
+            fullmove_number,
+            halfmove_clock_history: vec![]
+        };
+        game_state.position_hashes.push(game_state.position_hash());
+        Ok(game_state)
+    }
+}
+
+// the halfmove clock and fullmove number, 0 and 1 when they are left out
+fn parse_move_counts(encoded: &str) -> Result<(u16, u16), &'static str> {
+    let fields: Vec<&str> = encoded.split_whitespace().collect();
+
+    let halfmove_clock = match fields.get(4) {
+        Some(f) => f.parse().map_err(|_| "Error parsing state")?,
+        None => 0
+    };
+
+    let fullmove_number = match fields.get(5) {
+        Some(f) => f.parse().map_err(|_| "Error parsing state")?,
+        None => 1
+    };
+
+    Ok((halfmove_clock, fullmove_number))
+}
+
+// soldiers gain their sideways moves once they cross the river
+fn crossed_river(point: (i8, i8), player_number: i8) -> bool {
+    if player_number == 1 {
+        point.1 <= 4
+    } else {
+        point.1 >= 5
     }
 }
 
@@ -476,6 +747,78 @@ mod tests {
         assert_eq!(result.squares[9][8].player_number, 2);
     }
 
+    #[test]
+    fn format_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2e2");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.format(), "rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR b");
+    }
+
+    #[test]
+    fn parse_move_counts_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C4/9/RHEAKAEHR b - - 10 23");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.halfmove_clock, 10);
+        assert_eq!(result.fullmove_number, 23);
+    }
+
+    #[test]
+    fn parse_move_counts_missing_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.halfmove_clock, 0);
+        assert_eq!(result.fullmove_number, 1);
+    }
+
+    #[test]
+    fn to_fen_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2e2 h9g7 e2e6");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.to_fen(), "rheakae1r/9/1c4hc1/p1p1C1p1p/9/9/P1P1P1P1P/1C7/9/RHEAKAEHR b - - 0 2");
+    }
+
+    #[test]
+    fn to_fen_move_counts_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2e2 h9g7 h0g2");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.to_fen(), "rheakae1r/9/1c4hc1/p1p1p1p1p/9/9/P1P1P1P1P/1C2C1H2/9/RHEAKAE1R b - - 3 2");
+    }
+
+    // every position reached by playing through the game survives formatting and parsing, and undoing restores it
+    fn assert_round_trips(encoded: &str, move_count: usize) {
+        let mut game_state = parse(&encoded.to_string()).unwrap();
+        let mut played: Vec<(Move, String)> = vec![];
+
+        for step in 0..move_count {
+            let fen = game_state.to_fen();
+            let reparsed = parse(&fen).unwrap();
+            assert_eq!(reparsed.to_fen(), fen);
+            assert!(reparsed.squares == game_state.squares);
+            assert_eq!(reparsed.current_player_number, game_state.current_player_number);
+
+            let moves = game_state.possible_moves();
+            if moves.is_empty() {
+                break;
+            }
+            let mov = moves[(step * 7 + 3) % moves.len()].clone();
+            game_state.perform_move(&mov).unwrap();
+            played.push((mov, fen));
+        }
+
+        while let Some((mov, fen)) = played.pop() {
+            game_state.undo_move(&mov).unwrap();
+            assert_eq!(game_state.to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn to_fen_round_trip_test() {
+        assert_round_trips("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1", 120);
+        assert_round_trips("r1eakaehr/9/2h1c2c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C2H2/9/RHEAKAE1R w - - 4 2", 120);
+        assert_round_trips("4kaR2/4a4/3hR4/7H1/9/9/9/9/4Ap1r1/3AK3c b - - 17 40", 120);
+        assert_round_trips("3k5/9/9/9/9/9/9/9/9/4K3R b - - 0 1", 60);
+    }
+
     #[test]
     fn winner_test() {
         let encoded = String::from("1R1k1a3/2R1a4/9/9/9/9/9/9/9/4K4 w - - 0 1");
@@ -484,6 +827,128 @@ mod tests {
         assert_eq!(result, Some(1));
     }
 
+    #[test]
+    fn parse_with_moves_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves h2e2 h9g7");
+        let result = parse(&encoded).unwrap();
+        assert_eq!(result.current_player_number, 1);
+        assert_eq!(result.squares[7][4].kind, PieceKind::Cannon);
+        assert_eq!(result.squares[2][6].kind, PieceKind::Horse);
+        assert_eq!(result.move_history.len(), 2);
+        assert_eq!(result.position_hashes.len(), 3);
+    }
+
+    #[test]
+    fn parse_with_illegal_move_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1 moves a0a5");
+        let result = parse(&encoded);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn perform_move_records_position_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1");
+        let mut game_state = parse(&encoded).unwrap();
+        let initial_hash = game_state.position_hash();
+        let mov = parse_iccs_move("a0a1", &mut game_state).unwrap();
+
+        game_state.perform_move(&mov).unwrap();
+        assert_eq!(game_state.position_hashes.len(), 2);
+        assert_ne!(game_state.position_hash(), initial_hash);
+
+        game_state.undo_move(&mov).unwrap();
+        assert_eq!(game_state.position_hashes, vec![initial_hash]);
+        assert!(game_state.move_history.is_empty());
+    }
+
+    #[test]
+    fn repetition_start_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a1 e9e8 a1a0 e8e9 a0a1 e9e8 a1a0 e8e9");
+        let game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.repetition_start(), Some(0));
+    }
+
+    #[test]
+    fn repetition_start_twice_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a1 e9e8 a1a0 e8e9");
+        let game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.repetition_start(), None);
+    }
+
+    #[test]
+    fn outcome_idle_repetition_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a1 e9e8 a1a0 e8e9 a0a1 e9e8 a1a0 e8e9");
+        let mut game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.outcome(), Some(Outcome::Draw));
+        assert!(game_state.draw());
+        assert_eq!(game_state.winner(), None);
+    }
+
+    #[test]
+    fn outcome_perpetual_check_test() {
+        let encoded = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a9 e9e8 a9a8 e8e9 a8a9 e9e8 a9a8 e8e9 a8a9 e9e8");
+        let mut game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.outcome(), Some(Outcome::Win(2)));
+        assert_eq!(game_state.winner(), Some(2));
+    }
+
+    #[test]
+    fn outcome_perpetual_chase_test() {
+        let encoded = String::from("4k4/9/1h7/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0b0 b7a9 b0a0 a9b7 a0b0 b7a9 b0a0 a9b7");
+        let mut game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.outcome(), Some(Outcome::Win(2)));
+    }
+
+    #[test]
+    fn outcome_one_check_one_chase_test() {
+        let encoded = String::from("4k4/R8/9/9/8h/9/9/9/9/3K3R1 w - - 0 1 moves a8a9 e9e8 h0i0 i5h3 a9a8 e8e9 i0h0 h3i5 a8a9 e9e8 h0i0 i5h3 a9a8 e8e9 i0h0 h3i5");
+        let mut game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.outcome(), Some(Outcome::Win(2)));
+    }
+
+    #[test]
+    fn chased_points_unprotected_test() {
+        let encoded = String::from("4k4/9/1h7/9/9/9/9/9/9/1R1K5 w - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.chased_points(1), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn chased_points_protected_test() {
+        let encoded = String::from("4k4/1r7/1h7/9/9/9/9/9/9/1R1K5 w - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+        assert!(game_state.chased_points(1).is_empty());
+    }
+
+    #[test]
+    fn chased_points_protected_chariot_test() {
+        let encoded = String::from("1r2k4/9/1r7/9/9/1P7/9/9/9/1C1K5 w - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.chased_points(1), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn chased_points_soldier_test() {
+        let encoded = String::from("4k4/9/9/9/1p7/9/9/9/9/1R1K5 w - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+        assert!(game_state.chased_points(1).is_empty());
+    }
+
+    #[test]
+    fn chased_points_crossed_soldier_test() {
+        let encoded = String::from("4k4/9/9/9/9/1p7/9/9/9/1R1K5 w - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+        assert_eq!(game_state.chased_points(1), vec![(1, 5)]);
+    }
+
+    #[test]
+    fn chased_points_king_test() {
+        // the king attacks the unprotected horse
+        let encoded = String::from("3k5/9/9/9/9/9/9/9/4h4/4K4 w - - 0 1");
+        let game_state = parse(&encoded).unwrap();
+        assert!(game_state.chased_points(1).is_empty());
+    }
+
     #[test]
     fn in_checkmate_test() {
         let encoded = String::from("1R1k1a3/2R1a4/9/9/9/9/9/9/9/4K4 w - - 0 1");
@@ -0,0 +1,98 @@
+use crate::xiangqi::state::game_state::GameState;
+use crate::xiangqi::state::mov::Move;
+
+const FILE_FORMAT: [char; 9] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i'
+];
+
+// ICCS move notation
+// files are lettered a to i from left to right and ranks are numbered 0 to 9 from bottom to top, from the side of player 1
+//   h2e2 - move from h2 to e2
+// the H2-E2 form of game records is also accepted
+pub fn parse(encoded: &str, game_state: &mut GameState) -> Result<Move, &'static str> {
+    let chars: Vec<char> = encoded.chars().filter(|c| *c != '-').map(|c| c.to_ascii_lowercase()).collect();
+
+    if chars.len() != 4 {
+        return Err("iccs_mov::parse - Invalid move");
+    }
+
+    let from = parse_point(chars[0], chars[1])?;
+    let to = parse_point(chars[2], chars[3])?;
+
+    match game_state.possible_moves().into_iter().find(|m| m.from == from && m.to == to) {
+        Some(m) => Ok(m),
+        None => Err("iccs_mov::parse - Illegal move")
+    }
+}
+
+pub fn format(mov: &Move) -> String {
+    format!("{}{}", format_point(mov.from), format_point(mov.to))
+}
+
+fn format_point(point: (i8, i8)) -> String {
+    format!("{}{}", FILE_FORMAT[point.0 as usize], 9 - point.1)
+}
+
+pub fn parse_point(file: char, rank: char) -> Result<(i8, i8), &'static str> {
+    let x = match FILE_FORMAT.iter().position(|f| *f == file) {
+        Some(x) => x as i8,
+        None => return Err("iccs_mov::parse - Invalid file")
+    };
+
+    let y = match rank.to_digit(10) {
+        Some(n) => 9 - n as i8,
+        None => return Err("iccs_mov::parse - Invalid rank")
+    };
+
+    Ok((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xiangqi::state::game_state::parse as parse_game_state;
+    use crate::xiangqi::state::square::PieceKind;
+
+    #[test]
+    fn parse_move_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+        let result = parse("h2e2", &mut game_state).unwrap();
+
+        assert_eq!(result.from, (7, 7));
+        assert_eq!(result.to, (4, 7));
+        assert_eq!(result.moving_piece_kind, PieceKind::Cannon);
+    }
+
+    #[test]
+    fn parse_record_form_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+        let result = parse("H2-E2", &mut game_state).unwrap();
+
+        assert_eq!(result.from, (7, 7));
+        assert_eq!(result.to, (4, 7));
+    }
+
+    #[test]
+    fn parse_illegal_move_test() {
+        let encoded = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 1");
+        let mut game_state = parse_game_state(&encoded).unwrap();
+
+        assert!(parse("a0a5", &mut game_state).is_err());
+        assert!(parse("z2e2", &mut game_state).is_err());
+        assert!(parse("h2e", &mut game_state).is_err());
+    }
+
+    #[test]
+    fn format_move_test() {
+        let mov = Move {
+            from: (1, 9),
+            to: (2, 7),
+            moving_piece_kind: PieceKind::Horse,
+            capture_piece_kind: None
+        };
+
+        assert_eq!(format(&mov), "b0c2");
+    }
+}
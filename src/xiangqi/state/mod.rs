@@ -1,7 +1,8 @@
 pub mod point;
 mod piece_factory;
 pub mod square;
-mod square_set;
+pub mod square_set;
 pub mod external_mov;
+pub mod iccs_mov;
 pub mod mov;
 pub mod game_state;
@@ -21,6 +21,25 @@ pub fn parse(encoded: char) -> Result<Square, &'static str> {
     }
 }
 
+pub fn format(square: &Square) -> Option<char> {
+    let encoded = match square.kind {
+        PieceKind::Soldier => 'p',
+        PieceKind::Chariot => 'r',
+        PieceKind::Horse => 'h',
+        PieceKind::Elephant => 'e',
+        PieceKind::Advisor => 'a',
+        PieceKind::King => 'k',
+        PieceKind::Cannon => 'c',
+        PieceKind::Empty => return None
+    };
+
+    if square.player_number == 1 {
+        Some(encoded.to_ascii_uppercase())
+    } else {
+        Some(encoded)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,5 +127,11 @@ mod tests {
        let result = parse('k');
        assert_eq!(result, expected);
     }
-}
 
+    #[test]
+    fn format_test() {
+       assert_eq!(format(&Square { kind: PieceKind::Horse, player_number: 1 }), Some('H'));
+       assert_eq!(format(&Square { kind: PieceKind::Cannon, player_number: 2 }), Some('c'));
+       assert_eq!(format(&Square { kind: PieceKind::Empty, player_number: 0 }), None);
+    }
+}
@@ -11,7 +11,7 @@ use crate::xiangqi::state::square_set::between_l_unoccupied;
 use crate::xiangqi::state::square_set::between_occupied_by_one;
 use crate::xiangqi::state::game_state::GameState;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PieceKind {
     King,
     Chariot,
@@ -23,7 +23,7 @@ pub enum PieceKind {
     Empty
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Square {
     pub player_number: i8,
     pub kind: PieceKind
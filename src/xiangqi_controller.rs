@@ -1,17 +1,43 @@
 use std::env;
+use std::collections::HashMap;
 
 use actix_web::HttpResponse;
 
 use super::xiangqi;
+use super::xiangqi::notation::Notation;
+
+// the notation the move is written in, wxf when not given
+//   ?notation=chinese
+pub fn notation(params: &HashMap<String, String>) -> Result<Notation, &'static str> {
+    match params.get("notation") {
+        Some(name) => xiangqi::notation::parse(name),
+        None => Ok(Notation::Wxf)
+    }
+}
 
-pub fn opening(game_data: &String) -> HttpResponse {
-    match xiangqi::openings::recommended_move(game_data) {
-        Some(m) => HttpResponse::Ok().body(format!("{}\n", m)),
-        None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+pub fn opening(game_data: &String, notation: Notation) -> HttpResponse {
+    let recommended_move = match xiangqi::openings::recommended_move(game_data) {
+        Some(m) => m,
+        None => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    if notation == Notation::Wxf {
+        return HttpResponse::Ok().body(format!("{}\n", recommended_move));
+    }
+
+    // the openings db is written in wxf notation
+    let mut game_state = match xiangqi::state::game_state::parse(game_data) {
+        Ok(gs) => gs,
+        Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
+    };
+
+    match xiangqi::state::external_mov::parse(recommended_move, &mut game_state) {
+        Ok(m) => HttpResponse::Ok().body(format!("{}\n", xiangqi::notation::format(&game_state, m, notation))),
+        Err(_) => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     }
 }
 
-pub fn minimax(game_data: &String) -> HttpResponse {
+pub fn minimax(game_data: &String, notation: Notation) -> HttpResponse {
     let mut game_state = match xiangqi::state::game_state::parse(game_data) {
         Ok(gs) => gs,
         Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
@@ -25,15 +51,12 @@ pub fn minimax(game_data: &String) -> HttpResponse {
     let recommended_move = xiangqi::minimax::recommended_move(&mut game_state, minimax_depth);
 
     match recommended_move {
-        Some(m) => {
-            let external_move = build_external_move(&game_state, m);
-            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
-        },
+        Some(m) => HttpResponse::Ok().body(format!("{}\n", xiangqi::notation::format(&game_state, m, notation))),
         None => HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
     }
 }
 
-pub fn mcts(game_data: &String) -> HttpResponse {
+pub fn mcts(game_data: &String, notation: Notation) -> HttpResponse {
     let mut game_state = match xiangqi::state::game_state::parse(game_data) {
         Ok(gs) => gs,
         Err(_) => return HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
@@ -52,10 +75,7 @@ pub fn mcts(game_data: &String) -> HttpResponse {
     let recommended_move = xiangqi::mcts::recommended_move(&mut game_state, mcts_simulation_count, mcts_simulation_depth);
 
     match recommended_move {
-        Ok(m) => {
-            let external_move = build_external_move(&game_state, m);
-            HttpResponse::Ok().body(format!("{}\n", external_move.format()))
-        },
+        Ok(m) => HttpResponse::Ok().body(format!("{}\n", xiangqi::notation::format(&game_state, m, notation))),
         Err(e) => {
             println!("{}", e);
             HttpResponse::UnprocessableEntity().body("422 Unprocessable Entity\n")
@@ -63,90 +83,49 @@ pub fn mcts(game_data: &String) -> HttpResponse {
     }
 }
 
-fn build_external_move(game_state: &xiangqi::state::game_state::GameState, mov: xiangqi::state::mov::Move) -> xiangqi::state::external_mov::ExternalMove {
-    let mut new_state = game_state.clone();
-    let _result = new_state.perform_move(&mov);
-
-    let mut rank_disambiguation_count: i8 = 0;
-    let mut ranks_on_file = vec![];
-
-    for (y, row) in game_state.squares.iter().enumerate() {
-        for (x, s) in row.iter().enumerate() {
-            let s_player_number = s.player_number;
-            let s_kind = s.kind;
-            if s_kind == mov.moving_piece_kind && x as i8 == mov.from.0 && s_player_number == game_state.current_player_number {
-                ranks_on_file.push(y);
-                rank_disambiguation_count += 1;
-            }
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::MessageBody;
 
-    let rank_disambiguation = if rank_disambiguation_count == 2 {
-        if game_state.current_player_number == 1 {
-            if Some(&(mov.from.1 as usize)) == ranks_on_file.iter().min() {
-                Some('+')
-            } else {
-                Some('-')
-            }
-        } else {
-            if Some(&(mov.from.1 as usize)) == ranks_on_file.iter().max() {
-                Some('+')
-            } else {
-                Some('-')
-            }
-        }
-    } else {
-        None
-    };
+    #[test]
+    fn notation_test() {
+        let mut params = HashMap::new();
+        assert_eq!(notation(&params), Ok(Notation::Wxf));
+        params.insert(String::from("notation"), String::from("iccs"));
+        assert_eq!(notation(&params), Ok(Notation::Iccs));
+        params.insert(String::from("notation"), String::from("kanji"));
+        assert!(notation(&params).is_err());
+    }
 
-    let pawn_disambiguation = if rank_disambiguation_count > 2 && mov.moving_piece_kind == xiangqi::state::square::PieceKind::Soldier {
-        if game_state.current_player_number == 1 {
-            // 0, 1, 2 => 3, 2, 1 -len
-            // 0, 1, 2, 3 => 4, 3, 2, 1
-            let mut rank = 0;
-            for (i, x) in ranks_on_file.iter().enumerate() {
-                if x == &(mov.from.0 as usize) {
-                    rank = ranks_on_file.len() - i;
-                }
-            }
-            Some(rank)
-        } else {
-            // 0, 1, 2 => 1, 2, 3 +1
-            // 0, 1, 2, 3 => 1, 2, 3, 4
-            let mut rank = 0;
-            for (i, x) in ranks_on_file.iter().enumerate() {
-                if x == &(mov.from.0 as usize) {
-                    rank = i + 1;
-                }
-            }
-            Some(rank)
-        }
-    } else {
-        None
-    };
+    #[test]
+    fn opening_notation_test() {
+        let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b - - 1 0");
+        let result = opening(&game_state, Notation::Chinese);
 
-    let external_mov = xiangqi::state::external_mov::ExternalMove {
-        from: mov.from,
-        to: mov.to,
-        moving_piece_kind: mov.moving_piece_kind,
-        player_number: game_state.current_player_number,
-        capture_piece_kind: mov.capture_piece_kind,
-        rank_disambiguation,
-        pawn_disambiguation
-    };
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "砲８平５\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
 
-    external_mov
-}
+    #[test]
+    fn minimax_iccs_notation_test() {
+        let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0");
+        let result = minimax(&game_state, Notation::Iccs);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use actix_web::body::MessageBody;
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "h2h9\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
 
     #[test]
     fn opening_valid_test() {
         let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C1C5/9/RHEAKAEHR b - - 1 0");
-        let result = opening(&game_state);
+        let result = opening(&game_state, Notation::Wxf);
 
         assert_eq!(result.status(), 200);
         match result.into_body().try_into_bytes() {
@@ -158,7 +137,7 @@ mod tests {
     #[test]
     fn opening_no_moves_test() {
         let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/8R/RHEAKAEH1 b - - 1 0");
-        let result = opening(&game_state);
+        let result = opening(&game_state, Notation::Wxf);
 
         assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {
@@ -170,7 +149,7 @@ mod tests {
     #[test]
     fn minimax_valid_test() {
         let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0");
-        let result = minimax(&game_state);
+        let result = minimax(&game_state, Notation::Wxf);
 
         assert_eq!(result.status(), 200);
         match result.into_body().try_into_bytes() {
@@ -182,7 +161,32 @@ mod tests {
     #[test]
     fn minimax_invalid_game_state_test() {
         let game_state = String::from("xheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0");
-        let result = minimax(&game_state);
+        let result = minimax(&game_state, Notation::Wxf);
+
+        assert_eq!(result.status(), 422);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_eq!(bytes, "422 Unprocessable Entity\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_with_moves_test() {
+        // R9+1 checks for the third time and loses by perpetual check
+        let game_state = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a9 e9e8 a9a8 e8e9 a8a9 e9e8 a9a8 e8e9");
+        let result = minimax(&game_state, Notation::Wxf);
+
+        assert_eq!(result.status(), 200);
+        match result.into_body().try_into_bytes() {
+           Ok(bytes) => assert_ne!(bytes, "R9+1\n"),
+           Err(_) => assert!(false, "unexpected body")
+        };
+    }
+
+    #[test]
+    fn minimax_with_illegal_moves_test() {
+        let game_state = String::from("4k4/9/9/9/9/9/9/9/9/R2K5 w - - 0 1 moves a0a9 a9a8");
+        let result = minimax(&game_state, Notation::Wxf);
 
         assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {
@@ -194,7 +198,7 @@ mod tests {
     #[test]
     fn minimax_no_moves_test() {
         let game_state = String::from("1R1k1a3/R3a4/9/9/9/9/9/9/4P4/4K4 b - - 0 1");
-        let result = minimax(&game_state);
+        let result = minimax(&game_state, Notation::Wxf);
 
         // assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {
@@ -206,7 +210,7 @@ mod tests {
     #[test]
     fn mcts_valid_test() {
         let game_state = String::from("rheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0");
-        let result = mcts(&game_state);
+        let result = mcts(&game_state, Notation::Wxf);
 
         assert_eq!(result.status(), 200);
         match result.into_body().try_into_bytes() {
@@ -218,7 +222,7 @@ mod tests {
     #[test]
     fn mcts_invalid_game_state_test() {
         let game_state = String::from("xheakaehr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RHEAKAEHR w - - 0 0");
-        let result = mcts(&game_state);
+        let result = mcts(&game_state, Notation::Wxf);
 
         assert_eq!(result.status(), 422);
         match result.into_body().try_into_bytes() {